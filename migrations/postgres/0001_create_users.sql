@@ -0,0 +1,15 @@
+-- IDs are ObjectId hex strings, like the MongoDB adapter assigns, so the
+-- other adapters and API clients see the same format.
+CREATE TABLE users (
+    id          TEXT        PRIMARY KEY,
+    name        TEXT        NOT NULL,
+    email       TEXT        NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL,
+    updated_at  TIMESTAMPTZ NOT NULL,
+    version     BIGINT      NOT NULL DEFAULT 1,
+    deleted_at  TIMESTAMPTZ,
+    -- Like the MongoDB unique index, soft-deleted users keep their email
+    CONSTRAINT users_email_key UNIQUE (email)
+);
+
+CREATE INDEX users_live_created_idx ON users (created_at DESC) WHERE deleted_at IS NULL;
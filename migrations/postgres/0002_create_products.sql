@@ -0,0 +1,26 @@
+CREATE TABLE products (
+    id                   TEXT             PRIMARY KEY,
+    name                 TEXT             NOT NULL,
+    price                DOUBLE PRECISION NOT NULL,
+    stock                INTEGER          NOT NULL CHECK (stock >= 0),
+    status               TEXT             NOT NULL
+                         CHECK (status IN ('draft', 'active', 'archived', 'outofstock')),
+    description          TEXT,
+    category_id          TEXT,
+    tags                 TEXT[]           NOT NULL DEFAULT '{}',
+    sku                  TEXT             NOT NULL,
+    reorder_threshold    INTEGER,
+    low_stock_alerted_at TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ      NOT NULL,
+    updated_at           TIMESTAMPTZ      NOT NULL,
+    version              BIGINT           NOT NULL DEFAULT 1,
+    deleted_at           TIMESTAMPTZ
+);
+
+-- One live product per SKU; a deleted product frees its SKU
+CREATE UNIQUE INDEX products_live_sku_key ON products (sku) WHERE deleted_at IS NULL;
+CREATE INDEX products_live_created_idx ON products (created_at DESC) WHERE deleted_at IS NULL;
+CREATE INDEX products_live_category_idx ON products (category_id, created_at DESC)
+    WHERE deleted_at IS NULL;
+CREATE INDEX products_low_stock_idx ON products (stock)
+    WHERE deleted_at IS NULL AND reorder_threshold IS NOT NULL;
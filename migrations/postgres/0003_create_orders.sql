@@ -0,0 +1,18 @@
+-- No foreign keys: orders keep pointing at users and products after they are
+-- soft-deleted, as in MongoDB.
+CREATE TABLE orders (
+    id          TEXT             PRIMARY KEY,
+    user_id     TEXT             NOT NULL,
+    product_id  TEXT             NOT NULL,
+    quantity    INTEGER          NOT NULL,
+    total_price DOUBLE PRECISION NOT NULL,
+    country     TEXT,
+    created_at  TIMESTAMPTZ      NOT NULL,
+    updated_at  TIMESTAMPTZ      NOT NULL,
+    version     BIGINT           NOT NULL DEFAULT 1,
+    deleted_at  TIMESTAMPTZ
+);
+
+CREATE INDEX orders_live_created_idx ON orders (created_at DESC) WHERE deleted_at IS NULL;
+CREATE INDEX orders_user_created_idx ON orders (user_id, created_at DESC);
+CREATE INDEX orders_product_idx ON orders (product_id);
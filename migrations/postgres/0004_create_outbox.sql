@@ -0,0 +1,18 @@
+-- Domain events, written in the same transaction as the change they describe
+CREATE TABLE outbox (
+    id              TEXT        PRIMARY KEY,
+    seq             BIGSERIAL   NOT NULL,
+    aggregate_type  TEXT        NOT NULL,
+    aggregate_id    TEXT        NOT NULL,
+    event_type      TEXT        NOT NULL,
+    payload         JSONB       NOT NULL,
+    attempts        INTEGER     NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ,
+    locked_until    TIMESTAMPTZ,
+    last_error      TEXT,
+    occurred_at     TIMESTAMPTZ NOT NULL,
+    published_at    TIMESTAMPTZ
+);
+
+CREATE INDEX outbox_pending_idx ON outbox (aggregate_type, aggregate_id, seq)
+    WHERE published_at IS NULL;
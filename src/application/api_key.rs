@@ -0,0 +1,90 @@
+use crate::domain::entities::api_key::ApiKey;
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::api_key::ApiKeyRepositoryPort;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Prefix of generated keys unless [`ApiKeyService::with_prefix`] sets another.
+const KEY_PREFIX: &str = "sk_";
+
+/// Random characters after the prefix kept in clear to tell keys apart.
+const VISIBLE_CHARS: usize = 8;
+
+/// A newly issued key and its secret, which is not stored anywhere.
+#[derive(Debug, Clone)]
+pub struct IssuedApiKey {
+    pub key: ApiKey,
+    pub secret: String,
+}
+
+#[derive(Clone)]
+pub struct ApiKeyService {
+    repo: Arc<dyn ApiKeyRepositoryPort>,
+    prefix: String,
+}
+
+impl ApiKeyService {
+    pub fn new(repo: Arc<dyn ApiKeyRepositoryPort>) -> Self {
+        Self {
+            repo,
+            prefix: KEY_PREFIX.to_string(),
+        }
+    }
+
+    /// Issues keys starting with `prefix`; existing keys keep working.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Issues a key for `name`. The returned secret is the only copy.
+    #[tracing::instrument(skip_all, fields(%name))]
+    pub async fn issue(&self, name: &str) -> DomainResult<IssuedApiKey> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::required("name"));
+        }
+
+        let secret = format!(
+            "{}{}{}",
+            self.prefix,
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let mut key = ApiKey {
+            id: None,
+            name: name.to_string(),
+            prefix: secret[..self.prefix.len() + VISIBLE_CHARS].to_string(),
+            key_hash: hash(&secret),
+            created_at: chrono::Utc::now(),
+            revoked_at: None,
+        };
+
+        let id = self.repo.create(&key).await?;
+        key.id = Some(id);
+
+        tracing::info!(prefix = %key.prefix, "API key issued");
+        Ok(IssuedApiKey { key, secret })
+    }
+
+    /// The unrevoked key matching `secret`, if any.
+    #[tracing::instrument(skip_all)]
+    pub async fn authenticate(&self, secret: &str) -> DomainResult<Option<ApiKey>> {
+        Ok(self
+            .repo
+            .find_by_hash(&hash(secret))
+            .await?
+            .filter(|key| key.revoked_at.is_none()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn list(&self, pagination: Pagination) -> DomainResult<Vec<ApiKey>> {
+        self.repo.find_all(pagination).await
+    }
+}
+
+/// Hex SHA-256 of a key, as stored. Keys are random, so no salt is needed.
+fn hash(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,104 @@
+use crate::domain::entities::audit::{Actor, AuditAction, AuditEntry, diff};
+use crate::domain::error::DomainResult;
+use crate::domain::filter::AuditFilter;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::audit::AuditLogPort;
+use crate::domain::port::trace_context::TraceContextPort;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Records who changed what, for compliance and support.
+#[derive(Clone)]
+pub struct AuditService {
+    log: Arc<dyn AuditLogPort>,
+    trace_context: Arc<dyn TraceContextPort>,
+}
+
+impl AuditService {
+    pub fn new(log: Arc<dyn AuditLogPort>, trace_context: Arc<dyn TraceContextPort>) -> Self {
+        Self { log, trace_context }
+    }
+
+    /// Builds an entry from snapshots of the entity before and after the
+    /// change (`None` for creations and deletions respectively).
+    pub fn entry<T: Serialize>(
+        &self,
+        actor: &Actor,
+        action: AuditAction,
+        entity: &str,
+        entity_id: &str,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> AuditEntry {
+        let before = before.and_then(|value| serde_json::to_value(value).ok());
+        let after = after.and_then(|value| serde_json::to_value(value).ok());
+
+        AuditEntry {
+            id: None,
+            actor: actor.id.clone(),
+            action,
+            entity: entity.to_string(),
+            entity_id: entity_id.to_string(),
+            changes: diff(before.as_ref(), after.as_ref()),
+            trace_id: self.trace_id(),
+            ip: actor.ip.clone(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Records one change. The change itself has already been committed, so
+    /// a failure to record it is logged rather than returned.
+    pub async fn record<T: Serialize>(
+        &self,
+        actor: &Actor,
+        action: AuditAction,
+        entity: &str,
+        entity_id: &str,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) {
+        let entry = self.entry(actor, action, entity, entity_id, before, after);
+        self.record_all(vec![entry]).await;
+    }
+
+    /// Records a batch of changes in one write; see [`Self::record`].
+    #[tracing::instrument(skip_all, fields(entries = entries.len()))]
+    pub async fn record_all(&self, entries: Vec<AuditEntry>) {
+        if entries.is_empty() {
+            return;
+        }
+        if let Err(e) = self.log.append(&entries).await {
+            for entry in &entries {
+                tracing::error!(
+                    error = %e,
+                    actor = %entry.actor,
+                    action = ?entry.action,
+                    entity = %entry.entity,
+                    entity_id = %entry.entity_id,
+                    "Failed to record audit entry"
+                );
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn list(
+        &self,
+        filter: &AuditFilter,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<AuditEntry>> {
+        self.log.find(filter, pagination).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn count(&self, filter: &AuditFilter) -> DomainResult<u64> {
+        self.log.count(filter).await
+    }
+
+    /// Trace ID segment of the current `traceparent`.
+    fn trace_id(&self) -> Option<String> {
+        self.trace_context
+            .current()
+            .and_then(|traceparent| traceparent.split('-').nth(1).map(str::to_string))
+    }
+}
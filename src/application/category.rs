@@ -0,0 +1,250 @@
+use crate::domain::entities::category::{Category, CategoryId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::category::CategoryRepositoryPort;
+use crate::domain::port::product::ProductRepositoryPort;
+use std::sync::Arc;
+
+/// Outcome of converting one legacy free-form category string.
+#[derive(Debug, Clone)]
+pub struct LegacyCategoryMigration {
+    pub legacy: String,
+    pub category_id: CategoryId,
+    pub created: bool,
+    pub products_updated: u64,
+}
+
+#[derive(Clone)]
+pub struct CategoryService {
+    repo: Arc<dyn CategoryRepositoryPort>,
+    product_repo: Arc<dyn ProductRepositoryPort>,
+}
+
+impl CategoryService {
+    pub fn new(
+        repo: Arc<dyn CategoryRepositoryPort>,
+        product_repo: Arc<dyn ProductRepositoryPort>,
+    ) -> Self {
+        Self { repo, product_repo }
+    }
+
+    #[tracing::instrument(skip_all, fields(%name))]
+    pub async fn create_category(
+        &self,
+        name: &str,
+        slug: Option<&str>,
+        parent_id: Option<&CategoryId>,
+    ) -> DomainResult<Category> {
+        let slug = Self::resolve_slug(name, slug)?;
+
+        let parent = match parent_id {
+            Some(parent_id) => Some(self.get_category(parent_id).await?),
+            None => None,
+        };
+
+        let path = Category::child_path(parent.as_ref().map(|p| p.path.as_str()), &slug);
+        self.ensure_path_available(&path, None).await?;
+
+        let now = chrono::Utc::now();
+        let mut category = Category {
+            id: None,
+            name: name.to_string(),
+            slug,
+            parent_id: parent_id.cloned(),
+            path,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+
+        let id = self.repo.create(&category).await?;
+        category.id = Some(id);
+
+        tracing::info!(category_id = %category.id.as_deref().unwrap_or("unknown"), path = %category.path, "Category created");
+        Ok(category)
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn get_category(&self, id: &CategoryId) -> DomainResult<Category> {
+        self.repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Error::not_found("Category", id.to_string()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn list_categories(&self, pagination: Pagination) -> DomainResult<Vec<Category>> {
+        self.repo.find_all(pagination).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn count_categories(&self) -> DomainResult<u64> {
+        self.repo.count().await
+    }
+
+    /// Direct children of `parent_id`, or the root categories when `None`.
+    #[tracing::instrument(skip_all)]
+    pub async fn list_children(
+        &self,
+        parent_id: Option<&CategoryId>,
+    ) -> DomainResult<Vec<Category>> {
+        if let Some(parent_id) = parent_id {
+            self.get_category(parent_id).await?;
+        }
+        self.repo.find_children(parent_id).await
+    }
+
+    /// Renames and/or moves a category, rewriting the paths of its subtree.
+    #[tracing::instrument(skip_all, fields(%id, %name))]
+    pub async fn update_category(
+        &self,
+        id: &CategoryId,
+        name: &str,
+        slug: Option<&str>,
+        parent_id: Option<&CategoryId>,
+    ) -> DomainResult<Category> {
+        let mut category = self.get_category(id).await?;
+        let slug = Self::resolve_slug(name, slug)?;
+
+        let parent = match parent_id {
+            Some(parent_id) if **parent_id == **id => {
+                return Err(Error::business_rule("A category cannot be its own parent"));
+            }
+            Some(parent_id) => Some(self.get_category(parent_id).await?),
+            None => None,
+        };
+
+        // Business rule: a category cannot be moved below one of its descendants
+        if let Some(parent) = &parent
+            && category.contains_path(&parent.path)
+        {
+            return Err(Error::business_rule(
+                "A category cannot be moved below one of its descendants",
+            ));
+        }
+
+        let old_path = category.path.clone();
+        let new_path = Category::child_path(parent.as_ref().map(|p| p.path.as_str()), &slug);
+        if new_path != old_path {
+            self.ensure_path_available(&new_path, Some(id)).await?;
+        }
+
+        category.name = name.to_string();
+        category.slug = slug;
+        category.parent_id = parent_id.cloned();
+        category.path = new_path.clone();
+        category.updated_at = chrono::Utc::now();
+
+        if new_path == old_path {
+            if !self.repo.update(id, &category).await? {
+                return Err(Error::not_found("Category", id.to_string()));
+            }
+        } else {
+            let moved = self
+                .repo
+                .move_subtree(id, &category, &old_path)
+                .await?
+                .ok_or_else(|| Error::not_found("Category", id.to_string()))?;
+            tracing::info!(%old_path, %new_path, descendants = moved, "Category subtree moved");
+        }
+
+        tracing::info!("Category updated");
+        Ok(category)
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn delete_category(&self, id: &CategoryId) -> DomainResult<()> {
+        // Business rule: only leaf categories without products can be removed
+        if !self.repo.find_children(Some(id)).await?.is_empty() {
+            return Err(Error::business_rule(
+                "Category has child categories; move or delete them first",
+            ));
+        }
+        let products = self
+            .product_repo
+            .count_by_categories(std::slice::from_ref(id))
+            .await?;
+        if products > 0 {
+            return Err(Error::business_rule(format!(
+                "Category is still referenced by {} products",
+                products
+            )));
+        }
+
+        let deleted = self.repo.delete(id).await?;
+        if !deleted {
+            return Err(Error::not_found("Category", id.to_string()));
+        }
+        tracing::info!("Category soft-deleted");
+        Ok(())
+    }
+
+    /// Converts free-form product category strings into root categories.
+    ///
+    /// Idempotent: strings whose slug already exists as a root path are linked
+    /// to that category instead of creating a duplicate.
+    #[tracing::instrument(skip_all)]
+    pub async fn migrate_legacy_categories(&self) -> DomainResult<Vec<LegacyCategoryMigration>> {
+        let legacy_values = self.product_repo.find_legacy_categories().await?;
+        let mut report = Vec::with_capacity(legacy_values.len());
+
+        for legacy in legacy_values {
+            let slug = Category::slugify(&legacy);
+            if slug.is_empty() {
+                tracing::warn!(%legacy, "Skipping legacy category without a usable slug");
+                continue;
+            }
+
+            let (category, created) = match self.repo.find_by_path(&slug).await? {
+                Some(existing) => (existing, false),
+                None => (
+                    self.create_category(legacy.trim(), Some(&slug), None)
+                        .await?,
+                    true,
+                ),
+            };
+
+            let category_id = category
+                .id
+                .ok_or_else(|| Error::internal("Category missing ID"))?;
+            let products_updated = self
+                .product_repo
+                .assign_legacy_category(&legacy, &category_id)
+                .await?;
+
+            report.push(LegacyCategoryMigration {
+                legacy,
+                category_id,
+                created,
+                products_updated,
+            });
+        }
+
+        tracing::info!(migrated = report.len(), "Legacy categories migrated");
+        Ok(report)
+    }
+
+    fn resolve_slug(name: &str, slug: Option<&str>) -> DomainResult<String> {
+        let slug = Category::slugify(slug.unwrap_or(name));
+        if slug.is_empty() {
+            return Err(Error::invalid(
+                "slug",
+                "must contain at least one letter or digit",
+            ));
+        }
+        Ok(slug)
+    }
+
+    async fn ensure_path_available(
+        &self,
+        path: &str,
+        current: Option<&CategoryId>,
+    ) -> DomainResult<()> {
+        match self.repo.find_by_path(path).await? {
+            Some(existing) if existing.id.as_deref() != current.map(|id| &**id) => {
+                Err(Error::duplicate("Category", "path", path))
+            }
+            _ => Ok(()),
+        }
+    }
+}
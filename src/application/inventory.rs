@@ -0,0 +1,403 @@
+use crate::application::tasks::TaskHandler;
+use crate::domain::entities::inventory::{InventoryMovement, MovementKind, StockDrift};
+use crate::domain::entities::product::{Product, ProductId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::{DomainEvent, LowStock};
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::inventory::InventoryLedgerPort;
+use crate::domain::port::metrics::MetricsPort;
+use crate::domain::port::notifier::NotifierPort;
+use crate::domain::port::product::ProductRepositoryPort;
+use crate::domain::port::task_queue::TaskQueuePort;
+use crate::domain::task::{NewTask, Task, TaskPayload};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Opening-balance movements written per batch during reconciliation.
+const OPENING_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationReport {
+    pub checked: u64,
+    pub in_sync: u64,
+    /// Products without ledger history that received an opening balance.
+    pub baselined: u64,
+    pub drifted: Vec<StockDrift>,
+    /// Drifted products whose stock was reset to the ledger by
+    /// [`InventoryService::rebuild_stock`].
+    pub repaired: u64,
+}
+
+/// Every stock change goes through here so that it lands in the ledger.
+#[derive(Clone)]
+pub struct InventoryService {
+    product_repo: Arc<dyn ProductRepositoryPort>,
+    ledger: Arc<dyn InventoryLedgerPort>,
+    low_stock: LowStockMonitor,
+    metrics: Arc<dyn MetricsPort>,
+}
+
+impl InventoryService {
+    pub fn new(
+        product_repo: Arc<dyn ProductRepositoryPort>,
+        ledger: Arc<dyn InventoryLedgerPort>,
+        low_stock: LowStockMonitor,
+        metrics: Arc<dyn MetricsPort>,
+    ) -> Self {
+        Self {
+            product_repo,
+            ledger,
+            low_stock,
+            metrics,
+        }
+    }
+
+    /// Applies `movement.delta` to the product's stock and appends the movement
+    /// to the ledger in the same write. Returns `None` if the product does not exist or an outflow
+    /// exceeds the available stock.
+    #[tracing::instrument(skip_all, fields(product_id = %movement.product_id, delta = movement.delta, kind = ?movement.kind))]
+    pub async fn apply(&self, movement: InventoryMovement) -> DomainResult<Option<Product>> {
+        let event = DomainEvent::ProductStockChanged {
+            product_id: movement.product_id.clone(),
+            delta: movement.delta,
+            kind: movement.kind,
+            reference: movement.reference.clone(),
+        };
+        let Some(product) = self
+            .product_repo
+            .apply_movement(&movement, &[event])
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        if movement.delta < 0 && product.stock == 0 {
+            self.metrics.stock_out();
+        }
+
+        self.low_stock.observe(&product);
+        Ok(Some(product))
+    }
+
+    /// Manual correction by an operator. A non-empty reason is mandatory.
+    #[tracing::instrument(skip_all, fields(%product_id, %delta))]
+    pub async fn adjust_stock(
+        &self,
+        product_id: &ProductId,
+        delta: i32,
+        reason: &str,
+    ) -> DomainResult<Product> {
+        if delta == 0 {
+            return Err(Error::invalid("delta", "must not be zero"));
+        }
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(Error::required("reason"));
+        }
+
+        let movement = InventoryMovement::new(product_id.clone(), delta, MovementKind::Adjustment)
+            .with_reason(reason);
+
+        if let Some(product) = self.apply(movement).await? {
+            tracing::info!(stock = product.stock, "Stock adjusted");
+            return Ok(product);
+        }
+
+        match self.product_repo.find_by_id(product_id).await? {
+            Some(product) => Err(Error::business_rule(format!(
+                "Adjustment of {} would take stock below zero (available {})",
+                delta, product.stock
+            ))),
+            None => Err(Error::not_found("Product", product_id.to_string())),
+        }
+    }
+
+    /// Re-evaluates low-stock alerting after a threshold change.
+    pub fn check_low_stock(&self, product: &Product) {
+        self.low_stock.observe(product);
+    }
+
+    #[tracing::instrument(skip_all, fields(%product_id))]
+    pub async fn list_movements(
+        &self,
+        product_id: &ProductId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<InventoryMovement>> {
+        self.product_repo
+            .find_by_id(product_id)
+            .await?
+            .ok_or_else(|| Error::not_found("Product", product_id.to_string()))?;
+        self.ledger.find_by_product(product_id, pagination).await
+    }
+
+    #[tracing::instrument(skip_all, fields(%product_id))]
+    pub async fn count_movements(&self, product_id: &ProductId) -> DomainResult<u64> {
+        self.ledger.count_by_product(product_id).await
+    }
+
+    /// Recomputes every product's stock from the ledger and reports drift.
+    ///
+    /// Products with no ledger history get an `Opening` movement for their
+    /// current stock so later runs can track them. Stock that changes while the
+    /// job runs may show up as transient drift.
+    #[tracing::instrument(skip_all)]
+    pub async fn reconcile(&self) -> DomainResult<ReconciliationReport> {
+        let ledger: HashMap<String, i64> = self
+            .ledger
+            .sum_by_product()
+            .await?
+            .into_iter()
+            .map(|(id, total)| (id.into_inner(), total))
+            .collect();
+
+        let mut report = ReconciliationReport::default();
+        let mut openings = Vec::new();
+        let mut products = self
+            .product_repo
+            .stream_all(&DateRange::default(), None)
+            .await?;
+
+        while let Some(product) = products.try_next().await? {
+            let Some(id) = product.id else {
+                continue;
+            };
+            report.checked += 1;
+
+            match ledger.get(&*id) {
+                Some(&ledger_stock) if ledger_stock == i64::from(product.stock) => {
+                    report.in_sync += 1;
+                }
+                Some(&ledger_stock) => {
+                    let drift = StockDrift {
+                        product_id: id,
+                        sku: product.metadata.sku,
+                        stock: product.stock,
+                        ledger_stock,
+                    };
+                    tracing::warn!(
+                        product_id = %drift.product_id,
+                        stock = drift.stock,
+                        ledger_stock,
+                        drift = drift.drift(),
+                        "Stock drift detected"
+                    );
+                    report.drifted.push(drift);
+                }
+                None => {
+                    openings.push(
+                        InventoryMovement::new(id, product.stock, MovementKind::Opening)
+                            .with_stock_after(product.stock),
+                    );
+                    if openings.len() >= OPENING_BATCH_SIZE {
+                        report.baselined += self.ledger.append_many(&openings).await?;
+                        openings.clear();
+                    }
+                }
+            }
+        }
+        report.baselined += self.ledger.append_many(&openings).await?;
+
+        tracing::info!(
+            checked = report.checked,
+            in_sync = report.in_sync,
+            baselined = report.baselined,
+            drifted = report.drifted.len(),
+            "Inventory reconciliation finished"
+        );
+        Ok(report)
+    }
+
+    /// Reconciles, then resets the stock of every drifted product to its
+    /// ledger balance: the ledger is the source of truth. With `dry_run` the
+    /// drift is only reported (products without history are still baselined).
+    #[tracing::instrument(skip_all, fields(dry_run = dry_run))]
+    pub async fn rebuild_stock(&self, dry_run: bool) -> DomainResult<ReconciliationReport> {
+        let mut report = self.reconcile().await?;
+        if dry_run {
+            return Ok(report);
+        }
+
+        for drift in &report.drifted {
+            // A delta rather than an absolute value, so concurrent movements are kept
+            let Ok(delta) = i32::try_from(-drift.drift()) else {
+                tracing::error!(product_id = %drift.product_id, drift = drift.drift(), "Stock drift out of range, not repaired");
+                continue;
+            };
+            match self
+                .product_repo
+                .update_stock(&drift.product_id, delta, &[])
+                .await?
+            {
+                Some(_) => report.repaired += 1,
+                None => tracing::warn!(
+                    product_id = %drift.product_id,
+                    delta,
+                    "Product gone or stock too low, not repaired"
+                ),
+            }
+        }
+
+        tracing::info!(repaired = report.repaired, "Stock rebuilt from ledger");
+        Ok(report)
+    }
+
+    /// Runs [`Self::reconcile`] every `period` until the process exits.
+    pub fn spawn_reconciliation(self: Arc<Self>, period: Duration) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately; skip it so startup stays light
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.reconcile().await {
+                    tracing::error!(error = %e, "Inventory reconciliation failed");
+                }
+            }
+        });
+    }
+}
+
+/// Raises `LowStock` alerts when a product's stock drops below its reorder
+/// threshold. One alert is sent per drop; the product is re-armed once stock
+/// is back at or above the threshold.
+#[derive(Clone)]
+pub struct LowStockMonitor {
+    repo: Arc<dyn ProductRepositoryPort>,
+    notifier: Arc<dyn NotifierPort>,
+    queue: Option<Arc<dyn TaskQueuePort>>,
+}
+
+impl LowStockMonitor {
+    pub fn new(repo: Arc<dyn ProductRepositoryPort>, notifier: Arc<dyn NotifierPort>) -> Self {
+        Self {
+            repo,
+            notifier,
+            queue: None,
+        }
+    }
+
+    /// Deliver alerts through the task queue (with retries) instead of inline.
+    pub fn with_queue(mut self, queue: Arc<dyn TaskQueuePort>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    /// Evaluates `product` as it is after a stock or threshold change.
+    ///
+    /// Runs in the background so a slow notifier never delays the caller;
+    /// failures are logged and never surface to the request.
+    pub fn observe(&self, product: &Product) {
+        if !product.is_low_stock() && product.low_stock_alerted_at.is_none() {
+            return;
+        }
+
+        let monitor = self.clone();
+        let product = product.clone();
+        let span = tracing::info_span!(
+            "low_stock_check",
+            product_id = %product.id.as_deref().unwrap_or("unknown")
+        );
+
+        tokio::spawn(
+            async move {
+                if let Err(e) = monitor.evaluate(&product).await {
+                    tracing::warn!(error = %e, "Low-stock alert failed");
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    async fn evaluate(&self, product: &Product) -> DomainResult<()> {
+        let id = product
+            .id
+            .as_ref()
+            .ok_or_else(|| Error::internal("Product missing ID"))?;
+
+        if !product.is_low_stock() {
+            // Stock recovered (or the threshold was removed): re-arm for the next drop
+            self.repo.clear_low_stock_alert(id).await?;
+            return Ok(());
+        }
+
+        if product.low_stock_alerted_at.is_some() {
+            return Ok(());
+        }
+
+        // Concurrent orders may all observe the drop; only one claims the alert
+        let now = chrono::Utc::now();
+        if !self.repo.mark_low_stock_alerted(id, now).await? {
+            return Ok(());
+        }
+
+        let event = LowStock {
+            product_id: id.clone(),
+            sku: product.metadata.sku.clone(),
+            name: product.name.clone(),
+            stock: product.stock,
+            threshold: product.reorder_threshold.unwrap_or_default(),
+            occurred_at: now,
+        };
+
+        if let Some(queue) = &self.queue {
+            // The worker retries delivery; a dead-lettered alert keeps the claim
+            if let Err(e) = queue.enqueue(NewTask::new(&event)?).await {
+                self.repo.clear_low_stock_alert(id).await?;
+                return Err(e);
+            }
+            tracing::info!(stock = event.stock, "Low-stock alert queued");
+            return Ok(());
+        }
+
+        if let Err(e) = self.notifier.notify_low_stock(&event).await {
+            // Release the claim so the next stock change retries the alert
+            self.repo.clear_low_stock_alert(id).await?;
+            return Err(e);
+        }
+
+        tracing::info!(
+            stock = event.stock,
+            threshold = event.threshold,
+            "Low-stock alert sent"
+        );
+        Ok(())
+    }
+}
+
+impl TaskPayload for LowStock {
+    const KIND: &'static str = "low_stock_alert";
+}
+
+/// Delivers queued `LowStock` alerts.
+pub struct LowStockAlertHandler {
+    notifier: Arc<dyn NotifierPort>,
+}
+
+impl LowStockAlertHandler {
+    pub fn new(notifier: Arc<dyn NotifierPort>) -> Self {
+        Self { notifier }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for LowStockAlertHandler {
+    type Payload = LowStock;
+
+    #[tracing::instrument(skip_all, fields(product_id = %alert.product_id))]
+    async fn handle(&self, alert: LowStock, _task: &Task) -> DomainResult<()> {
+        self.notifier.notify_low_stock(&alert).await?;
+        tracing::info!(
+            stock = alert.stock,
+            threshold = alert.threshold,
+            "Low-stock alert sent"
+        );
+        Ok(())
+    }
+}
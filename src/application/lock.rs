@@ -0,0 +1,174 @@
+use crate::domain::error::{DomainError, DomainResult};
+use crate::domain::port::lock::{Lease, LockPort};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Pause between attempts while waiting for a held lock.
+const RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Takes named locks shared by every instance and keeps them renewed while
+/// they are held, so work like migrations or sweeps runs on one instance at
+/// a time.
+#[derive(Clone)]
+pub struct Locks {
+    port: Arc<dyn LockPort>,
+}
+
+impl Locks {
+    pub fn new(port: Arc<dyn LockPort>) -> Self {
+        Self { port }
+    }
+
+    /// The lock, or `None` when another holder has it. The lease lasts `ttl`
+    /// and is renewed every third of it until the guard is released or dropped.
+    pub async fn try_lock(&self, name: &str, ttl: Duration) -> DomainResult<Option<LockGuard>> {
+        let lease = self.port.try_acquire(name, ttl).await?;
+        Ok(lease.map(|lease| LockGuard::new(self.port.clone(), lease, ttl)))
+    }
+
+    /// Waits up to `timeout` for the lock.
+    pub async fn lock(
+        &self,
+        name: &str,
+        ttl: Duration,
+        timeout: Duration,
+    ) -> DomainResult<LockGuard> {
+        let started = Instant::now();
+        loop {
+            if let Some(guard) = self.try_lock(name, ttl).await? {
+                return Ok(guard);
+            }
+            if started.elapsed() >= timeout {
+                return Err(DomainError::Conflict(format!(
+                    "Lock '{}' is held by another instance (waited {}s)",
+                    name,
+                    timeout.as_secs()
+                )));
+            }
+            tokio::time::sleep(RETRY_INTERVAL).await;
+        }
+    }
+
+    /// Runs `work` under the lock and releases it; `None`, without running
+    /// it, when another holder has the lock.
+    pub async fn run_exclusive<F>(
+        &self,
+        name: &str,
+        ttl: Duration,
+        work: F,
+    ) -> DomainResult<Option<F::Output>>
+    where
+        F: Future,
+    {
+        let Some(guard) = self.try_lock(name, ttl).await? else {
+            return Ok(None);
+        };
+        let output = work.await;
+        guard.release().await;
+        Ok(Some(output))
+    }
+}
+
+/// A held lock, renewed in the background. Dropping it releases the lock
+/// too, from a spawned task.
+pub struct LockGuard {
+    port: Arc<dyn LockPort>,
+    lease: Lease,
+    held: Arc<AtomicBool>,
+    renewal: Option<JoinHandle<()>>,
+    released: bool,
+}
+
+impl LockGuard {
+    fn new(port: Arc<dyn LockPort>, lease: Lease, ttl: Duration) -> Self {
+        let held = Arc::new(AtomicBool::new(true));
+        let renewal = tokio::spawn(renew(port.clone(), lease.clone(), ttl, held.clone()));
+        Self {
+            port,
+            lease,
+            held,
+            renewal: Some(renewal),
+            released: false,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.lease.name
+    }
+
+    /// Fencing token of this acquisition; pass it along with writes made
+    /// under the lock.
+    pub fn token(&self) -> u64 {
+        self.lease.token
+    }
+
+    /// `false` once the lease was lost (renewals failed for a whole TTL, or
+    /// it lapsed and another holder took it): stop, someone else may be running.
+    pub fn is_held(&self) -> bool {
+        self.held.load(Ordering::Relaxed)
+    }
+
+    /// Stops renewing and frees the lock. A failure is only logged: the
+    /// lease lapses on its own.
+    pub async fn release(mut self) {
+        self.stop_renewal();
+        self.released = true;
+        if let Err(e) = self.port.release(&self.lease).await {
+            tracing::warn!("Failed to release lock '{}': {}", self.lease.name, e);
+        }
+    }
+
+    fn stop_renewal(&mut self) {
+        if let Some(renewal) = self.renewal.take() {
+            renewal.abort();
+        }
+        self.held.store(false, Ordering::Relaxed);
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        self.stop_renewal();
+        if self.released {
+            return;
+        }
+        // Outside a runtime the lease just lapses
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let port = self.port.clone();
+            let lease = self.lease.clone();
+            runtime.spawn(async move {
+                if let Err(e) = port.release(&lease).await {
+                    tracing::warn!("Failed to release lock '{}': {}", lease.name, e);
+                }
+            });
+        }
+    }
+}
+
+async fn renew(port: Arc<dyn LockPort>, lease: Lease, ttl: Duration, held: Arc<AtomicBool>) {
+    let mut interval = tokio::time::interval(ttl / 3);
+    interval.tick().await;
+    let mut renewed_at = Instant::now();
+    loop {
+        interval.tick().await;
+        match port.renew(&lease, ttl).await {
+            Ok(true) => renewed_at = Instant::now(),
+            Ok(false) => {
+                tracing::warn!("Lock '{}' was lost", lease.name);
+                break;
+            }
+            Err(e) if renewed_at.elapsed() < ttl => {
+                tracing::warn!("Failed to renew lock '{}': {}", lease.name, e);
+            }
+            Err(e) => {
+                tracing::warn!("Lock '{}' lapsed, renewals failed: {}", lease.name, e);
+                break;
+            }
+        }
+    }
+    held.store(false, Ordering::Relaxed);
+}
@@ -1,3 +1,14 @@
+pub mod api_key;
+pub mod audit;
+pub mod category;
+pub mod inventory;
+pub mod lock;
 pub mod order;
+pub mod order_feed;
+pub mod outbox;
 pub mod product;
+pub mod scheduler;
+pub mod seed;
+pub mod tasks;
 pub mod user;
+pub mod webhook;
@@ -1,11 +1,26 @@
+use crate::application::audit::AuditService;
+use crate::application::inventory::InventoryService;
+use crate::application::order_feed::{OrderFeed, OrderSubscription, OrderUpdateFilter, OrderUpdateKind};
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
+use crate::domain::entities::audit::{Actor, AuditAction};
+use crate::domain::entities::country::Country;
+use crate::domain::entities::inventory::{InventoryMovement, MovementKind};
 use crate::domain::entities::order::{Order, OrderId};
+use crate::domain::event::DomainEvent;
 use crate::domain::pagination::Pagination;
+use crate::domain::port::metrics::MetricsPort;
 use crate::domain::port::order::OrderRepositoryPort;
 use crate::domain::port::product::ProductRepositoryPort;
 use crate::domain::port::user::UserRepositoryPort;
 use crate::domain::entities::product::ProductId;
 use crate::domain::entities::user::UserId;
+use crate::domain::stream::DomainStream;
+use crate::domain::values::INITIAL_VERSION;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use futures::TryStreamExt;
+use std::fmt;
 use std::sync::Arc;
 
 #[derive(Clone)]
@@ -13,6 +28,10 @@ pub struct OrderService {
     order_repo: Arc<dyn OrderRepositoryPort>,
     user_repo: Arc<dyn UserRepositoryPort>,
     product_repo: Arc<dyn ProductRepositoryPort>,
+    inventory: Arc<InventoryService>,
+    metrics: Arc<dyn MetricsPort>,
+    audit: Arc<AuditService>,
+    feed: Arc<OrderFeed>,
 }
 
 impl OrderService {
@@ -20,20 +39,29 @@ impl OrderService {
         order_repo: Arc<dyn OrderRepositoryPort>,
         user_repo: Arc<dyn UserRepositoryPort>,
         product_repo: Arc<dyn ProductRepositoryPort>,
+        inventory: Arc<InventoryService>,
+        metrics: Arc<dyn MetricsPort>,
+        audit: Arc<AuditService>,
     ) -> Self {
         Self {
             order_repo,
             user_repo,
             product_repo,
+            inventory,
+            metrics,
+            audit,
+            feed: Arc::new(OrderFeed::new()),
         }
     }
 
     #[tracing::instrument(skip_all, fields(%user_id, %product_id, %quantity))]
     pub async fn create_order(
         &self,
+        actor: &Actor,
         user_id: &UserId,
         product_id: &ProductId,
         quantity: i32,
+        country: Option<Country>,
     ) -> DomainResult<Order> {
         // 1. Validate user exists
         let user_opt: Option<crate::domain::entities::user::User> =
@@ -60,41 +88,74 @@ impl OrderService {
         // 4. Calculate total price
         let total_price = product.price * (quantity as f64);
 
-        // 5. Decrement stock atomically
+        // 5. Reserve stock atomically under the order's ID
         let pid = product
             .id
             .as_ref()
             .ok_or_else(|| Error::internal("Product missing ID"))?;
+        let id = self.order_repo.next_id();
 
-        let stock_updated = self.product_repo.update_stock(pid, -quantity).await?;
-
-        if !stock_updated {
+        let movement = InventoryMovement::new(pid.clone(), -quantity, MovementKind::Sale)
+            .with_reference(id.to_string());
+        if self.inventory.apply(movement).await?.is_none() {
             return Err(Error::business_rule(
                 "Failed to reserve stock — product may have been modified concurrently",
             ));
         }
 
-        // 6. Persist order
+        // 6. Persist the order; return the units if that fails
         let now = chrono::Utc::now();
         let mut order = Order {
-            id: None,
+            id: Some(id.clone()),
             user_id: user_id.clone(),
             product_id: product_id.clone(),
             quantity,
             total_price,
+            country,
             created_at: now,
             updated_at: now,
+            version: INITIAL_VERSION,
             deleted_at: None,
         };
+        let event = DomainEvent::OrderCreated {
+            order_id: id.clone(),
+            user_id: user_id.clone(),
+            product_id: product_id.clone(),
+            quantity,
+            total_price,
+        };
 
-        let id = self.order_repo.create(&order).await?;
-        order.id = Some(id);
+        match self.order_repo.create(&order, &[event]).await {
+            Ok(id) => order.id = Some(id),
+            Err(e) => {
+                let restock =
+                    InventoryMovement::new(pid.clone(), quantity, MovementKind::OrderCancellation)
+                        .with_reference(id.to_string());
+                if let Err(restock_err) = self.inventory.apply(restock).await {
+                    tracing::error!(error = %restock_err, "Failed to release reserved stock");
+                }
+                return Err(e);
+            }
+        }
 
         tracing::info!(
             order_id = %order.id.as_deref().unwrap_or("unknown"),
             %total_price,
             "Order created"
         );
+        self.metrics
+            .order_created(order.country.as_ref(), order.total_price);
+        self.audit
+            .record(
+                actor,
+                AuditAction::Create,
+                "Order",
+                &id,
+                None,
+                Some(&snapshot(&order)),
+            )
+            .await;
+        self.feed.publish(OrderUpdateKind::Created, order.clone());
         Ok(order)
     }
 
@@ -106,11 +167,79 @@ impl OrderService {
             .ok_or_else(|| Error::not_found("Order", id.to_string()))
     }
 
+    /// Cancels (soft-deletes) an order and returns its units to stock.
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn cancel_order(&self, actor: &Actor, id: &OrderId) -> DomainResult<()> {
+        let mut order = self.get_order(id).await?;
+        let before = snapshot(&order);
+
+        let event = DomainEvent::OrderCancelled {
+            order_id: id.clone(),
+            product_id: order.product_id.clone(),
+            quantity: order.quantity,
+        };
+        if !self.order_repo.delete(id, &[event]).await? {
+            return Err(Error::not_found("Order", id.to_string()));
+        }
+
+        let movement = InventoryMovement::new(
+            order.product_id.clone(),
+            order.quantity,
+            MovementKind::OrderCancellation,
+        )
+        .with_reference(id.to_string());
+        if self.inventory.apply(movement).await?.is_none() {
+            tracing::warn!(product_id = %order.product_id, "Product no longer exists; stock not restored");
+        }
+
+        tracing::info!("Order cancelled");
+        let now = chrono::Utc::now();
+        order.updated_at = now;
+        order.deleted_at = Some(now);
+        order.version += 1;
+        self.audit
+            .record(
+                actor,
+                AuditAction::Transition,
+                "Order",
+                id,
+                Some(&before),
+                Some(&snapshot(&order)),
+            )
+            .await;
+        self.feed.publish(OrderUpdateKind::Cancelled, order);
+        Ok(())
+    }
+
+    /// Live order changes made through this instance; see [`OrderFeed`].
+    pub fn subscribe_updates(
+        &self,
+        filter: OrderUpdateFilter,
+        last_event_id: Option<&str>,
+    ) -> OrderSubscription {
+        self.feed.subscribe(filter, last_event_id)
+    }
+
+    /// Ends all open update subscriptions.
+    pub fn close_update_streams(&self) {
+        self.feed.close();
+    }
+
     #[tracing::instrument(skip_all)]
     pub async fn list_orders(&self, pagination: Pagination) -> DomainResult<Vec<Order>> {
         self.order_repo.find_all(pagination).await
     }
 
+    /// Streams every live order in `range` for bulk export.
+    #[tracing::instrument(skip_all)]
+    pub async fn export_orders(
+        &self,
+        range: &DateRange,
+        user_id: Option<&UserId>,
+    ) -> DomainResult<DomainStream<Order>> {
+        self.order_repo.stream_all(range, user_id).await
+    }
+
     #[tracing::instrument(skip_all, fields(%user_id))]
     pub async fn list_orders_by_user(
         &self,
@@ -126,4 +255,69 @@ impl OrderService {
 
         self.order_repo.find_by_user_id(user_id, pagination).await
     }
+
+    /// Totals of the live orders created on `day` in `tz`.
+    #[tracing::instrument(skip_all, fields(%day, %tz))]
+    pub async fn sales_summary(&self, day: NaiveDate, tz: Tz) -> DomainResult<SalesSummary> {
+        let range = DateRange {
+            from: start_of_day(day, tz),
+            to: day.succ_opt().and_then(|next| start_of_day(next, tz)),
+        };
+        let mut orders = self.order_repo.stream_all(&range, None).await?;
+
+        let mut summary = SalesSummary {
+            day,
+            orders: 0,
+            units: 0,
+            revenue: 0.0,
+        };
+        while let Some(order) = orders.try_next().await? {
+            summary.orders += 1;
+            summary.units += i64::from(order.quantity);
+            summary.revenue += order.total_price;
+        }
+        Ok(summary)
+    }
+}
+
+/// Orders placed on one local day; cancelled ones are not counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SalesSummary {
+    pub day: NaiveDate,
+    pub orders: u64,
+    pub units: i64,
+    pub revenue: f64,
+}
+
+impl fmt::Display for SalesSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} orders, {} units, {:.2} revenue",
+            self.day, self.orders, self.units, self.revenue
+        )
+    }
+}
+
+/// First instant of `day` in `tz`. Where a DST change skips midnight (as in
+/// Chile), the day starts an hour later.
+fn start_of_day(day: NaiveDate, tz: Tz) -> Option<DateTime<Utc>> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    tz.from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(midnight + chrono::Duration::hours(1)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+}
+
+/// Audit snapshot of `order`, including its derived status so cancellations
+/// show up as a status change.
+fn snapshot(order: &Order) -> serde_json::Value {
+    let mut value = serde_json::to_value(order).unwrap_or_default();
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("status".to_string(), serde_json::json!(order.status()));
+    }
+    value
 }
@@ -0,0 +1,209 @@
+use crate::domain::entities::order::{Order, OrderStatus};
+use crate::domain::entities::user::UserId;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, watch};
+
+/// Updates kept for `Last-Event-ID` resume.
+const HISTORY_SIZE: usize = 1_000;
+
+/// Updates a slow subscriber may fall behind before it is disconnected.
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderUpdateKind {
+    Created,
+    Cancelled,
+}
+
+impl OrderUpdateKind {
+    /// Same names as the matching [`DomainEvent`](crate::domain::event::DomainEvent) types.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "order_created",
+            Self::Cancelled => "order_cancelled",
+        }
+    }
+}
+
+/// An order as it was right after a change.
+#[derive(Debug, Clone)]
+pub struct OrderUpdate {
+    /// `{epoch}-{seq}`; increases with every update of this process.
+    pub id: String,
+    pub kind: OrderUpdateKind,
+    pub order: Order,
+}
+
+/// Which updates a subscriber receives; `None` fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct OrderUpdateFilter {
+    pub user_id: Option<UserId>,
+    pub status: Option<OrderStatus>,
+}
+
+impl OrderUpdateFilter {
+    fn matches(&self, update: &OrderUpdate) -> bool {
+        self.user_id
+            .as_ref()
+            .is_none_or(|user_id| **user_id == *update.order.user_id)
+            && self
+                .status
+                .is_none_or(|status| status == update.order.status())
+    }
+}
+
+struct History {
+    next_seq: u64,
+    updates: VecDeque<Arc<OrderUpdate>>,
+}
+
+/// In-process fan-out of order changes to live subscribers.
+///
+/// Only changes made through this instance are seen. The last
+/// [`HISTORY_SIZE`] updates are kept so a reconnecting client can resume
+/// from its last event ID without gaps.
+pub struct OrderFeed {
+    /// Process start (ms); makes event IDs unique across restarts.
+    epoch: i64,
+    history: Mutex<History>,
+    sender: broadcast::Sender<Arc<OrderUpdate>>,
+    closed: watch::Sender<bool>,
+}
+
+impl Default for OrderFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderFeed {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            epoch: chrono::Utc::now().timestamp_millis(),
+            history: Mutex::new(History {
+                next_seq: 1,
+                updates: VecDeque::with_capacity(HISTORY_SIZE),
+            }),
+            sender,
+            closed: watch::Sender::new(false),
+        }
+    }
+
+    pub fn publish(&self, kind: OrderUpdateKind, order: Order) {
+        let mut history = self.lock();
+        let update = Arc::new(OrderUpdate {
+            id: format!("{}-{}", self.epoch, history.next_seq),
+            kind,
+            order,
+        });
+        history.next_seq += 1;
+
+        if history.updates.len() == HISTORY_SIZE {
+            history.updates.pop_front();
+        }
+        history.updates.push_back(update.clone());
+
+        // Sent under the lock so `subscribe` never sees an update twice or misses one
+        let _ = self.sender.send(update);
+    }
+
+    /// Subscribes to updates matching `filter`. With `last_event_id`, updates
+    /// after it that are still in the history are replayed first; an ID from
+    /// a previous process replays the whole history.
+    pub fn subscribe(
+        &self,
+        filter: OrderUpdateFilter,
+        last_event_id: Option<&str>,
+    ) -> OrderSubscription {
+        let history = self.lock();
+
+        let backlog = match last_event_id {
+            Some(last_event_id) => {
+                let after = self.parse_seq(last_event_id).unwrap_or(0);
+                history
+                    .updates
+                    .iter()
+                    .filter(|update| self.parse_seq(&update.id).unwrap_or(0) > after)
+                    .cloned()
+                    .collect()
+            }
+            None => VecDeque::new(),
+        };
+
+        OrderSubscription {
+            filter,
+            backlog,
+            receiver: self.sender.subscribe(),
+            closed: self.closed.subscribe(),
+        }
+    }
+
+    /// Ends every open subscription, e.g. so the HTTP server can shut down.
+    pub fn close(&self) {
+        self.closed.send_replace(true);
+    }
+
+    /// Sequence number of an ID issued by this process.
+    fn parse_seq(&self, id: &str) -> Option<u64> {
+        let (epoch, seq) = id.split_once('-')?;
+        if epoch.parse::<i64>().ok()? != self.epoch {
+            return None;
+        }
+        seq.parse().ok()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, History> {
+        self.history
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub struct OrderSubscription {
+    filter: OrderUpdateFilter,
+    backlog: VecDeque<Arc<OrderUpdate>>,
+    receiver: broadcast::Receiver<Arc<OrderUpdate>>,
+    closed: watch::Receiver<bool>,
+}
+
+impl OrderSubscription {
+    /// Next matching update. `None` once the feed closes or when this
+    /// subscriber fell behind; the client should then reconnect with its
+    /// last event ID.
+    pub async fn next(&mut self) -> Option<Arc<OrderUpdate>> {
+        while let Some(update) = self.backlog.pop_front() {
+            if self.filter.matches(&update) {
+                return Some(update);
+            }
+        }
+
+        loop {
+            if *self.closed.borrow() {
+                return None;
+            }
+
+            let update = tokio::select! {
+                received = self.receiver.recv() => match received {
+                    Ok(update) => update,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Order stream subscriber lagged, disconnecting");
+                        return None;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+                changed = self.closed.changed() => {
+                    if changed.is_err() {
+                        return None;
+                    }
+                    continue;
+                }
+            };
+
+            if self.filter.matches(&update) {
+                return Some(update);
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::domain::event::OutboxMessage;
+use crate::domain::port::event_publisher::EventPublisherPort;
+use crate::domain::port::outbox::OutboxPort;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Messages claimed per poll.
+const RELAY_BATCH_SIZE: i64 = 100;
+
+/// How long a claim lasts before another relay instance may retry the message.
+const CLAIM_LEASE: Duration = Duration::from_secs(60);
+
+/// Retry delay after the first failed publish; doubles up to [`MAX_RETRY_DELAY`].
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Moves events from the outbox to the configured publisher.
+///
+/// Safe to run on every instance: messages are claimed with a lease, and only
+/// the oldest pending message of each aggregate is handed out, so per-aggregate
+/// order is preserved.
+#[derive(Clone)]
+pub struct OutboxRelay {
+    outbox: Arc<dyn OutboxPort>,
+    publisher: Arc<dyn EventPublisherPort>,
+}
+
+impl OutboxRelay {
+    pub fn new(outbox: Arc<dyn OutboxPort>, publisher: Arc<dyn EventPublisherPort>) -> Self {
+        Self { outbox, publisher }
+    }
+
+    /// Claims and publishes one batch. Returns how many messages were claimed.
+    #[tracing::instrument(skip_all)]
+    pub async fn relay_once(&self) -> usize {
+        let messages = match self.outbox.claim_due(RELAY_BATCH_SIZE, CLAIM_LEASE).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to claim outbox messages");
+                return 0;
+            }
+        };
+
+        for message in &messages {
+            self.deliver(message).await;
+        }
+        messages.len()
+    }
+
+    #[tracing::instrument(skip_all, fields(id = %message.id, event = message.event.event_type()))]
+    async fn deliver(&self, message: &OutboxMessage) {
+        let outcome = match self.publisher.publish(message).await {
+            Ok(()) => self.outbox.mark_published(&message.id).await,
+            Err(e) => {
+                let delay = retry_delay(message.attempts);
+                tracing::warn!(
+                    error = %e,
+                    attempts = message.attempts + 1,
+                    retry_in_secs = delay.as_secs(),
+                    "Failed to publish event"
+                );
+                let retry_at =
+                    chrono::Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+                self.outbox
+                    .mark_failed(&message.id, &e.to_string(), retry_at)
+                    .await
+            }
+        };
+
+        // The claim lease expires, so the message is retried either way
+        if let Err(e) = outcome {
+            tracing::error!(error = %e, "Failed to update outbox message");
+        }
+    }
+
+    /// Polls every `interval`, or again right away while full batches keep coming.
+    pub fn spawn(self, interval: Duration) {
+        tokio::spawn(
+            async move {
+                loop {
+                    if self.relay_once().await < RELAY_BATCH_SIZE as usize {
+                        tokio::time::sleep(interval).await;
+                    }
+                }
+            }
+            .instrument(tracing::info_span!("outbox_relay")),
+        );
+    }
+}
+
+fn retry_delay(attempts: u32) -> Duration {
+    BASE_RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(attempts))
+        .min(MAX_RETRY_DELAY)
+}
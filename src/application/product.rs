@@ -1,41 +1,121 @@
+use crate::application::audit::AuditService;
+use crate::application::inventory::InventoryService;
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
+use crate::domain::event::DomainEvent;
+use crate::domain::port::category::CategoryRepositoryPort;
 use crate::domain::port::product::ProductRepositoryPort;
-use crate::domain::entities::product::{Product, ProductId, ProductMetadata, ProductStatus};
+use crate::domain::stream::DomainStream;
+use crate::domain::values::INITIAL_VERSION;
+use crate::domain::entities::audit::{Actor, AuditAction, AuditEntry};
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::inventory::{InventoryMovement, MovementKind};
+use crate::domain::entities::product::{
+    Product, ProductDraft, ProductId, ProductMetadata, ProductStatus, SkuUpsertOutcome,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Rows buffered by a [`ProductImport`] before they are written in one batch.
+pub const IMPORT_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportRowStatus {
+    Created,
+    Updated,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportRowResult {
+    /// 1-based data row number within the upload.
+    pub row: usize,
+    pub sku: Option<String>,
+    pub status: ImportRowStatus,
+    pub product_id: Option<ProductId>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: u64,
+    pub updated: u64,
+    pub failed: u64,
+    pub rows: Vec<ImportRowResult>,
+}
+
 #[derive(Clone)]
 pub struct ProductService {
     repo: Arc<dyn ProductRepositoryPort>,
+    category_repo: Arc<dyn CategoryRepositoryPort>,
+    inventory: Arc<InventoryService>,
+    audit: Arc<AuditService>,
 }
 
 impl ProductService {
-    pub fn new(repo: Arc<dyn ProductRepositoryPort>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<dyn ProductRepositoryPort>,
+        category_repo: Arc<dyn CategoryRepositoryPort>,
+        inventory: Arc<InventoryService>,
+        audit: Arc<AuditService>,
+    ) -> Self {
+        Self {
+            repo,
+            category_repo,
+            inventory,
+            audit,
+        }
     }
 
     #[tracing::instrument(skip_all, fields(%name))]
     pub async fn create_product(
         &self,
+        actor: &Actor,
         name: &str,
         price: f64,
         stock: i32,
         metadata: ProductMetadata,
     ) -> DomainResult<Product> {
+        self.ensure_category_exists(metadata.category_id.as_ref())
+            .await?;
+
         let now = chrono::Utc::now();
+        let id = self.repo.next_id();
         let mut product = Product {
-            id: None,
+            id: Some(id.clone()),
             name: name.to_string(),
             price,
             stock,
             status: ProductStatus::Draft,
             metadata,
+            reorder_threshold: None,
+            low_stock_alerted_at: None,
             created_at: now,
             updated_at: now,
+            version: INITIAL_VERSION,
             deleted_at: None,
         };
 
-        let id = self.repo.create(&product).await?;
+        let event = DomainEvent::ProductCreated {
+            product_id: id,
+            sku: product.metadata.sku.clone(),
+            name: product.name.clone(),
+            price,
+            stock,
+        };
+        let id = self.repo.create(&product, &[event]).await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::Create,
+                "Product",
+                &id,
+                None,
+                Some(&product),
+            )
+            .await;
         product.id = Some(id);
 
         tracing::info!(product_id = %product.id.as_deref().unwrap_or("unknown"), "Product created");
@@ -53,31 +133,128 @@ impl ProductService {
         self.repo.find_all(pagination).await
     }
 
-    #[tracing::instrument(skip_all, fields(%id))]
+    /// Products in `category_id` or any of its descendant categories.
+    #[tracing::instrument(skip_all, fields(%category_id))]
+    pub async fn list_products_by_category(
+        &self,
+        category_id: &CategoryId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Product>> {
+        let category_ids = self.category_subtree(category_id).await?;
+        self.repo
+            .find_by_categories(&category_ids, pagination)
+            .await
+    }
+
+    /// Streams every live product in `range`, optionally within a category subtree.
+    #[tracing::instrument(skip_all)]
+    pub async fn export_products(
+        &self,
+        range: &DateRange,
+        category_id: Option<&CategoryId>,
+    ) -> DomainResult<DomainStream<Product>> {
+        let category_ids = match category_id {
+            Some(category_id) => Some(self.category_subtree(category_id).await?),
+            None => None,
+        };
+        self.repo.stream_all(range, category_ids.as_deref()).await
+    }
+
+    /// Applies only if the product is still at `expected_version`.
+    #[tracing::instrument(skip_all, fields(%id, expected_version))]
     pub async fn update_metadata(
         &self,
+        actor: &Actor,
         id: &ProductId,
+        expected_version: u64,
         metadata: ProductMetadata,
     ) -> DomainResult<Product> {
-        let updated = self.repo.update_metadata(id, &metadata).await?;
+        self.ensure_category_exists(metadata.category_id.as_ref())
+            .await?;
+        let before = self.get_product(id).await?;
+        if before.version != expected_version {
+            return Err(Error::version_conflict(
+                "Product",
+                id.to_string(),
+                expected_version,
+            ));
+        }
+
+        let event = DomainEvent::ProductUpdated {
+            product_id: id.clone(),
+            metadata: metadata.clone(),
+        };
+        let updated = self
+            .repo
+            .update_metadata(id, expected_version, &metadata, &[event])
+            .await?;
         if !updated {
-            return Err(Error::not_found("Product", id.to_string()));
+            return Err(Error::version_conflict(
+                "Product",
+                id.to_string(),
+                expected_version,
+            ));
         }
 
         tracing::info!("Product metadata updated");
-        self.get_product(id).await
+        let product = self.get_product(id).await?;
+        self.audit
+            .record(
+                actor,
+                AuditAction::Update,
+                "Product",
+                id,
+                Some(&before),
+                Some(&product),
+            )
+            .await;
+        Ok(product)
     }
 
     #[tracing::instrument(skip_all, fields(%id))]
-    pub async fn delete_product(&self, id: &ProductId) -> DomainResult<()> {
-        let deleted = self.repo.delete(id).await?;
+    pub async fn delete_product(&self, actor: &Actor, id: &ProductId) -> DomainResult<()> {
+        let before = self.get_product(id).await?;
+        let event = DomainEvent::ProductDeleted {
+            product_id: id.clone(),
+        };
+        let deleted = self.repo.delete(id, &[event]).await?;
         if !deleted {
             return Err(Error::not_found("Product", id.to_string()));
         }
+        self.audit
+            .record(
+                actor,
+                AuditAction::Delete,
+                "Product",
+                id,
+                Some(&before),
+                None,
+            )
+            .await;
         tracing::info!("Product soft-deleted");
         Ok(())
     }
 
+    /// Starts a bulk import session that upserts products by SKU in batches.
+    pub fn start_import(&self, actor: &Actor, dry_run: bool) -> ProductImport {
+        ProductImport {
+            repo: self.repo.clone(),
+            category_repo: self.category_repo.clone(),
+            audit: self.audit.clone(),
+            actor: actor.clone(),
+            batch_id: uuid::Uuid::new_v4().to_string(),
+            dry_run,
+            pending: Vec::with_capacity(IMPORT_BATCH_SIZE),
+            seen_skus: HashMap::new(),
+            known_categories: HashSet::new(),
+            missing_categories: HashSet::new(),
+            report: ImportReport {
+                dry_run,
+                ..Default::default()
+            },
+        }
+    }
+
     /// Atomically decrement stock. Returns error if product not found or insufficient.
     #[tracing::instrument(skip_all, fields(%id, %quantity))]
     pub async fn decrement_stock(&self, id: &ProductId, quantity: i32) -> DomainResult<()> {
@@ -90,12 +267,290 @@ impl ProductService {
             )));
         }
 
-        let updated = self.repo.update_stock(id, -quantity).await?;
-        if !updated {
-            return Err(Error::not_found("Product", id.to_string()));
+        let movement = InventoryMovement::new(id.clone(), -quantity, MovementKind::Sale);
+        let updated = self.inventory.apply(movement).await?.ok_or_else(|| {
+            Error::business_rule(format!(
+                "Failed to decrement stock for product {} — it may have been modified concurrently",
+                id
+            ))
+        })?;
+
+        tracing::info!(remaining = updated.stock, "Stock decremented");
+        Ok(())
+    }
+
+    /// Sets (or clears with `None`) the stock level below which `LowStock` alerts fire.
+    /// Applies only if the product is still at `expected_version`.
+    #[tracing::instrument(skip_all, fields(%id, expected_version, ?threshold))]
+    pub async fn set_reorder_threshold(
+        &self,
+        actor: &Actor,
+        id: &ProductId,
+        expected_version: u64,
+        threshold: Option<i32>,
+    ) -> DomainResult<Product> {
+        if threshold.is_some_and(|t| t < 0) {
+            return Err(Error::invalid("reorder_threshold", "must be non-negative"));
+        }
+        let before = self.get_product(id).await?;
+        if before.version != expected_version {
+            return Err(Error::version_conflict(
+                "Product",
+                id.to_string(),
+                expected_version,
+            ));
         }
 
-        tracing::info!(remaining = product.stock - quantity, "Stock decremented");
+        let product = self
+            .repo
+            .set_reorder_threshold(id, expected_version, threshold)
+            .await?
+            .ok_or_else(|| Error::version_conflict("Product", id.to_string(), expected_version))?;
+        self.inventory.check_low_stock(&product);
+        self.audit
+            .record(
+                actor,
+                AuditAction::Update,
+                "Product",
+                id,
+                Some(&before),
+                Some(&product),
+            )
+            .await;
+
+        tracing::info!("Reorder threshold updated");
+        Ok(product)
+    }
+
+    /// Products currently below their reorder threshold, lowest stock first.
+    #[tracing::instrument(skip_all)]
+    pub async fn list_low_stock(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        self.repo.find_low_stock(pagination).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn count_low_stock(&self) -> DomainResult<u64> {
+        self.repo.count_low_stock().await
+    }
+
+    async fn category_subtree(&self, category_id: &CategoryId) -> DomainResult<Vec<CategoryId>> {
+        let category = self
+            .category_repo
+            .find_by_id(category_id)
+            .await?
+            .ok_or_else(|| Error::not_found("Category", category_id.to_string()))?;
+
+        let mut ids = vec![category_id.clone()];
+        ids.extend(
+            self.category_repo
+                .find_descendant_ids(&category.path)
+                .await?,
+        );
+        Ok(ids)
+    }
+
+    async fn ensure_category_exists(&self, category_id: Option<&CategoryId>) -> DomainResult<()> {
+        if let Some(category_id) = category_id
+            && self.category_repo.find_by_id(category_id).await?.is_none()
+        {
+            return Err(Error::not_found("Category", category_id.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Streaming import session: rows are pushed one at a time and written every
+/// [`IMPORT_BATCH_SIZE`] rows, so memory is bounded by the batch, not the upload.
+pub struct ProductImport {
+    repo: Arc<dyn ProductRepositoryPort>,
+    category_repo: Arc<dyn CategoryRepositoryPort>,
+    audit: Arc<AuditService>,
+    actor: Actor,
+    /// Ledger reference shared by every movement of this import.
+    batch_id: String,
+    dry_run: bool,
+    pending: Vec<(usize, ProductDraft)>,
+    seen_skus: HashMap<String, usize>,
+    known_categories: HashSet<String>,
+    missing_categories: HashSet<String>,
+    report: ImportReport,
+}
+
+impl ProductImport {
+    pub async fn push(&mut self, row: usize, draft: ProductDraft) -> DomainResult<()> {
+        let sku = draft.metadata.sku.clone();
+
+        // Business rule: a SKU may only appear once per upload
+        if let Some(first_row) = self.seen_skus.get(&sku) {
+            let reason = format!("Duplicate SKU in upload (first seen on row {})", first_row);
+            self.reject(row, Some(sku), reason);
+            return Ok(());
+        }
+        self.seen_skus.insert(sku, row);
+
+        self.pending.push((row, draft));
+        if self.pending.len() >= IMPORT_BATCH_SIZE {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Records a row that failed parsing or validation before reaching the service.
+    pub fn reject(&mut self, row: usize, sku: Option<String>, reason: impl Into<String>) {
+        self.record(row, sku, ImportRowStatus::Failed, None, Some(reason.into()));
+    }
+
+    #[tracing::instrument(skip_all, fields(dry_run = self.dry_run))]
+    pub async fn finish(mut self) -> DomainResult<ImportReport> {
+        self.flush().await?;
+        self.report.rows.sort_by_key(|r| r.row);
+
+        tracing::info!(
+            created = self.report.created,
+            updated = self.report.updated,
+            failed = self.report.failed,
+            "Product import finished"
+        );
+        Ok(self.report)
+    }
+
+    #[tracing::instrument(skip_all, fields(batch = self.pending.len()))]
+    async fn flush(&mut self) -> DomainResult<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let batch = std::mem::take(&mut self.pending);
+
+        // 1. Resolve categories once per import, not once per row
+        for (_, draft) in &batch {
+            if let Some(category_id) = &draft.metadata.category_id {
+                let key = category_id.to_string();
+                if self.known_categories.contains(&key) || self.missing_categories.contains(&key) {
+                    continue;
+                }
+                if self.category_repo.find_by_id(category_id).await?.is_some() {
+                    self.known_categories.insert(key);
+                } else {
+                    self.missing_categories.insert(key);
+                }
+            }
+        }
+
+        let mut rows = Vec::with_capacity(batch.len());
+        let mut products = Vec::with_capacity(batch.len());
+        let now = chrono::Utc::now();
+        for (row, draft) in batch {
+            if let Some(category_id) = &draft.metadata.category_id
+                && self.missing_categories.contains(&**category_id)
+            {
+                let reason = format!("Category not found: {}", category_id);
+                self.reject(row, Some(draft.metadata.sku), reason);
+                continue;
+            }
+
+            rows.push(row);
+            products.push(Product {
+                id: None,
+                name: draft.name,
+                price: draft.price,
+                stock: draft.stock,
+                status: ProductStatus::Draft,
+                metadata: draft.metadata,
+                reorder_threshold: None,
+                low_stock_alerted_at: None,
+                created_at: now,
+                updated_at: now,
+                version: INITIAL_VERSION,
+                deleted_at: None,
+            });
+        }
+
+        // 2. Dry run: report what would happen without writing
+        if self.dry_run {
+            let skus: Vec<String> = products.iter().map(|p| p.metadata.sku.clone()).collect();
+            let existing: HashMap<String, ProductId> = self
+                .repo
+                .find_ids_by_skus(&skus)
+                .await?
+                .into_iter()
+                .collect();
+
+            for (row, product) in rows.into_iter().zip(products) {
+                let sku = product.metadata.sku;
+                match existing.get(&sku) {
+                    Some(id) => self.record(
+                        row,
+                        Some(sku),
+                        ImportRowStatus::Updated,
+                        Some(id.clone()),
+                        None,
+                    ),
+                    None => self.record(row, Some(sku), ImportRowStatus::Created, None, None),
+                }
+            }
+            return Ok(());
+        }
+
+        // 3. Upsert the batch, with its ledger movements and events
+        let outcomes = self.repo.upsert_by_sku(&products, &self.batch_id).await?;
+        let mut audit_entries = Vec::with_capacity(outcomes.len());
+        for ((row, product), outcome) in rows.into_iter().zip(products).zip(outcomes) {
+            match outcome {
+                SkuUpsertOutcome::Created(id) => {
+                    audit_entries.push(self.import_audit(AuditAction::Create, &id, None, &product));
+                    let sku = product.metadata.sku;
+                    self.record(row, Some(sku), ImportRowStatus::Created, Some(id), None)
+                }
+                SkuUpsertOutcome::Updated { id, previous } => {
+                    audit_entries.push(self.import_audit(
+                        AuditAction::Update,
+                        &id,
+                        Some(&previous),
+                        &product,
+                    ));
+                    let sku = product.metadata.sku;
+                    self.record(row, Some(sku), ImportRowStatus::Updated, Some(id), None)
+                }
+                SkuUpsertOutcome::Failed(reason) => {
+                    let sku = product.metadata.sku;
+                    self.record(row, Some(sku), ImportRowStatus::Failed, None, Some(reason))
+                }
+            }
+        }
+        self.audit.record_all(audit_entries).await;
         Ok(())
     }
+
+    fn import_audit(
+        &self,
+        action: AuditAction,
+        id: &ProductId,
+        before: Option<&Product>,
+        product: &Product,
+    ) -> AuditEntry {
+        self.audit
+            .entry(&self.actor, action, "Product", id, before, Some(product))
+    }
+
+    fn record(
+        &mut self,
+        row: usize,
+        sku: Option<String>,
+        status: ImportRowStatus,
+        product_id: Option<ProductId>,
+        reason: Option<String>,
+    ) {
+        match status {
+            ImportRowStatus::Created => self.report.created += 1,
+            ImportRowStatus::Updated => self.report.updated += 1,
+            ImportRowStatus::Failed => self.report.failed += 1,
+        }
+        self.report.rows.push(ImportRowResult {
+            row,
+            sku,
+            status,
+            product_id,
+            reason,
+        });
+    }
 }
@@ -0,0 +1,200 @@
+use crate::application::lock::Locks;
+use crate::domain::error::DomainResult;
+use crate::domain::job::{Cron, JobOutcome, JobRun};
+use crate::domain::port::job_run::JobRunPort;
+use crate::domain::port::trace_context::TraceContextPort;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// Lease of a job's lock, renewed while the job runs.
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+type JobFn = Arc<dyn Fn() -> BoxFuture<'static, DomainResult<String>> + Send + Sync>;
+
+#[derive(Clone)]
+struct ScheduledJob {
+    name: &'static str,
+    cron: Cron,
+    run: JobFn,
+}
+
+/// A job's schedule and last run, as shown to operators.
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub name: String,
+    pub schedule: String,
+    pub timezone: Tz,
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_run: Option<JobRun>,
+}
+
+/// Runs registered jobs at the occurrences of their cron expressions, in one
+/// timezone.
+///
+/// Every instance runs the scheduler, but each occurrence runs once: the
+/// job's lock keeps runs from overlapping, and an instance that gets the
+/// lock after another already ran the occurrence skips it.
+#[derive(Clone)]
+pub struct Scheduler {
+    jobs: Vec<ScheduledJob>,
+    timezone: Tz,
+    locks: Locks,
+    runs: Arc<dyn JobRunPort>,
+    trace_context: Option<Arc<dyn TraceContextPort>>,
+}
+
+impl Scheduler {
+    pub fn new(timezone: Tz, locks: Locks, runs: Arc<dyn JobRunPort>) -> Self {
+        Self {
+            jobs: Vec::new(),
+            timezone,
+            locks,
+            runs,
+            trace_context: None,
+        }
+    }
+
+    /// Records the trace ID of each run with its status.
+    pub fn with_trace_context(mut self, trace_context: Arc<dyn TraceContextPort>) -> Self {
+        self.trace_context = Some(trace_context);
+        self
+    }
+
+    /// Runs `job` at every occurrence of `cron`. What it returns is kept as
+    /// the detail of the run.
+    pub fn register<F, Fut>(mut self, name: &'static str, cron: Cron, job: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = DomainResult<String>> + Send + 'static,
+    {
+        self.jobs.push(ScheduledJob {
+            name,
+            cron,
+            run: Arc::new(move || Box::pin(job())),
+        });
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Starts one loop per job. Each exits once `shutdown` turns `true`,
+    /// after finishing its current run.
+    pub fn spawn(&self, shutdown: watch::Receiver<bool>) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .iter()
+            .map(|job| tokio::spawn(self.clone().run(job.clone(), shutdown.clone())))
+            .collect()
+    }
+
+    /// Every job, in registration order.
+    pub async fn status(&self) -> DomainResult<Vec<JobStatus>> {
+        let now = Utc::now().with_timezone(&self.timezone);
+        let mut statuses = Vec::with_capacity(self.jobs.len());
+        for job in &self.jobs {
+            statuses.push(JobStatus {
+                name: job.name.to_string(),
+                schedule: job.cron.to_string(),
+                timezone: self.timezone,
+                next_run: job.cron.next_after(&now).map(|at| at.with_timezone(&Utc)),
+                last_run: self.runs.last(job.name).await?,
+            });
+        }
+        Ok(statuses)
+    }
+
+    async fn run(self, job: ScheduledJob, mut shutdown: watch::Receiver<bool>) {
+        tracing::info!(job = job.name, schedule = %job.cron, timezone = %self.timezone, "Job scheduled");
+        while !*shutdown.borrow() {
+            let now = Utc::now().with_timezone(&self.timezone);
+            let Some(next) = job.cron.next_after(&now) else {
+                tracing::info!(job = job.name, "Job has no further occurrences");
+                return;
+            };
+
+            let wait = (next - now).to_std().unwrap_or_default();
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = shutdown.changed() => continue,
+            }
+
+            let scheduled_for = next.with_timezone(&Utc);
+            // Each run is the root of its own trace
+            let span = tracing::info_span!(
+                parent: None,
+                "scheduled_job",
+                job = job.name,
+                %scheduled_for
+            );
+            self.run_once(&job, scheduled_for).instrument(span).await;
+        }
+    }
+
+    async fn run_once(&self, job: &ScheduledJob, scheduled_for: DateTime<Utc>) {
+        let lock = format!("scheduler:{}", job.name);
+        let guard = match self.locks.try_lock(&lock, LOCK_TTL).await {
+            Ok(Some(guard)) => guard,
+            Ok(None) => {
+                tracing::debug!("Job is running on another instance, skipped");
+                return;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to lock job, skipped");
+                return;
+            }
+        };
+
+        // Another instance may have run this occurrence and released the lock already
+        match self.runs.last(job.name).await {
+            Ok(Some(last)) if last.scheduled_for >= scheduled_for => {
+                tracing::debug!("Job already ran on another instance, skipped");
+                guard.release().await;
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to read the last run of job"),
+        }
+
+        let started_at = Utc::now();
+        let (outcome, detail) = match (job.run)().await {
+            Ok(detail) => {
+                tracing::info!(detail = %detail, "Job succeeded");
+                (JobOutcome::Succeeded, detail)
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Job failed");
+                (JobOutcome::Failed, e.to_string())
+            }
+        };
+
+        let run = JobRun {
+            job: job.name.to_string(),
+            scheduled_for,
+            started_at,
+            finished_at: Utc::now(),
+            outcome,
+            detail,
+            trace_id: self.trace_id(),
+        };
+        if let Err(e) = self.runs.record(&run).await {
+            tracing::error!(error = %e, "Failed to record job run");
+        }
+        guard.release().await;
+    }
+
+    /// Trace ID segment of the current `traceparent`.
+    fn trace_id(&self) -> Option<String> {
+        self.trace_context
+            .as_ref()?
+            .current()
+            .and_then(|traceparent| traceparent.split('-').nth(1).map(str::to_string))
+    }
+}
@@ -0,0 +1,203 @@
+use crate::application::category::CategoryService;
+use crate::application::product::ProductService;
+use crate::application::user::UserService;
+use crate::domain::entities::audit::Actor;
+use crate::domain::entities::category::{Category, CategoryId};
+use crate::domain::entities::product::ProductMetadata;
+use crate::domain::error::{DomainError, DomainResult, Error};
+use crate::domain::port::category::CategoryRepositoryPort;
+use crate::domain::port::product::ProductRepositoryPort;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Seed data, usually read from `fixtures/seed.json`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Fixtures {
+    #[serde(default)]
+    pub users: Vec<UserFixture>,
+    /// Top-level categories, referenced by products through their slug.
+    #[serde(default)]
+    pub categories: Vec<CategoryFixture>,
+    #[serde(default)]
+    pub products: Vec<ProductFixture>,
+}
+
+impl Fixtures {
+    pub fn load(path: impl AsRef<Path>) -> DomainResult<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| Error::invalid("seed_file", format!("{}: {}", path.display(), e)))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| Error::invalid("seed_file", format!("{}: {}", path.display(), e)))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserFixture {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CategoryFixture {
+    pub name: String,
+    pub slug: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProductFixture {
+    pub name: String,
+    pub price: f64,
+    pub stock: i32,
+    pub sku: String,
+    /// Slug of a category from the same fixtures or already in the database.
+    pub category: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SeedCount {
+    pub created: u64,
+    /// Already present (same email, category path or SKU).
+    pub skipped: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SeedReport {
+    pub users: SeedCount,
+    pub categories: SeedCount,
+    pub products: SeedCount,
+}
+
+/// Loads fixtures through the services, so they are validated, versioned and
+/// audited like any other write. Running it twice creates nothing new.
+#[derive(Clone)]
+pub struct Seeder {
+    users: Arc<UserService>,
+    categories: Arc<CategoryService>,
+    products: Arc<ProductService>,
+    category_repo: Arc<dyn CategoryRepositoryPort>,
+    product_repo: Arc<dyn ProductRepositoryPort>,
+}
+
+impl Seeder {
+    pub fn new(
+        users: Arc<UserService>,
+        categories: Arc<CategoryService>,
+        products: Arc<ProductService>,
+        category_repo: Arc<dyn CategoryRepositoryPort>,
+        product_repo: Arc<dyn ProductRepositoryPort>,
+    ) -> Self {
+        Self {
+            users,
+            categories,
+            products,
+            category_repo,
+            product_repo,
+        }
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn seed(&self, fixtures: &Fixtures) -> DomainResult<SeedReport> {
+        let actor = Actor::system();
+        let mut report = SeedReport::default();
+
+        for user in &fixtures.users {
+            match self
+                .users
+                .create_user(&actor, &user.name, &user.email)
+                .await
+            {
+                Ok(_) => report.users.created += 1,
+                Err(DomainError::AlreadyExists { .. }) => report.users.skipped += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut category_ids: HashMap<String, CategoryId> = HashMap::new();
+        for category in &fixtures.categories {
+            let slug = Category::slugify(category.slug.as_deref().unwrap_or(&category.name));
+            match self
+                .categories
+                .create_category(&category.name, Some(&slug), None)
+                .await
+            {
+                Ok(created) => {
+                    report.categories.created += 1;
+                    if let Some(id) = created.id {
+                        category_ids.insert(slug, id);
+                    }
+                }
+                Err(DomainError::AlreadyExists { .. }) => report.categories.skipped += 1,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let skus: Vec<String> = fixtures.products.iter().map(|p| p.sku.clone()).collect();
+        let existing: HashMap<String, _> = self
+            .product_repo
+            .find_ids_by_skus(&skus)
+            .await?
+            .into_iter()
+            .collect();
+
+        for product in &fixtures.products {
+            if existing.contains_key(&product.sku) {
+                report.products.skipped += 1;
+                continue;
+            }
+            let category_id = self
+                .category_id(&product.category, &mut category_ids)
+                .await?;
+            let metadata = ProductMetadata {
+                description: product.description.clone(),
+                category_id: Some(category_id),
+                tags: product.tags.clone(),
+                sku: product.sku.clone(),
+            };
+            self.products
+                .create_product(
+                    &actor,
+                    &product.name,
+                    product.price,
+                    product.stock,
+                    metadata,
+                )
+                .await?;
+            report.products.created += 1;
+        }
+
+        tracing::info!(
+            users = report.users.created,
+            categories = report.categories.created,
+            products = report.products.created,
+            "Fixtures seeded"
+        );
+        Ok(report)
+    }
+
+    /// ID of the top-level category `slug`, from this run or the database.
+    async fn category_id(
+        &self,
+        slug: &str,
+        known: &mut HashMap<String, CategoryId>,
+    ) -> DomainResult<CategoryId> {
+        if let Some(id) = known.get(slug) {
+            return Ok(id.clone());
+        }
+        let id = self
+            .category_repo
+            .find_by_path(&Category::child_path(None, slug))
+            .await?
+            .and_then(|category| category.id)
+            .ok_or_else(|| Error::not_found("Category", slug))?;
+        known.insert(slug.to_string(), id.clone());
+        Ok(id)
+    }
+}
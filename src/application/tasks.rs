@@ -0,0 +1,186 @@
+use crate::domain::error::{DomainError, DomainResult};
+use crate::domain::port::task_queue::TaskQueuePort;
+use crate::domain::port::trace_context::{TRACEPARENT, TraceContextPort};
+use crate::domain::task::{Task, TaskPayload};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// Retry delay after the first failed attempt; doubles up to [`MAX_RETRY_DELAY`].
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+
+/// Runs tasks of one type. Tasks may be delivered more than once, so handlers
+/// must be idempotent.
+#[async_trait]
+pub trait TaskHandler: Send + Sync + 'static {
+    type Payload: TaskPayload;
+
+    /// `task` carries delivery metadata (attempt number, enqueue time).
+    async fn handle(&self, payload: Self::Payload, task: &Task) -> DomainResult<()>;
+}
+
+#[async_trait]
+trait ErasedHandler: Send + Sync {
+    async fn run(&self, task: &Task) -> DomainResult<()>;
+}
+
+struct Typed<H>(H);
+
+#[async_trait]
+impl<H: TaskHandler> ErasedHandler for Typed<H> {
+    async fn run(&self, task: &Task) -> DomainResult<()> {
+        let payload = task.payload::<H::Payload>()?;
+        self.0.handle(payload, task).await
+    }
+}
+
+/// Handlers by task kind.
+#[derive(Clone, Default)]
+pub struct TaskRegistry {
+    handlers: HashMap<&'static str, Arc<dyn ErasedHandler>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<H: TaskHandler>(mut self, handler: H) -> Self {
+        self.handlers
+            .insert(H::Payload::KIND, Arc::new(Typed(handler)));
+        self
+    }
+}
+
+/// Pool of worker loops that pull tasks from the queue and dispatch them.
+#[derive(Clone)]
+pub struct TaskWorkers {
+    queue: Arc<dyn TaskQueuePort>,
+    registry: TaskRegistry,
+    poll_interval: Duration,
+    trace_context: Option<Arc<dyn TraceContextPort>>,
+}
+
+impl TaskWorkers {
+    pub fn new(
+        queue: Arc<dyn TaskQueuePort>,
+        registry: TaskRegistry,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            queue,
+            registry,
+            poll_interval,
+            trace_context: None,
+        }
+    }
+
+    /// Runs each task in the trace of the request that enqueued it.
+    pub fn with_trace_context(mut self, trace_context: Arc<dyn TraceContextPort>) -> Self {
+        self.trace_context = Some(trace_context);
+        self
+    }
+
+    /// Starts `concurrency` workers. Each finishes its current task and exits
+    /// once `shutdown` turns `true`.
+    pub fn spawn(
+        &self,
+        concurrency: usize,
+        shutdown: watch::Receiver<bool>,
+    ) -> Vec<JoinHandle<()>> {
+        let instance = uuid::Uuid::new_v4().simple().to_string();
+
+        (0..concurrency)
+            .map(|n| {
+                let worker = format!("{}-{}", instance, n);
+                let span = tracing::info_span!("task_worker", %worker);
+                tokio::spawn(self.clone().run(worker, shutdown.clone()).instrument(span))
+            })
+            .collect()
+    }
+
+    async fn run(self, worker: String, mut shutdown: watch::Receiver<bool>) {
+        while !*shutdown.borrow() {
+            let idle = match self.queue.reserve(&worker).await {
+                Ok(Some(task)) => {
+                    let span = tracing::info_span!(
+                        "process",
+                        task_id = %task.id,
+                        kind = %task.kind,
+                        attempt = task.attempt
+                    );
+                    if let (Some(trace_context), Some(trace_parent)) =
+                        (&self.trace_context, &task.trace_parent)
+                    {
+                        trace_context.continue_trace(&span, &[(TRACEPARENT, trace_parent)]);
+                    }
+                    self.process(task).instrument(span).await;
+                    false
+                }
+                Ok(None) => true,
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to reserve task");
+                    true
+                }
+            };
+
+            if idle {
+                tokio::select! {
+                    _ = tokio::time::sleep(self.poll_interval) => {}
+                    _ = shutdown.changed() => {}
+                }
+            }
+        }
+    }
+
+    async fn process(&self, task: Task) {
+        let outcome = match self.registry.handlers.get(task.kind.as_str()) {
+            Some(handler) => handler.run(&task).await,
+            None => {
+                tracing::error!("No handler registered for task kind");
+                let settled = self.queue.dead_letter(&task, "No handler registered").await;
+                log_unsettled(&task, settled);
+                return;
+            }
+        };
+
+        let settled = match outcome {
+            Ok(()) => self.queue.complete(&task).await,
+            // Retrying cannot fix a malformed payload
+            Err(e @ (DomainError::Invalid { .. } | DomainError::Required { .. })) => {
+                tracing::error!(error = %e, "Task rejected, moving to dead-letter queue");
+                self.queue.dead_letter(&task, &e.to_string()).await
+            }
+            Err(e) if task.is_last_attempt() => {
+                tracing::error!(error = %e, "Task failed on its last attempt, moving to dead-letter queue");
+                self.queue.dead_letter(&task, &e.to_string()).await
+            }
+            Err(e) => {
+                let delay = retry_delay(task.attempt);
+                tracing::warn!(error = %e, retry_in_secs = delay.as_secs(), "Task failed, retrying");
+                let retry_at =
+                    chrono::Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+                self.queue.retry(&task, &e.to_string(), retry_at).await
+            }
+        };
+        log_unsettled(&task, settled);
+    }
+}
+
+/// The reservation times out if settling fails, so the task is redelivered.
+fn log_unsettled(task: &Task, settled: DomainResult<()>) {
+    if let Err(e) = settled {
+        tracing::error!(error = %e, task_id = %task.id, "Failed to settle task");
+    }
+}
+
+fn retry_delay(attempt: u32) -> Duration {
+    BASE_RETRY_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .min(MAX_RETRY_DELAY)
+}
@@ -1,37 +1,54 @@
+use crate::application::audit::AuditService;
+use crate::domain::entities::audit::{Actor, AuditAction};
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
 use crate::domain::port::user::UserRepositoryPort;
 use crate::domain::entities::user::{User, UserId};
+use crate::domain::event::DomainEvent;
+use crate::domain::stream::DomainStream;
+use crate::domain::values::INITIAL_VERSION;
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct UserService {
     repo: Arc<dyn UserRepositoryPort>,
+    audit: Arc<AuditService>,
 }
 
 impl UserService {
-    pub fn new(repo: Arc<dyn UserRepositoryPort>) -> Self {
-        Self { repo }
+    pub fn new(repo: Arc<dyn UserRepositoryPort>, audit: Arc<AuditService>) -> Self {
+        Self { repo, audit }
     }
 
     #[tracing::instrument(skip_all, fields(%email))]
-    pub async fn create_user(&self, name: &str, email: &str) -> DomainResult<User> {
+    pub async fn create_user(&self, actor: &Actor, name: &str, email: &str) -> DomainResult<User> {
         let existing: Option<User> = self.repo.find_by_email(email).await?;
         if existing.is_some() {
             return Err(Error::duplicate("User", "email", email));
         }
 
         let now = chrono::Utc::now();
+        let id = self.repo.next_id();
         let mut user = User {
-            id: None,
+            id: Some(id.clone()),
             name: name.to_string(),
             email: email.to_string(),
             created_at: now,
             updated_at: now,
+            version: INITIAL_VERSION,
             deleted_at: None,
         };
 
-        let id = self.repo.create(&user).await?;
+        let event = DomainEvent::UserCreated {
+            user_id: id,
+            name: user.name.clone(),
+            email: user.email.clone(),
+        };
+        let id = self.repo.create(&user, &[event]).await?;
+        self.audit
+            .record(actor, AuditAction::Create, "User", &id, None, Some(&user))
+            .await;
         user.id = Some(id);
 
         tracing::info!(user_id = %user.id.as_deref().unwrap_or("unknown"), "User created");
@@ -49,9 +66,31 @@ impl UserService {
         self.repo.find_all(pagination).await
     }
 
-    #[tracing::instrument(skip_all, fields(%id, %email))]
-    pub async fn update_user(&self, id: &UserId, name: &str, email: &str) -> DomainResult<User> {
-        let mut user = self.get_user(id).await?;
+    /// Streams every live user in `range` for bulk export.
+    #[tracing::instrument(skip_all)]
+    pub async fn export_users(&self, range: &DateRange) -> DomainResult<DomainStream<User>> {
+        self.repo.stream_all(range).await
+    }
+
+    /// Applies only if the user is still at `expected_version`.
+    #[tracing::instrument(skip_all, fields(%id, %email, expected_version))]
+    pub async fn update_user(
+        &self,
+        actor: &Actor,
+        id: &UserId,
+        expected_version: u64,
+        name: &str,
+        email: &str,
+    ) -> DomainResult<User> {
+        let before = self.get_user(id).await?;
+        if before.version != expected_version {
+            return Err(Error::version_conflict(
+                "User",
+                id.to_string(),
+                expected_version,
+            ));
+        }
+        let mut user = before.clone();
 
         // Business rule: cannot change email to one already in use
         if email != user.email {
@@ -65,7 +104,33 @@ impl UserService {
         user.email = email.to_string();
         user.updated_at = chrono::Utc::now();
 
-        self.repo.update(id, &user).await?;
+        let event = DomainEvent::UserUpdated {
+            user_id: id.clone(),
+            name: user.name.clone(),
+            email: user.email.clone(),
+        };
+        if !self
+            .repo
+            .update(id, expected_version, &user, &[event])
+            .await?
+        {
+            return Err(Error::version_conflict(
+                "User",
+                id.to_string(),
+                expected_version,
+            ));
+        }
+        user.version += 1;
+        self.audit
+            .record(
+                actor,
+                AuditAction::Update,
+                "User",
+                id,
+                Some(&before),
+                Some(&user),
+            )
+            .await;
 
         tracing::info!("User updated");
         Ok(user)
@@ -77,11 +142,18 @@ impl UserService {
     }
 
     #[tracing::instrument(skip_all, fields(%id))]
-    pub async fn delete_user(&self, id: &UserId) -> DomainResult<()> {
-        let deleted = self.repo.delete(id).await?;
+    pub async fn delete_user(&self, actor: &Actor, id: &UserId) -> DomainResult<()> {
+        let before = self.get_user(id).await?;
+        let event = DomainEvent::UserDeleted {
+            user_id: id.clone(),
+        };
+        let deleted = self.repo.delete(id, &[event]).await?;
         if !deleted {
             return Err(Error::not_found("User", id.to_string()));
         }
+        self.audit
+            .record(actor, AuditAction::Delete, "User", id, Some(&before), None)
+            .await;
         tracing::info!("User soft-deleted");
         Ok(())
     }
@@ -0,0 +1,404 @@
+use crate::application::tasks::TaskHandler;
+use crate::domain::entities::webhook::{
+    ALL_EVENTS, DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookDeliveryId,
+    WebhookSubscription, WebhookSubscriptionId,
+};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::{DomainEvent, OutboxMessage};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::event_publisher::EventPublisherPort;
+use crate::domain::port::task_queue::TaskQueuePort;
+use crate::domain::port::webhook::{WebhookDeliveryRepositoryPort, WebhookSubscriptionRepositoryPort};
+use crate::domain::port::webhook_client::WebhookClientPort;
+use crate::domain::task::{NewTask, Task, TaskPayload};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Attempts per delivery. With the worker backoff (5s doubling) the last one
+/// runs roughly 40 minutes after the event.
+const DELIVERY_MAX_ATTEMPTS: u32 = 10;
+
+/// Prefix of generated signing secrets.
+const SECRET_PREFIX: &str = "whsec_";
+
+/// Sends one stored delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliverWebhook {
+    pub delivery_id: WebhookDeliveryId,
+}
+
+impl TaskPayload for DeliverWebhook {
+    const KIND: &'static str = "deliver_webhook";
+}
+
+/// Body POSTed to partners: `{"id", "occurred_at", "type", "data"}`.
+#[derive(Serialize)]
+struct WebhookBody<'a> {
+    id: &'a str,
+    occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    event: &'a DomainEvent,
+}
+
+/// Fields a partner may set on a subscription.
+#[derive(Debug, Clone)]
+pub struct SubscriptionInput {
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub description: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    subscriptions: Arc<dyn WebhookSubscriptionRepositoryPort>,
+    deliveries: Arc<dyn WebhookDeliveryRepositoryPort>,
+    client: Arc<dyn WebhookClientPort>,
+    queue: Option<Arc<dyn TaskQueuePort>>,
+}
+
+impl WebhookService {
+    pub fn new(
+        subscriptions: Arc<dyn WebhookSubscriptionRepositoryPort>,
+        deliveries: Arc<dyn WebhookDeliveryRepositoryPort>,
+        client: Arc<dyn WebhookClientPort>,
+    ) -> Self {
+        Self {
+            subscriptions,
+            deliveries,
+            client,
+            queue: None,
+        }
+    }
+
+    /// Deliveries are sent by task workers; without a queue nothing is sent.
+    pub fn with_queue(mut self, queue: Arc<dyn TaskQueuePort>) -> Self {
+        self.queue = Some(queue);
+        self
+    }
+
+    // ===== SUBSCRIPTIONS =====
+
+    /// Creates a subscription. When `secret` is `None` one is generated; the
+    /// returned subscription is the only place the caller gets to see it.
+    #[tracing::instrument(skip_all, fields(url = %input.url))]
+    pub async fn create_subscription(
+        &self,
+        input: SubscriptionInput,
+        secret: Option<String>,
+    ) -> DomainResult<WebhookSubscription> {
+        Self::validate(&input)?;
+
+        let now = Utc::now();
+        let mut subscription = WebhookSubscription {
+            id: None,
+            url: input.url,
+            secret: secret.unwrap_or_else(Self::generate_secret),
+            events: input.events,
+            active: input.active,
+            description: input.description,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+
+        let id = self.subscriptions.create(&subscription).await?;
+        subscription.id = Some(id);
+
+        tracing::info!(subscription_id = %subscription.id.as_deref().unwrap_or("unknown"), "Webhook subscription created");
+        Ok(subscription)
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn get_subscription(
+        &self,
+        id: &WebhookSubscriptionId,
+    ) -> DomainResult<WebhookSubscription> {
+        self.subscriptions
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Error::not_found("WebhookSubscription", id.to_string()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn list_subscriptions(
+        &self,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<WebhookSubscription>> {
+        self.subscriptions.find_all(pagination).await
+    }
+
+    #[tracing::instrument(skip_all)]
+    pub async fn count_subscriptions(&self) -> DomainResult<u64> {
+        self.subscriptions.count().await
+    }
+
+    /// Replaces the URL, filter and status. The secret is kept.
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn update_subscription(
+        &self,
+        id: &WebhookSubscriptionId,
+        input: SubscriptionInput,
+    ) -> DomainResult<WebhookSubscription> {
+        Self::validate(&input)?;
+        let mut subscription = self.get_subscription(id).await?;
+
+        subscription.url = input.url;
+        subscription.events = input.events;
+        subscription.active = input.active;
+        subscription.description = input.description;
+        subscription.updated_at = Utc::now();
+
+        if !self.subscriptions.update(id, &subscription).await? {
+            return Err(Error::not_found("WebhookSubscription", id.to_string()));
+        }
+        Ok(subscription)
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    pub async fn delete_subscription(&self, id: &WebhookSubscriptionId) -> DomainResult<()> {
+        if !self.subscriptions.delete(id).await? {
+            return Err(Error::not_found("WebhookSubscription", id.to_string()));
+        }
+        tracing::info!("Webhook subscription deleted");
+        Ok(())
+    }
+
+    fn validate(input: &SubscriptionInput) -> DomainResult<()> {
+        if !(input.url.starts_with("https://") || input.url.starts_with("http://")) {
+            return Err(Error::invalid("url", "must use http or https"));
+        }
+
+        if input.events.is_empty() {
+            return Err(Error::required("events"));
+        }
+        if let Some(unknown) = input
+            .events
+            .iter()
+            .find(|e| *e != ALL_EVENTS && !DomainEvent::TYPES.contains(&e.as_str()))
+        {
+            return Err(Error::invalid(
+                "events",
+                format!("unknown event type '{}'", unknown),
+            ));
+        }
+        Ok(())
+    }
+
+    fn generate_secret() -> String {
+        format!(
+            "{}{}{}",
+            SECRET_PREFIX,
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        )
+    }
+
+    // ===== DELIVERIES =====
+
+    #[tracing::instrument(skip_all, fields(%subscription_id))]
+    pub async fn list_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        self.get_subscription(subscription_id).await?;
+        self.deliveries
+            .find_by_subscription(subscription_id, pagination)
+            .await
+    }
+
+    #[tracing::instrument(skip_all, fields(%subscription_id))]
+    pub async fn count_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> DomainResult<u64> {
+        self.deliveries.count_by_subscription(subscription_id).await
+    }
+
+    /// Sends a succeeded or failed delivery again, with a fresh set of
+    /// attempts. A pending delivery already has a task queued, so it is
+    /// returned as is rather than sent twice.
+    #[tracing::instrument(skip_all, fields(%subscription_id, %delivery_id))]
+    pub async fn redeliver(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        delivery_id: &WebhookDeliveryId,
+    ) -> DomainResult<WebhookDelivery> {
+        let subscription = self.get_subscription(subscription_id).await?;
+        if !subscription.active {
+            return Err(Error::business_rule(
+                "Cannot redeliver to an inactive subscription",
+            ));
+        }
+
+        let mut delivery = self
+            .deliveries
+            .find_by_id(delivery_id)
+            .await?
+            .filter(|d| *d.subscription_id == **subscription_id)
+            .ok_or_else(|| Error::not_found("WebhookDelivery", delivery_id.to_string()))?;
+
+        if !self.deliveries.reopen(delivery_id).await? {
+            tracing::info!("Webhook delivery already pending, not scheduled again");
+            delivery.status = DeliveryStatus::Pending;
+            return Ok(delivery);
+        }
+        self.schedule(delivery_id).await?;
+
+        delivery.status = DeliveryStatus::Pending;
+        tracing::info!("Webhook redelivery scheduled");
+        Ok(delivery)
+    }
+
+    /// Records a delivery for every subscription interested in the event and
+    /// schedules it. Safe to call again for the same message: deliveries are
+    /// stored once per subscription and event.
+    #[tracing::instrument(skip_all, fields(id = %message.id, event = message.event.event_type()))]
+    pub async fn dispatch(&self, message: &OutboxMessage) -> DomainResult<()> {
+        let event_type = message.event.event_type();
+        let subscriptions = self.subscriptions.find_for_event(event_type).await?;
+        if subscriptions.is_empty() {
+            return Ok(());
+        }
+
+        let body = serde_json::to_string(&WebhookBody {
+            id: &message.id,
+            occurred_at: message.occurred_at,
+            event: &message.event,
+        })
+        .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+
+        let now = Utc::now();
+        for subscription in subscriptions {
+            let Some(subscription_id) = subscription.id else {
+                continue;
+            };
+            let delivery = WebhookDelivery {
+                id: None,
+                subscription_id,
+                event_id: message.id.to_string(),
+                event_type: event_type.to_string(),
+                body: body.clone(),
+                status: DeliveryStatus::Pending,
+                attempts: Vec::new(),
+                created_at: now,
+                updated_at: now,
+            };
+
+            // A relay retrying the message finds the delivery already scheduled
+            let (delivery_id, created) = self.deliveries.create_once(&delivery).await?;
+            if created {
+                self.schedule(&delivery_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn schedule(&self, delivery_id: &WebhookDeliveryId) -> DomainResult<()> {
+        let Some(queue) = &self.queue else {
+            return Err(Error::internal(
+                "Webhook delivery requires the task queue (Redis)",
+            ));
+        };
+
+        let task = NewTask::new(&DeliverWebhook {
+            delivery_id: delivery_id.clone(),
+        })?
+        .with_max_attempts(DELIVERY_MAX_ATTEMPTS);
+        queue.enqueue(task).await?;
+        Ok(())
+    }
+
+    /// Makes one attempt and logs it. Errors when the partner did not answer
+    /// with a 2xx, so the task is retried.
+    #[tracing::instrument(skip_all, fields(%delivery_id, attempt = task.attempt))]
+    pub async fn deliver(&self, delivery_id: &WebhookDeliveryId, task: &Task) -> DomainResult<()> {
+        let Some(delivery) = self.deliveries.find_by_id(delivery_id).await? else {
+            tracing::warn!("Webhook delivery no longer exists, skipping");
+            return Ok(());
+        };
+        // A duplicate task, or a redelivery that already went through
+        if delivery.status == DeliveryStatus::Succeeded {
+            return Ok(());
+        }
+
+        let subscription = match self
+            .subscriptions
+            .find_by_id(&delivery.subscription_id)
+            .await?
+        {
+            Some(subscription) if subscription.active => subscription,
+            _ => {
+                tracing::info!("Webhook subscription removed or inactive, giving up");
+                self.deliveries
+                    .set_status(delivery_id, DeliveryStatus::Failed)
+                    .await?;
+                return Ok(());
+            }
+        };
+
+        let started = Instant::now();
+        let sent = self.client.send(&subscription, &delivery).await;
+        let attempt = DeliveryAttempt {
+            attempted_at: Utc::now(),
+            status_code: sent.as_ref().ok().copied(),
+            error: sent.as_ref().err().map(|e| e.to_string()),
+            duration_ms: started.elapsed().as_millis() as u64,
+        };
+
+        let status = if attempt.succeeded() {
+            DeliveryStatus::Succeeded
+        } else if task.is_last_attempt() {
+            DeliveryStatus::Failed
+        } else {
+            DeliveryStatus::Pending
+        };
+        self.deliveries
+            .record_attempt(delivery_id, &attempt, status)
+            .await?;
+
+        match sent {
+            Ok(_) if status == DeliveryStatus::Succeeded => {
+                tracing::info!(duration_ms = attempt.duration_ms, "Webhook delivered");
+                Ok(())
+            }
+            Ok(code) => Err(Error::external(
+                "webhook",
+                format!("{} answered {}", subscription.url, code),
+            )),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Lets the outbox relay fan events out to webhook subscriptions.
+#[async_trait]
+impl EventPublisherPort for WebhookService {
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()> {
+        self.dispatch(message).await
+    }
+}
+
+/// Runs queued [`DeliverWebhook`] tasks.
+pub struct DeliverWebhookHandler {
+    service: Arc<WebhookService>,
+}
+
+impl DeliverWebhookHandler {
+    pub fn new(service: Arc<WebhookService>) -> Self {
+        Self { service }
+    }
+}
+
+#[async_trait]
+impl TaskHandler for DeliverWebhookHandler {
+    type Payload = DeliverWebhook;
+
+    async fn handle(&self, payload: DeliverWebhook, task: &Task) -> DomainResult<()> {
+        self.service.deliver(&payload.delivery_id, task).await
+    }
+}
@@ -0,0 +1,506 @@
+//! Operations tooling that runs against the same MongoDB as the service:
+//! `service-admin --help`.
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use service::application::{
+    api_key::ApiKeyService,
+    audit::AuditService,
+    category::CategoryService,
+    inventory::{InventoryService, LowStockMonitor},
+    product::ProductService,
+    seed::{Fixtures, SeedReport, Seeder},
+    user::UserService,
+};
+use service::domain::pagination::Pagination;
+use service::domain::port::{
+    api_key::ApiKeyRepositoryPort, audit::AuditLogPort, category::CategoryRepositoryPort,
+    inventory::InventoryLedgerPort, metrics::MetricsPort, product::ProductRepositoryPort,
+    trace_context::TraceContextPort, user::UserRepositoryPort,
+};
+use service::infrastructure::metrics::noop::NoopMetrics;
+use service::infrastructure::notifier::log::LogNotifier;
+use service::infrastructure::persistence::{
+    api_key::repository::ApiKeyRepository,
+    audit::repository::AuditLogRepository,
+    category::repository::CategoryRepository,
+    inventory::repository::InventoryLedgerRepository,
+    maintenance::{self, IndexReport, PurgeReport, RebuildReport},
+    outbox::writer::OutboxWriter,
+    product::repository::ProductRepository,
+    user::repository::UserRepository,
+};
+use service::infrastructure::providers::mongo::MongoProvider;
+use service::infrastructure::providers::propagation::OtelTraceContext;
+use service::{cli, config};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "service-admin",
+    version,
+    about = "Maintenance tasks for the service's database"
+)]
+struct Cli {
+    /// Output format; `json` prints one document on stdout
+    #[arg(long, value_enum, default_value_t = Format::Human, global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create or verify the indexes declared by the repositories
+    Indexes {
+        #[command(subcommand)]
+        action: IndexesAction,
+    },
+    /// Rebuild changed indexes and drop stale ones, for every collection or
+    /// just one; unique indexes are never dropped
+    RebuildIndexes {
+        #[arg(long)]
+        collection: Option<String>,
+    },
+    /// Manage schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: cli::MigrateAction,
+    },
+    /// Load users, categories and products from a fixtures file (idempotent)
+    Seed {
+        #[arg(long, default_value = "fixtures/seed.json")]
+        file: PathBuf,
+    },
+    /// Permanently delete records soft-deleted more than N days ago
+    Purge {
+        #[arg(long)]
+        older_than_days: u32,
+        /// Count what would be deleted
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Reset each product's stock to its inventory ledger balance
+    RebuildStock {
+        /// Only report the drift
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Issue and list API keys
+    ApiKeys {
+        #[command(subcommand)]
+        action: ApiKeysAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum IndexesAction {
+    /// Create missing indexes (idempotent)
+    Create,
+    /// Report missing and unexpected indexes; fails when any is missing
+    Verify,
+}
+
+#[derive(Debug, Subcommand)]
+enum ApiKeysAction {
+    /// Issue a key; it is printed once and only its hash is stored
+    Issue {
+        /// Who the key is for, e.g. the partner name
+        #[arg(long)]
+        name: String,
+    },
+    /// List issued keys, newest first
+    List,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .expect("Failed to install rustls crypto provider");
+
+    let env = match config::load() {
+        Ok(env) => env,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Logs go to stderr so that stdout only carries the command's output
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(tracing_subscriber::EnvFilter::new(
+            env.telemetry.debug_level.to_lowercase(),
+        ))
+        .init();
+
+    let mongo =
+        match MongoProvider::new(&env.server.service_name, &env.mongo, Arc::new(NoopMetrics)).await
+        {
+            Ok(mongo) => mongo,
+            Err(e) => {
+                eprintln!("Failed to connect to MongoDB: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+    let admin = Admin {
+        db: mongo.get_database(),
+        transactions: mongo.supports_transactions().await,
+        format: cli.format,
+    };
+
+    match admin.run(cli.command).await {
+        Ok(code) => code,
+        Err(e) => {
+            match cli.format {
+                Format::Human => eprintln!("Error: {}", e),
+                Format::Json => println!("{}", serde_json::json!({ "error": e.to_string() })),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct Admin {
+    db: mongodb::Database,
+    transactions: bool,
+    format: Format,
+}
+
+impl Admin {
+    async fn run(&self, command: Command) -> anyhow::Result<ExitCode> {
+        match command {
+            Command::Indexes {
+                action: IndexesAction::Create,
+            } => {
+                let reports = maintenance::create_indexes(&self.db).await?;
+                Ok(self.indexes(&reports))
+            }
+            Command::Indexes {
+                action: IndexesAction::Verify,
+            } => {
+                let reports = maintenance::verify_indexes(&self.db).await?;
+                Ok(self.indexes(&reports))
+            }
+            Command::RebuildIndexes { collection } => {
+                let reports = maintenance::rebuild_indexes(&self.db, collection.as_deref()).await?;
+                self.emit(&reports, |reports| print_rebuild(reports));
+                Ok(ExitCode::SUCCESS)
+            }
+            Command::Migrate { action } => self.migrate(action).await,
+            Command::Seed { file } => {
+                let fixtures = Fixtures::load(&file)?;
+                let report = self.seeder().seed(&fixtures).await?;
+                self.emit(&report, print_seed);
+                Ok(ExitCode::SUCCESS)
+            }
+            Command::Purge {
+                older_than_days,
+                dry_run,
+            } => {
+                let before = chrono::Utc::now() - chrono::Duration::days(older_than_days.into());
+                let reports = maintenance::purge_deleted(&self.db, before, dry_run).await?;
+                self.emit(&reports, |reports| print_purge(reports, dry_run));
+                Ok(ExitCode::SUCCESS)
+            }
+            Command::RebuildStock { dry_run } => {
+                let report = self.inventory().rebuild_stock(dry_run).await?;
+                let output = StockRebuildOutput {
+                    dry_run,
+                    checked: report.checked,
+                    in_sync: report.in_sync,
+                    baselined: report.baselined,
+                    repaired: report.repaired,
+                    drifted: report
+                        .drifted
+                        .iter()
+                        .map(|drift| StockDriftOutput {
+                            product_id: drift.product_id.to_string(),
+                            sku: drift.sku.clone(),
+                            stock: drift.stock,
+                            ledger_stock: drift.ledger_stock,
+                        })
+                        .collect(),
+                };
+                self.emit(&output, print_stock_rebuild);
+                Ok(ExitCode::SUCCESS)
+            }
+            Command::ApiKeys { action } => self.api_keys(action).await,
+        }
+    }
+
+    /// Fails when an index is missing, so `indexes verify` can gate a deploy.
+    fn indexes(&self, reports: &[IndexReport]) -> ExitCode {
+        self.emit(&reports, |reports| print_indexes(reports));
+        if reports.iter().all(IndexReport::is_ok) {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+
+    async fn migrate(&self, action: cli::MigrateAction) -> anyhow::Result<ExitCode> {
+        let migrator = cli::migrator(&self.db);
+        match action {
+            cli::MigrateAction::Status => {
+                let statuses = migrator.status().await?;
+                self.emit(&statuses, |statuses| cli::print_status(statuses));
+            }
+            cli::MigrateAction::Up { dry_run } => {
+                let outcomes = migrator.run(dry_run).await?;
+                self.emit(&outcomes, |outcomes| cli::print_outcomes(outcomes));
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+
+    async fn api_keys(&self, action: ApiKeysAction) -> anyhow::Result<ExitCode> {
+        let service = ApiKeyService::new(
+            Arc::new(ApiKeyRepository::new(&self.db)) as Arc<dyn ApiKeyRepositoryPort>
+        )
+        .with_prefix(&config::get().auth.api_key_prefix);
+        match action {
+            ApiKeysAction::Issue { name } => {
+                let issued = service.issue(&name).await?;
+                let output = ApiKeyOutput {
+                    id: issued.key.id.map(|id| id.to_string()),
+                    name: issued.key.name,
+                    prefix: issued.key.prefix,
+                    created_at: issued.key.created_at,
+                    revoked_at: None,
+                    key: Some(issued.secret),
+                };
+                self.emit(&output, |key| {
+                    println!("Issued API key for {} ({})", key.name, key.prefix);
+                    println!("{}", key.key.as_deref().unwrap_or_default());
+                    println!("Store it now: it cannot be shown again.");
+                });
+            }
+            ApiKeysAction::List => {
+                let mut all = Vec::new();
+                for page in 1.. {
+                    let batch = service.list(Pagination { page, limit: 10 }).await?;
+                    let done = batch.len() < 10;
+                    all.extend(batch);
+                    if done {
+                        break;
+                    }
+                }
+                let keys: Vec<ApiKeyOutput> = all
+                    .into_iter()
+                    .map(|key| ApiKeyOutput {
+                        id: key.id.map(|id| id.to_string()),
+                        name: key.name,
+                        prefix: key.prefix,
+                        created_at: key.created_at,
+                        revoked_at: key.revoked_at,
+                        key: None,
+                    })
+                    .collect();
+                self.emit(&keys, |keys| {
+                    println!("{:<16} {:<24} {:<22} STATUS", "PREFIX", "NAME", "CREATED");
+                    for key in keys {
+                        let status = if key.revoked_at.is_some() {
+                            "revoked"
+                        } else {
+                            "active"
+                        };
+                        println!(
+                            "{:<16} {:<24} {:<22} {}",
+                            key.prefix,
+                            key.name,
+                            key.created_at.format("%Y-%m-%d %H:%M UTC"),
+                            status
+                        );
+                    }
+                });
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+
+    fn emit<T: Serialize + ?Sized>(&self, value: &T, human: impl FnOnce(&T)) {
+        match self.format {
+            Format::Human => human(value),
+            Format::Json => match serde_json::to_string_pretty(value) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("Failed to serialize output: {}", e),
+            },
+        }
+    }
+
+    // ===== WIRING =====
+
+    fn inventory(&self) -> Arc<InventoryService> {
+        let product_repo: Arc<dyn ProductRepositoryPort> = Arc::new(ProductRepository::new(
+            &self.db,
+            OutboxWriter::new(&self.db, self.transactions),
+        ));
+        let metrics: Arc<dyn MetricsPort> = Arc::new(NoopMetrics);
+        Arc::new(InventoryService::new(
+            product_repo.clone(),
+            Arc::new(InventoryLedgerRepository::new(&self.db)) as Arc<dyn InventoryLedgerPort>,
+            LowStockMonitor::new(product_repo, Arc::new(LogNotifier)),
+            metrics,
+        ))
+    }
+
+    fn seeder(&self) -> Seeder {
+        let outbox_writer = OutboxWriter::new(&self.db, self.transactions);
+        let user_repo: Arc<dyn UserRepositoryPort> =
+            Arc::new(UserRepository::new(&self.db, outbox_writer.clone()));
+        let product_repo: Arc<dyn ProductRepositoryPort> =
+            Arc::new(ProductRepository::new(&self.db, outbox_writer.clone()));
+        let category_repo: Arc<dyn CategoryRepositoryPort> =
+            Arc::new(CategoryRepository::new(&self.db, outbox_writer));
+        let trace_context: Arc<dyn TraceContextPort> = Arc::new(OtelTraceContext);
+        let audit = Arc::new(AuditService::new(
+            Arc::new(AuditLogRepository::new(
+                &self.db,
+                Duration::from_secs(config::get().audit.retention_days * 24 * 60 * 60),
+            )) as Arc<dyn AuditLogPort>,
+            trace_context,
+        ));
+
+        let users = Arc::new(UserService::new(user_repo, audit.clone()));
+        let categories = Arc::new(CategoryService::new(
+            category_repo.clone(),
+            product_repo.clone(),
+        ));
+        let products = Arc::new(ProductService::new(
+            product_repo.clone(),
+            category_repo.clone(),
+            self.inventory(),
+            audit,
+        ));
+        Seeder::new(users, categories, products, category_repo, product_repo)
+    }
+}
+
+// ===== OUTPUT =====
+
+#[derive(Debug, Serialize)]
+struct StockDriftOutput {
+    product_id: String,
+    sku: String,
+    stock: i32,
+    ledger_stock: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct StockRebuildOutput {
+    dry_run: bool,
+    checked: u64,
+    in_sync: u64,
+    baselined: u64,
+    repaired: u64,
+    drifted: Vec<StockDriftOutput>,
+}
+
+#[derive(Debug, Serialize)]
+struct ApiKeyOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    name: String,
+    prefix: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    revoked_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only when issued.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+}
+
+fn print_indexes(reports: &[IndexReport]) {
+    for report in reports {
+        let state = if report.is_ok() { "ok" } else { "MISSING" };
+        println!(
+            "{:<24} {:<8} {} declared",
+            report.collection,
+            state,
+            report.expected.len()
+        );
+        for name in &report.missing {
+            println!("{:<24}   missing:    {}", "", name);
+        }
+        for name in &report.unexpected {
+            println!("{:<24}   unexpected: {}", "", name);
+        }
+    }
+}
+
+fn print_rebuild(reports: &[RebuildReport]) {
+    for report in reports {
+        println!(
+            "{:<24} {} created, {} rebuilt, {} dropped, {} kept",
+            report.collection,
+            report.created.len(),
+            report.rebuilt.len(),
+            report.dropped.len(),
+            report.kept.len()
+        );
+        for (label, names) in [
+            ("created", &report.created),
+            ("rebuilt", &report.rebuilt),
+            ("dropped", &report.dropped),
+            ("kept", &report.kept),
+        ] {
+            for name in names {
+                println!("{:<24}   {:<8} {}", "", format!("{}:", label), name);
+            }
+        }
+    }
+}
+
+fn print_seed(report: &SeedReport) {
+    for (label, count) in [
+        ("users", report.users),
+        ("categories", report.categories),
+        ("products", report.products),
+    ] {
+        println!(
+            "{:<12} {} created, {} already present",
+            label, count.created, count.skipped
+        );
+    }
+}
+
+fn print_purge(reports: &[PurgeReport], dry_run: bool) {
+    let verb = if dry_run { "would purge" } else { "purged" };
+    for report in reports {
+        println!("{:<24} {} {}", report.collection, verb, report.purged);
+    }
+}
+
+fn print_stock_rebuild(output: &StockRebuildOutput) {
+    println!(
+        "{} checked, {} in sync, {} baselined, {} drifted, {} repaired",
+        output.checked,
+        output.in_sync,
+        output.baselined,
+        output.drifted.len(),
+        output.repaired
+    );
+    for drift in &output.drifted {
+        println!(
+            "  {} ({}): stock {} vs ledger {}",
+            drift.product_id, drift.sku, drift.stock, drift.ledger_stock
+        );
+    }
+    if output.dry_run && !output.drifted.is_empty() {
+        println!("Dry run: run without --dry-run to reset stock to the ledger");
+    }
+}
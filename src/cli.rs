@@ -0,0 +1,144 @@
+use crate::application::lock::Locks;
+use crate::config::MigrationsMode;
+use crate::domain::error::DomainResult;
+use crate::infrastructure::persistence::lease::MongoLock;
+use crate::infrastructure::persistence::migrations::runner::{
+    MigrationOutcome, MigrationStatus, Migrator,
+};
+use clap::{Parser, Subcommand};
+use mongodb::Database;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+/// Runs the HTTP server unless a maintenance subcommand is given.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Print the resolved settings (secrets redacted) and exit
+    #[arg(long, global = true)]
+    pub print_config: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the HTTP server (default)
+    Serve,
+    /// Manage MongoDB schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateAction {
+    /// List applied and pending migrations
+    Status,
+    /// Apply pending migrations in order
+    Up {
+        /// Report what each pending migration would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Migrations of `db`, locked in `db` itself so that instances agree on the
+/// lock whether or not Redis is up.
+pub fn migrator(db: &Database) -> Migrator {
+    Migrator::new(db, Locks::new(Arc::new(MongoLock::new(db))))
+}
+
+/// `service migrate ...`: prints the result and fails when migrations fail.
+pub async fn migrate(db: &Database, action: MigrateAction) -> ExitCode {
+    let migrator = migrator(db);
+    let result = match action {
+        MigrateAction::Status => migrator.status().await.map(|s| print_status(&s)),
+        MigrateAction::Up { dry_run } => migrator.run(dry_run).await.map(|o| print_outcomes(&o)),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Migration failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `MIGRATIONS_ON_STARTUP`: `apply`, `dry-run`, `status` or `off`.
+pub async fn migrate_on_startup(db: &Database, mode: MigrationsMode) -> DomainResult<()> {
+    let migrator = migrator(db);
+    match mode {
+        MigrationsMode::Apply => {
+            migrator.run(false).await?;
+        }
+        MigrationsMode::DryRun => {
+            for outcome in migrator.run(true).await? {
+                tracing::info!(
+                    "Pending migration {} {}: {}",
+                    outcome.version,
+                    outcome.name,
+                    outcome.summary
+                );
+            }
+        }
+        MigrationsMode::Status => {
+            for status in migrator.status().await? {
+                if status.is_pending() {
+                    tracing::warn!(
+                        "Pending migration {} {} (run `service migrate up`)",
+                        status.version,
+                        status.name
+                    );
+                } else if status.unknown {
+                    tracing::warn!(
+                        "Migration {} {} was applied by a newer release",
+                        status.version,
+                        status.name
+                    );
+                }
+            }
+        }
+        MigrationsMode::Off => {}
+    }
+    Ok(())
+}
+
+pub fn print_status(statuses: &[MigrationStatus]) {
+    if statuses.is_empty() {
+        println!("No migrations");
+        return;
+    }
+
+    println!("{:<8} {:<32} STATUS", "VERSION", "NAME");
+    for status in statuses {
+        let state = match (&status.applied_at, status.unknown) {
+            (None, _) => "pending".to_string(),
+            (Some(at), false) => format!("applied {}", at.format("%Y-%m-%d %H:%M:%S UTC")),
+            (Some(at), true) => format!(
+                "applied {} (unknown to this release)",
+                at.format("%Y-%m-%d %H:%M:%S UTC")
+            ),
+        };
+        println!("{:<8} {:<32} {}", status.version, status.name, state);
+        if let Some(summary) = &status.summary {
+            println!("{:<8} {:<32} {}", "", "", summary);
+        }
+    }
+}
+
+pub fn print_outcomes(outcomes: &[MigrationOutcome]) {
+    if outcomes.is_empty() {
+        println!("No pending migrations");
+    }
+    for outcome in outcomes {
+        let prefix = if outcome.dry_run { "[dry-run] " } else { "" };
+        println!(
+            "{}{} {}: {} ({} ms)",
+            prefix, outcome.version, outcome.name, outcome.summary, outcome.duration_ms
+        );
+    }
+}
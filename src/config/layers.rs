@@ -0,0 +1,231 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Every problem found while resolving the settings, reported together.
+#[derive(Debug)]
+pub struct ConfigError {
+    pub problems: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid configuration:")?;
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The TOML file and environment layers over the defaults. Lookups never
+/// fail: problems are collected and returned by [`Layers::finish`].
+pub(super) struct Layers {
+    env: HashMap<String, String>,
+    file: Option<PathBuf>,
+    /// File settings not read yet, by dotted key (`server.port`).
+    entries: BTreeMap<String, toml::Value>,
+    problems: Vec<String>,
+}
+
+impl Layers {
+    /// `CONFIG_FILE` when set (it must exist), otherwise
+    /// `config/{app_env}.toml` when there is one.
+    pub fn new(env: HashMap<String, String>, app_env: &str) -> Self {
+        let mut layers = Self {
+            env,
+            file: None,
+            entries: BTreeMap::new(),
+            problems: Vec::new(),
+        };
+
+        let (path, explicit) = match layers.env_var("CONFIG_FILE") {
+            Some(path) => (PathBuf::from(path), true),
+            None => (
+                PathBuf::from(format!("config/{}.toml", app_env.to_lowercase())),
+                false,
+            ),
+        };
+        if explicit || path.exists() {
+            layers.read_file(&path);
+        }
+        layers
+    }
+
+    /// The file the settings were read from, if any.
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// An environment variable, ignoring empty ones like `SEED_FILE=`.
+    pub fn env_var(&self, var: &str) -> Option<String> {
+        self.env.get(var).filter(|value| !value.is_empty()).cloned()
+    }
+
+    /// `var` over `key` in the file over `default`.
+    pub fn get<T>(&mut self, key: &str, var: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.optional(key, var).unwrap_or(default)
+    }
+
+    /// Like [`Layers::get`], but a value of `0` is a problem.
+    pub fn positive(&mut self, key: &str, var: &str, default: u64) -> u64 {
+        let value = self.get(key, var, default);
+        if value == 0 {
+            self.problems
+                .push(format!("{} must be greater than 0", self.name(key, var)));
+            return default;
+        }
+        value
+    }
+
+    /// `var` or `key` in the file; a problem when neither is set and
+    /// `required` holds.
+    pub fn required<T>(&mut self, key: &str, var: &str, required: bool) -> T
+    where
+        T: FromStr + Default,
+        T::Err: fmt::Display,
+    {
+        match self.optional(key, var) {
+            Some(value) => value,
+            None => {
+                if required {
+                    self.problems
+                        .push(format!("{} is required", self.name(key, var)));
+                }
+                T::default()
+            }
+        }
+    }
+
+    pub fn optional<T>(&mut self, key: &str, var: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        // Taken even when the variable wins, so it is not reported as unknown
+        let from_file = self.entries.remove(key);
+
+        let (source, raw) = match (self.env_var(var), from_file) {
+            (Some(value), _) => (var.to_string(), value),
+            (None, Some(value)) => {
+                let source = self.file_source(key);
+                match scalar(&value) {
+                    Some(raw) => (source, raw),
+                    None => {
+                        self.problems.push(format!(
+                            "{}: expected a string, number, boolean or list",
+                            source
+                        ));
+                        return None;
+                    }
+                }
+            }
+            (None, None) => return None,
+        };
+
+        match raw.trim().parse() {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.problems
+                    .push(format!("{}: invalid value '{}': {}", source, raw, e));
+                None
+            }
+        }
+    }
+
+    /// Records a problem found across settings.
+    pub fn problem(&mut self, problem: impl Into<String>) {
+        self.problems.push(problem.into());
+    }
+
+    /// The settings, or every problem found, including file keys nothing read.
+    pub fn finish<T>(mut self, config: T) -> Result<T, ConfigError> {
+        let unknown: Vec<String> = self.entries.keys().cloned().collect();
+        for key in unknown {
+            let source = self.file_source(&key);
+            self.problems.push(format!("{}: unknown setting", source));
+        }
+
+        if self.problems.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError {
+                problems: self.problems,
+            })
+        }
+    }
+
+    fn read_file(&mut self, path: &Path) {
+        let table = match std::fs::read_to_string(path) {
+            Ok(contents) => contents.parse::<toml::Table>(),
+            Err(e) => {
+                self.problems
+                    .push(format!("{}: cannot read: {}", path.display(), e));
+                return;
+            }
+        };
+        match table {
+            Ok(table) => {
+                flatten("", table, &mut self.entries);
+                self.file = Some(path.to_path_buf());
+            }
+            Err(e) => self
+                .problems
+                .push(format!("{}: {}", path.display(), e.message())),
+        }
+    }
+
+    /// How to refer to a setting: the variable, plus the file key when a
+    /// file is in use.
+    fn name(&self, key: &str, var: &str) -> String {
+        match &self.file {
+            Some(path) => format!("{} (or `{}` in {})", var, key, path.display()),
+            None => var.to_string(),
+        }
+    }
+
+    fn file_source(&self, key: &str) -> String {
+        let path = self.file.as_deref().unwrap_or(Path::new("config"));
+        format!("{} `{}`", path.display(), key)
+    }
+}
+
+/// `[server] port = 8080` becomes `server.port`.
+fn flatten(prefix: &str, table: toml::Table, entries: &mut BTreeMap<String, toml::Value>) {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, entries),
+            value => {
+                entries.insert(key, value);
+            }
+        }
+    }
+}
+
+/// File values are parsed like variables; lists are comma-separated.
+fn scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Array(items) => items
+            .iter()
+            .map(scalar)
+            .collect::<Option<Vec<_>>>()
+            .map(|items| items.join(",")),
+        toml::Value::Datetime(_) | toml::Value::Table(_) => None,
+    }
+}
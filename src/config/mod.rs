@@ -0,0 +1,682 @@
+//! Service settings, resolved once at startup from three layers, each
+//! overriding the previous one:
+//!
+//! 1. the defaults below;
+//! 2. a TOML file: `CONFIG_FILE`, or `config/{app_env}.toml` when present;
+//! 3. environment variables, including `.env`.
+//!
+//! Every missing or invalid setting is collected into a single
+//! [`ConfigError`], so a bad deploy reports all its problems at once.
+
+mod layers;
+mod secret;
+
+pub use layers::ConfigError;
+pub use secret::Secret;
+
+use crate::domain::entities::country::Country;
+use crate::domain::job::Cron;
+use crate::domain::port::rate_limit::RateLimit;
+use axum::http::{HeaderName, HeaderValue};
+use chrono_tz::Tz;
+use dotenvy::dotenv;
+use layers::Layers;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The TOML file the settings were read from, if any.
+    pub file: Option<PathBuf>,
+    pub server: ServerConfig,
+    pub cors: CorsConfig,
+    pub auth: AuthConfig,
+    pub persistence: PersistenceConfig,
+    pub mongo: MongoConfig,
+    pub postgres: PostgresConfig,
+    pub redis: RedisConfig,
+    pub telemetry: TelemetryConfig,
+    pub events: EventsConfig,
+    pub tasks: TasksConfig,
+    pub inventory: InventoryConfig,
+    pub webhooks: WebhooksConfig,
+    pub audit: AuditConfig,
+    pub storage: StorageConfig,
+    pub rate_limit: RateLimitConfig,
+    pub scheduler: SchedulerConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub port: u16,
+    pub service_name: String,
+    /// `APP_ENV` (`LCL`, `SBX`, `PRD`, ...); also picks the TOML file, so it
+    /// is only read from the environment.
+    pub app_env: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    pub origins: CorsOrigins,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// Request header carrying a partner's API key.
+    pub api_key_header: HeaderName,
+    /// Prefix of issued API keys (`sk_`), kept in clear to tell them apart.
+    pub api_key_prefix: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PersistenceConfig {
+    /// Storage adapters: `mongo`, `postgres`, or `memory` (nothing survives a restart).
+    pub backend: Persistence,
+    /// Fixtures loaded at startup through the services (idempotent); none when unset.
+    pub seed_file: Option<String>,
+}
+
+/// Only required with `PERSISTENCE=mongo`. Driver settings left unset keep
+/// the value from the URL, or the driver's default.
+#[derive(Debug, Clone)]
+pub struct MongoConfig {
+    pub url: Secret,
+    pub db: String,
+    /// Migrations at startup: `apply`, `dry-run`, `status` (log pending) or `off`.
+    pub migrations_on_startup: MigrationsMode,
+    pub max_pool_size: Option<u32>,
+    pub min_pool_size: Option<u32>,
+    pub connect_timeout_ms: Option<u64>,
+    /// How long an operation waits for a suitable server, e.g. during a failover.
+    pub server_selection_timeout_ms: Option<u64>,
+    pub read_preference: Option<ReadPreference>,
+    pub read_concern: Option<ReadConcern>,
+    pub write_concern: Option<WriteConcern>,
+    pub retry_writes: Option<bool>,
+    /// Wire compression, in order of preference; none when empty.
+    pub compressors: Vec<Compressor>,
+    /// Pings at startup before giving up on MongoDB.
+    pub connect_attempts: u64,
+    /// Delay after the first failed ping; doubles on each attempt.
+    pub connect_backoff_ms: u64,
+}
+
+/// Only required with `PERSISTENCE=postgres`.
+#[derive(Debug, Clone)]
+pub struct PostgresConfig {
+    pub url: Secret,
+    /// Accepts keeping the ports with no PostgreSQL adapter (categories,
+    /// inventory ledger, webhooks, audit log, API keys) in process. Without
+    /// it `PERSISTENCE=postgres` is refused.
+    pub allow_in_memory: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    pub url: Secret,
+}
+
+#[derive(Debug, Clone)]
+pub struct TelemetryConfig {
+    pub debug_level: String,
+    /// Log event format: `stackdriver`, `json`, `pretty` or `none`.
+    pub log_format: LogFormat,
+    /// Span exporter: `gcp`, `otlp`, `stdout`, `json` or `none`.
+    pub tracing_exporter: TracingExporter,
+    /// OTLP collector base URL; the exporter's default (`localhost`) when unset.
+    pub otlp_endpoint: Option<String>,
+    pub otlp_protocol: OtlpProtocol,
+    /// GCP project of the traces and log entries; required with
+    /// `TRACING_EXPORTER=gcp` or `LOG_FORMAT=stackdriver`.
+    pub project_id: String,
+    /// Serve Prometheus metrics at `/metrics`; when off, nothing is recorded.
+    pub metrics_enabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventsConfig {
+    /// Where outbox events are relayed: `redis`, `memory` or `none` (kept in the outbox).
+    pub publisher: EventPublisher,
+    /// Milliseconds between outbox polls when the relay is idle.
+    pub relay_interval_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TasksConfig {
+    /// Background task workers per instance; `0` only enqueues (another instance works).
+    pub workers: usize,
+    /// Milliseconds an idle worker waits before polling the queue again.
+    pub poll_interval_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct InventoryConfig {
+    /// Where `LowStock` alerts are POSTed; alerts are only logged when unset.
+    pub low_stock_webhook_url: Option<Secret>,
+    /// Seconds between background inventory reconciliations; `0` disables them.
+    pub reconcile_interval_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhooksConfig {
+    /// Seconds to wait for a partner endpoint before a webhook attempt fails.
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditConfig {
+    /// Days audit entries are kept before MongoDB expires them.
+    pub retention_days: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    pub bucket: String,
+}
+
+/// Route groups with their own limit: the prefixes under `/api/v1`.
+pub const RATE_LIMIT_GROUPS: &[&str] = &[
+    "users",
+    "products",
+    "categories",
+    "orders",
+    "inventory",
+    "webhooks",
+    "audit",
+    "admin",
+];
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Limit of each route group without its own, per client.
+    pub default: RateLimit,
+    /// Per route group of [`RATE_LIMIT_GROUPS`]: `RATE_LIMIT_ORDERS=60/1m`.
+    pub groups: BTreeMap<String, RateLimit>,
+}
+
+/// Recurring jobs; each runs only when its cron expression is set.
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Market whose timezone the cron expressions are evaluated in; UTC when unset.
+    pub country: Option<Country>,
+    /// Permanently removes soft-deleted MongoDB documents.
+    pub purge_deleted: Option<Cron>,
+    /// Days soft-deleted documents are kept before the purge job removes them.
+    pub purge_after_days: u64,
+    /// Logs the previous day's sales totals.
+    pub sales_summary: Option<Cron>,
+}
+
+impl SchedulerConfig {
+    pub fn timezone(&self) -> Tz {
+        self.country
+            .as_ref()
+            .map(Country::timezone_offset)
+            .unwrap_or(Tz::UTC)
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Resolves the settings from `.env`, the TOML file and the environment.
+/// Runs once, first thing at startup; later calls return the same settings.
+pub fn load() -> Result<&'static Config, ConfigError> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    dotenv().ok();
+    let env = std::env::vars_os()
+        .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+        .collect();
+    let config = Config::resolve(env)?;
+
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// The settings resolved by [`load`].
+pub fn get() -> &'static Config {
+    CONFIG
+        .get()
+        .expect("config::load() must run before the settings are read")
+}
+
+impl Config {
+    /// Resolves the settings from `env` and the TOML file it points at.
+    pub fn resolve(env: HashMap<String, String>) -> Result<Self, ConfigError> {
+        let app_env = env
+            .get("APP_ENV")
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_uppercase())
+            .unwrap_or_else(|| "DEV".to_string());
+        let mut l = Layers::new(env, &app_env);
+
+        // Each backend's settings are only required when it is selected
+        let backend = l.get("persistence.backend", "PERSISTENCE", Persistence::Mongo);
+        let mongo = backend == Persistence::Mongo;
+        let postgres = backend == Persistence::Postgres;
+
+        // Cloud Trace and Cloud Logging both need the GCP project
+        let log_format = l.get("telemetry.log_format", "LOG_FORMAT", LogFormat::Stackdriver);
+        let tracing_exporter = l.get(
+            "telemetry.tracing_exporter",
+            "TRACING_EXPORTER",
+            TracingExporter::Gcp,
+        );
+        let gcp = tracing_exporter == TracingExporter::Gcp || log_format == LogFormat::Stackdriver;
+
+        let config = Self {
+            file: l.file().map(|path| path.to_path_buf()),
+            server: ServerConfig {
+                port: l.get("server.port", "PORT", 3000),
+                service_name: l.required("server.service_name", "SERVICE_NAME", true),
+                app_env,
+            },
+            cors: CorsConfig {
+                origins: l.get("cors.origins", "CORS_ORIGINS", CorsOrigins::Any),
+            },
+            auth: AuthConfig {
+                api_key_header: l.get(
+                    "auth.api_key_header",
+                    "API_KEY_HEADER",
+                    HeaderName::from_static("x-api-key"),
+                ),
+                api_key_prefix: l.get("auth.api_key_prefix", "API_KEY_PREFIX", "sk_".to_string()),
+            },
+            persistence: PersistenceConfig {
+                backend,
+                seed_file: l.optional("persistence.seed_file", "SEED_FILE"),
+            },
+            mongo: MongoConfig {
+                url: l.required("mongo.url", "MONGO_URL", mongo),
+                db: l.required("mongo.db", "MONGO_DB", mongo),
+                migrations_on_startup: l.get(
+                    "mongo.migrations_on_startup",
+                    "MIGRATIONS_ON_STARTUP",
+                    MigrationsMode::Status,
+                ),
+                max_pool_size: l.optional("mongo.max_pool_size", "MONGO_MAX_POOL_SIZE"),
+                min_pool_size: l.optional("mongo.min_pool_size", "MONGO_MIN_POOL_SIZE"),
+                connect_timeout_ms: l
+                    .optional("mongo.connect_timeout_ms", "MONGO_CONNECT_TIMEOUT_MS"),
+                server_selection_timeout_ms: l.optional(
+                    "mongo.server_selection_timeout_ms",
+                    "MONGO_SERVER_SELECTION_TIMEOUT_MS",
+                ),
+                read_preference: l.optional("mongo.read_preference", "MONGO_READ_PREFERENCE"),
+                read_concern: l.optional("mongo.read_concern", "MONGO_READ_CONCERN"),
+                write_concern: l.optional("mongo.write_concern", "MONGO_WRITE_CONCERN"),
+                retry_writes: l
+                    .optional::<Flag>("mongo.retry_writes", "MONGO_RETRY_WRITES")
+                    .map(|flag| flag.0),
+                compressors: l
+                    .get("mongo.compressors", "MONGO_COMPRESSORS", List(Vec::new()))
+                    .0,
+                connect_attempts: l.positive("mongo.connect_attempts", "MONGO_CONNECT_ATTEMPTS", 5),
+                connect_backoff_ms: l.positive(
+                    "mongo.connect_backoff_ms",
+                    "MONGO_CONNECT_BACKOFF_MS",
+                    1000,
+                ),
+            },
+            postgres: PostgresConfig {
+                url: l.required("postgres.url", "POSTGRES_URL", postgres),
+                allow_in_memory: l
+                    .get(
+                        "postgres.allow_in_memory",
+                        "POSTGRES_ALLOW_IN_MEMORY",
+                        Flag(false),
+                    )
+                    .0,
+            },
+            redis: RedisConfig {
+                url: l.get(
+                    "redis.url",
+                    "REDIS_URL",
+                    Secret::new("redis://127.0.0.1:6379"),
+                ),
+            },
+            telemetry: TelemetryConfig {
+                debug_level: l.get("telemetry.debug_level", "DEBUG_LEVEL", "info".to_string()),
+                log_format,
+                tracing_exporter,
+                otlp_endpoint: l.optional("telemetry.otlp_endpoint", "OTEL_EXPORTER_OTLP_ENDPOINT"),
+                otlp_protocol: l.get(
+                    "telemetry.otlp_protocol",
+                    "OTEL_EXPORTER_OTLP_PROTOCOL",
+                    OtlpProtocol::Grpc,
+                ),
+                project_id: l.required("telemetry.project_id", "PROJECT_ID", gcp),
+                metrics_enabled: l
+                    .get("telemetry.metrics_enabled", "METRICS_ENABLED", Flag(true))
+                    .0,
+            },
+            events: EventsConfig {
+                publisher: l.get("events.publisher", "EVENT_PUBLISHER", EventPublisher::Redis),
+                relay_interval_ms: l.positive(
+                    "events.relay_interval_ms",
+                    "OUTBOX_RELAY_INTERVAL_MS",
+                    1000,
+                ),
+            },
+            tasks: TasksConfig {
+                workers: l.get("tasks.workers", "TASK_WORKERS", 2),
+                poll_interval_ms: l.positive(
+                    "tasks.poll_interval_ms",
+                    "TASK_POLL_INTERVAL_MS",
+                    1000,
+                ),
+            },
+            inventory: InventoryConfig {
+                low_stock_webhook_url: l
+                    .optional("inventory.low_stock_webhook_url", "LOW_STOCK_WEBHOOK_URL"),
+                reconcile_interval_secs: l.get(
+                    "inventory.reconcile_interval_secs",
+                    "INVENTORY_RECONCILE_INTERVAL_SECS",
+                    0,
+                ),
+            },
+            webhooks: WebhooksConfig {
+                timeout_secs: l.positive("webhooks.timeout_secs", "WEBHOOK_TIMEOUT_SECS", 10),
+            },
+            audit: AuditConfig {
+                retention_days: l.positive("audit.retention_days", "AUDIT_RETENTION_DAYS", 365),
+            },
+            storage: StorageConfig {
+                bucket: l.get("storage.bucket", "STORAGE_BUCKET", String::new()),
+            },
+            rate_limit: RateLimitConfig {
+                enabled: l
+                    .get("rate_limit.enabled", "RATE_LIMIT_ENABLED", Flag(false))
+                    .0,
+                default: l.get(
+                    "rate_limit.default",
+                    "RATE_LIMIT_DEFAULT",
+                    RateLimit {
+                        requests: 600,
+                        window: Duration::from_secs(60),
+                    },
+                ),
+                groups: RATE_LIMIT_GROUPS
+                    .iter()
+                    .filter_map(|group| {
+                        let key = format!("rate_limit.{}", group);
+                        let var = format!("RATE_LIMIT_{}", group.to_uppercase());
+                        let limit = l.optional(&key, &var)?;
+                        Some((group.to_string(), limit))
+                    })
+                    .collect(),
+            },
+            scheduler: SchedulerConfig {
+                country: l
+                    .optional::<CountryCode>("scheduler.country", "SCHEDULER_COUNTRY")
+                    .map(|code| code.0),
+                purge_deleted: l.optional("scheduler.purge_deleted", "SCHEDULER_PURGE_DELETED"),
+                purge_after_days: l.positive(
+                    "scheduler.purge_after_days",
+                    "SCHEDULER_PURGE_AFTER_DAYS",
+                    30,
+                ),
+                sales_summary: l.optional("scheduler.sales_summary", "SCHEDULER_SALES_SUMMARY"),
+            },
+        };
+
+        if postgres && !config.postgres.allow_in_memory {
+            l.problem(
+                "PERSISTENCE=postgres keeps categories, inventory ledger, webhooks, audit log \
+                 and API keys in process, lost on restart; set POSTGRES_ALLOW_IN_MEMORY=true \
+                 to accept that",
+            );
+        }
+
+        if config.auth.api_key_prefix.is_empty()
+            || !config
+                .auth
+                .api_key_prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            l.problem(format!(
+                "API_KEY_PREFIX ({:?}) must be non-empty letters, digits or underscores",
+                config.auth.api_key_prefix
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (config.mongo.min_pool_size, config.mongo.max_pool_size)
+            && min > max
+        {
+            l.problem(format!(
+                "MONGO_MIN_POOL_SIZE ({}) is greater than MONGO_MAX_POOL_SIZE ({})",
+                min, max
+            ));
+        }
+
+        l.finish(config)
+    }
+}
+
+/// Declares a setting with a fixed set of values, parsed case-insensitively
+/// and displayed as written in the environment.
+macro_rules! choice {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident = $value:literal $(| $alias:literal)*,)+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl $name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value,)+
+                }
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_lowercase().as_str() {
+                    $($value $(| $alias)* => Ok(Self::$variant),)+
+                    _ => Err(format!("expected one of {}", [$($value),+].join(", "))),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+choice! {
+    Persistence {
+        Mongo = "mongo",
+        Postgres = "postgres",
+        Memory = "memory",
+    }
+}
+
+choice! {
+    LogFormat {
+        Stackdriver = "stackdriver",
+        Json = "json",
+        Pretty = "pretty",
+        None = "none",
+    }
+}
+
+choice! {
+    TracingExporter {
+        Gcp = "gcp",
+        Otlp = "otlp",
+        Stdout = "stdout",
+        Json = "json",
+        None = "none",
+    }
+}
+
+choice! {
+    OtlpProtocol {
+        Grpc = "grpc",
+        HttpProtobuf = "http/protobuf" | "http",
+    }
+}
+
+choice! {
+    EventPublisher {
+        Redis = "redis",
+        Memory = "memory",
+        None = "none",
+    }
+}
+
+choice! {
+    /// What the service does with pending MongoDB migrations on startup.
+    MigrationsMode {
+        Apply = "apply",
+        DryRun = "dry-run",
+        Status = "status",
+        Off = "off",
+    }
+}
+
+choice! {
+    /// Which members of the replica set serve reads.
+    ReadPreference {
+        Primary = "primary",
+        PrimaryPreferred = "primary_preferred" | "primarypreferred",
+        Secondary = "secondary",
+        SecondaryPreferred = "secondary_preferred" | "secondarypreferred",
+        Nearest = "nearest",
+    }
+}
+
+choice! {
+    ReadConcern {
+        Local = "local",
+        Available = "available",
+        Majority = "majority",
+        Linearizable = "linearizable",
+    }
+}
+
+choice! {
+    Compressor {
+        Zstd = "zstd",
+        Zlib = "zlib",
+        Snappy = "snappy",
+    }
+}
+
+/// `MONGO_WRITE_CONCERN`: `majority`, or how many members must acknowledge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteConcern {
+    Majority,
+    Nodes(u32),
+}
+
+impl FromStr for WriteConcern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("majority") {
+            return Ok(Self::Majority);
+        }
+        s.parse()
+            .map(Self::Nodes)
+            .map_err(|_| "expected majority or a number of members".to_string())
+    }
+}
+
+/// `CORS_ORIGINS`: `*`, or a comma-separated list of origins.
+#[derive(Debug, Clone)]
+pub enum CorsOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+impl FromStr for CorsOrigins {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() == "*" {
+            return Ok(Self::Any);
+        }
+
+        s.split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(|origin| {
+                if origin == "*" {
+                    return Err("`*` cannot be combined with other origins".to_string());
+                }
+                if !origin.starts_with("http://") && !origin.starts_with("https://") {
+                    return Err(format!(
+                        "origin '{}' must start with http:// or https://",
+                        origin
+                    ));
+                }
+                HeaderValue::from_str(origin)
+                    .map_err(|_| format!("origin '{}' is not valid", origin))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::List)
+    }
+}
+
+/// A comma-separated list; a list in the TOML file.
+struct List<T>(Vec<T>);
+
+impl<T: FromStr> FromStr for List<T>
+where
+    T::Err: fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.parse().map_err(|e| format!("'{}': {}", item, e)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+/// A market code, in any case (`MEX`, `chl`).
+struct CountryCode(Country);
+
+impl FromStr for CountryCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.to_uppercase()
+            .parse()
+            .map(Self)
+            .map_err(|_| "expected one of MEX, CHL, COL, PER".to_string())
+    }
+}
+
+/// A boolean that also takes `1`/`0`, `yes`/`no` and `on`/`off`.
+struct Flag(bool);
+
+impl FromStr for Flag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(Self(true)),
+            "false" | "0" | "no" | "off" => Ok(Self(false)),
+            _ => Err("expected true or false".to_string()),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::values;
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyMarker;
+pub type ApiKeyId = values::DomainId<ApiKeyMarker>;
+
+/// A credential issued to a partner or internal client. Only the SHA-256 hash
+/// of the key is stored; the key itself is shown once, when it is issued.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKey {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<ApiKeyId>,
+    /// Who the key was issued to, e.g. the partner name.
+    pub name: String,
+    /// First characters of the key, to recognise it in logs and listings.
+    pub prefix: String,
+    pub key_hash: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::values;
+
+#[derive(Debug, Clone)]
+pub struct AuditEntryMarker;
+pub type AuditEntryId = values::DomainId<AuditEntryMarker>;
+
+/// Actor recorded for changes made without an identified caller.
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// Who performed a change, as seen at the edge of the system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Actor {
+    pub id: String,
+    pub ip: Option<String>,
+}
+
+impl Actor {
+    pub fn new(id: impl Into<String>, ip: Option<String>) -> Self {
+        Self { id: id.into(), ip }
+    }
+
+    /// Changes made by the service itself (jobs, admin tooling).
+    pub fn system() -> Self {
+        Self::new("system", None)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    /// Lifecycle change other than deletion, e.g. an order being cancelled.
+    Transition,
+}
+
+/// One field that differs between the before and after snapshots.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+}
+
+/// Append-only record of a mutating operation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<AuditEntryId>,
+    pub actor: String,
+    pub action: AuditAction,
+    /// Entity type: `User`, `Product`, `Order`.
+    pub entity: String,
+    pub entity_id: String,
+    pub changes: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Top-level fields that differ between two JSON snapshots of an entity.
+/// Timestamps maintained by the repositories are left out.
+pub fn diff(
+    before: Option<&serde_json::Value>,
+    after: Option<&serde_json::Value>,
+) -> Vec<FieldChange> {
+    const IGNORED: &[&str] = &["id", "updated_at", "version"];
+
+    let empty = serde_json::Map::new();
+    let before = before.and_then(|v| v.as_object()).unwrap_or(&empty);
+    let after = after.and_then(|v| v.as_object()).unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|field| !IGNORED.contains(&field.as_str()))
+        .filter_map(|field| {
+            let old = before.get(field);
+            let new = after.get(field);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect()
+}
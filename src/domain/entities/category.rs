@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::values;
+
+#[derive(Debug, Clone)]
+pub struct CategoryMarker;
+pub type CategoryId = values::DomainId<CategoryMarker>;
+
+/// Separator used between slugs in a materialised path (`electronics/phones`).
+pub const PATH_SEPARATOR: char = '/';
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Category {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<CategoryId>,
+    pub name: String,
+    pub slug: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<CategoryId>,
+    /// Materialised path of slugs from the root down to this category.
+    pub path: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl Category {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Number of ancestors above this category (roots have depth 0).
+    pub fn depth(&self) -> usize {
+        self.path.matches(PATH_SEPARATOR).count()
+    }
+
+    /// Builds the materialised path for `slug` under an optional parent path.
+    pub fn child_path(parent_path: Option<&str>, slug: &str) -> String {
+        match parent_path {
+            Some(parent) => format!("{}{}{}", parent, PATH_SEPARATOR, slug),
+            None => slug.to_string(),
+        }
+    }
+
+    /// Whether `path` is this category or one of its descendants.
+    pub fn contains_path(&self, path: &str) -> bool {
+        path == self.path
+            || path
+                .strip_prefix(self.path.as_str())
+                .is_some_and(|rest| rest.starts_with(PATH_SEPARATOR))
+    }
+
+    /// Normalises free-form text into a URL-safe slug (`"Home & Garden"` → `home-garden`).
+    pub fn slugify(value: &str) -> String {
+        let mut slug = String::with_capacity(value.len());
+        for c in value.trim().chars().flat_map(char::to_lowercase) {
+            if c.is_ascii_alphanumeric() {
+                slug.push(c);
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        slug.trim_end_matches('-').to_string()
+    }
+}
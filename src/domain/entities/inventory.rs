@@ -0,0 +1,98 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::product::ProductId;
+use crate::domain::values;
+
+#[derive(Debug, Clone)]
+pub struct MovementMarker;
+pub type MovementId = values::DomainId<MovementMarker>;
+
+/// Why a product's stock changed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MovementKind {
+    /// Stock the product was created with.
+    Initial,
+    /// Units sold by an order.
+    Sale,
+    /// Units returned to stock when an order is cancelled.
+    OrderCancellation,
+    /// Manual correction by an operator; always carries a reason.
+    Adjustment,
+    /// Stock set by a bulk import.
+    Import,
+    /// Opening balance recorded for products that predate the ledger.
+    Opening,
+}
+
+/// One append-only entry in the inventory ledger.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryMovement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<MovementId>,
+    pub product_id: ProductId,
+    /// Signed change in units (negative for outflows).
+    pub delta: i32,
+    pub kind: MovementKind,
+    /// Identifier of what caused the movement (order ID, import batch ID, ...).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Product stock right after the movement was applied, when known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stock_after: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InventoryMovement {
+    pub fn new(product_id: ProductId, delta: i32, kind: MovementKind) -> Self {
+        Self {
+            id: None,
+            product_id,
+            delta,
+            kind,
+            reference: None,
+            reason: None,
+            stock_after: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Opening entry of a newly created product: all of its stock.
+    pub fn initial(product_id: ProductId, stock: i32) -> Self {
+        Self::new(product_id, stock, MovementKind::Initial).with_stock_after(stock)
+    }
+
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    pub fn with_stock_after(mut self, stock: i32) -> Self {
+        self.stock_after = Some(stock);
+        self
+    }
+}
+
+/// A product whose stored stock disagrees with the sum of its ledger.
+#[derive(Debug, Clone)]
+pub struct StockDrift {
+    pub product_id: ProductId,
+    pub sku: String,
+    pub stock: i32,
+    pub ledger_stock: i64,
+}
+
+impl StockDrift {
+    /// Stored stock minus ledger stock.
+    pub fn drift(&self) -> i64 {
+        i64::from(self.stock) - self.ledger_stock
+    }
+}
@@ -1,4 +1,9 @@
+pub mod api_key;
+pub mod audit;
+pub mod category;
 pub mod country;
+pub mod inventory;
 pub mod order;
 pub mod product;
 pub mod user;
+pub mod webhook;
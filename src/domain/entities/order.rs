@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::entities::country::Country;
 use crate::domain::entities::product::ProductId;
 use crate::domain::entities::user::UserId;
 use crate::domain::values;
@@ -9,6 +10,14 @@ use crate::domain::values;
 pub struct OrderMarker;
 pub type OrderId = values::DomainId<OrderMarker>;
 
+/// Derived from the soft-delete marker: a cancelled order is a deleted one.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Placed,
+    Cancelled,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Order {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -17,8 +26,14 @@ pub struct Order {
     pub product_id: ProductId,
     pub quantity: i32,
     pub total_price: f64,
+    /// Market the order was placed in, when the client sent it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<Country>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Incremented by every write; clients send it back in `If-Match`.
+    #[serde(default)]
+    pub version: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -27,4 +42,12 @@ impl Order {
     pub fn is_deleted(&self) -> bool {
         self.deleted_at.is_some()
     }
+
+    pub fn status(&self) -> OrderStatus {
+        if self.is_deleted() {
+            OrderStatus::Cancelled
+        } else {
+            OrderStatus::Placed
+        }
+    }
 }
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::inventory::{InventoryMovement, MovementKind};
+use crate::domain::event::DomainEvent;
 use crate::domain::values;
 
 #[derive(Debug, Clone)]
@@ -20,7 +23,9 @@ pub enum ProductStatus {
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ProductMetadata {
     pub description: Option<String>,
-    pub category: String,
+    /// Legacy free-form documents have no `category_id` until migrated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<CategoryId>,
     pub tags: Vec<String>,
     pub sku: String,
 }
@@ -34,8 +39,17 @@ pub struct Product {
     pub stock: i32,
     pub status: ProductStatus,
     pub metadata: ProductMetadata,
+    /// Reorder point: stock strictly below this value is considered low.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorder_threshold: Option<i32>,
+    /// Set when a `LowStock` alert has been sent; cleared once stock recovers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_alerted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Incremented by every write; clients send it back in `If-Match`.
+    #[serde(default)]
+    pub version: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<DateTime<Utc>>,
 }
@@ -44,4 +58,65 @@ impl Product {
     pub fn is_deleted(&self) -> bool {
         self.deleted_at.is_some()
     }
+
+    pub fn is_low_stock(&self) -> bool {
+        self.reorder_threshold
+            .is_some_and(|threshold| self.stock < threshold)
+    }
+}
+
+/// Product fields supplied by a caller before the product is persisted.
+#[derive(Debug, Clone)]
+pub struct ProductDraft {
+    pub name: String,
+    pub price: f64,
+    pub stock: i32,
+    pub metadata: ProductMetadata,
+}
+
+/// Per-item result of an upsert keyed by SKU.
+#[derive(Debug, Clone)]
+pub enum SkuUpsertOutcome {
+    Created(ProductId),
+    Updated {
+        id: ProductId,
+        /// The product before the import overwrote it, for the audit log and
+        /// the inventory ledger.
+        previous: Box<Product>,
+    },
+    Failed(String),
+}
+
+impl SkuUpsertOutcome {
+    /// What import batch `batch_id` records in the same write as its upserts,
+    /// given each row's outcome and product: an `Import` ledger movement for
+    /// every stock it changed, and a `ProductImported` event per written row.
+    pub fn import_records<'a>(
+        rows: impl IntoIterator<Item = (&'a Self, &'a Product)>,
+        batch_id: &str,
+    ) -> (Vec<InventoryMovement>, Vec<DomainEvent>) {
+        let mut movements = Vec::new();
+        let mut events = Vec::new();
+        for (outcome, product) in rows {
+            let (id, delta, created) = match outcome {
+                Self::Created(id) => (id, product.stock, true),
+                Self::Updated { id, previous } => (id, product.stock - previous.stock, false),
+                Self::Failed(_) => continue,
+            };
+            if delta != 0 {
+                movements.push(
+                    InventoryMovement::new(id.clone(), delta, MovementKind::Import)
+                        .with_reference(batch_id)
+                        .with_stock_after(product.stock),
+                );
+            }
+            events.push(DomainEvent::ProductImported {
+                product_id: id.clone(),
+                sku: product.metadata.sku.clone(),
+                created,
+                stock: product.stock,
+            });
+        }
+        (movements, events)
+    }
 }
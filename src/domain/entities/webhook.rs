@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::values;
+
+#[derive(Debug, Clone)]
+pub struct WebhookSubscriptionMarker;
+pub type WebhookSubscriptionId = values::DomainId<WebhookSubscriptionMarker>;
+
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryMarker;
+pub type WebhookDeliveryId = values::DomainId<WebhookDeliveryMarker>;
+
+/// Event filter entry that matches every event type.
+pub const ALL_EVENTS: &str = "*";
+
+/// A partner endpoint that receives domain events.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookSubscription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<WebhookSubscriptionId>,
+    pub url: String,
+    /// HMAC-SHA256 key shared with the partner to verify signatures.
+    pub secret: String,
+    /// Event types (`order_created`, ...) sent to this endpoint, or `*`.
+    pub events: Vec<String>,
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.active
+            && self
+                .events
+                .iter()
+                .any(|e| e == ALL_EVENTS || e == event_type)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Not yet delivered; attempts may still be scheduled.
+    Pending,
+    Succeeded,
+    /// Gave up after the last attempt. Can still be redelivered manually.
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeliveryAttempt {
+    pub attempted_at: DateTime<Utc>,
+    /// HTTP status of the response; `None` when none was received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl DeliveryAttempt {
+    pub fn succeeded(&self) -> bool {
+        self.status_code
+            .is_some_and(|code| (200..300).contains(&code))
+    }
+}
+
+/// One event sent to one subscription, with the log of every attempt.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebhookDelivery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<WebhookDeliveryId>,
+    pub subscription_id: WebhookSubscriptionId,
+    /// Outbox message ID; also sent to the partner for deduplication.
+    pub event_id: String,
+    pub event_type: String,
+    /// Exact JSON body, identical on every attempt.
+    pub body: String,
+    pub status: DeliveryStatus,
+    pub attempts: Vec<DeliveryAttempt>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
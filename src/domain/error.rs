@@ -26,6 +26,16 @@ pub enum DomainError {
     #[error("Business rule violated: {0}")]
     BusinessRule(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("{entity} {id} is no longer at version {expected}")]
+    VersionConflict {
+        entity: &'static str,
+        id: String,
+        expected: u64,
+    },
+
     #[error("External service error: {service} - {message}")]
     ExternalService { service: String, message: String },
 
@@ -134,6 +144,15 @@ impl DomainError {
         }
     }
 
+    /// The caller's copy of the entity is stale: it was modified since `expected`.
+    pub fn version_conflict(entity: &'static str, id: impl Into<String>, expected: u64) -> Self {
+        Self::VersionConflict {
+            entity,
+            id: id.into(),
+            expected,
+        }
+    }
+
     pub fn operation_not_allowed(operation: impl Into<String>, reason: impl Into<String>) -> Self {
         Self::BusinessRule(format!("{}: {}", operation.into(), reason.into()))
     }
@@ -0,0 +1,155 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::inventory::MovementKind;
+use crate::domain::entities::order::OrderId;
+use crate::domain::entities::product::{ProductId, ProductMetadata};
+use crate::domain::entities::user::UserId;
+use crate::domain::values;
+
+/// Raised when a stock change takes a product below its reorder threshold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LowStock {
+    pub product_id: ProductId,
+    pub sku: String,
+    pub name: String,
+    pub stock: i32,
+    pub threshold: i32,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// State changes announced to downstream systems through the outbox.
+///
+/// Serialized as `{"type": "user_created", "data": {...}}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum DomainEvent {
+    UserCreated {
+        user_id: UserId,
+        name: String,
+        email: String,
+    },
+    UserUpdated {
+        user_id: UserId,
+        name: String,
+        email: String,
+    },
+    UserDeleted {
+        user_id: UserId,
+    },
+    ProductCreated {
+        product_id: ProductId,
+        sku: String,
+        name: String,
+        price: f64,
+        stock: i32,
+    },
+    ProductUpdated {
+        product_id: ProductId,
+        metadata: ProductMetadata,
+    },
+    /// Product written by a bulk import, either created or overwritten.
+    ProductImported {
+        product_id: ProductId,
+        sku: String,
+        created: bool,
+        stock: i32,
+    },
+    ProductStockChanged {
+        product_id: ProductId,
+        delta: i32,
+        kind: MovementKind,
+        reference: Option<String>,
+    },
+    ProductDeleted {
+        product_id: ProductId,
+    },
+    OrderCreated {
+        order_id: OrderId,
+        user_id: UserId,
+        product_id: ProductId,
+        quantity: i32,
+        total_price: f64,
+    },
+    OrderCancelled {
+        order_id: OrderId,
+        product_id: ProductId,
+        quantity: i32,
+    },
+}
+
+impl DomainEvent {
+    /// Every value [`Self::event_type`] can return.
+    pub const TYPES: &'static [&'static str] = &[
+        "user_created",
+        "user_updated",
+        "user_deleted",
+        "product_created",
+        "product_updated",
+        "product_imported",
+        "product_stock_changed",
+        "product_deleted",
+        "order_created",
+        "order_cancelled",
+    ];
+
+    /// `snake_case` name, identical to the serialized `type` tag.
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::UserCreated { .. } => "user_created",
+            Self::UserUpdated { .. } => "user_updated",
+            Self::UserDeleted { .. } => "user_deleted",
+            Self::ProductCreated { .. } => "product_created",
+            Self::ProductUpdated { .. } => "product_updated",
+            Self::ProductImported { .. } => "product_imported",
+            Self::ProductStockChanged { .. } => "product_stock_changed",
+            Self::ProductDeleted { .. } => "product_deleted",
+            Self::OrderCreated { .. } => "order_created",
+            Self::OrderCancelled { .. } => "order_cancelled",
+        }
+    }
+
+    /// Kind of aggregate the event belongs to (`user`, `product`, `order`).
+    pub fn aggregate_type(&self) -> &'static str {
+        match self {
+            Self::UserCreated { .. } | Self::UserUpdated { .. } | Self::UserDeleted { .. } => {
+                "user"
+            }
+            Self::ProductCreated { .. }
+            | Self::ProductUpdated { .. }
+            | Self::ProductImported { .. }
+            | Self::ProductStockChanged { .. }
+            | Self::ProductDeleted { .. } => "product",
+            Self::OrderCreated { .. } | Self::OrderCancelled { .. } => "order",
+        }
+    }
+
+    /// ID of the aggregate; events are delivered in order per aggregate.
+    pub fn aggregate_id(&self) -> &str {
+        match self {
+            Self::UserCreated { user_id, .. }
+            | Self::UserUpdated { user_id, .. }
+            | Self::UserDeleted { user_id } => user_id,
+            Self::ProductCreated { product_id, .. }
+            | Self::ProductUpdated { product_id, .. }
+            | Self::ProductImported { product_id, .. }
+            | Self::ProductStockChanged { product_id, .. }
+            | Self::ProductDeleted { product_id } => product_id,
+            Self::OrderCreated { order_id, .. } | Self::OrderCancelled { order_id, .. } => order_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxMessageMarker;
+pub type OutboxMessageId = values::DomainId<OutboxMessageMarker>;
+
+/// A [`DomainEvent`] stored in the outbox, waiting to be published.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: OutboxMessageId,
+    pub event: DomainEvent,
+    pub occurred_at: DateTime<Utc>,
+    /// Failed publish attempts so far.
+    pub attempts: u32,
+}
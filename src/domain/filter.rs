@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+
+/// Half-open `[from, to)` window over `created_at`. Missing bounds are unbounded.
+#[derive(Debug, Clone, Default)]
+pub struct DateRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.from.is_none_or(|from| at >= from) && self.to.is_none_or(|to| at < to)
+    }
+}
+
+/// Narrows the audit log to one entity type and, optionally, one entity.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub entity: Option<String>,
+    pub entity_id: Option<String>,
+}
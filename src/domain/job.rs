@@ -0,0 +1,78 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// When a scheduled job runs: a cron expression, either the usual five
+/// fields (`30 2 * * *`, minute first) or six to seven with seconds first
+/// and an optional year (`0 30 2 * * * 2027`).
+#[derive(Clone)]
+pub struct Cron {
+    expression: String,
+    schedule: cron::Schedule,
+}
+
+impl Cron {
+    /// The first occurrence after `after`, in its timezone.
+    pub fn next_after<Z: TimeZone>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>> {
+        self.schedule.after(after).next()
+    }
+
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+}
+
+impl FromStr for Cron {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expression = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        // The cron crate wants seconds first
+        let full = match expression.split(' ').count() {
+            5 => format!("0 {}", expression),
+            _ => expression.clone(),
+        };
+        let schedule = full
+            .parse()
+            .map_err(|e: cron::error::Error| format!("invalid cron expression: {}", e))?;
+        Ok(Self {
+            expression,
+            schedule,
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl fmt::Debug for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cron({:?})", self.expression)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobOutcome {
+    Succeeded,
+    Failed,
+}
+
+/// One run of a scheduled job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    pub job: String,
+    /// The occurrence the run was for.
+    pub scheduled_for: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: JobOutcome,
+    /// What the job reported, or its error.
+    pub detail: String,
+    /// Trace of the run, to jump to its spans and logs.
+    pub trace_id: Option<String>,
+}
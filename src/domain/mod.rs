@@ -1,5 +1,10 @@
 pub mod entities;
 pub mod error;
+pub mod event;
+pub mod filter;
+pub mod job;
 pub mod pagination;
 pub mod port;
+pub mod stream;
+pub mod task;
 pub mod values;
@@ -0,0 +1,16 @@
+use crate::domain::entities::api_key::{ApiKey, ApiKeyId};
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use async_trait::async_trait;
+
+/// Repository Interface for API Keys.
+#[async_trait]
+pub trait ApiKeyRepositoryPort: Send + Sync {
+    async fn create(&self, key: &ApiKey) -> DomainResult<ApiKeyId>;
+
+    /// The key whose hash is `key_hash`, revoked or not.
+    async fn find_by_hash(&self, key_hash: &str) -> DomainResult<Option<ApiKey>>;
+
+    /// Newest first.
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<ApiKey>>;
+}
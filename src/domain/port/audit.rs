@@ -0,0 +1,21 @@
+use crate::domain::entities::audit::AuditEntry;
+use crate::domain::error::DomainResult;
+use crate::domain::filter::AuditFilter;
+use crate::domain::pagination::Pagination;
+use async_trait::async_trait;
+
+/// Append-only store of audit entries. Entries are never updated or deleted;
+/// they expire after the configured retention.
+#[async_trait]
+pub trait AuditLogPort: Send + Sync {
+    async fn append(&self, entries: &[AuditEntry]) -> DomainResult<()>;
+
+    /// Matching entries, newest first.
+    async fn find(
+        &self,
+        filter: &AuditFilter,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<AuditEntry>>;
+
+    async fn count(&self, filter: &AuditFilter) -> DomainResult<u64>;
+}
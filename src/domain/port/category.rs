@@ -0,0 +1,41 @@
+use crate::domain::entities::category::{Category, CategoryId};
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use async_trait::async_trait;
+
+/// Repository Interface for the Category tree.
+#[async_trait]
+pub trait CategoryRepositoryPort: Send + Sync {
+    /// Fails with `AlreadyExists` when a live category has the same path, as
+    /// do [`Self::update`] and [`Self::move_subtree`].
+    async fn create(&self, category: &Category) -> DomainResult<CategoryId>;
+
+    async fn find_by_id(&self, id: &CategoryId) -> DomainResult<Option<Category>>;
+
+    async fn find_by_path(&self, path: &str) -> DomainResult<Option<Category>>;
+
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Category>>;
+
+    /// Direct children of `parent_id`, or root categories when `None`.
+    async fn find_children(&self, parent_id: Option<&CategoryId>) -> DomainResult<Vec<Category>>;
+
+    /// IDs of every category strictly below `path` in the tree.
+    async fn find_descendant_ids(&self, path: &str) -> DomainResult<Vec<CategoryId>>;
+
+    async fn update(&self, id: &CategoryId, category: &Category) -> DomainResult<bool>;
+
+    /// Like [`Self::update`] for a category whose path changed from
+    /// `old_path`: the node and every descendant path are rewritten in one
+    /// transaction. Returns how many descendants moved, or `None` when the
+    /// category does not exist.
+    async fn move_subtree(
+        &self,
+        id: &CategoryId,
+        category: &Category,
+        old_path: &str,
+    ) -> DomainResult<Option<u64>>;
+
+    async fn delete(&self, id: &CategoryId) -> DomainResult<bool>;
+
+    async fn count(&self) -> DomainResult<u64>;
+}
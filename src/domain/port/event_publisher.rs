@@ -0,0 +1,12 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::OutboxMessage;
+use async_trait::async_trait;
+
+/// Destination for events relayed from the outbox (Redis Streams, in-memory, ...).
+///
+/// Delivery is at-least-once: a message may be published again if the relay
+/// stops before recording success, so consumers should dedupe on `message.id`.
+#[async_trait]
+pub trait EventPublisherPort: Send + Sync {
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()>;
+}
@@ -0,0 +1,25 @@
+use crate::domain::entities::inventory::{InventoryMovement, MovementId};
+use crate::domain::entities::product::ProductId;
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use async_trait::async_trait;
+
+/// Append-only store of stock movements. There is deliberately no update or delete.
+#[async_trait]
+pub trait InventoryLedgerPort: Send + Sync {
+    async fn append(&self, movement: &InventoryMovement) -> DomainResult<MovementId>;
+
+    async fn append_many(&self, movements: &[InventoryMovement]) -> DomainResult<u64>;
+
+    /// Movements of one product, newest first.
+    async fn find_by_product(
+        &self,
+        product_id: &ProductId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<InventoryMovement>>;
+
+    async fn count_by_product(&self, product_id: &ProductId) -> DomainResult<u64>;
+
+    /// Sum of `delta` per product, for every product that has movements.
+    async fn sum_by_product(&self) -> DomainResult<Vec<(ProductId, i64)>>;
+}
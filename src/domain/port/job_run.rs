@@ -0,0 +1,12 @@
+use crate::domain::error::DomainResult;
+use crate::domain::job::JobRun;
+use async_trait::async_trait;
+
+/// Last run of each scheduled job, shared by every instance.
+#[async_trait]
+pub trait JobRunPort: Send + Sync {
+    /// Keeps `run` as the last run of its job.
+    async fn record(&self, run: &JobRun) -> DomainResult<()>;
+
+    async fn last(&self, job: &str) -> DomainResult<Option<JobRun>>;
+}
@@ -0,0 +1,29 @@
+use crate::domain::error::DomainResult;
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// A held lock. The lease lapses after its TTL unless renewed, so a crashed
+/// holder only blocks the others that long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    pub name: String,
+    /// Unique to this acquisition; only it can renew or release the lease.
+    pub owner: String,
+    /// Fencing token: grows with every acquisition of `name`. Writes made
+    /// under the lock can carry it, so storage can reject a holder whose
+    /// lease lapsed while it was paused.
+    pub token: u64,
+}
+
+/// Named locks shared by every instance of the service.
+#[async_trait]
+pub trait LockPort: Send + Sync {
+    /// Takes `name` for `ttl`, or `None` when another holder has it.
+    async fn try_acquire(&self, name: &str, ttl: Duration) -> DomainResult<Option<Lease>>;
+
+    /// Extends the lease to `ttl` from now; `false` when it was already lost.
+    async fn renew(&self, lease: &Lease, ttl: Duration) -> DomainResult<bool>;
+
+    /// Frees the lock if `lease` still holds it; `false` when it did not.
+    async fn release(&self, lease: &Lease) -> DomainResult<bool>;
+}
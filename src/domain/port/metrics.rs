@@ -0,0 +1,41 @@
+use crate::domain::entities::country::Country;
+use std::time::Duration;
+
+/// Backend a datastore timing belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Datastore {
+    Mongo,
+    Postgres,
+    Redis,
+}
+
+impl Datastore {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mongo => "mongo",
+            Self::Postgres => "postgres",
+            Self::Redis => "redis",
+        }
+    }
+}
+
+/// Sink for operational and business metrics (Prometheus, no-op, ...).
+///
+/// Recording must be cheap and never fail: it runs on the request path.
+pub trait MetricsPort: Send + Sync {
+    /// One handled HTTP request. `route` is the matched route template
+    /// (`/api/v1/orders/{id}`), never the raw path.
+    fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration);
+
+    /// One datastore call; `operation` is `{component}.{method}` (`order.find_by_id`).
+    fn record_datastore_call(&self, store: Datastore, operation: &str, elapsed: Duration);
+
+    /// A connection pool event (`connection_created`, `pool_cleared`,
+    /// `checkout_failed`, ...), counted to spot churn and failovers.
+    fn record_pool_event(&self, store: Datastore, event: &str);
+
+    fn order_created(&self, country: Option<&Country>, total_price: f64);
+
+    /// A sale or adjustment left a product with no stock.
+    fn stock_out(&self);
+}
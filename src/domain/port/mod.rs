@@ -1,3 +1,18 @@
+pub mod api_key;
+pub mod audit;
+pub mod category;
+pub mod event_publisher;
+pub mod inventory;
+pub mod job_run;
+pub mod lock;
+pub mod metrics;
+pub mod notifier;
 pub mod order;
+pub mod outbox;
 pub mod product;
+pub mod rate_limit;
+pub mod task_queue;
+pub mod trace_context;
 pub mod user;
+pub mod webhook;
+pub mod webhook_client;
@@ -0,0 +1,9 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::LowStock;
+use async_trait::async_trait;
+
+/// Outbound channel for inventory alerts (webhook, log, ...).
+#[async_trait]
+pub trait NotifierPort: Send + Sync {
+    async fn notify_low_stock(&self, event: &LowStock) -> DomainResult<()>;
+}
@@ -1,14 +1,21 @@
 use crate::domain::error::DomainResult;
+use crate::domain::filter::DateRange;
 use crate::domain::entities::order::{Order, OrderId};
+use crate::domain::event::DomainEvent;
 use crate::domain::pagination::Pagination;
 use crate::domain::entities::user::UserId;
+use crate::domain::stream::DomainStream;
 use async_trait::async_trait;
 
 /// Repository Interface for Order Management.
 /// strictly decoupled from persistence implementation.
 #[async_trait]
 pub trait OrderRepositoryPort: Send + Sync {
-    async fn create(&self, order: &Order) -> DomainResult<OrderId>;
+    /// Allocate the ID of an order about to be created, so its events can reference it.
+    fn next_id(&self) -> OrderId;
+
+    /// Store `order` (with the ID from [`Self::next_id`]) and record `events` atomically.
+    async fn create(&self, order: &Order, events: &[DomainEvent]) -> DomainResult<OrderId>;
 
     async fn find_by_id(&self, id: &OrderId) -> DomainResult<Option<Order>>;
 
@@ -20,7 +27,15 @@ pub trait OrderRepositoryPort: Send + Sync {
         pagination: Pagination,
     ) -> DomainResult<Vec<Order>>;
 
-    async fn delete(&self, id: &OrderId) -> DomainResult<bool>;
+    /// Every live order in `range` (optionally for one user), newest first.
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        user_id: Option<&UserId>,
+    ) -> DomainResult<DomainStream<Order>>;
+
+    /// `events` are recorded only if the order was found.
+    async fn delete(&self, id: &OrderId, events: &[DomainEvent]) -> DomainResult<bool>;
 
     async fn count(&self) -> DomainResult<u64>;
 }
@@ -0,0 +1,29 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Relay-side access to the transactional outbox.
+///
+/// Events are normally written by the repositories together with the state
+/// change they describe; `append` exists for flows that cannot share a
+/// transaction with their writes (bulk imports).
+#[async_trait]
+pub trait OutboxPort: Send + Sync {
+    async fn append(&self, events: &[DomainEvent]) -> DomainResult<u64>;
+
+    /// Claims up to `limit` messages that are due, at most one per aggregate:
+    /// the oldest pending message of each. Claims expire after `lease`.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> DomainResult<Vec<OutboxMessage>>;
+
+    async fn mark_published(&self, id: &OutboxMessageId) -> DomainResult<()>;
+
+    /// Records a failed attempt and schedules the next one.
+    async fn mark_failed(
+        &self,
+        id: &OutboxMessageId,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> DomainResult<()>;
+}
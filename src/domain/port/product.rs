@@ -1,27 +1,127 @@
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::inventory::InventoryMovement;
 use crate::domain::error::DomainResult;
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
-use crate::domain::entities::product::{Product, ProductId, ProductMetadata};
+use crate::domain::entities::product::{Product, ProductId, ProductMetadata, SkuUpsertOutcome};
+use crate::domain::stream::DomainStream;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 /// Repository Interface for Product Management.
 #[async_trait]
 pub trait ProductRepositoryPort: Send + Sync {
-    async fn create(&self, product: &Product) -> DomainResult<ProductId>;
+    /// Allocate the ID of a product about to be created, so its events can reference it.
+    fn next_id(&self) -> ProductId;
+
+    /// Store `product` (with the ID from [`Self::next_id`]) together with its
+    /// [`InventoryMovement::initial`] ledger entry, and record `events` atomically.
+    async fn create(&self, product: &Product, events: &[DomainEvent]) -> DomainResult<ProductId>;
 
     async fn find_by_id(&self, id: &ProductId) -> DomainResult<Option<Product>>;
 
     async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Product>>;
 
+    /// IDs of live products for each SKU that exists, keyed by SKU.
+    async fn find_ids_by_skus(&self, skus: &[String]) -> DomainResult<Vec<(String, ProductId)>>;
+
+    /// Products whose category is any of `category_ids`.
+    async fn find_by_categories(
+        &self,
+        category_ids: &[CategoryId],
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Product>>;
+
+    /// Every live product in `range` (optionally within `category_ids`), newest first.
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        category_ids: Option<&[CategoryId]>,
+    ) -> DomainResult<DomainStream<Product>>;
+
+    async fn count_by_categories(&self, category_ids: &[CategoryId]) -> DomainResult<u64>;
+
+    /// Distinct free-form category strings on products not yet linked to a category.
+    async fn find_legacy_categories(&self) -> DomainResult<Vec<String>>;
+
+    /// Link every product still using the `legacy` category string to `category_id`.
+    async fn assign_legacy_category(
+        &self,
+        legacy: &str,
+        category_id: &CategoryId,
+    ) -> DomainResult<u64>;
+
+    /// Applies only if the product is still at `expected_version`, and
+    /// increments it; `events` are recorded only when the update is applied.
     async fn update_metadata(
         &self,
         id: &ProductId,
+        expected_version: u64,
         metadata: &ProductMetadata,
+        events: &[DomainEvent],
     ) -> DomainResult<bool>;
 
-    /// Update stock by delta (positive or negative).
-    async fn update_stock(&self, id: &ProductId, delta: i32) -> DomainResult<bool>;
+    /// Apply `movement.delta` to the product's stock and append the movement,
+    /// with the resulting `stock_after`, to the inventory ledger in the same
+    /// write. Returns the product as it is after the update, or `None` if it
+    /// was not found or an outflow exceeds the current stock; neither the
+    /// movement nor `events` are recorded then.
+    async fn apply_movement(
+        &self,
+        movement: &InventoryMovement,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>>;
+
+    /// Update stock by delta (positive or negative) without touching the
+    /// ledger, for stock rebuilt from it; every other stock change goes
+    /// through [`Self::apply_movement`].
+    /// Returns the product as it is after the update, or `None` if it was not
+    /// found or a negative delta exceeds the current stock; `events` are
+    /// recorded only when the update is applied.
+    async fn update_stock(
+        &self,
+        id: &ProductId,
+        delta: i32,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>>;
+
+    /// Set or clear (`None`) the reorder threshold if the product is still at
+    /// `expected_version`; returns the updated product.
+    async fn set_reorder_threshold(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        threshold: Option<i32>,
+    ) -> DomainResult<Option<Product>>;
+
+    /// Products whose stock is below their reorder threshold, lowest stock first.
+    async fn find_low_stock(&self, pagination: Pagination) -> DomainResult<Vec<Product>>;
+
+    async fn count_low_stock(&self) -> DomainResult<u64>;
+
+    /// Record that a low-stock alert was sent. Returns `false` if one was already
+    /// recorded, so concurrent stock updates notify at most once.
+    async fn mark_low_stock_alerted(&self, id: &ProductId, at: DateTime<Utc>)
+    -> DomainResult<bool>;
+
+    /// Re-arm low-stock alerting for the product.
+    async fn clear_low_stock_alert(&self, id: &ProductId) -> DomainResult<bool>;
+
+    /// Insert or update each product by `metadata.sku` in one write, reporting
+    /// updated rows with the product they replaced, and record the batch's
+    /// [`SkuUpsertOutcome::import_records`] for `batch_id` atomically with it.
+    /// A row the store rejects (a concurrent insert of the same SKU, say) is
+    /// `Failed` rather than an error. Outcomes are returned in the same order
+    /// as `products`.
+    async fn upsert_by_sku(
+        &self,
+        products: &[Product],
+        batch_id: &str,
+    ) -> DomainResult<Vec<SkuUpsertOutcome>>;
 
-    async fn delete(&self, id: &ProductId) -> DomainResult<bool>;
+    /// `events` are recorded only if the product was found.
+    async fn delete(&self, id: &ProductId, events: &[DomainEvent]) -> DomainResult<bool>;
 
     async fn count(&self) -> DomainResult<u64>;
 }
@@ -0,0 +1,82 @@
+use crate::domain::error::DomainResult;
+use async_trait::async_trait;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// At most `requests` per `window`, as a token bucket: the bucket holds
+/// `requests` tokens and refills continuously over `window`, so bursts up to
+/// the limit are allowed and the sustained rate is `requests / window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub window: Duration,
+}
+
+impl RateLimit {
+    /// Time to earn back one request.
+    pub fn refill_interval(&self) -> Duration {
+        self.window / self.requests.max(1)
+    }
+}
+
+/// `600/1m`, `20/s`, `5000/1h`: requests per window of seconds, minutes or hours.
+impl FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || "expected requests/window, e.g. 600/1m".to_string();
+        let (requests, window) = s.split_once('/').ok_or_else(invalid)?;
+        let requests: u32 = requests.trim().parse().map_err(|_| invalid())?;
+        if requests == 0 {
+            return Err("requests must be greater than 0".to_string());
+        }
+
+        let window = window.trim();
+        let (count, unit) = window.split_at(window.len().saturating_sub(1));
+        let count: u64 = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| invalid())?
+        };
+        let unit = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            _ => return Err("window unit must be s, m or h".to_string()),
+        };
+        if count == 0 {
+            return Err("window must be greater than 0".to_string());
+        }
+
+        Ok(Self {
+            requests,
+            window: Duration::from_secs(count * unit),
+        })
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}s", self.requests, self.window.as_secs())
+    }
+}
+
+/// Outcome of taking one request from a bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    /// Requests left in the bucket after this one.
+    pub remaining: u32,
+    /// Until the bucket is full again.
+    pub reset_after: Duration,
+    /// Until the next request is allowed; zero when this one was.
+    pub retry_after: Duration,
+}
+
+/// Request budgets shared by every instance of the service.
+#[async_trait]
+pub trait RateLimiterPort: Send + Sync {
+    /// Takes one request from the bucket of `key` under `limit`.
+    async fn acquire(&self, key: &str, limit: RateLimit) -> DomainResult<RateLimitDecision>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::error::DomainResult;
+use crate::domain::task::{NewTask, Task, TaskId};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Durable queue of background tasks.
+///
+/// Delivery is at-least-once: a task reserved by a worker that dies is handed
+/// out again once its visibility timeout expires, so handlers must be idempotent.
+#[async_trait]
+pub trait TaskQueuePort: Send + Sync {
+    async fn enqueue(&self, task: NewTask) -> DomainResult<TaskId>;
+
+    /// Reserves the next due task for `worker`, if any. Does not wait.
+    async fn reserve(&self, worker: &str) -> DomainResult<Option<Task>>;
+
+    async fn complete(&self, task: &Task) -> DomainResult<()>;
+
+    /// Releases the task and schedules another attempt at `retry_at`.
+    async fn retry(&self, task: &Task, error: &str, retry_at: DateTime<Utc>) -> DomainResult<()>;
+
+    /// Moves the task to the dead-letter queue for manual inspection.
+    async fn dead_letter(&self, task: &Task, error: &str) -> DomainResult<()>;
+}
@@ -0,0 +1,14 @@
+/// W3C Trace Context header, also the carrier key for queued tasks.
+pub const TRACEPARENT: &str = "traceparent";
+
+/// Joins spans to traces started elsewhere: upstream callers or the request
+/// that enqueued a task.
+pub trait TraceContextPort: Send + Sync {
+    /// `traceparent` of the current span, if it belongs to a trace.
+    fn current(&self) -> Option<String>;
+
+    /// Makes `span` a child of the remote parent found in `carrier`
+    /// (lower-case header names and their values). Without a valid parent
+    /// `span` stays a root. Must be called before `span` is first entered.
+    fn continue_trace(&self, span: &tracing::Span, carrier: &[(&str, &str)]);
+}
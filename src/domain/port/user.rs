@@ -1,13 +1,20 @@
 use crate::domain::error::DomainResult;
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
 use crate::domain::entities::user::{User, UserId};
+use crate::domain::event::DomainEvent;
+use crate::domain::stream::DomainStream;
 use async_trait::async_trait;
 
 /// Repository Interface for User Management.
 /// Strictly decoupled from persistence implementation.
 #[async_trait]
 pub trait UserRepositoryPort: Send + Sync {
-    async fn create(&self, user: &User) -> DomainResult<UserId>;
+    /// Allocate the ID of a user about to be created, so its events can reference it.
+    fn next_id(&self) -> UserId;
+
+    /// Store `user` (with the ID from [`Self::next_id`]) and record `events` atomically.
+    async fn create(&self, user: &User, events: &[DomainEvent]) -> DomainResult<UserId>;
 
     async fn find_by_id(&self, id: &UserId) -> DomainResult<Option<User>>;
 
@@ -16,9 +23,22 @@ pub trait UserRepositoryPort: Send + Sync {
     /// List users with pagination.
     async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<User>>;
 
-    async fn update(&self, id: &UserId, user: &User) -> DomainResult<bool>;
+    /// Every live user in `range`, newest first, read lazily from storage.
+    async fn stream_all(&self, range: &DateRange) -> DomainResult<DomainStream<User>>;
+
+    /// Replaces the user if it is still at `expected_version`, incrementing
+    /// the version. Returns `false` if it is missing or was modified since;
+    /// `events` are recorded only when the update is applied.
+    async fn update(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        user: &User,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool>;
 
-    async fn delete(&self, id: &UserId) -> DomainResult<bool>;
+    /// `events` are recorded only if the user was found.
+    async fn delete(&self, id: &UserId, events: &[DomainEvent]) -> DomainResult<bool>;
 
     async fn count(&self) -> DomainResult<u64>;
 }
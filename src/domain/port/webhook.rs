@@ -0,0 +1,80 @@
+use crate::domain::entities::webhook::{
+    DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookDeliveryId, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use async_trait::async_trait;
+
+/// Repository Interface for Webhook Subscriptions.
+#[async_trait]
+pub trait WebhookSubscriptionRepositoryPort: Send + Sync {
+    async fn create(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscriptionId>;
+
+    async fn find_by_id(
+        &self,
+        id: &WebhookSubscriptionId,
+    ) -> DomainResult<Option<WebhookSubscription>>;
+
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<WebhookSubscription>>;
+
+    /// Active subscriptions whose filter matches `event_type`.
+    async fn find_for_event(&self, event_type: &str) -> DomainResult<Vec<WebhookSubscription>>;
+
+    async fn update(
+        &self,
+        id: &WebhookSubscriptionId,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<bool>;
+
+    async fn delete(&self, id: &WebhookSubscriptionId) -> DomainResult<bool>;
+
+    async fn count(&self) -> DomainResult<u64>;
+}
+
+/// Repository Interface for the Webhook Delivery log.
+#[async_trait]
+pub trait WebhookDeliveryRepositoryPort: Send + Sync {
+    /// Inserts the delivery unless one already exists for the same subscription
+    /// and event. Returns the ID of the stored delivery either way, and whether
+    /// this call created it.
+    async fn create_once(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> DomainResult<(WebhookDeliveryId, bool)>;
+
+    async fn find_by_id(&self, id: &WebhookDeliveryId) -> DomainResult<Option<WebhookDelivery>>;
+
+    /// Deliveries of one subscription, newest first.
+    async fn find_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<WebhookDelivery>>;
+
+    async fn count_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> DomainResult<u64>;
+
+    /// Appends `attempt` to the log and moves the delivery to `status`.
+    async fn record_attempt(
+        &self,
+        id: &WebhookDeliveryId,
+        attempt: &DeliveryAttempt,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool>;
+
+    async fn set_status(
+        &self,
+        id: &WebhookDeliveryId,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool>;
+
+    /// Moves a succeeded or failed delivery back to `Pending`. Returns `false`
+    /// when it is already pending (its task is still queued) or gone.
+    async fn reopen(&self, id: &WebhookDeliveryId) -> DomainResult<bool>;
+}
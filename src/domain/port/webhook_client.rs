@@ -0,0 +1,15 @@
+use crate::domain::entities::webhook::{WebhookDelivery, WebhookSubscription};
+use crate::domain::error::DomainResult;
+use async_trait::async_trait;
+
+/// Sends signed webhook requests to partner endpoints.
+#[async_trait]
+pub trait WebhookClientPort: Send + Sync {
+    /// POSTs `delivery.body` to `subscription.url`. Any HTTP response is `Ok`
+    /// with its status code; transport failures and timeouts are errors.
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> DomainResult<u16>;
+}
@@ -0,0 +1,11 @@
+use std::pin::Pin;
+
+use futures::Stream;
+
+use crate::domain::error::DomainResult;
+
+/// Lazily-evaluated sequence of entities, e.g. backed by a database cursor.
+///
+/// Lets callers process whole collections with constant memory instead of
+/// collecting pages into a `Vec`.
+pub type DomainStream<T> = Pin<Box<dyn Stream<Item = DomainResult<T>> + Send>>;
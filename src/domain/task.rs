@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::time::Duration;
+
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::values;
+
+/// Attempts (first run included) before a task is dead-lettered.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone)]
+pub struct TaskMarker;
+pub type TaskId = values::DomainId<TaskMarker>;
+
+/// Payload of one task type. `KIND` routes the task to its handler, so it must
+/// stay stable while tasks of that type may still be queued.
+pub trait TaskPayload: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const KIND: &'static str;
+}
+
+/// A task to be enqueued.
+#[derive(Debug, Clone)]
+pub struct NewTask {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Run no earlier than this long after enqueueing.
+    pub delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl NewTask {
+    pub fn new<T: TaskPayload>(payload: &T) -> DomainResult<Self> {
+        let payload = serde_json::to_value(payload)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+
+        Ok(Self {
+            kind: T::KIND.to_string(),
+            payload,
+            delay: Duration::ZERO,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        })
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+}
+
+/// A task as stored in, and delivered by, the queue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: TaskId,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    /// Deliveries so far, the current one included.
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub enqueued_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// `traceparent` of the span that enqueued the task; its run continues
+    /// that trace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_parent: Option<String>,
+    /// Opaque handle the queue uses to acknowledge this delivery.
+    #[serde(skip)]
+    pub receipt: String,
+}
+
+impl Task {
+    pub fn payload<T: TaskPayload>(&self) -> DomainResult<T> {
+        serde_json::from_value(self.payload.clone())
+            .map_err(|e| Error::invalid("payload", format!("{} task: {}", self.kind, e)))
+    }
+
+    pub fn is_last_attempt(&self) -> bool {
+        self.attempt >= self.max_attempts
+    }
+}
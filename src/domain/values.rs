@@ -12,7 +12,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 /// pub struct UserMarker;
 /// pub type UserId = DomainId<UserMarker>;
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct DomainId<T> {
     id: String,
     _marker: PhantomData<T>,
@@ -33,6 +33,22 @@ impl<T> DomainId<T> {
     }
 }
 
+// ===== Equality (by ID only; markers need no impls) =====
+
+impl<T> PartialEq for DomainId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for DomainId<T> {}
+
+impl<T> std::hash::Hash for DomainId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 // ===== Serde (plain string, not object) =====
 
 impl<T> Serialize for DomainId<T> {
@@ -93,3 +109,7 @@ impl<T> Default for DomainId<T> {
         Self::new(String::new())
     }
 }
+
+/// Version of an entity that has just been created; see the `version` field
+/// of versioned entities.
+pub const INITIAL_VERSION: u64 = 1;
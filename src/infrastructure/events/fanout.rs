@@ -0,0 +1,29 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::OutboxMessage;
+use crate::domain::port::event_publisher::EventPublisherPort;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Publishes every message to each inner publisher in turn.
+///
+/// Stops at the first failure; the relay then retries the message on all of
+/// them, so inner publishers must tolerate duplicates.
+pub struct FanoutPublisher {
+    publishers: Vec<Arc<dyn EventPublisherPort>>,
+}
+
+impl FanoutPublisher {
+    pub fn new(publishers: Vec<Arc<dyn EventPublisherPort>>) -> Self {
+        Self { publishers }
+    }
+}
+
+#[async_trait]
+impl EventPublisherPort for FanoutPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()> {
+        for publisher in &self.publishers {
+            publisher.publish(message).await?;
+        }
+        Ok(())
+    }
+}
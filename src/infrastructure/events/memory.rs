@@ -0,0 +1,41 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::OutboxMessage;
+use crate::domain::port::event_publisher::EventPublisherPort;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// Keeps published messages in process; for local runs and tests.
+#[derive(Default)]
+pub struct InMemoryEventPublisher {
+    published: Mutex<Vec<OutboxMessage>>,
+}
+
+impl InMemoryEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(dead_code)]
+    pub fn published(&self) -> Vec<OutboxMessage> {
+        self.published
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[async_trait]
+impl EventPublisherPort for InMemoryEventPublisher {
+    #[tracing::instrument(skip_all, fields(id = %message.id))]
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()> {
+        tracing::debug!(
+            event = message.event.event_type(),
+            "Event published in memory"
+        );
+        self.published
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(message.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fanout;
+pub mod memory;
+pub mod redis_streams;
@@ -0,0 +1,56 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::OutboxMessage;
+use crate::domain::port::event_publisher::EventPublisherPort;
+use crate::infrastructure::providers::redis::RedisProvider;
+use async_trait::async_trait;
+
+/// Approximate number of entries kept per stream (`XADD ... MAXLEN ~`).
+const STREAM_MAX_LEN: usize = 100_000;
+
+/// Publishes each event to the `{prefix}:events:{aggregate_type}` stream.
+///
+/// Entry fields: `id` (outbox message ID, for deduplication), `type`,
+/// `aggregate_id`, `occurred_at` (RFC 3339) and `payload` (event JSON).
+pub struct RedisStreamsPublisher {
+    redis: RedisProvider,
+}
+
+impl RedisStreamsPublisher {
+    pub fn new(redis: RedisProvider) -> Self {
+        Self { redis }
+    }
+}
+
+#[async_trait]
+impl EventPublisherPort for RedisStreamsPublisher {
+    #[tracing::instrument(skip_all, fields(id = %message.id))]
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()> {
+        let event = &message.event;
+        let stream = self.redis.get_path(&["events", event.aggregate_type()]);
+        let payload = serde_json::to_string(event)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+
+        let mut conn = self.redis.connection();
+        redis::cmd("XADD")
+            .arg(&stream)
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(STREAM_MAX_LEN)
+            .arg("*")
+            .arg("id")
+            .arg(&*message.id)
+            .arg("type")
+            .arg(event.event_type())
+            .arg("aggregate_id")
+            .arg(event.aggregate_id())
+            .arg("occurred_at")
+            .arg(message.occurred_at.to_rfc3339())
+            .arg("payload")
+            .arg(payload)
+            .query_async::<String>(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+
+        Ok(())
+    }
+}
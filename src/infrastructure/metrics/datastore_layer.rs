@@ -0,0 +1,132 @@
+use crate::domain::port::metrics::{Datastore, MetricsPort};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::span::{Attributes, Id};
+use tracing::{Level, Metadata, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Modules whose instrumented methods are datastore calls, submodules included.
+/// The operation label is `{last module segment}.{method}`, e.g.
+/// `order.find_by_id`. The in-memory adapters are left out: they are not a
+/// datastore.
+const DATASTORE_MODULES: &[(&str, Datastore)] = &[
+    ("::infrastructure::persistence::api_key", Datastore::Mongo),
+    ("::infrastructure::persistence::audit", Datastore::Mongo),
+    ("::infrastructure::persistence::category", Datastore::Mongo),
+    ("::infrastructure::persistence::inventory", Datastore::Mongo),
+    ("::infrastructure::persistence::job_run", Datastore::Mongo),
+    ("::infrastructure::persistence::lease", Datastore::Mongo),
+    (
+        "::infrastructure::persistence::maintenance",
+        Datastore::Mongo,
+    ),
+    (
+        "::infrastructure::persistence::migrations",
+        Datastore::Mongo,
+    ),
+    ("::infrastructure::persistence::order", Datastore::Mongo),
+    ("::infrastructure::persistence::outbox", Datastore::Mongo),
+    ("::infrastructure::persistence::product", Datastore::Mongo),
+    ("::infrastructure::persistence::user", Datastore::Mongo),
+    ("::infrastructure::persistence::webhook", Datastore::Mongo),
+    (
+        "::infrastructure::persistence::postgres",
+        Datastore::Postgres,
+    ),
+    ("::infrastructure::providers::tasks", Datastore::Redis),
+    ("::infrastructure::providers::redis", Datastore::Redis),
+    ("::infrastructure::providers::job_run", Datastore::Redis),
+    ("::infrastructure::events::redis_streams", Datastore::Redis),
+    (
+        "::infrastructure::rate_limit::redis_bucket",
+        Datastore::Redis,
+    ),
+];
+
+struct Timing {
+    store: Datastore,
+    operation: String,
+    started: Instant,
+}
+
+/// Times the `#[tracing::instrument]` spans of repositories and Redis
+/// adapters, so datastore latency is measured without touching each method.
+pub struct DatastoreMetricsLayer {
+    metrics: Arc<dyn MetricsPort>,
+}
+
+impl DatastoreMetricsLayer {
+    pub fn new(metrics: Arc<dyn MetricsPort>) -> Self {
+        Self { metrics }
+    }
+
+    /// Per-layer filter enabling the datastore modules' spans, so the metrics
+    /// do not depend on `DEBUG_LEVEL`, which only filters logs and traces.
+    pub fn targets() -> Targets {
+        Targets::new().with_targets(DATASTORE_MODULES.iter().map(|(module, _)| {
+            let module = module.trim_matches(':');
+            (
+                format!("{}::{}", env!("CARGO_CRATE_NAME"), module),
+                Level::INFO,
+            )
+        }))
+    }
+
+    fn classify(metadata: &Metadata<'_>) -> Option<(Datastore, String)> {
+        let target = metadata.target();
+        let (_, store) = DATASTORE_MODULES
+            .iter()
+            .find(|(module, _)| within(target, module))?;
+
+        // `...::persistence::order::repository` -> `order`
+        let mut segments = target.rsplit("::");
+        let last = segments.next()?;
+        let component = match last {
+            "repository" | "writer" | "model" => segments.next().unwrap_or(last),
+            _ => last,
+        };
+        Some((*store, format!("{}.{}", component, metadata.name())))
+    }
+}
+
+/// Whether `target` is `module` or one of its submodules, so `...::order`
+/// does not take in `...::order_line`.
+fn within(target: &str, module: &str) -> bool {
+    target.match_indices(module).any(|(at, _)| {
+        let rest = &target[at + module.len()..];
+        rest.is_empty() || rest.starts_with("::")
+    })
+}
+
+impl<S> Layer<S> for DatastoreMetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some((store, operation)) = Self::classify(attrs.metadata()) else {
+            return;
+        };
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Timing {
+                store,
+                operation,
+                started: Instant::now(),
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(timing) = span.extensions_mut().remove::<Timing>() {
+            self.metrics.record_datastore_call(
+                timing.store,
+                &timing.operation,
+                timing.started.elapsed(),
+            );
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod datastore_layer;
+pub mod noop;
+pub mod prometheus;
@@ -0,0 +1,19 @@
+use crate::domain::entities::country::Country;
+use crate::domain::port::metrics::{Datastore, MetricsPort};
+use std::time::Duration;
+
+/// Discards everything; used when metrics are disabled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopMetrics;
+
+impl MetricsPort for NoopMetrics {
+    fn record_http_request(&self, _method: &str, _route: &str, _status: u16, _elapsed: Duration) {}
+
+    fn record_datastore_call(&self, _store: Datastore, _operation: &str, _elapsed: Duration) {}
+
+    fn record_pool_event(&self, _store: Datastore, _event: &str) {}
+
+    fn order_created(&self, _country: Option<&Country>, _total_price: f64) {}
+
+    fn stock_out(&self) {}
+}
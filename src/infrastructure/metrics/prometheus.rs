@@ -0,0 +1,164 @@
+use crate::domain::entities::country::Country;
+use crate::domain::port::metrics::{Datastore, MetricsPort};
+use prometheus::{
+    CounterVec, Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
+    TextEncoder,
+};
+use std::time::Duration;
+
+/// Buckets (seconds) for HTTP latency.
+const HTTP_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Buckets (seconds) for datastore calls, which are expected to be faster.
+const DATASTORE_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
+/// Label used for orders placed without a country.
+const UNKNOWN_COUNTRY: &str = "unknown";
+
+/// [`MetricsPort`] backed by a private Prometheus registry.
+///
+/// - `http_requests_total{method, route, status}`
+/// - `http_request_duration_seconds{method, route, status}`
+/// - `datastore_operation_duration_seconds{store, operation}`
+/// - `datastore_pool_events_total{store, event}`
+/// - `orders_created_total{country}`, `order_revenue_total{country}`
+/// - `stock_outs_total`
+#[derive(Clone)]
+pub struct PrometheusMetrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    datastore_duration: HistogramVec,
+    pool_events: IntCounterVec,
+    orders_created: IntCounterVec,
+    order_revenue: CounterVec,
+    stock_outs: IntCounter,
+}
+
+impl PrometheusMetrics {
+    /// Metric names are prefixed with `namespace` (e.g. the service name).
+    pub fn new(namespace: &str) -> Result<Self, prometheus::Error> {
+        let namespace = sanitize(namespace);
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled").namespace(&namespace),
+            &["method", "route", "status"],
+        )?;
+        let http_duration = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency")
+                .namespace(&namespace)
+                .buckets(HTTP_BUCKETS.to_vec()),
+            &["method", "route", "status"],
+        )?;
+        let datastore_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "datastore_operation_duration_seconds",
+                "Latency of MongoDB repository methods and Redis calls",
+            )
+            .namespace(&namespace)
+            .buckets(DATASTORE_BUCKETS.to_vec()),
+            &["store", "operation"],
+        )?;
+        let pool_events = IntCounterVec::new(
+            Opts::new(
+                "datastore_pool_events_total",
+                "Connection pool events (connections opened and closed, checkout failures, pool clears)",
+            )
+            .namespace(&namespace),
+            &["store", "event"],
+        )?;
+        let orders_created = IntCounterVec::new(
+            Opts::new("orders_created_total", "Orders placed").namespace(&namespace),
+            &["country"],
+        )?;
+        let order_revenue = CounterVec::new(
+            Opts::new("order_revenue_total", "Sum of order totals at creation")
+                .namespace(&namespace),
+            &["country"],
+        )?;
+        let stock_outs = IntCounter::with_opts(
+            Opts::new(
+                "stock_outs_total",
+                "Stock changes that left a product at zero",
+            )
+            .namespace(&namespace),
+        )?;
+
+        registry.register(Box::new(http_requests.clone()))?;
+        registry.register(Box::new(http_duration.clone()))?;
+        registry.register(Box::new(datastore_duration.clone()))?;
+        registry.register(Box::new(pool_events.clone()))?;
+        registry.register(Box::new(orders_created.clone()))?;
+        registry.register(Box::new(order_revenue.clone()))?;
+        registry.register(Box::new(stock_outs.clone()))?;
+
+        Ok(Self {
+            registry,
+            http_requests,
+            http_duration,
+            datastore_duration,
+            pool_events,
+            orders_created,
+            order_revenue,
+            stock_outs,
+        })
+    }
+
+    /// Current values in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::error!(error = %e, "Failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl MetricsPort for PrometheusMetrics {
+    fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    fn record_datastore_call(&self, store: Datastore, operation: &str, elapsed: Duration) {
+        self.datastore_duration
+            .with_label_values(&[store.as_str(), operation])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    fn record_pool_event(&self, store: Datastore, event: &str) {
+        self.pool_events
+            .with_label_values(&[store.as_str(), event])
+            .inc();
+    }
+
+    fn order_created(&self, country: Option<&Country>, total_price: f64) {
+        let country = country.map_or_else(|| UNKNOWN_COUNTRY.to_string(), |c| c.to_string());
+        self.orders_created.with_label_values(&[&country]).inc();
+        if total_price > 0.0 {
+            self.order_revenue
+                .with_label_values(&[&country])
+                .inc_by(total_price);
+        }
+    }
+
+    fn stock_out(&self) {
+        self.stock_outs.inc();
+    }
+}
+
+/// Prometheus names allow `[a-zA-Z0-9_]` only.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
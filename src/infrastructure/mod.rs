@@ -1,3 +1,8 @@
+pub mod events;
+pub mod metrics;
+pub mod notifier;
 pub mod persistence;
 pub mod providers; // Asumiendo que moveremos providers aquí o re-exportaremos
+pub mod rate_limit;
 pub mod serde;
+pub mod webhooks;
@@ -0,0 +1,26 @@
+use crate::domain::error::DomainResult;
+use crate::domain::event::LowStock;
+use crate::domain::port::notifier::NotifierPort;
+use async_trait::async_trait;
+
+/// Writes alerts to the application log. Used when no webhook is configured.
+#[derive(Debug, Clone, Default)]
+pub struct LogNotifier;
+
+#[async_trait]
+impl NotifierPort for LogNotifier {
+    #[tracing::instrument(skip_all)]
+    async fn notify_low_stock(&self, event: &LowStock) -> DomainResult<()> {
+        tracing::warn!(
+            product_id = %event.product_id,
+            sku = %event.sku,
+            stock = event.stock,
+            threshold = event.threshold,
+            "Low stock: {} has {} units left (threshold {})",
+            event.name,
+            event.stock,
+            event.threshold
+        );
+        Ok(())
+    }
+}
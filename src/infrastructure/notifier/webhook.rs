@@ -0,0 +1,70 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::LowStock;
+use crate::domain::port::notifier::NotifierPort;
+use crate::infrastructure::providers::propagation;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::time::Duration;
+
+const SERVICE: &str = "notifier-webhook";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct WebhookPayload<'a, T: Serialize> {
+    event: &'static str,
+    data: &'a T,
+}
+
+/// POSTs alerts as JSON (`{"event": "low_stock", "data": {...}}`) to a fixed URL.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            url: url.to_string(),
+        })
+    }
+
+    async fn post<T: Serialize + Sync>(&self, event: &'static str, data: &T) -> DomainResult<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .headers(propagation::outgoing_headers())
+            .json(&WebhookPayload { event, data })
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    Error::external_timeout(SERVICE)
+                } else {
+                    Error::external(SERVICE, e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::external(
+                SERVICE,
+                format!("{} answered {}", self.url, status),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotifierPort for WebhookNotifier {
+    #[tracing::instrument(skip_all, fields(product_id = %event.product_id))]
+    async fn notify_low_stock(&self, event: &LowStock) -> DomainResult<()> {
+        self.post("low_stock", event).await
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain::entities::api_key::{ApiKey, ApiKeyId};
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub created_at: bson::DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<bson::DateTime>,
+}
+
+impl From<ApiKey> for ApiKeyDocument {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key
+                .id
+                .as_ref()
+                .and_then(|id| ObjectId::parse_str(&**id).ok()),
+            name: key.name,
+            prefix: key.prefix,
+            key_hash: key.key_hash,
+            created_at: bson::DateTime::from_chrono(key.created_at),
+            revoked_at: key.revoked_at.map(bson::DateTime::from_chrono),
+        }
+    }
+}
+
+impl From<ApiKeyDocument> for ApiKey {
+    fn from(doc: ApiKeyDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| ApiKeyId::new(oid.to_hex())),
+            name: doc.name,
+            prefix: doc.prefix,
+            key_hash: doc.key_hash,
+            created_at: doc.created_at.to_chrono(),
+            revoked_at: doc.revoked_at.map(|dt| dt.to_chrono()),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::domain::entities::api_key::{ApiKey, ApiKeyId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::api_key::ApiKeyRepositoryPort;
+use crate::infrastructure::persistence::api_key::model::ApiKeyDocument;
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{Collection, Database, IndexModel, bson::doc, options::IndexOptions};
+
+pub const API_KEYS_COLLECTION: &str = "api_keys";
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    collection: Collection<ApiKeyDocument>,
+}
+
+impl ApiKeyRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(API_KEYS_COLLECTION),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! { "key_hash": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .name("key_hash_unique_idx".to_string())
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ API key indexes created");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepositoryPort for ApiKeyRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, key: &ApiKey) -> DomainResult<ApiKeyId> {
+        let result = self
+            .collection
+            .insert_one(ApiKeyDocument::from(key.clone()))
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        result
+            .inserted_id
+            .as_object_id()
+            .map(|oid| ApiKeyId::new(oid.to_hex()))
+            .ok_or_else(|| Error::internal("Failed to get inserted ID"))
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_hash(&self, key_hash: &str) -> DomainResult<Option<ApiKey>> {
+        let doc = self
+            .collection
+            .find_one(doc! { "key_hash": key_hash })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(ApiKey::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<ApiKey>> {
+        let cursor = self
+            .collection
+            .find(doc! {})
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<ApiKeyDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(ApiKey::from).collect())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::domain::entities::audit::{AuditAction, AuditEntry, AuditEntryId, FieldChange};
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntryDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub actor: String,
+    pub action: AuditAction,
+    pub entity: String,
+    pub entity_id: String,
+    #[serde(default)]
+    pub changes: Vec<FieldChange>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    pub created_at: bson::DateTime,
+    /// Removed by the TTL index once reached.
+    pub expires_at: bson::DateTime,
+}
+
+impl AuditEntryDocument {
+    pub fn new(entry: AuditEntry, retention: chrono::Duration) -> Self {
+        Self {
+            id: entry
+                .id
+                .and_then(|id| ObjectId::parse_str(id.into_inner()).ok()),
+            actor: entry.actor,
+            action: entry.action,
+            entity: entry.entity,
+            entity_id: entry.entity_id,
+            changes: entry.changes,
+            trace_id: entry.trace_id,
+            ip: entry.ip,
+            created_at: bson::DateTime::from_chrono(entry.created_at),
+            expires_at: bson::DateTime::from_chrono(entry.created_at + retention),
+        }
+    }
+}
+
+impl From<AuditEntryDocument> for AuditEntry {
+    fn from(doc: AuditEntryDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| AuditEntryId::new(oid.to_hex())),
+            actor: doc.actor,
+            action: doc.action,
+            entity: doc.entity,
+            entity_id: doc.entity_id,
+            changes: doc.changes,
+            trace_id: doc.trace_id,
+            ip: doc.ip,
+            created_at: doc.created_at.to_chrono(),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::domain::entities::audit::AuditEntry;
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::AuditFilter;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::audit::AuditLogPort;
+use crate::infrastructure::persistence::audit::model::AuditEntryDocument;
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{Document, doc},
+    options::IndexOptions,
+};
+use std::time::Duration;
+
+pub const AUDIT_COLLECTION: &str = "audit_log";
+
+#[derive(Clone)]
+pub struct AuditLogRepository {
+    collection: Collection<AuditEntryDocument>,
+    retention: chrono::Duration,
+}
+
+impl AuditLogRepository {
+    /// Entries expire `retention` after they are written. Changing it only
+    /// affects entries written afterwards.
+    pub fn new(db: &Database, retention: Duration) -> Self {
+        Self {
+            collection: db.collection(AUDIT_COLLECTION),
+            retention: chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! { "entity": 1, "entity_id": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("entity_created_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("created_at_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "expires_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("expires_ttl_idx".to_string())
+                        .expire_after(Duration::ZERO)
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Audit log indexes created");
+        Ok(())
+    }
+
+    fn filter(filter: &AuditFilter) -> Document {
+        let mut query = Document::new();
+        if let Some(entity) = &filter.entity {
+            query.insert("entity", entity);
+        }
+        if let Some(entity_id) = &filter.entity_id {
+            query.insert("entity_id", entity_id);
+        }
+        query
+    }
+}
+
+#[async_trait]
+impl AuditLogPort for AuditLogRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all, fields(entries = entries.len()))]
+    async fn append(&self, entries: &[AuditEntry]) -> DomainResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let docs: Vec<AuditEntryDocument> = entries
+            .iter()
+            .cloned()
+            .map(|entry| AuditEntryDocument::new(entry, self.retention))
+            .collect();
+
+        self.collection
+            .insert_many(docs)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+        Ok(())
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find(
+        &self,
+        filter: &AuditFilter,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<AuditEntry>> {
+        let cursor = self
+            .collection
+            .find(Self::filter(filter))
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<AuditEntryDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(AuditEntry::from).collect())
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self, filter: &AuditFilter) -> DomainResult<u64> {
+        self.collection
+            .count_documents(Self::filter(filter))
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+}
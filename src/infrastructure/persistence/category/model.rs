@@ -0,0 +1,66 @@
+use crate::domain::entities::category::{Category, CategoryId};
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub parent_id: Option<ObjectId>,
+    pub path: String,
+    pub created_at: bson::DateTime,
+    pub updated_at: bson::DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<bson::DateTime>,
+}
+
+impl TryFrom<Category> for CategoryDocument {
+    type Error = String;
+
+    fn try_from(category: Category) -> Result<Self, Self::Error> {
+        let id = match category.id {
+            Some(id) => Some(
+                ObjectId::parse_str(&*id)
+                    .map_err(|_| format!("Invalid Category ID format: {}", id))?,
+            ),
+            None => None,
+        };
+
+        let parent_id = match category.parent_id {
+            Some(parent_id) => Some(
+                ObjectId::parse_str(&*parent_id)
+                    .map_err(|_| format!("Invalid parent Category ID format: {}", parent_id))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            id,
+            name: category.name,
+            slug: category.slug,
+            parent_id,
+            path: category.path,
+            created_at: bson::DateTime::from_chrono(category.created_at),
+            updated_at: bson::DateTime::from_chrono(category.updated_at),
+            deleted_at: category.deleted_at.map(bson::DateTime::from_chrono),
+        })
+    }
+}
+
+impl From<CategoryDocument> for Category {
+    fn from(doc: CategoryDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| CategoryId::new(oid.to_hex())),
+            name: doc.name,
+            slug: doc.slug,
+            parent_id: doc.parent_id.map(|oid| CategoryId::new(oid.to_hex())),
+            path: doc.path,
+            created_at: doc.created_at.to_chrono(),
+            updated_at: doc.updated_at.to_chrono(),
+            deleted_at: doc.deleted_at.map(|dt| dt.to_chrono()),
+        }
+    }
+}
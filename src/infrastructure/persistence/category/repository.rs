@@ -0,0 +1,345 @@
+use crate::domain::entities::category::{Category, CategoryId, PATH_SEPARATOR};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::category::CategoryRepositoryPort;
+use crate::infrastructure::persistence::category::model::CategoryDocument;
+use crate::infrastructure::persistence::is_duplicate_key;
+use crate::infrastructure::persistence::outbox::writer::OutboxWriter;
+use async_trait::async_trait;
+use futures::FutureExt;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{self, Document, doc, oid::ObjectId},
+    options::IndexOptions,
+};
+
+pub const CATEGORIES_COLLECTION: &str = "categories";
+
+/// Unique `{path, deleted_at}` index: one live category per path.
+pub const PATH_UNIQUE_INDEX: &str = "path_deleted_unique_idx";
+
+#[derive(Clone)]
+pub struct CategoryRepository {
+    collection: Collection<CategoryDocument>,
+    outbox: OutboxWriter,
+}
+
+impl CategoryRepository {
+    pub fn new(db: &Database, outbox: OutboxWriter) -> Self {
+        Self {
+            collection: db.collection(CATEGORIES_COLLECTION),
+            outbox,
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            // Soft-deleted categories differ by `deleted_at`, so their paths can be reused
+            IndexModel::builder()
+                .keys(doc! { "path": 1, "deleted_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name(PATH_UNIQUE_INDEX.to_string())
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "deleted_at": 1, "parent_id": 1, "name": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("deleted_parent_name_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Categories indexes created");
+        Ok(())
+    }
+}
+
+/// A write that collided with a live category on [`PATH_UNIQUE_INDEX`].
+#[derive(Debug, Clone)]
+struct PathTaken;
+
+fn write_error(e: mongodb::error::Error, path: &str) -> Error {
+    if is_duplicate_key(&e) {
+        Error::duplicate("Category", "path", path)
+    } else {
+        Error::database(e.to_string())
+    }
+}
+
+/// Anchored regex matching every path strictly below `path`.
+fn descendants_filter(path: &str) -> Document {
+    let pattern = format!("^{}{}", escape_regex(path), PATH_SEPARATOR);
+    doc! {
+        "path": { "$regex": pattern },
+        "deleted_at": { "$exists": false }
+    }
+}
+
+fn escape_regex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[async_trait]
+impl CategoryRepositoryPort for CategoryRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, category: &Category) -> DomainResult<CategoryId> {
+        let doc = CategoryDocument::try_from(category.clone()).map_err(Error::internal)?;
+
+        let result = self
+            .collection
+            .insert_one(doc)
+            .await
+            .map_err(|e| write_error(e, &category.path))?;
+
+        result
+            .inserted_id
+            .as_object_id()
+            .map(|oid| CategoryId::new(oid.to_hex()))
+            .ok_or_else(|| Error::internal("Failed to get inserted ID"))
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &CategoryId) -> DomainResult<Option<Category>> {
+        let oid = ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("id", "Category", &**id))?;
+
+        let doc = self
+            .collection
+            .find_one(doc! { "_id": oid, "deleted_at": { "$exists": false } })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(Category::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_path(&self, path: &str) -> DomainResult<Option<Category>> {
+        let doc = self
+            .collection
+            .find_one(doc! { "path": path, "deleted_at": { "$exists": false } })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(Category::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Category>> {
+        let cursor = self
+            .collection
+            .find(doc! { "deleted_at": { "$exists": false } })
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "path": 1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<CategoryDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(Category::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_children(&self, parent_id: Option<&CategoryId>) -> DomainResult<Vec<Category>> {
+        let parent = match parent_id {
+            Some(id) => bson::Bson::ObjectId(
+                ObjectId::parse_str(&**id)
+                    .map_err(|_| Error::invalid_param("parent_id", "Category", &**id))?,
+            ),
+            None => bson::Bson::Null,
+        };
+
+        let cursor = self
+            .collection
+            .find(doc! { "parent_id": parent, "deleted_at": { "$exists": false } })
+            .sort(doc! { "name": 1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<CategoryDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(Category::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_descendant_ids(&self, path: &str) -> DomainResult<Vec<CategoryId>> {
+        let cursor = self
+            .collection
+            .clone_with_type::<Document>()
+            .find(descendants_filter(path))
+            .projection(doc! { "_id": 1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<Document> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| doc.get_object_id("_id").ok())
+            .map(|oid| CategoryId::new(oid.to_hex()))
+            .collect())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(&self, id: &CategoryId, category: &Category) -> DomainResult<bool> {
+        let oid = ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("id", "Category", &**id))?;
+
+        let doc = CategoryDocument::try_from(category.clone()).map_err(Error::internal)?;
+        let bson_doc = mongodb::bson::serialize_to_document(&doc)
+            .map_err(|e| Error::internal(e.to_string()))?;
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                doc! { "$set": bson_doc },
+            )
+            .await
+            .map_err(|e| write_error(e, &category.path))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(%old_path, new_path = %category.path))]
+    async fn move_subtree(
+        &self,
+        id: &CategoryId,
+        category: &Category,
+        old_path: &str,
+    ) -> DomainResult<Option<u64>> {
+        let oid = ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("id", "Category", &**id))?;
+
+        let doc = CategoryDocument::try_from(category.clone()).map_err(Error::internal)?;
+        let bson_doc = mongodb::bson::serialize_to_document(&doc)
+            .map_err(|e| Error::internal(e.to_string()))?;
+        let now = bson::DateTime::from_chrono(category.updated_at);
+        let old_len = old_path.chars().count() as i64;
+
+        // Pipeline update keeps the suffix below the moved node intact.
+        let pipeline = vec![doc! {
+            "$set": {
+                "path": {
+                    "$concat": [
+                        &category.path,
+                        { "$substrCP": ["$path", old_len, { "$strLenCP": "$path" }] }
+                    ]
+                },
+                "updated_at": now
+            }
+        }];
+
+        // The node goes first: a path taken by a live category refuses the
+        // move before any descendant is rewritten
+        let moved = self
+            .outbox
+            .try_write(
+                (
+                    &self.collection,
+                    doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                    doc! { "$set": bson_doc },
+                    descendants_filter(old_path),
+                    pipeline,
+                ),
+                |session, (collection, filter, update, descendants, pipeline)| {
+                    async move {
+                        let node = match collection
+                            .update_one(filter.clone(), update.clone())
+                            .session(&mut *session)
+                            .await
+                        {
+                            Ok(node) => node,
+                            Err(e) if is_duplicate_key(&e) => return Ok(Err(PathTaken)),
+                            Err(e) => return Err(e),
+                        };
+                        if node.matched_count == 0 {
+                            return Ok(Ok((None, Vec::new())));
+                        }
+                        let subtree = match collection
+                            .update_many(descendants.clone(), pipeline.clone())
+                            .session(&mut *session)
+                            .await
+                        {
+                            Ok(subtree) => subtree,
+                            Err(e) if is_duplicate_key(&e) => return Ok(Err(PathTaken)),
+                            Err(e) => return Err(e),
+                        };
+                        Ok(Ok((Some(subtree.modified_count), Vec::new())))
+                    }
+                    .boxed()
+                },
+            )
+            .await?;
+
+        moved.map_err(|PathTaken| Error::duplicate("Category", "path", &category.path))
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &CategoryId) -> DomainResult<bool> {
+        let oid = ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("id", "Category", &**id))?;
+
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                doc! { "$set": { "deleted_at": now } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        self.collection
+            .count_documents(doc! { "deleted_at": { "$exists": false } })
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+}
@@ -0,0 +1,31 @@
+use crate::domain::filter::DateRange;
+use mongodb::bson::{self, Document, doc};
+
+/// Base filter for live documents, optionally restricted to a `created_at` window.
+pub fn live_in_range(range: &DateRange) -> Document {
+    let mut filter = doc! { "deleted_at": { "$exists": false } };
+
+    let mut created_at = Document::new();
+    if let Some(from) = range.from {
+        created_at.insert("$gte", bson::DateTime::from_chrono(from));
+    }
+    if let Some(to) = range.to {
+        created_at.insert("$lt", bson::DateTime::from_chrono(to));
+    }
+    if !created_at.is_empty() {
+        filter.insert("created_at", created_at);
+    }
+
+    filter
+}
+
+/// Matches documents still at `expected` version. Documents written before
+/// versioning have no `version` field and count as version `0`.
+pub fn at_version(expected: u64) -> bson::Bson {
+    let expected = i64::try_from(expected).unwrap_or(i64::MAX);
+    if expected == 0 {
+        bson::Bson::Document(doc! { "$in": [0_i64, bson::Bson::Null] })
+    } else {
+        bson::Bson::Int64(expected)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::domain::entities::inventory::{InventoryMovement, MovementId, MovementKind};
+use crate::domain::entities::product::ProductId;
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InventoryMovementDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub product_id: ObjectId,
+    pub delta: i32,
+    pub kind: MovementKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stock_after: Option<i32>,
+    pub created_at: bson::DateTime,
+}
+
+impl TryFrom<InventoryMovement> for InventoryMovementDocument {
+    type Error = String;
+
+    fn try_from(movement: InventoryMovement) -> Result<Self, Self::Error> {
+        let product_oid = ObjectId::parse_str(&*movement.product_id)
+            .map_err(|_| format!("Invalid Product ID format: {}", movement.product_id))?;
+
+        Ok(Self {
+            // The ledger is append-only: IDs are always assigned on insert
+            id: None,
+            product_id: product_oid,
+            delta: movement.delta,
+            kind: movement.kind,
+            reference: movement.reference,
+            reason: movement.reason,
+            stock_after: movement.stock_after,
+            created_at: bson::DateTime::from_chrono(movement.created_at),
+        })
+    }
+}
+
+impl From<InventoryMovementDocument> for InventoryMovement {
+    fn from(doc: InventoryMovementDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| MovementId::new(oid.to_hex())),
+            product_id: ProductId::new(doc.product_id.to_hex()),
+            delta: doc.delta,
+            kind: doc.kind,
+            reference: doc.reference,
+            reason: doc.reason,
+            stock_after: doc.stock_after,
+            created_at: doc.created_at.to_chrono(),
+        }
+    }
+}
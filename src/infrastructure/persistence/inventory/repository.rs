@@ -0,0 +1,174 @@
+use crate::domain::entities::inventory::{InventoryMovement, MovementId};
+use crate::domain::entities::product::ProductId;
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::inventory::InventoryLedgerPort;
+use crate::infrastructure::persistence::inventory::model::InventoryMovementDocument;
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{Bson, Document, doc, oid::ObjectId},
+    options::IndexOptions,
+};
+
+pub const INVENTORY_COLLECTION: &str = "inventory_movements";
+
+#[derive(Clone)]
+pub struct InventoryLedgerRepository {
+    collection: Collection<InventoryMovementDocument>,
+}
+
+impl InventoryLedgerRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(INVENTORY_COLLECTION),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! { "product_id": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("product_created_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "reference": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("reference_idx".to_string())
+                        .sparse(true)
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Inventory movements indexes created");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InventoryLedgerPort for InventoryLedgerRepository {
+    // ===== APPEND =====
+
+    #[tracing::instrument(skip_all)]
+    async fn append(&self, movement: &InventoryMovement) -> DomainResult<MovementId> {
+        let doc = InventoryMovementDocument::try_from(movement.clone()).map_err(Error::internal)?;
+
+        let result = self
+            .collection
+            .insert_one(doc)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        result
+            .inserted_id
+            .as_object_id()
+            .map(|oid| MovementId::new(oid.to_hex()))
+            .ok_or_else(|| Error::internal("Failed to get inserted ID"))
+    }
+
+    #[tracing::instrument(skip_all, fields(movements = movements.len()))]
+    async fn append_many(&self, movements: &[InventoryMovement]) -> DomainResult<u64> {
+        if movements.is_empty() {
+            return Ok(0);
+        }
+
+        let docs = movements
+            .iter()
+            .cloned()
+            .map(InventoryMovementDocument::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Error::internal)?;
+
+        let result = self
+            .collection
+            .insert_many(docs)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_product(
+        &self,
+        product_id: &ProductId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<InventoryMovement>> {
+        let oid = ObjectId::parse_str(&**product_id)
+            .map_err(|_| Error::invalid_param("product_id", "Product", &**product_id))?;
+
+        let cursor = self
+            .collection
+            .find(doc! { "product_id": oid })
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<InventoryMovementDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(InventoryMovement::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_by_product(&self, product_id: &ProductId) -> DomainResult<u64> {
+        let oid = ObjectId::parse_str(&**product_id)
+            .map_err(|_| Error::invalid_param("product_id", "Product", &**product_id))?;
+
+        self.collection
+            .count_documents(doc! { "product_id": oid })
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn sum_by_product(&self) -> DomainResult<Vec<(ProductId, i64)>> {
+        let cursor = self
+            .collection
+            .aggregate(vec![doc! {
+                "$group": { "_id": "$product_id", "total": { "$sum": { "$toLong": "$delta" } } }
+            }])
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<Document> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| {
+                let oid = doc.get_object_id("_id").ok()?;
+                let total = match doc.get("total")? {
+                    Bson::Int64(total) => *total,
+                    Bson::Int32(total) => i64::from(*total),
+                    _ => return None,
+                };
+                Some((ProductId::new(oid.to_hex()), total))
+            })
+            .collect())
+    }
+}
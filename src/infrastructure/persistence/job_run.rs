@@ -0,0 +1,60 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::job::JobRun;
+use crate::domain::port::job_run::JobRunPort;
+use async_trait::async_trait;
+use mongodb::{
+    Collection, Database,
+    bson::{self, Document, doc},
+};
+
+pub const JOB_RUNS_COLLECTION: &str = "_job_runs";
+
+/// MongoDB-backed [`JobRunPort`], the counterpart of
+/// [`MongoLock`](super::lease::MongoLock) for instances without Redis.
+///
+/// One document per job: the last run, with the job name as `_id`.
+#[derive(Clone)]
+pub struct MongoJobRuns {
+    collection: Collection<Document>,
+}
+
+impl MongoJobRuns {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(JOB_RUNS_COLLECTION),
+        }
+    }
+}
+
+#[async_trait]
+impl JobRunPort for MongoJobRuns {
+    #[tracing::instrument(skip_all, fields(job = %run.job))]
+    async fn record(&self, run: &JobRun) -> DomainResult<()> {
+        let mut document = bson::to_document(run)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+        document.insert("_id", &run.job);
+
+        self.collection
+            .replace_one(doc! { "_id": &run.job }, document)
+            .upsert(true)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(%job))]
+    async fn last(&self, job: &str) -> DomainResult<Option<JobRun>> {
+        let document = self
+            .collection
+            .find_one(doc! { "_id": job })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        document
+            .map(|document| {
+                bson::from_document(document)
+                    .map_err(|e| Error::internal(format!("Deserialization error: {}", e)))
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::port::lock::{Lease, LockPort};
+use crate::infrastructure::persistence::is_duplicate_key;
+use async_trait::async_trait;
+use mongodb::{
+    Collection, Database,
+    bson::{self, Document, doc},
+    options::ReturnDocument,
+};
+use std::time::Duration;
+
+pub const LOCKS_COLLECTION: &str = "_locks";
+
+/// MongoDB-backed [`LockPort`], for locks that must hold wherever the data
+/// lives, with or without Redis (migrations, above all).
+///
+/// One document per lock name: `{ _id: name, owner, token, expires_at }`.
+/// Released and lapsed locks keep their document, so `token` keeps growing.
+#[derive(Clone)]
+pub struct MongoLock {
+    collection: Collection<Document>,
+}
+
+impl MongoLock {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(LOCKS_COLLECTION),
+        }
+    }
+}
+
+fn expires_in(ttl: Duration) -> bson::DateTime {
+    bson::DateTime::from_millis(bson::DateTime::now().timestamp_millis() + ttl.as_millis() as i64)
+}
+
+#[async_trait]
+impl LockPort for MongoLock {
+    /// Takes the lock if it is free or its lease has lapsed. While it is held,
+    /// the filter matches nothing and the upsert collides on `_id`.
+    #[tracing::instrument(skip_all, fields(%name))]
+    async fn try_acquire(&self, name: &str, ttl: Duration) -> DomainResult<Option<Lease>> {
+        let owner = uuid::Uuid::new_v4().to_string();
+
+        let result = self
+            .collection
+            .find_one_and_update(
+                doc! { "_id": name, "expires_at": { "$lte": bson::DateTime::now() } },
+                doc! {
+                    "$set": { "owner": &owner, "expires_at": expires_in(ttl) },
+                    "$inc": { "token": 1_i64 }
+                },
+            )
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .await;
+
+        match result {
+            Ok(Some(doc)) => {
+                let token = doc
+                    .get_i64("token")
+                    .map_err(|e| Error::internal(format!("Lock '{}' token: {}", name, e)))?;
+                Ok(Some(Lease {
+                    name: name.to_string(),
+                    owner,
+                    token: token as u64,
+                }))
+            }
+            Ok(None) => Err(Error::internal(format!(
+                "Lock '{}' upsert returned nothing",
+                name
+            ))),
+            Err(e) if is_duplicate_key(&e) => Ok(None),
+            Err(e) => Err(Error::database(e.to_string())),
+        }
+    }
+
+    #[tracing::instrument(skip_all, fields(name = %lease.name))]
+    async fn renew(&self, lease: &Lease, ttl: Duration) -> DomainResult<bool> {
+        let result = self
+            .collection
+            .update_one(
+                doc! {
+                    "_id": &lease.name,
+                    "owner": &lease.owner,
+                    "expires_at": { "$gt": bson::DateTime::now() }
+                },
+                doc! { "$set": { "expires_at": expires_in(ttl) } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+        Ok(result.matched_count > 0)
+    }
+
+    /// Expires the lease rather than deleting it, to keep the fencing counter.
+    #[tracing::instrument(skip_all, fields(name = %lease.name))]
+    async fn release(&self, lease: &Lease) -> DomainResult<bool> {
+        let now = bson::DateTime::now();
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": &lease.name, "owner": &lease.owner, "expires_at": { "$gt": now } },
+                doc! { "$set": { "expires_at": now } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+        Ok(result.matched_count > 0)
+    }
+}
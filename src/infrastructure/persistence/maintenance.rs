@@ -0,0 +1,379 @@
+//! Collection-level operations for `service-admin`, outside any repository port.
+
+use crate::domain::error::{DomainResult, Error};
+use crate::infrastructure::persistence::{
+    api_key::repository::{API_KEYS_COLLECTION, ApiKeyRepository},
+    audit::repository::{AUDIT_COLLECTION, AuditLogRepository},
+    category::repository::{CATEGORIES_COLLECTION, CategoryRepository},
+    inventory::repository::{INVENTORY_COLLECTION, InventoryLedgerRepository},
+    order::repository::{ORDERS_COLLECTION, OrderRepository},
+    outbox::repository::{OUTBOX_COLLECTION, OutboxRepository},
+    product::repository::{PRODUCTS_COLLECTION, ProductRepository},
+    user::repository::{USERS_COLLECTION, UserRepository},
+    webhook::repository::{
+        WEBHOOK_DELIVERIES_COLLECTION, WEBHOOK_SUBSCRIPTIONS_COLLECTION, WebhookDeliveryRepository,
+        WebhookSubscriptionRepository,
+    },
+};
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use mongodb::{
+    Database, IndexModel,
+    bson::{self, Document, doc},
+    error::ErrorKind,
+    options::IndexOptions,
+};
+use serde::Serialize;
+
+/// Collections with soft deletes, in the order they are purged.
+pub const SOFT_DELETED_COLLECTIONS: &[&str] = &[
+    ORDERS_COLLECTION,
+    PRODUCTS_COLLECTION,
+    USERS_COLLECTION,
+    CATEGORIES_COLLECTION,
+    WEBHOOK_SUBSCRIPTIONS_COLLECTION,
+];
+
+/// The indexes one repository declares for its collection.
+pub struct CollectionIndexes {
+    pub collection: &'static str,
+    pub indexes: fn() -> Vec<IndexModel>,
+}
+
+/// Every collection with indexes, as declared by the repositories.
+pub fn catalog() -> Vec<CollectionIndexes> {
+    vec![
+        CollectionIndexes {
+            collection: USERS_COLLECTION,
+            indexes: UserRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: PRODUCTS_COLLECTION,
+            indexes: ProductRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: CATEGORIES_COLLECTION,
+            indexes: CategoryRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: ORDERS_COLLECTION,
+            indexes: OrderRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: INVENTORY_COLLECTION,
+            indexes: InventoryLedgerRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: OUTBOX_COLLECTION,
+            indexes: OutboxRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: WEBHOOK_SUBSCRIPTIONS_COLLECTION,
+            indexes: WebhookSubscriptionRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: WEBHOOK_DELIVERIES_COLLECTION,
+            indexes: WebhookDeliveryRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: AUDIT_COLLECTION,
+            indexes: AuditLogRepository::indexes,
+        },
+        CollectionIndexes {
+            collection: API_KEYS_COLLECTION,
+            indexes: ApiKeyRepository::indexes,
+        },
+    ]
+}
+
+/// Declared versus existing indexes of one collection.
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexReport {
+    pub collection: String,
+    pub expected: Vec<String>,
+    /// Declared but absent: run `indexes create`.
+    pub missing: Vec<String>,
+    /// Present but no longer declared (left over from an older release).
+    pub unexpected: Vec<String>,
+}
+
+impl IndexReport {
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// Creates every declared index (idempotent).
+#[tracing::instrument(skip_all)]
+pub async fn create_indexes(db: &Database) -> DomainResult<Vec<IndexReport>> {
+    for entry in catalog() {
+        db.collection::<Document>(entry.collection)
+            .create_indexes((entry.indexes)())
+            .await
+            .map_err(|e| Error::database(format!("{}: {}", entry.collection, e)))?;
+    }
+    verify_indexes(db).await
+}
+
+/// Compares declared indexes with the ones in the database, by name.
+#[tracing::instrument(skip_all)]
+pub async fn verify_indexes(db: &Database) -> DomainResult<Vec<IndexReport>> {
+    let mut reports = Vec::new();
+    for entry in catalog() {
+        let expected: Vec<String> = (entry.indexes)()
+            .iter()
+            .filter_map(|index| index.options.as_ref().and_then(|o| o.name.clone()))
+            .collect();
+        let existing = existing_indexes(db, entry.collection).await?;
+
+        reports.push(IndexReport {
+            collection: entry.collection.to_string(),
+            missing: expected
+                .iter()
+                .filter(|name| !existing.contains(name))
+                .cloned()
+                .collect(),
+            unexpected: existing
+                .into_iter()
+                .filter(|name| name != "_id_" && !expected.contains(name))
+                .collect(),
+            expected,
+        });
+    }
+    Ok(reports)
+}
+
+/// What `rebuild_indexes` did to one collection.
+#[derive(Debug, Clone, Serialize)]
+pub struct RebuildReport {
+    pub collection: String,
+    /// Declared but absent, now created.
+    pub created: Vec<String>,
+    /// Definition changed, recreated behind a stand-in index.
+    pub rebuilt: Vec<String>,
+    /// No longer declared, dropped.
+    pub dropped: Vec<String>,
+    /// Unique indexes that differ from their declaration or are no longer
+    /// declared. Never dropped here: until the new one is built nothing would
+    /// stop duplicates, so rebuild them by hand in a maintenance window.
+    pub kept: Vec<String>,
+}
+
+/// Brings the indexes of `collection` (every collection when `None`) in line
+/// with their declarations without leaving queries on a full scan.
+///
+/// A changed index is rebuilt by first creating a stand-in under a temporary
+/// name — the same keys followed by `_id`, so it serves the same queries —
+/// then dropping and recreating the original and finally dropping the
+/// stand-in. Unique indexes are created when missing but never dropped.
+#[tracing::instrument(skip_all)]
+pub async fn rebuild_indexes(
+    db: &Database,
+    collection: Option<&str>,
+) -> DomainResult<Vec<RebuildReport>> {
+    let entries: Vec<CollectionIndexes> = catalog()
+        .into_iter()
+        .filter(|entry| collection.is_none_or(|name| name == entry.collection))
+        .collect();
+    if let Some(name) = collection
+        && entries.is_empty()
+    {
+        return Err(Error::not_found("Collection", name));
+    }
+
+    let mut reports = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        reports.push(rebuild_collection(db, entry).await?);
+        tracing::info!("✓ {} indexes rebuilt", entry.collection);
+    }
+    Ok(reports)
+}
+
+async fn rebuild_collection(
+    db: &Database,
+    entry: &CollectionIndexes,
+) -> DomainResult<RebuildReport> {
+    let target = db.collection::<Document>(entry.collection);
+    let failed = |e: mongodb::error::Error| Error::database(format!("{}: {}", entry.collection, e));
+    let mut existing = existing_index_models(db, entry.collection).await?;
+    let mut report = RebuildReport {
+        collection: entry.collection.to_string(),
+        created: Vec::new(),
+        rebuilt: Vec::new(),
+        dropped: Vec::new(),
+        kept: Vec::new(),
+    };
+
+    for declared in (entry.indexes)() {
+        let name = index_name(&declared).to_string();
+        let Some(position) = existing.iter().position(|index| index_name(index) == name) else {
+            target.create_index(declared).await.map_err(failed)?;
+            report.created.push(name);
+            continue;
+        };
+        let current = existing.remove(position);
+        if same_definition(&declared, &current) {
+            continue;
+        }
+        if is_unique(&declared) || is_unique(&current) {
+            report.kept.push(name);
+            continue;
+        }
+
+        let stand_in = stand_in(&declared);
+        let stand_in_name = index_name(&stand_in).to_string();
+        target.create_index(stand_in).await.map_err(failed)?;
+        target.drop_index(&name).await.map_err(failed)?;
+        target.create_index(declared).await.map_err(failed)?;
+        target.drop_index(&stand_in_name).await.map_err(failed)?;
+        report.rebuilt.push(name);
+    }
+
+    // What is left is no longer declared
+    for stale in existing {
+        let name = index_name(&stale).to_string();
+        if name == "_id_" {
+            continue;
+        }
+        if is_unique(&stale) {
+            report.kept.push(name);
+        } else {
+            target.drop_index(&name).await.map_err(failed)?;
+            report.dropped.push(name);
+        }
+    }
+    Ok(report)
+}
+
+fn index_name(index: &IndexModel) -> &str {
+    index
+        .options
+        .as_ref()
+        .and_then(|options| options.name.as_deref())
+        .unwrap_or_default()
+}
+
+fn is_unique(index: &IndexModel) -> bool {
+    index
+        .options
+        .as_ref()
+        .and_then(|options| options.unique)
+        .unwrap_or(false)
+}
+
+/// Same keys, in order, and the same options that change what is indexed.
+fn same_definition(declared: &IndexModel, current: &IndexModel) -> bool {
+    let keys_match = declared.keys.len() == current.keys.len()
+        && declared
+            .keys
+            .iter()
+            .zip(current.keys.iter())
+            .all(|((a, x), (b, y))| {
+                a == b && (x == y || as_f64(x).zip(as_f64(y)).is_some_and(|(x, y)| x == y))
+            });
+    let options = |index: &IndexModel| {
+        let options = index.options.clone().unwrap_or_default();
+        (
+            options.unique.unwrap_or(false),
+            options.partial_filter_expression,
+            options.expire_after,
+        )
+    };
+    keys_match && options(declared) == options(current)
+}
+
+/// The server may return `1` as a double.
+fn as_f64(value: &bson::Bson) -> Option<f64> {
+    match value {
+        bson::Bson::Int32(n) => Some(f64::from(*n)),
+        bson::Bson::Int64(n) => Some(*n as f64),
+        bson::Bson::Double(n) => Some(*n),
+        _ => None,
+    }
+}
+
+/// Temporary copy of `index` that covers the same queries while it is rebuilt.
+/// The trailing `_id` key lets it coexist with the original; TTL is left out
+/// as it only applies to single-field indexes.
+fn stand_in(index: &IndexModel) -> IndexModel {
+    let mut keys = index.keys.clone();
+    keys.insert("_id", 1);
+    let options = index.options.clone().unwrap_or_default();
+    IndexModel::builder()
+        .keys(keys)
+        .options(
+            IndexOptions::builder()
+                .name(format!("{}_rebuild", index_name(index)))
+                .partial_filter_expression(options.partial_filter_expression)
+                .build(),
+        )
+        .build()
+}
+
+/// Soft-deleted documents removed (or, with `dry_run`, that would be) per collection.
+#[derive(Debug, Clone, Serialize)]
+pub struct PurgeReport {
+    pub collection: String,
+    pub purged: u64,
+}
+
+/// Permanently removes documents soft-deleted before `before`.
+#[tracing::instrument(skip_all, fields(%before, dry_run = dry_run))]
+pub async fn purge_deleted(
+    db: &Database,
+    before: DateTime<Utc>,
+    dry_run: bool,
+) -> DomainResult<Vec<PurgeReport>> {
+    let filter = doc! { "deleted_at": { "$lt": bson::DateTime::from_chrono(before) } };
+
+    let mut reports = Vec::with_capacity(SOFT_DELETED_COLLECTIONS.len());
+    for name in SOFT_DELETED_COLLECTIONS {
+        let collection = db.collection::<Document>(name);
+        let purged = if dry_run {
+            collection.count_documents(filter.clone()).await
+        } else {
+            collection
+                .delete_many(filter.clone())
+                .await
+                .map(|result| result.deleted_count)
+        }
+        .map_err(|e| Error::database(format!("{}: {}", name, e)))?;
+
+        reports.push(PurgeReport {
+            collection: name.to_string(),
+            purged,
+        });
+    }
+    Ok(reports)
+}
+
+/// Index names of `collection`; none when it does not exist yet.
+async fn existing_indexes(db: &Database, collection: &str) -> DomainResult<Vec<String>> {
+    const NAMESPACE_NOT_FOUND: i32 = 26;
+    match db
+        .collection::<Document>(collection)
+        .list_index_names()
+        .await
+    {
+        Ok(names) => Ok(names),
+        Err(e) if matches!(e.kind.as_ref(), ErrorKind::Command(err) if err.code == NAMESPACE_NOT_FOUND) => {
+            Ok(Vec::new())
+        }
+        Err(e) => Err(Error::database(format!("{}: {}", collection, e))),
+    }
+}
+
+/// Indexes of `collection` as the server describes them; none when it does
+/// not exist yet.
+async fn existing_index_models(db: &Database, collection: &str) -> DomainResult<Vec<IndexModel>> {
+    if existing_indexes(db, collection).await?.is_empty() {
+        return Ok(Vec::new());
+    }
+    db.collection::<Document>(collection)
+        .list_indexes()
+        .await
+        .map_err(|e| Error::database(format!("{}: {}", collection, e)))?
+        .try_collect()
+        .await
+        .map_err(|e| Error::database(format!("{}: {}", collection, e)))
+}
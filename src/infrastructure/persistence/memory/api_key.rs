@@ -0,0 +1,58 @@
+use super::{lock, new_id, newest_first, paginate};
+use crate::domain::entities::api_key::{ApiKey, ApiKeyId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::api_key::ApiKeyRepositoryPort;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// Keys in the order they were issued, revoked ones included.
+#[derive(Default)]
+pub struct InMemoryApiKeyRepository {
+    keys: Mutex<Vec<ApiKey>>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepositoryPort for InMemoryApiKeyRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, key: &ApiKey) -> DomainResult<ApiKeyId> {
+        let mut keys = lock(&self.keys);
+        if keys.iter().any(|k| k.key_hash == key.key_hash) {
+            return Err(Error::duplicate("ApiKey", "key_hash", &key.prefix));
+        }
+
+        let id = ApiKeyId::new(new_id());
+        keys.push(ApiKey {
+            id: Some(id.clone()),
+            ..key.clone()
+        });
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_hash(&self, key_hash: &str) -> DomainResult<Option<ApiKey>> {
+        Ok(lock(&self.keys)
+            .iter()
+            .find(|k| k.key_hash == key_hash)
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<ApiKey>> {
+        let keys = lock(&self.keys);
+        let sorted = newest_first(keys.iter().cloned(), |k| k.created_at);
+
+        Ok(paginate(sorted, &pagination))
+    }
+}
@@ -0,0 +1,75 @@
+use super::{lock, new_id, newest_first, paginate};
+use crate::domain::entities::audit::{AuditEntry, AuditEntryId};
+use crate::domain::error::DomainResult;
+use crate::domain::filter::AuditFilter;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::audit::AuditLogPort;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// Entries in the order they were appended. Nothing expires: retention only
+/// applies to the MongoDB log.
+#[derive(Default)]
+pub struct InMemoryAuditLog {
+    entries: Mutex<Vec<AuditEntry>>,
+}
+
+impl InMemoryAuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn matching(&self, filter: &AuditFilter) -> Vec<AuditEntry> {
+        let entries = lock(&self.entries);
+        newest_first(
+            entries
+                .iter()
+                .filter(|e| {
+                    filter
+                        .entity
+                        .as_ref()
+                        .is_none_or(|entity| &e.entity == entity)
+                })
+                .filter(|e| {
+                    filter
+                        .entity_id
+                        .as_ref()
+                        .is_none_or(|id| &e.entity_id == id)
+                })
+                .cloned(),
+            |e| e.created_at,
+        )
+    }
+}
+
+#[async_trait]
+impl AuditLogPort for InMemoryAuditLog {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all, fields(entries = entries.len()))]
+    async fn append(&self, entries: &[AuditEntry]) -> DomainResult<()> {
+        lock(&self.entries).extend(entries.iter().map(|entry| AuditEntry {
+            id: Some(AuditEntryId::new(new_id())),
+            ..entry.clone()
+        }));
+        Ok(())
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find(
+        &self,
+        filter: &AuditFilter,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<AuditEntry>> {
+        Ok(paginate(self.matching(filter), &pagination))
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self, filter: &AuditFilter) -> DomainResult<u64> {
+        Ok(self.matching(filter).len() as u64)
+    }
+}
@@ -0,0 +1,206 @@
+use super::{check_id, lock, new_id, paginate};
+use crate::domain::entities::category::{Category, CategoryId, PATH_SEPARATOR};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::category::CategoryRepositoryPort;
+use async_trait::async_trait;
+use std::sync::Mutex;
+
+/// Categories in insertion order, soft-deleted ones included.
+#[derive(Default)]
+pub struct InMemoryCategoryRepository {
+    categories: Mutex<Vec<Category>>,
+}
+
+impl InMemoryCategoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Whether `path` is strictly below `ancestor` in the tree.
+fn is_descendant(path: &str, ancestor: &str) -> bool {
+    path.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with(PATH_SEPARATOR))
+}
+
+/// Fails like the unique path index when a live category other than `id`
+/// has `path`.
+fn ensure_path_free(categories: &[Category], path: &str, id: &CategoryId) -> DomainResult<()> {
+    if categories
+        .iter()
+        .any(|c| c.path == path && !c.is_deleted() && c.id.as_ref() != Some(id))
+    {
+        return Err(Error::duplicate("Category", "path", path));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl CategoryRepositoryPort for InMemoryCategoryRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, category: &Category) -> DomainResult<CategoryId> {
+        let id = category
+            .id
+            .clone()
+            .unwrap_or_else(|| CategoryId::new(new_id()));
+        check_id(&id, "id", "Category")?;
+        if let Some(parent_id) = &category.parent_id {
+            check_id(parent_id, "parent_id", "Category")?;
+        }
+
+        let mut categories = lock(&self.categories);
+        ensure_path_free(&categories, &category.path, &id)?;
+        categories.push(Category {
+            id: Some(id.clone()),
+            ..category.clone()
+        });
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &CategoryId) -> DomainResult<Option<Category>> {
+        check_id(id, "id", "Category")?;
+
+        Ok(lock(&self.categories)
+            .iter()
+            .find(|c| c.id.as_ref() == Some(id) && !c.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_path(&self, path: &str) -> DomainResult<Option<Category>> {
+        Ok(lock(&self.categories)
+            .iter()
+            .find(|c| c.path == path && !c.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Category>> {
+        let mut categories: Vec<Category> = lock(&self.categories)
+            .iter()
+            .filter(|c| !c.is_deleted())
+            .cloned()
+            .collect();
+        categories.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(paginate(categories, &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_children(&self, parent_id: Option<&CategoryId>) -> DomainResult<Vec<Category>> {
+        if let Some(id) = parent_id {
+            check_id(id, "parent_id", "Category")?;
+        }
+
+        let mut children: Vec<Category> = lock(&self.categories)
+            .iter()
+            .filter(|c| !c.is_deleted() && c.parent_id.as_ref() == parent_id)
+            .cloned()
+            .collect();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(children)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_descendant_ids(&self, path: &str) -> DomainResult<Vec<CategoryId>> {
+        Ok(lock(&self.categories)
+            .iter()
+            .filter(|c| !c.is_deleted() && is_descendant(&c.path, path))
+            .filter_map(|c| c.id.clone())
+            .collect())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(&self, id: &CategoryId, category: &Category) -> DomainResult<bool> {
+        check_id(id, "id", "Category")?;
+
+        let mut categories = lock(&self.categories);
+        ensure_path_free(&categories, &category.path, id)?;
+        let Some(stored) = categories
+            .iter_mut()
+            .find(|c| c.id.as_ref() == Some(id) && !c.is_deleted())
+        else {
+            return Ok(false);
+        };
+        *stored = Category {
+            id: Some(id.clone()),
+            ..category.clone()
+        };
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip_all, fields(%old_path, new_path = %category.path))]
+    async fn move_subtree(
+        &self,
+        id: &CategoryId,
+        category: &Category,
+        old_path: &str,
+    ) -> DomainResult<Option<u64>> {
+        check_id(id, "id", "Category")?;
+
+        // One lock for the node and its subtree, so readers never see half a move
+        let mut categories = lock(&self.categories);
+        ensure_path_free(&categories, &category.path, id)?;
+        let Some(stored) = categories
+            .iter_mut()
+            .find(|c| c.id.as_ref() == Some(id) && !c.is_deleted())
+        else {
+            return Ok(None);
+        };
+        *stored = Category {
+            id: Some(id.clone()),
+            ..category.clone()
+        };
+
+        let mut moved = 0;
+        for descendant in categories
+            .iter_mut()
+            .filter(|c| !c.is_deleted() && is_descendant(&c.path, old_path))
+        {
+            descendant.path = format!("{}{}", category.path, &descendant.path[old_path.len()..]);
+            descendant.updated_at = category.updated_at;
+            moved += 1;
+        }
+
+        Ok(Some(moved))
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &CategoryId) -> DomainResult<bool> {
+        check_id(id, "id", "Category")?;
+
+        let mut categories = lock(&self.categories);
+        let Some(stored) = categories
+            .iter_mut()
+            .find(|c| c.id.as_ref() == Some(id) && !c.is_deleted())
+        else {
+            return Ok(false);
+        };
+        stored.deleted_at = Some(chrono::Utc::now());
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        Ok(lock(&self.categories)
+            .iter()
+            .filter(|c| !c.is_deleted())
+            .count() as u64)
+    }
+}
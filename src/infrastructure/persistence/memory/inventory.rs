@@ -0,0 +1,101 @@
+use super::{check_id, lock, new_id, newest_first, paginate};
+use crate::domain::entities::inventory::{InventoryMovement, MovementId};
+use crate::domain::entities::product::ProductId;
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::inventory::InventoryLedgerPort;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Movements in the order they were appended.
+#[derive(Default)]
+pub struct InMemoryInventoryLedger {
+    movements: Mutex<Vec<InventoryMovement>>,
+}
+
+impl InMemoryInventoryLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends movements written alongside a product change, under the
+    /// caller's lock on the products.
+    pub(super) fn record(&self, movements: &[InventoryMovement]) {
+        lock(&self.movements).extend(movements.iter().map(|movement| InventoryMovement {
+            id: Some(MovementId::new(new_id())),
+            ..movement.clone()
+        }));
+    }
+}
+
+#[async_trait]
+impl InventoryLedgerPort for InMemoryInventoryLedger {
+    // ===== APPEND =====
+
+    #[tracing::instrument(skip_all)]
+    async fn append(&self, movement: &InventoryMovement) -> DomainResult<MovementId> {
+        check_id(&movement.product_id, "product_id", "Product")?;
+
+        let id = MovementId::new(new_id());
+        lock(&self.movements).push(InventoryMovement {
+            id: Some(id.clone()),
+            ..movement.clone()
+        });
+
+        Ok(id)
+    }
+
+    #[tracing::instrument(skip_all, fields(movements = movements.len()))]
+    async fn append_many(&self, movements: &[InventoryMovement]) -> DomainResult<u64> {
+        for movement in movements {
+            check_id(&movement.product_id, "product_id", "Product")?;
+        }
+
+        self.record(movements);
+
+        Ok(movements.len() as u64)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_product(
+        &self,
+        product_id: &ProductId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<InventoryMovement>> {
+        check_id(product_id, "product_id", "Product")?;
+
+        let movements = lock(&self.movements);
+        let matching = newest_first(
+            movements
+                .iter()
+                .filter(|m| &m.product_id == product_id)
+                .cloned(),
+            |m| m.created_at,
+        );
+
+        Ok(paginate(matching, &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_by_product(&self, product_id: &ProductId) -> DomainResult<u64> {
+        check_id(product_id, "product_id", "Product")?;
+
+        Ok(lock(&self.movements)
+            .iter()
+            .filter(|m| &m.product_id == product_id)
+            .count() as u64)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn sum_by_product(&self) -> DomainResult<Vec<(ProductId, i64)>> {
+        let mut totals: HashMap<ProductId, i64> = HashMap::new();
+        for movement in lock(&self.movements).iter() {
+            *totals.entry(movement.product_id.clone()).or_default() += i64::from(movement.delta);
+        }
+
+        Ok(totals.into_iter().collect())
+    }
+}
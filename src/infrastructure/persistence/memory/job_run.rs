@@ -0,0 +1,30 @@
+use super::lock;
+use crate::domain::error::DomainResult;
+use crate::domain::job::JobRun;
+use crate::domain::port::job_run::JobRunPort;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Default)]
+pub struct InMemoryJobRuns {
+    runs: Mutex<HashMap<String, JobRun>>,
+}
+
+impl InMemoryJobRuns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JobRunPort for InMemoryJobRuns {
+    async fn record(&self, run: &JobRun) -> DomainResult<()> {
+        lock(&self.runs).insert(run.job.clone(), run.clone());
+        Ok(())
+    }
+
+    async fn last(&self, job: &str) -> DomainResult<Option<JobRun>> {
+        Ok(lock(&self.runs).get(job).cloned())
+    }
+}
@@ -0,0 +1,91 @@
+use super::lock;
+use crate::domain::error::DomainResult;
+use crate::domain::port::lock::{Lease, LockPort};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+struct Held {
+    owner: String,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+struct Locks {
+    held: HashMap<String, Held>,
+    /// Last fencing token by lock name; kept after release, like Redis'.
+    fences: HashMap<String, u64>,
+}
+
+/// Locks within one process, with the same leases and fencing tokens as the
+/// Redis lock.
+#[derive(Default)]
+pub struct InMemoryLock {
+    locks: Mutex<Locks>,
+}
+
+impl InMemoryLock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl LockPort for InMemoryLock {
+    async fn try_acquire(&self, name: &str, ttl: Duration) -> DomainResult<Option<Lease>> {
+        let now = Instant::now();
+        let mut locks = lock(&self.locks);
+        if locks
+            .held
+            .get(name)
+            .is_some_and(|held| held.expires_at > now)
+        {
+            return Ok(None);
+        }
+
+        let owner = uuid::Uuid::new_v4().to_string();
+        let token = locks.fences.entry(name.to_string()).or_default();
+        *token += 1;
+        let token = *token;
+        locks.held.insert(
+            name.to_string(),
+            Held {
+                owner: owner.clone(),
+                expires_at: now + ttl,
+            },
+        );
+
+        Ok(Some(Lease {
+            name: name.to_string(),
+            owner,
+            token,
+        }))
+    }
+
+    async fn renew(&self, lease: &Lease, ttl: Duration) -> DomainResult<bool> {
+        let now = Instant::now();
+        let mut locks = lock(&self.locks);
+        match locks.held.get_mut(&lease.name) {
+            Some(held) if held.owner == lease.owner && held.expires_at > now => {
+                held.expires_at = now + ttl;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn release(&self, lease: &Lease) -> DomainResult<bool> {
+        let now = Instant::now();
+        let mut locks = lock(&self.locks);
+        let owned = locks
+            .held
+            .get(&lease.name)
+            .is_some_and(|held| held.owner == lease.owner && held.expires_at > now);
+        if owned {
+            locks.held.remove(&lease.name);
+        }
+        Ok(owned)
+    }
+}
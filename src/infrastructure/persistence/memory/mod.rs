@@ -0,0 +1,119 @@
+//! In-process adapters for every repository port (and the lock and job
+//! runs), for unit tests and local runs without MongoDB.
+//!
+//! They follow the MongoDB repositories: soft-deleted records are invisible,
+//! lists are newest first and paginated the same way, versions are checked
+//! and incremented, and events reach the outbox only when a write changes
+//! state. IDs are ObjectId hex strings, so malformed IDs fail the same way.
+
+pub mod api_key;
+pub mod audit;
+pub mod category;
+pub mod inventory;
+pub mod job_run;
+pub mod lease;
+pub mod order;
+pub mod outbox;
+pub mod product;
+pub mod user;
+pub mod webhook;
+
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use chrono::{DateTime, Utc};
+use mongodb::bson::oid::ObjectId;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use api_key::InMemoryApiKeyRepository;
+use audit::InMemoryAuditLog;
+use category::InMemoryCategoryRepository;
+use inventory::InMemoryInventoryLedger;
+use order::InMemoryOrderRepository;
+use outbox::InMemoryOutbox;
+use product::InMemoryProductRepository;
+use user::InMemoryUserRepository;
+use webhook::{InMemoryWebhookDeliveryRepository, InMemoryWebhookSubscriptionRepository};
+
+/// One of each repository, with the user, product and order repositories
+/// recording their events in the shared `outbox`, and the product repository
+/// its stock movements in the shared `ledger`.
+#[derive(Clone)]
+pub struct InMemoryRepositories {
+    pub outbox: InMemoryOutbox,
+    pub users: Arc<InMemoryUserRepository>,
+    pub products: Arc<InMemoryProductRepository>,
+    pub categories: Arc<InMemoryCategoryRepository>,
+    pub orders: Arc<InMemoryOrderRepository>,
+    pub ledger: Arc<InMemoryInventoryLedger>,
+    pub webhook_subscriptions: Arc<InMemoryWebhookSubscriptionRepository>,
+    pub webhook_deliveries: Arc<InMemoryWebhookDeliveryRepository>,
+    pub audit: Arc<InMemoryAuditLog>,
+    pub api_keys: Arc<InMemoryApiKeyRepository>,
+}
+
+impl InMemoryRepositories {
+    pub fn new() -> Self {
+        let outbox = InMemoryOutbox::new();
+        let ledger = Arc::new(InMemoryInventoryLedger::new());
+        Self {
+            users: Arc::new(InMemoryUserRepository::new(outbox.clone())),
+            products: Arc::new(InMemoryProductRepository::new(
+                outbox.clone(),
+                ledger.clone(),
+            )),
+            orders: Arc::new(InMemoryOrderRepository::new(outbox.clone())),
+            categories: Arc::new(InMemoryCategoryRepository::new()),
+            ledger,
+            webhook_subscriptions: Arc::new(InMemoryWebhookSubscriptionRepository::new()),
+            webhook_deliveries: Arc::new(InMemoryWebhookDeliveryRepository::new()),
+            audit: Arc::new(InMemoryAuditLog::new()),
+            api_keys: Arc::new(InMemoryApiKeyRepository::new()),
+            outbox,
+        }
+    }
+}
+
+impl Default for InMemoryRepositories {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A new ID in the format MongoDB assigns.
+fn new_id() -> String {
+    ObjectId::new().to_hex()
+}
+
+/// Rejects IDs that are not ObjectIds, with the error the MongoDB repositories return.
+fn check_id(id: &str, param: &'static str, entity: &'static str) -> DomainResult<()> {
+    ObjectId::parse_str(id)
+        .map(|_| ())
+        .map_err(|_| Error::invalid_param(param, entity, id))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Records sorted newest first; ties keep the most recently inserted first,
+/// as `records` are kept in insertion order.
+fn newest_first<T: Clone>(
+    records: impl DoubleEndedIterator<Item = T>,
+    created_at: impl Fn(&T) -> DateTime<Utc>,
+) -> Vec<T> {
+    let mut sorted: Vec<T> = records.rev().collect();
+    sorted.sort_by_key(|record| std::cmp::Reverse(created_at(record)));
+    sorted
+}
+
+/// The page of `records` selected by `pagination`, with the same skip and
+/// limit as the MongoDB queries.
+fn paginate<T>(records: Vec<T>, pagination: &Pagination) -> Vec<T> {
+    records
+        .into_iter()
+        .skip(pagination.get_skip() as usize)
+        .take(pagination.get_limit() as usize)
+        .collect()
+}
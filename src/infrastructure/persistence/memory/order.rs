@@ -0,0 +1,135 @@
+use super::{check_id, lock, new_id, newest_first, outbox::InMemoryOutbox, paginate};
+use crate::domain::entities::order::{Order, OrderId};
+use crate::domain::entities::user::UserId;
+use crate::domain::error::DomainResult;
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::order::OrderRepositoryPort;
+use crate::domain::stream::DomainStream;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Mutex;
+
+/// Orders in insertion order, cancelled (soft-deleted) ones included.
+pub struct InMemoryOrderRepository {
+    orders: Mutex<Vec<Order>>,
+    outbox: InMemoryOutbox,
+}
+
+impl InMemoryOrderRepository {
+    pub fn new(outbox: InMemoryOutbox) -> Self {
+        Self {
+            orders: Mutex::new(Vec::new()),
+            outbox,
+        }
+    }
+
+    /// Live orders matching `filter`, newest first.
+    fn live(&self, filter: impl Fn(&Order) -> bool) -> Vec<Order> {
+        let orders = lock(&self.orders);
+        newest_first(
+            orders
+                .iter()
+                .filter(|o| !o.is_deleted() && filter(o))
+                .cloned(),
+            |o| o.created_at,
+        )
+    }
+}
+
+#[async_trait]
+impl OrderRepositoryPort for InMemoryOrderRepository {
+    fn next_id(&self) -> OrderId {
+        OrderId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, order: &Order, events: &[DomainEvent]) -> DomainResult<OrderId> {
+        let id = order.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "Order")?;
+        check_id(&order.user_id, "user_id", "User")?;
+        check_id(&order.product_id, "product_id", "Product")?;
+
+        lock(&self.orders).push(Order {
+            id: Some(id.clone()),
+            ..order.clone()
+        });
+        self.outbox.record(events);
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &OrderId) -> DomainResult<Option<Order>> {
+        check_id(id, "id", "Order")?;
+
+        Ok(lock(&self.orders)
+            .iter()
+            .find(|o| o.id.as_ref() == Some(id) && !o.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Order>> {
+        Ok(paginate(self.live(|_| true), &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_user_id(
+        &self,
+        user_id: &UserId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Order>> {
+        check_id(user_id, "user_id", "Order")?;
+
+        Ok(paginate(self.live(|o| &o.user_id == user_id), &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        user_id: Option<&UserId>,
+    ) -> DomainResult<DomainStream<Order>> {
+        let orders = self.live(|o| {
+            range.contains(o.created_at) && user_id.is_none_or(|user_id| &o.user_id == user_id)
+        });
+        Ok(futures::stream::iter(orders.into_iter().map(Ok)).boxed())
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &OrderId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "Order")?;
+
+        let mut orders = lock(&self.orders);
+        let Some(stored) = orders
+            .iter_mut()
+            .find(|o| o.id.as_ref() == Some(id) && !o.is_deleted())
+        else {
+            return Ok(false);
+        };
+
+        stored.deleted_at = Some(chrono::Utc::now());
+        stored.version += 1;
+        self.outbox.record(events);
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        Ok(lock(&self.orders)
+            .iter()
+            .filter(|o| !o.is_deleted())
+            .count() as u64)
+    }
+}
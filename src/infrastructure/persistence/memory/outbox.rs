@@ -0,0 +1,152 @@
+use super::{check_id, lock, new_id};
+use crate::domain::error::DomainResult;
+use crate::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use crate::domain::port::outbox::OutboxPort;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+struct StoredMessage {
+    id: OutboxMessageId,
+    event: DomainEvent,
+    occurred_at: DateTime<Utc>,
+    attempts: u32,
+    next_attempt_at: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+    published_at: Option<DateTime<Utc>>,
+}
+
+impl StoredMessage {
+    fn is_pending(&self) -> bool {
+        self.published_at.is_none()
+    }
+
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt_at.is_none_or(|at| at <= now)
+            && self.locked_until.is_none_or(|until| until <= now)
+    }
+}
+
+/// Outbox shared by the in-memory repositories, which record their events in
+/// it while still holding their own lock, so a write and its events are seen
+/// together. Clones share the same messages.
+#[derive(Clone, Default)]
+pub struct InMemoryOutbox {
+    messages: Arc<Mutex<Vec<StoredMessage>>>,
+}
+
+impl InMemoryOutbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every event recorded so far, published or not, oldest first.
+    pub fn events(&self) -> Vec<DomainEvent> {
+        lock(&self.messages)
+            .iter()
+            .map(|message| message.event.clone())
+            .collect()
+    }
+
+    pub(super) fn record(&self, events: &[DomainEvent]) -> u64 {
+        let now = Utc::now();
+        let mut messages = lock(&self.messages);
+        messages.extend(events.iter().map(|event| StoredMessage {
+            id: OutboxMessageId::new(new_id()),
+            event: event.clone(),
+            occurred_at: now,
+            attempts: 0,
+            next_attempt_at: None,
+            locked_until: None,
+            published_at: None,
+        }));
+        events.len() as u64
+    }
+}
+
+#[async_trait]
+impl OutboxPort for InMemoryOutbox {
+    // ===== APPEND =====
+
+    #[tracing::instrument(skip_all, fields(events = events.len()))]
+    async fn append(&self, events: &[DomainEvent]) -> DomainResult<u64> {
+        Ok(self.record(events))
+    }
+
+    // ===== RELAY =====
+
+    #[tracing::instrument(skip_all)]
+    async fn claim_due(&self, limit: i64, lease: Duration) -> DomainResult<Vec<OutboxMessage>> {
+        let now = Utc::now();
+        let lease_until = now + chrono::Duration::from_std(lease).unwrap_or_default();
+        let mut messages = lock(&self.messages);
+
+        // Messages are kept in insertion order, so the first pending message
+        // of each aggregate is its head; later ones wait behind it.
+        let mut seen: Vec<(&'static str, String)> = Vec::new();
+        let mut heads: Vec<usize> = Vec::new();
+        for (index, message) in messages.iter().enumerate() {
+            if !message.is_pending() {
+                continue;
+            }
+            let aggregate = (
+                message.event.aggregate_type(),
+                message.event.aggregate_id().to_string(),
+            );
+            if seen.contains(&aggregate) {
+                continue;
+            }
+            seen.push(aggregate);
+            if message.is_due(now) {
+                heads.push(index);
+            }
+        }
+        heads.sort_by_key(|&index| messages[index].occurred_at);
+        heads.truncate(usize::try_from(limit).unwrap_or_default());
+
+        Ok(heads
+            .into_iter()
+            .map(|index| {
+                let message = &mut messages[index];
+                message.locked_until = Some(lease_until);
+                OutboxMessage {
+                    id: message.id.clone(),
+                    event: message.event.clone(),
+                    occurred_at: message.occurred_at,
+                    attempts: message.attempts,
+                }
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_published(&self, id: &OutboxMessageId) -> DomainResult<()> {
+        check_id(id, "id", "OutboxMessage")?;
+
+        if let Some(message) = lock(&self.messages).iter_mut().find(|m| &m.id == id) {
+            message.published_at = Some(Utc::now());
+            message.locked_until = None;
+            message.next_attempt_at = None;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_failed(
+        &self,
+        id: &OutboxMessageId,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        check_id(id, "id", "OutboxMessage")?;
+
+        if let Some(message) = lock(&self.messages).iter_mut().find(|m| &m.id == id) {
+            tracing::debug!(error, "Outbox message failed");
+            message.attempts += 1;
+            message.next_attempt_at = Some(retry_at);
+            message.locked_until = None;
+        }
+        Ok(())
+    }
+}
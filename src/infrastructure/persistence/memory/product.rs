@@ -0,0 +1,388 @@
+use super::{
+    check_id, inventory::InMemoryInventoryLedger, lock, new_id, newest_first,
+    outbox::InMemoryOutbox, paginate,
+};
+use crate::domain::entities::inventory::InventoryMovement;
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::product::{Product, ProductId, ProductMetadata, SkuUpsertOutcome};
+use crate::domain::error::DomainResult;
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::product::ProductRepositoryPort;
+use crate::domain::stream::DomainStream;
+use crate::domain::values::INITIAL_VERSION;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use std::sync::{Arc, Mutex};
+
+/// Products in insertion order, soft-deleted ones included.
+///
+/// Products are always stored with a `category_id`, so there are no legacy
+/// free-form categories to migrate.
+pub struct InMemoryProductRepository {
+    products: Mutex<Vec<Product>>,
+    ledger: Arc<InMemoryInventoryLedger>,
+    outbox: InMemoryOutbox,
+}
+
+impl InMemoryProductRepository {
+    pub fn new(outbox: InMemoryOutbox, ledger: Arc<InMemoryInventoryLedger>) -> Self {
+        Self {
+            products: Mutex::new(Vec::new()),
+            ledger,
+            outbox,
+        }
+    }
+
+    /// Live products matching `filter`, newest first.
+    fn live(&self, filter: impl Fn(&Product) -> bool) -> Vec<Product> {
+        let products = lock(&self.products);
+        newest_first(
+            products
+                .iter()
+                .filter(|p| !p.is_deleted() && filter(p))
+                .cloned(),
+            |p| p.created_at,
+        )
+    }
+
+    /// Applies `write` to the live product `id` at `expected_version` (any
+    /// version when `None`) and returns it as it is afterwards.
+    fn modify(
+        &self,
+        id: &ProductId,
+        expected_version: Option<u64>,
+        events: &[DomainEvent],
+        write: impl FnOnce(&mut Product) -> bool,
+    ) -> DomainResult<Option<Product>> {
+        check_id(id, "id", "Product")?;
+
+        let mut products = lock(&self.products);
+        let Some(stored) = products.iter_mut().find(|p| {
+            p.id.as_ref() == Some(id)
+                && !p.is_deleted()
+                && expected_version.is_none_or(|version| p.version == version)
+        }) else {
+            return Ok(None);
+        };
+        if !write(stored) {
+            return Ok(None);
+        }
+
+        stored.updated_at = Utc::now();
+        stored.version += 1;
+        self.outbox.record(events);
+
+        Ok(Some(stored.clone()))
+    }
+}
+
+/// Never lets an outflow take stock below zero.
+fn apply_delta(product: &mut Product, delta: i32) -> bool {
+    if delta < 0 && product.stock < -delta {
+        return false;
+    }
+    product.stock += delta;
+    true
+}
+
+fn in_categories(product: &Product, category_ids: &[CategoryId]) -> bool {
+    product
+        .metadata
+        .category_id
+        .as_ref()
+        .is_some_and(|id| category_ids.contains(id))
+}
+
+#[async_trait]
+impl ProductRepositoryPort for InMemoryProductRepository {
+    fn next_id(&self) -> ProductId {
+        ProductId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, product: &Product, events: &[DomainEvent]) -> DomainResult<ProductId> {
+        let id = product.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "Product")?;
+
+        let mut products = lock(&self.products);
+        products.push(Product {
+            id: Some(id.clone()),
+            ..product.clone()
+        });
+        self.ledger
+            .record(&[InventoryMovement::initial(id.clone(), product.stock)]);
+        self.outbox.record(events);
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &ProductId) -> DomainResult<Option<Product>> {
+        check_id(id, "id", "Product")?;
+
+        Ok(lock(&self.products)
+            .iter()
+            .find(|p| p.id.as_ref() == Some(id) && !p.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        Ok(paginate(self.live(|_| true), &pagination))
+    }
+
+    #[tracing::instrument(skip_all, fields(skus = skus.len()))]
+    async fn find_ids_by_skus(&self, skus: &[String]) -> DomainResult<Vec<(String, ProductId)>> {
+        Ok(lock(&self.products)
+            .iter()
+            .filter(|p| !p.is_deleted() && skus.contains(&p.metadata.sku))
+            .filter_map(|p| Some((p.metadata.sku.clone(), p.id.clone()?)))
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn find_by_categories(
+        &self,
+        category_ids: &[CategoryId],
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Product>> {
+        Ok(paginate(
+            self.live(|p| in_categories(p, category_ids)),
+            &pagination,
+        ))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_low_stock(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        let mut products = self.live(Product::is_low_stock);
+        products.sort_by(|a, b| {
+            a.stock
+                .cmp(&b.stock)
+                .then_with(|| a.id.as_deref().cmp(&b.id.as_deref()))
+        });
+        Ok(paginate(products, &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        category_ids: Option<&[CategoryId]>,
+    ) -> DomainResult<DomainStream<Product>> {
+        let products = self.live(|p| {
+            range.contains(p.created_at) && category_ids.is_none_or(|ids| in_categories(p, ids))
+        });
+        Ok(futures::stream::iter(products.into_iter().map(Ok)).boxed())
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn count_by_categories(&self, category_ids: &[CategoryId]) -> DomainResult<u64> {
+        Ok(self.live(|p| in_categories(p, category_ids)).len() as u64)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_legacy_categories(&self) -> DomainResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all, fields(%legacy, %category_id))]
+    async fn assign_legacy_category(
+        &self,
+        legacy: &str,
+        category_id: &CategoryId,
+    ) -> DomainResult<u64> {
+        Ok(0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_metadata(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        metadata: &ProductMetadata,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool> {
+        let updated = self.modify(id, Some(expected_version), events, |product| {
+            product.metadata = metadata.clone();
+            true
+        })?;
+        Ok(updated.is_some())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn apply_movement(
+        &self,
+        movement: &InventoryMovement,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        // Recorded while `modify` holds the products lock, so the two writes
+        // land together
+        self.modify(&movement.product_id, None, events, |product| {
+            if !apply_delta(product, movement.delta) {
+                return false;
+            }
+            self.ledger
+                .record(&[movement.clone().with_stock_after(product.stock)]);
+            true
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_stock(
+        &self,
+        id: &ProductId,
+        delta: i32,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        self.modify(id, None, events, |product| apply_delta(product, delta))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_reorder_threshold(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        threshold: Option<i32>,
+    ) -> DomainResult<Option<Product>> {
+        self.modify(id, Some(expected_version), &[], |product| {
+            product.reorder_threshold = threshold;
+            if threshold.is_none() {
+                product.low_stock_alerted_at = None;
+            }
+            true
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn mark_low_stock_alerted(
+        &self,
+        id: &ProductId,
+        at: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        // Only the first writer wins; later updates see the field and back off
+        let mut products = lock(&self.products);
+        let Some(stored) = products.iter_mut().find(|p| {
+            p.id.as_ref() == Some(id) && !p.is_deleted() && p.low_stock_alerted_at.is_none()
+        }) else {
+            return Ok(false);
+        };
+        stored.low_stock_alerted_at = Some(at);
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn clear_low_stock_alert(&self, id: &ProductId) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        let mut products = lock(&self.products);
+        let Some(stored) = products
+            .iter_mut()
+            .find(|p| p.id.as_ref() == Some(id) && p.low_stock_alerted_at.is_some())
+        else {
+            return Ok(false);
+        };
+        stored.low_stock_alerted_at = None;
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip_all, fields(batch = products.len()))]
+    async fn upsert_by_sku(
+        &self,
+        products: &[Product],
+        batch_id: &str,
+    ) -> DomainResult<Vec<SkuUpsertOutcome>> {
+        let mut stored = lock(&self.products);
+
+        let outcomes: Vec<SkuUpsertOutcome> = products
+            .iter()
+            .map(|product| {
+                let existing = stored
+                    .iter_mut()
+                    .find(|p| !p.is_deleted() && p.metadata.sku == product.metadata.sku);
+                match existing {
+                    Some(existing) => {
+                        let previous = Box::new(existing.clone());
+                        existing.name = product.name.clone();
+                        existing.price = product.price;
+                        existing.stock = product.stock;
+                        existing.metadata = product.metadata.clone();
+                        existing.updated_at = product.updated_at;
+                        existing.version += 1;
+                        SkuUpsertOutcome::Updated {
+                            id: existing.id.clone().unwrap_or_default(),
+                            previous,
+                        }
+                    }
+                    None => {
+                        let id = ProductId::new(new_id());
+                        stored.push(Product {
+                            id: Some(id.clone()),
+                            reorder_threshold: None,
+                            low_stock_alerted_at: None,
+                            version: INITIAL_VERSION,
+                            deleted_at: None,
+                            ..product.clone()
+                        });
+                        SkuUpsertOutcome::Created(id)
+                    }
+                }
+            })
+            .collect();
+
+        let (movements, events) =
+            SkuUpsertOutcome::import_records(outcomes.iter().zip(products), batch_id);
+        self.ledger.record(&movements);
+        self.outbox.record(&events);
+
+        Ok(outcomes)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &ProductId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        let mut products = lock(&self.products);
+        let Some(stored) = products
+            .iter_mut()
+            .find(|p| p.id.as_ref() == Some(id) && !p.is_deleted())
+        else {
+            return Ok(false);
+        };
+
+        stored.deleted_at = Some(Utc::now());
+        stored.version += 1;
+        self.outbox.record(events);
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        Ok(lock(&self.products)
+            .iter()
+            .filter(|p| !p.is_deleted())
+            .count() as u64)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_low_stock(&self) -> DomainResult<u64> {
+        Ok(self.live(Product::is_low_stock).len() as u64)
+    }
+}
@@ -0,0 +1,162 @@
+use super::{check_id, lock, new_id, newest_first, outbox::InMemoryOutbox, paginate};
+use crate::domain::entities::user::{User, UserId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::user::UserRepositoryPort;
+use crate::domain::stream::DomainStream;
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::sync::Mutex;
+
+/// Users in insertion order, soft-deleted ones included.
+pub struct InMemoryUserRepository {
+    users: Mutex<Vec<User>>,
+    outbox: InMemoryOutbox,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(outbox: InMemoryOutbox) -> Self {
+        Self {
+            users: Mutex::new(Vec::new()),
+            outbox,
+        }
+    }
+
+    /// Live users, newest first.
+    fn live(&self, range: &DateRange) -> Vec<User> {
+        let users = lock(&self.users);
+        newest_first(
+            users
+                .iter()
+                .filter(|u| !u.is_deleted() && range.contains(u.created_at))
+                .cloned(),
+            |u| u.created_at,
+        )
+    }
+}
+
+/// Like the unique index, emails stay taken by soft-deleted users.
+fn check_email(users: &[User], email: &str, except: Option<&UserId>) -> DomainResult<()> {
+    let taken = users
+        .iter()
+        .any(|u| u.email == email && u.id.as_ref() != except);
+    if taken {
+        return Err(Error::duplicate("User", "email", email));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl UserRepositoryPort for InMemoryUserRepository {
+    fn next_id(&self) -> UserId {
+        UserId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, user: &User, events: &[DomainEvent]) -> DomainResult<UserId> {
+        let id = user.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "User")?;
+
+        let mut users = lock(&self.users);
+        check_email(&users, &user.email, None)?;
+        users.push(User {
+            id: Some(id.clone()),
+            ..user.clone()
+        });
+        self.outbox.record(events);
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &UserId) -> DomainResult<Option<User>> {
+        check_id(id, "id", "User")?;
+
+        Ok(lock(&self.users)
+            .iter()
+            .find(|u| u.id.as_ref() == Some(id) && !u.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<User>> {
+        Ok(lock(&self.users)
+            .iter()
+            .find(|u| u.email == email && !u.is_deleted())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<User>> {
+        Ok(paginate(self.live(&DateRange::default()), &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(&self, range: &DateRange) -> DomainResult<DomainStream<User>> {
+        Ok(futures::stream::iter(self.live(range).into_iter().map(Ok)).boxed())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        user: &User,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "User")?;
+
+        let mut users = lock(&self.users);
+        check_email(&users, &user.email, Some(id))?;
+        let Some(stored) = users.iter_mut().find(|u| {
+            u.id.as_ref() == Some(id) && !u.is_deleted() && u.version == expected_version
+        }) else {
+            return Ok(false);
+        };
+
+        *stored = User {
+            id: Some(id.clone()),
+            version: stored.version + 1,
+            ..user.clone()
+        };
+        self.outbox.record(events);
+
+        Ok(true)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &UserId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "User")?;
+
+        let mut users = lock(&self.users);
+        let Some(stored) = users
+            .iter_mut()
+            .find(|u| u.id.as_ref() == Some(id) && !u.is_deleted())
+        else {
+            return Ok(false);
+        };
+
+        stored.deleted_at = Some(chrono::Utc::now());
+        stored.version += 1;
+        self.outbox.record(events);
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        Ok(lock(&self.users).iter().filter(|u| !u.is_deleted()).count() as u64)
+    }
+}
@@ -0,0 +1,287 @@
+use super::{check_id, lock, new_id, newest_first, paginate};
+use crate::domain::entities::webhook::{
+    DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookDeliveryId, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use crate::domain::error::DomainResult;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::webhook::{WebhookDeliveryRepositoryPort, WebhookSubscriptionRepositoryPort};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Mutex;
+
+/// Subscriptions in insertion order, soft-deleted ones included.
+#[derive(Default)]
+pub struct InMemoryWebhookSubscriptionRepository {
+    subscriptions: Mutex<Vec<WebhookSubscription>>,
+}
+
+impl InMemoryWebhookSubscriptionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl WebhookSubscriptionRepositoryPort for InMemoryWebhookSubscriptionRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscriptionId> {
+        let id = subscription
+            .id
+            .clone()
+            .unwrap_or_else(|| WebhookSubscriptionId::new(new_id()));
+        check_id(&id, "id", "WebhookSubscription")?;
+
+        lock(&self.subscriptions).push(WebhookSubscription {
+            id: Some(id.clone()),
+            ..subscription.clone()
+        });
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(
+        &self,
+        id: &WebhookSubscriptionId,
+    ) -> DomainResult<Option<WebhookSubscription>> {
+        check_id(id, "id", "WebhookSubscription")?;
+
+        Ok(lock(&self.subscriptions)
+            .iter()
+            .find(|s| s.id.as_ref() == Some(id) && s.deleted_at.is_none())
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<WebhookSubscription>> {
+        let subscriptions = lock(&self.subscriptions);
+        let live = newest_first(
+            subscriptions
+                .iter()
+                .filter(|s| s.deleted_at.is_none())
+                .cloned(),
+            |s| s.created_at,
+        );
+
+        Ok(paginate(live, &pagination))
+    }
+
+    #[tracing::instrument(skip_all, fields(%event_type))]
+    async fn find_for_event(&self, event_type: &str) -> DomainResult<Vec<WebhookSubscription>> {
+        Ok(lock(&self.subscriptions)
+            .iter()
+            .filter(|s| s.deleted_at.is_none() && s.wants(event_type))
+            .cloned()
+            .collect())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(
+        &self,
+        id: &WebhookSubscriptionId,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "WebhookSubscription")?;
+
+        let mut subscriptions = lock(&self.subscriptions);
+        let Some(stored) = subscriptions
+            .iter_mut()
+            .find(|s| s.id.as_ref() == Some(id) && s.deleted_at.is_none())
+        else {
+            return Ok(false);
+        };
+        *stored = WebhookSubscription {
+            id: Some(id.clone()),
+            ..subscription.clone()
+        };
+
+        Ok(true)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &WebhookSubscriptionId) -> DomainResult<bool> {
+        check_id(id, "id", "WebhookSubscription")?;
+
+        let mut subscriptions = lock(&self.subscriptions);
+        let Some(stored) = subscriptions
+            .iter_mut()
+            .find(|s| s.id.as_ref() == Some(id) && s.deleted_at.is_none())
+        else {
+            return Ok(false);
+        };
+        stored.deleted_at = Some(Utc::now());
+        stored.active = false;
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        Ok(lock(&self.subscriptions)
+            .iter()
+            .filter(|s| s.deleted_at.is_none())
+            .count() as u64)
+    }
+}
+
+/// Deliveries in insertion order.
+#[derive(Default)]
+pub struct InMemoryWebhookDeliveryRepository {
+    deliveries: Mutex<Vec<WebhookDelivery>>,
+}
+
+impl InMemoryWebhookDeliveryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn modify(
+        &self,
+        id: &WebhookDeliveryId,
+        write: impl FnOnce(&mut WebhookDelivery),
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "WebhookDelivery")?;
+
+        let mut deliveries = lock(&self.deliveries);
+        let Some(stored) = deliveries.iter_mut().find(|d| d.id.as_ref() == Some(id)) else {
+            return Ok(false);
+        };
+        write(stored);
+        stored.updated_at = Utc::now();
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryRepositoryPort for InMemoryWebhookDeliveryRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create_once(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> DomainResult<(WebhookDeliveryId, bool)> {
+        check_id(
+            &delivery.subscription_id,
+            "subscription_id",
+            "WebhookSubscription",
+        )?;
+
+        let mut deliveries = lock(&self.deliveries);
+        let existing = deliveries.iter().find(|d| {
+            d.subscription_id == delivery.subscription_id && d.event_id == delivery.event_id
+        });
+        if let Some(id) = existing.and_then(|d| d.id.clone()) {
+            return Ok((id, false));
+        }
+
+        let id = WebhookDeliveryId::new(new_id());
+        deliveries.push(WebhookDelivery {
+            id: Some(id.clone()),
+            ..delivery.clone()
+        });
+
+        Ok((id, true))
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &WebhookDeliveryId) -> DomainResult<Option<WebhookDelivery>> {
+        check_id(id, "id", "WebhookDelivery")?;
+
+        Ok(lock(&self.deliveries)
+            .iter()
+            .find(|d| d.id.as_ref() == Some(id))
+            .cloned())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        check_id(subscription_id, "subscription_id", "WebhookSubscription")?;
+
+        let deliveries = lock(&self.deliveries);
+        let matching = newest_first(
+            deliveries
+                .iter()
+                .filter(|d| &d.subscription_id == subscription_id)
+                .cloned(),
+            |d| d.created_at,
+        );
+
+        Ok(paginate(matching, &pagination))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> DomainResult<u64> {
+        check_id(subscription_id, "subscription_id", "WebhookSubscription")?;
+
+        Ok(lock(&self.deliveries)
+            .iter()
+            .filter(|d| &d.subscription_id == subscription_id)
+            .count() as u64)
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn record_attempt(
+        &self,
+        id: &WebhookDeliveryId,
+        attempt: &DeliveryAttempt,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool> {
+        self.modify(id, |delivery| {
+            delivery.attempts.push(attempt.clone());
+            delivery.status = status;
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_status(
+        &self,
+        id: &WebhookDeliveryId,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool> {
+        self.modify(id, |delivery| delivery.status = status)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn reopen(&self, id: &WebhookDeliveryId) -> DomainResult<bool> {
+        check_id(id, "id", "WebhookDelivery")?;
+
+        let mut deliveries = lock(&self.deliveries);
+        let Some(stored) = deliveries
+            .iter_mut()
+            .find(|d| d.id.as_ref() == Some(id) && d.status != DeliveryStatus::Pending)
+        else {
+            return Ok(false);
+        };
+        stored.status = DeliveryStatus::Pending;
+        stored.updated_at = Utc::now();
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::values::INITIAL_VERSION;
+use crate::infrastructure::persistence::migrations::Migration;
+use async_trait::async_trait;
+use mongodb::{
+    Database,
+    bson::{Document, doc},
+};
+
+const COLLECTIONS: &[&str] = &["users", "products", "orders"];
+
+/// Gives documents written before optimistic concurrency a `version`, so the
+/// repositories no longer need to treat a missing field as version `0`.
+pub struct BackfillVersions;
+
+#[async_trait]
+impl Migration for BackfillVersions {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "backfill_entity_versions"
+    }
+
+    async fn up(&self, db: &Database, dry_run: bool) -> DomainResult<String> {
+        let missing = doc! { "version": { "$exists": false } };
+        let initial = i64::try_from(INITIAL_VERSION).unwrap_or(i64::MAX);
+
+        let mut counts = Vec::with_capacity(COLLECTIONS.len());
+        for name in COLLECTIONS {
+            let collection = db.collection::<Document>(name);
+            let count = if dry_run {
+                collection.count_documents(missing.clone()).await
+            } else {
+                collection
+                    .update_many(missing.clone(), doc! { "$set": { "version": initial } })
+                    .await
+                    .map(|result| result.modified_count)
+            }
+            .map_err(|e| Error::database(e.to_string()))?;
+            counts.push(format!("{} {}", count, name));
+        }
+
+        let verb = if dry_run { "would set" } else { "set" };
+        Ok(format!(
+            "{} version {} on {}",
+            verb,
+            INITIAL_VERSION,
+            counts.join(", ")
+        ))
+    }
+}
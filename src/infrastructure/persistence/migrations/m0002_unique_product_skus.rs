@@ -0,0 +1,54 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::infrastructure::persistence::migrations::{Migration, live_duplicates, replace_index};
+use crate::infrastructure::persistence::product::repository::{
+    PRODUCTS_COLLECTION, ProductRepository, SKU_UNIQUE_INDEX,
+};
+use async_trait::async_trait;
+use mongodb::{Database, bson::Document};
+
+/// Non-unique index replaced by [`SKU_UNIQUE_INDEX`].
+const LEGACY_SKU_INDEX: &str = "deleted_sku_compound_idx";
+
+/// Makes SKUs unique among live products, replacing the old non-unique index.
+///
+/// Refuses to run while two live products share a SKU: which one to keep is
+/// a business decision, so the duplicates are listed for someone to resolve.
+pub struct UniqueProductSkus;
+
+#[async_trait]
+impl Migration for UniqueProductSkus {
+    fn version(&self) -> u32 {
+        2
+    }
+
+    fn name(&self) -> &'static str {
+        "unique_product_skus"
+    }
+
+    async fn up(&self, db: &Database, dry_run: bool) -> DomainResult<String> {
+        let collection = db.collection::<Document>(PRODUCTS_COLLECTION);
+
+        let skus = live_duplicates(&collection, "metadata.sku").await?;
+        if !skus.is_empty() {
+            return Err(Error::business_rule(format!(
+                "Live products share a SKU, soft-delete all but one first: {}",
+                skus.join(", ")
+            )));
+        }
+
+        if dry_run {
+            return Ok(format!(
+                "would create {} and drop {}",
+                SKU_UNIQUE_INDEX, LEGACY_SKU_INDEX
+            ));
+        }
+
+        replace_index(
+            &collection,
+            ProductRepository::indexes(),
+            SKU_UNIQUE_INDEX,
+            LEGACY_SKU_INDEX,
+        )
+        .await
+    }
+}
@@ -0,0 +1,55 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::infrastructure::persistence::category::repository::{
+    CATEGORIES_COLLECTION, CategoryRepository, PATH_UNIQUE_INDEX,
+};
+use crate::infrastructure::persistence::migrations::{Migration, live_duplicates, replace_index};
+use async_trait::async_trait;
+use mongodb::{Database, bson::Document};
+
+/// Non-unique index replaced by [`PATH_UNIQUE_INDEX`].
+const LEGACY_PATH_INDEX: &str = "deleted_path_compound_idx";
+
+/// Makes category paths unique among live categories, so two concurrent
+/// creates or moves cannot both take a path.
+///
+/// Refuses to run while two live categories share a path, listing them: they
+/// must be renamed, merged or deleted by hand.
+pub struct UniqueCategoryPaths;
+
+#[async_trait]
+impl Migration for UniqueCategoryPaths {
+    fn version(&self) -> u32 {
+        3
+    }
+
+    fn name(&self) -> &'static str {
+        "unique_category_paths"
+    }
+
+    async fn up(&self, db: &Database, dry_run: bool) -> DomainResult<String> {
+        let collection = db.collection::<Document>(CATEGORIES_COLLECTION);
+
+        let paths = live_duplicates(&collection, "path").await?;
+        if !paths.is_empty() {
+            return Err(Error::business_rule(format!(
+                "Live categories share a path, rename or delete all but one first: {}",
+                paths.join(", ")
+            )));
+        }
+
+        if dry_run {
+            return Ok(format!(
+                "would create {} and drop {}",
+                PATH_UNIQUE_INDEX, LEGACY_PATH_INDEX
+            ));
+        }
+
+        replace_index(
+            &collection,
+            CategoryRepository::indexes(),
+            PATH_UNIQUE_INDEX,
+            LEGACY_PATH_INDEX,
+        )
+        .await
+    }
+}
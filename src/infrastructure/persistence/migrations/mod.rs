@@ -0,0 +1,117 @@
+pub mod runner;
+
+mod m0001_backfill_versions;
+mod m0002_unique_product_skus;
+mod m0003_unique_category_paths;
+
+use crate::domain::error::{DomainResult, Error};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{Document, doc},
+};
+
+/// Applied migrations, one document per version.
+pub const MIGRATIONS_COLLECTION: &str = "_migrations";
+
+/// One versioned schema or data change.
+///
+/// `up` must be safe to run again: an instance that dies after applying it
+/// but before recording it leaves the migration pending.
+#[async_trait]
+pub trait Migration: Send + Sync {
+    /// Position in the sequence. Never reuse or reorder a released version.
+    fn version(&self) -> u32;
+
+    fn name(&self) -> &'static str;
+
+    /// Applies the change, or with `dry_run` only counts what it would touch.
+    /// Returns a one-line summary for the status report.
+    async fn up(&self, db: &Database, dry_run: bool) -> DomainResult<String>;
+}
+
+/// Every migration, in the order they are applied. Add new ones at the end.
+pub fn all() -> Vec<Box<dyn Migration>> {
+    vec![
+        Box::new(m0001_backfill_versions::BackfillVersions),
+        Box::new(m0002_unique_product_skus::UniqueProductSkus),
+        Box::new(m0003_unique_category_paths::UniqueCategoryPaths),
+    ]
+}
+
+/// Up to 20 values of `field` that more than one live document shares, for a
+/// migration adding a unique index to refuse to run until they are resolved.
+async fn live_duplicates(
+    collection: &Collection<Document>,
+    field: &str,
+) -> DomainResult<Vec<String>> {
+    let duplicates: Vec<Document> = collection
+        .aggregate(vec![
+            doc! { "$match": { "deleted_at": { "$exists": false } } },
+            doc! { "$group": { "_id": format!("${field}"), "count": { "$sum": 1 } } },
+            doc! { "$match": { "count": { "$gt": 1 } } },
+            doc! { "$limit": 20 },
+        ])
+        .await
+        .map_err(|e| Error::database(e.to_string()))?
+        .try_collect()
+        .await
+        .map_err(|e| Error::database(e.to_string()))?;
+
+    Ok(duplicates
+        .iter()
+        .map(|group| {
+            group
+                .get("_id")
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        })
+        .collect())
+}
+
+/// Creates the `unique` index from `declared`, the repository's indexes, and
+/// drops the `legacy` one it replaces if it still exists. Returns the summary.
+async fn replace_index(
+    collection: &Collection<Document>,
+    declared: Vec<IndexModel>,
+    unique: &str,
+    legacy: &str,
+) -> DomainResult<String> {
+    let index = declared
+        .into_iter()
+        .find(|index| {
+            index
+                .options
+                .as_ref()
+                .and_then(|options| options.name.as_deref())
+                == Some(unique)
+        })
+        .ok_or_else(|| Error::internal(format!("{} is not declared", unique)))?;
+    collection
+        .create_index(index)
+        .await
+        .map_err(|e| Error::database(e.to_string()))?;
+
+    let existing: Vec<String> = collection
+        .list_index_names()
+        .await
+        .map_err(|e| Error::database(e.to_string()))?;
+    let dropped = existing.iter().any(|name| name == legacy);
+    if dropped {
+        collection
+            .drop_index(legacy)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+    }
+
+    Ok(format!(
+        "created {}{}",
+        unique,
+        if dropped {
+            format!(", dropped {}", legacy)
+        } else {
+            String::new()
+        }
+    ))
+}
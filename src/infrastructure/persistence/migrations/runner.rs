@@ -0,0 +1,219 @@
+use crate::application::lock::Locks;
+use crate::domain::error::{DomainResult, Error};
+use crate::infrastructure::persistence::migrations::{self, MIGRATIONS_COLLECTION, Migration};
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{Collection, Database, bson::doc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const LOCK_NAME: &str = "migrations";
+
+/// How long a holder that stopped renewing (crashed) blocks the others.
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// How long `run` waits for another instance to finish migrating.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MigrationRecord {
+    #[serde(rename = "_id")]
+    version: i64,
+    name: String,
+    applied_at: bson::DateTime,
+    duration_ms: i64,
+    summary: String,
+}
+
+/// A migration as known to this binary and/or the database.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// Recorded in `_migrations` but not part of this binary (a newer release ran).
+    pub unknown: bool,
+}
+
+impl MigrationStatus {
+    pub fn is_pending(&self) -> bool {
+        self.applied_at.is_none()
+    }
+}
+
+/// Outcome of one pending migration in a run.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationOutcome {
+    pub version: u32,
+    pub name: String,
+    pub summary: String,
+    pub duration_ms: u64,
+    pub dry_run: bool,
+}
+
+/// Applies pending migrations in version order and records them in `_migrations`.
+pub struct Migrator {
+    db: Database,
+    records: Collection<MigrationRecord>,
+    locks: Locks,
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Migrator {
+    /// `locks` must be shared by every instance that migrates `db`, such as
+    /// a [`MongoLock`](crate::infrastructure::persistence::lease::MongoLock)
+    /// on the same database.
+    pub fn new(db: &Database, locks: Locks) -> Self {
+        Self::with_migrations(db, locks, migrations::all())
+    }
+
+    /// Panics when versions are not strictly increasing: that is a bug in
+    /// the migration list, not something to run against a database.
+    pub fn with_migrations(
+        db: &Database,
+        locks: Locks,
+        migrations: Vec<Box<dyn Migration>>,
+    ) -> Self {
+        for pair in migrations.windows(2) {
+            assert!(
+                pair[0].version() < pair[1].version(),
+                "Migration {} ({}) must come after {} ({})",
+                pair[1].version(),
+                pair[1].name(),
+                pair[0].version(),
+                pair[0].name()
+            );
+        }
+
+        Self {
+            db: db.clone(),
+            records: db.collection(MIGRATIONS_COLLECTION),
+            locks,
+            migrations,
+        }
+    }
+
+    /// Every migration in version order, applied or pending.
+    #[tracing::instrument(skip_all)]
+    pub async fn status(&self) -> DomainResult<Vec<MigrationStatus>> {
+        let mut applied: HashMap<i64, MigrationRecord> = self
+            .records
+            .find(doc! {})
+            .await
+            .map_err(|e| Error::database(e.to_string()))?
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?
+            .into_iter()
+            .map(|record| (record.version, record))
+            .collect();
+
+        let mut statuses: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let record = applied.remove(&i64::from(migration.version()));
+                MigrationStatus {
+                    version: migration.version(),
+                    name: migration.name().to_string(),
+                    applied_at: record.as_ref().map(|r| r.applied_at.to_chrono()),
+                    summary: record.map(|r| r.summary),
+                    unknown: false,
+                }
+            })
+            .collect();
+
+        statuses.extend(applied.into_values().map(|record| MigrationStatus {
+            version: u32::try_from(record.version).unwrap_or_default(),
+            name: record.name,
+            applied_at: Some(record.applied_at.to_chrono()),
+            summary: Some(record.summary),
+            unknown: true,
+        }));
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+
+    /// Applies every pending migration under the distributed lock, stopping
+    /// at the first failure. With `dry_run` nothing is locked or written, and
+    /// each migration reports what it would change against the current data
+    /// (later ones may see more once earlier ones are applied).
+    #[tracing::instrument(skip_all, fields(dry_run = dry_run))]
+    pub async fn run(&self, dry_run: bool) -> DomainResult<Vec<MigrationOutcome>> {
+        if dry_run {
+            return self.apply_pending(true).await;
+        }
+
+        let guard = self.locks.lock(LOCK_NAME, LOCK_TTL, LOCK_TIMEOUT).await?;
+        // Re-read the status under the lock: another instance may have just finished
+        let result = self.apply_pending(false).await;
+        guard.release().await;
+        result
+    }
+
+    async fn apply_pending(&self, dry_run: bool) -> DomainResult<Vec<MigrationOutcome>> {
+        let pending: Vec<u32> = self
+            .status()
+            .await?
+            .into_iter()
+            .filter(|status| status.is_pending())
+            .map(|status| status.version)
+            .collect();
+
+        let mut outcomes = Vec::with_capacity(pending.len());
+        for migration in &self.migrations {
+            if !pending.contains(&migration.version()) {
+                continue;
+            }
+            outcomes.push(self.apply(migration.as_ref(), dry_run).await?);
+        }
+        Ok(outcomes)
+    }
+
+    #[tracing::instrument(skip_all, fields(version = migration.version(), name = migration.name()))]
+    async fn apply(
+        &self,
+        migration: &dyn Migration,
+        dry_run: bool,
+    ) -> DomainResult<MigrationOutcome> {
+        let started = Instant::now();
+        let summary = migration.up(&self.db, dry_run).await.map_err(|e| {
+            tracing::error!("Migration {} failed: {}", migration.name(), e);
+            e
+        })?;
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        if !dry_run {
+            let record = MigrationRecord {
+                version: i64::from(migration.version()),
+                name: migration.name().to_string(),
+                applied_at: bson::DateTime::now(),
+                duration_ms: i64::try_from(duration_ms).unwrap_or(i64::MAX),
+                summary: summary.clone(),
+            };
+            self.records
+                .insert_one(record)
+                .await
+                .map_err(|e| Error::database(e.to_string()))?;
+            tracing::info!(
+                "✓ Migration {} {} applied: {}",
+                migration.version(),
+                migration.name(),
+                summary
+            );
+        }
+
+        Ok(MigrationOutcome {
+            version: migration.version(),
+            name: migration.name().to_string(),
+            summary,
+            duration_ms,
+            dry_run,
+        })
+    }
+}
@@ -1,3 +1,30 @@
+pub mod api_key;
+pub mod audit;
+pub mod category;
+pub mod filter;
+pub mod inventory;
+pub mod job_run;
+pub mod lease;
+pub mod maintenance;
+pub mod memory;
+pub mod migrations;
 pub mod order;
+pub mod outbox;
+pub mod postgres;
 pub mod product;
 pub mod user;
+pub mod webhook;
+
+use mongodb::error::{ErrorKind, WriteFailure};
+
+/// Server error code of a unique index violation.
+pub const DUPLICATE_KEY: i32 = 11000;
+
+/// Unique index violation (E11000).
+pub fn is_duplicate_key(e: &mongodb::error::Error) -> bool {
+    match e.kind.as_ref() {
+        ErrorKind::Command(err) => err.code == DUPLICATE_KEY,
+        ErrorKind::Write(WriteFailure::WriteError(err)) => err.code == DUPLICATE_KEY,
+        _ => false,
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::entities::country::Country;
 use crate::domain::entities::order::{Order, OrderId};
 use crate::domain::entities::product::ProductId;
 use crate::domain::entities::user::UserId;
@@ -12,8 +13,13 @@ pub struct OrderDocument {
     pub product_id: ObjectId,
     pub quantity: i32,
     pub total_price: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<Country>,
     pub created_at: bson::DateTime,
     pub updated_at: bson::DateTime,
+    /// Missing on documents written before versioning; read as `0`.
+    #[serde(default)]
+    pub version: i64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<bson::DateTime>,
 }
@@ -42,8 +48,10 @@ impl TryFrom<Order> for OrderDocument {
             product_id: product_oid,
             quantity: order.quantity,
             total_price: order.total_price,
+            country: order.country,
             created_at: bson::DateTime::from_chrono(order.created_at),
             updated_at: bson::DateTime::from_chrono(order.updated_at),
+            version: i64::try_from(order.version).unwrap_or(i64::MAX),
             deleted_at: order.deleted_at.map(bson::DateTime::from_chrono),
         })
     }
@@ -57,8 +65,10 @@ impl From<OrderDocument> for Order {
             product_id: ProductId::new(doc.product_id.to_hex()),
             quantity: doc.quantity,
             total_price: doc.total_price,
+            country: doc.country,
             created_at: doc.created_at.to_chrono(),
             updated_at: doc.updated_at.to_chrono(),
+            version: u64::try_from(doc.version).unwrap_or_default(),
             deleted_at: doc.deleted_at.map(|dt| dt.to_chrono()),
         }
     }
@@ -1,32 +1,42 @@
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
 use crate::domain::entities::order::{Order, OrderId};
 use crate::domain::pagination::Pagination;
 use crate::domain::port::order::OrderRepositoryPort;
 use crate::domain::entities::user::UserId;
+use crate::domain::event::DomainEvent;
+use crate::domain::stream::DomainStream;
+use crate::infrastructure::persistence::filter::live_in_range;
 use crate::infrastructure::persistence::order::model::OrderDocument;
+use crate::infrastructure::persistence::outbox::writer::OutboxWriter;
 use async_trait::async_trait;
-use futures::stream::TryStreamExt;
+use futures::FutureExt;
+use futures::stream::{StreamExt, TryStreamExt};
 use mongodb::{
     Collection, Database, IndexModel,
     bson::{doc, oid::ObjectId},
     options::IndexOptions,
 };
 
+pub const ORDERS_COLLECTION: &str = "orders";
+
 #[derive(Clone)]
 pub struct OrderRepository {
     collection: Collection<OrderDocument>,
+    outbox: OutboxWriter,
 }
 
 impl OrderRepository {
-    pub fn new(db: &Database) -> Self {
+    pub fn new(db: &Database, outbox: OutboxWriter) -> Self {
         Self {
-            collection: db.collection("orders"),
+            collection: db.collection(ORDERS_COLLECTION),
+            outbox,
         }
     }
 
-    /// Create database indexes (idempotent — safe to call on every startup)
-    pub async fn create_indexes(&self) -> DomainResult<()> {
-        let indexes = vec![
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
             IndexModel::builder()
                 .keys(doc! { "user_id": 1, "created_at": -1 })
                 .options(
@@ -43,10 +53,13 @@ impl OrderRepository {
                         .build(),
                 )
                 .build(),
-        ];
+        ]
+    }
 
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
         self.collection
-            .create_indexes(indexes)
+            .create_indexes(Self::indexes())
             .await
             .map_err(|e| Error::database(e.to_string()))?;
 
@@ -57,17 +70,26 @@ impl OrderRepository {
 
 #[async_trait]
 impl OrderRepositoryPort for OrderRepository {
+    fn next_id(&self) -> OrderId {
+        OrderId::new(ObjectId::new().to_hex())
+    }
+
     // ===== CREATE =====
 
     #[tracing::instrument(skip_all)]
-    async fn create(&self, order: &Order) -> DomainResult<OrderId> {
+    async fn create(&self, order: &Order, events: &[DomainEvent]) -> DomainResult<OrderId> {
         let doc = OrderDocument::try_from(order.clone()).map_err(Error::internal)?;
 
         let result = self
-            .collection
-            .insert_one(doc)
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .outbox
+            .write(
+                events,
+                (&self.collection, &doc),
+                |session, (collection, doc)| {
+                    async move { collection.insert_one(*doc).session(session).await }.boxed()
+                },
+            )
+            .await?;
 
         result
             .inserted_id
@@ -140,23 +162,50 @@ impl OrderRepositoryPort for OrderRepository {
         Ok(docs.into_iter().map(Order::from).collect())
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        user_id: Option<&UserId>,
+    ) -> DomainResult<DomainStream<Order>> {
+        let mut filter = live_in_range(range);
+        if let Some(user_id) = user_id {
+            let oid = ObjectId::parse_str(&**user_id)
+                .map_err(|_| Error::invalid_param("user_id", "Order", &**user_id))?;
+            filter.insert("user_id", oid);
+        }
+
+        let cursor = self
+            .collection
+            .find(filter)
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(cursor
+            .map_ok(Order::from)
+            .map_err(|e| Error::database(e.to_string()))
+            .boxed())
+    }
+
     // ===== DELETE =====
 
     #[tracing::instrument(skip_all)]
-    async fn delete(&self, id: &OrderId) -> DomainResult<bool> {
+    async fn delete(&self, id: &OrderId, events: &[DomainEvent]) -> DomainResult<bool> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Order", &**id))?;
 
         let now = bson::DateTime::from_chrono(chrono::Utc::now());
 
         let result = self
-            .collection
+            .outbox
             .update_one(
+                events,
+                &self.collection,
                 doc! { "_id": oid, "deleted_at": { "$exists": false } },
-                doc! { "$set": { "deleted_at": now } },
+                doc! { "$set": { "deleted_at": now }, "$inc": { "version": 1 } },
             )
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .await?;
 
         Ok(result.matched_count > 0)
     }
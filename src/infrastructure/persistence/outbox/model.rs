@@ -0,0 +1,65 @@
+use crate::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutboxStatus {
+    Pending,
+    Published,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutboxDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub event_type: String,
+    pub payload: DomainEvent,
+    pub status: OutboxStatus,
+    pub attempts: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<bson::DateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked_until: Option<bson::DateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub occurred_at: bson::DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<bson::DateTime>,
+}
+
+impl From<&DomainEvent> for OutboxDocument {
+    fn from(event: &DomainEvent) -> Self {
+        Self {
+            // Assigned here so insertion order is preserved within a batch
+            id: Some(ObjectId::new()),
+            aggregate_type: event.aggregate_type().to_string(),
+            aggregate_id: event.aggregate_id().to_string(),
+            event_type: event.event_type().to_string(),
+            payload: event.clone(),
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            next_attempt_at: None,
+            locked_until: None,
+            last_error: None,
+            occurred_at: bson::DateTime::now(),
+            published_at: None,
+        }
+    }
+}
+
+impl TryFrom<OutboxDocument> for OutboxMessage {
+    type Error = String;
+
+    fn try_from(doc: OutboxDocument) -> Result<Self, Self::Error> {
+        let id = doc.id.ok_or("Outbox document without _id")?;
+        Ok(Self {
+            id: OutboxMessageId::new(id.to_hex()),
+            event: doc.payload,
+            occurred_at: doc.occurred_at.to_chrono(),
+            attempts: u32::try_from(doc.attempts).unwrap_or_default(),
+        })
+    }
+}
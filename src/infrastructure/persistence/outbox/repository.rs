@@ -0,0 +1,217 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use crate::domain::port::outbox::OutboxPort;
+use crate::infrastructure::persistence::outbox::model::{OutboxDocument, OutboxStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::stream::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{self, Document, doc, oid::ObjectId},
+    options::IndexOptions,
+};
+use std::time::Duration;
+
+pub const OUTBOX_COLLECTION: &str = "outbox";
+
+/// Published messages are kept this long for troubleshooting, then expire.
+const PUBLISHED_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Clone)]
+pub struct OutboxRepository {
+    collection: Collection<OutboxDocument>,
+}
+
+impl OutboxRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(OUTBOX_COLLECTION),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! {
+                    "status": 1,
+                    "aggregate_type": 1,
+                    "aggregate_id": 1,
+                    "occurred_at": 1,
+                    "_id": 1
+                })
+                .options(
+                    IndexOptions::builder()
+                        .name("pending_aggregate_order_idx".to_string())
+                        .partial_filter_expression(doc! { "status": "pending" })
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "published_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("published_ttl_idx".to_string())
+                        .expire_after(PUBLISHED_RETENTION)
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Outbox indexes created");
+        Ok(())
+    }
+
+    fn parse_id(id: &OutboxMessageId) -> DomainResult<ObjectId> {
+        ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "OutboxMessage", &**id))
+    }
+}
+
+#[async_trait]
+impl OutboxPort for OutboxRepository {
+    // ===== APPEND =====
+
+    #[tracing::instrument(skip_all, fields(events = events.len()))]
+    async fn append(&self, events: &[DomainEvent]) -> DomainResult<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let docs: Vec<OutboxDocument> = events.iter().map(OutboxDocument::from).collect();
+        let result = self
+            .collection
+            .insert_many(docs)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    // ===== RELAY =====
+
+    #[tracing::instrument(skip_all)]
+    async fn claim_due(&self, limit: i64, lease: Duration) -> DomainResult<Vec<OutboxMessage>> {
+        let now = bson::DateTime::now();
+        let available = doc! {
+            "$or": [
+                { "locked_until": { "$exists": false } },
+                { "locked_until": { "$lte": now } }
+            ]
+        };
+
+        // Only the oldest pending message of each aggregate is eligible, so a
+        // failing message holds back later events of the same aggregate only.
+        let pipeline = vec![
+            doc! { "$match": { "status": "pending" } },
+            doc! { "$sort": { "aggregate_type": 1, "aggregate_id": 1, "occurred_at": 1, "_id": 1 } },
+            doc! { "$group": {
+                "_id": { "type": "$aggregate_type", "id": "$aggregate_id" },
+                "head": { "$first": "$$ROOT" }
+            } },
+            doc! { "$replaceRoot": { "newRoot": "$head" } },
+            doc! { "$match": { "$and": [
+                { "$or": [
+                    { "next_attempt_at": { "$exists": false } },
+                    { "next_attempt_at": { "$lte": now } }
+                ] },
+                available.clone()
+            ] } },
+            doc! { "$sort": { "occurred_at": 1, "_id": 1 } },
+            doc! { "$limit": limit },
+        ];
+
+        let heads: Vec<Document> = self
+            .collection
+            .aggregate(pipeline)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let lease_until = bson::DateTime::from_chrono(
+            Utc::now() + chrono::Duration::from_std(lease).unwrap_or_default(),
+        );
+
+        let mut claimed = Vec::with_capacity(heads.len());
+        for head in heads {
+            let doc: OutboxDocument = bson::deserialize_from_document(head)
+                .map_err(|e| Error::internal(format!("Invalid outbox document: {}", e)))?;
+            let Some(oid) = doc.id else {
+                continue;
+            };
+
+            // Another relay may have claimed it since the read above
+            let mut filter = doc! { "_id": oid, "status": "pending" };
+            filter.extend(available.clone());
+            let result = self
+                .collection
+                .update_one(filter, doc! { "$set": { "locked_until": lease_until } })
+                .await
+                .map_err(|e| Error::database(e.to_string()))?;
+
+            if result.modified_count > 0 {
+                claimed.push(OutboxMessage::try_from(doc).map_err(Error::internal)?);
+            }
+        }
+
+        Ok(claimed)
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_published(&self, id: &OutboxMessageId) -> DomainResult<()> {
+        let oid = Self::parse_id(id)?;
+
+        self.collection
+            .update_one(
+                doc! { "_id": oid },
+                doc! {
+                    "$set": {
+                        "status": bson::serialize_to_bson(&OutboxStatus::Published)
+                            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?,
+                        "published_at": bson::DateTime::now()
+                    },
+                    "$unset": { "locked_until": "", "next_attempt_at": "" }
+                },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_failed(
+        &self,
+        id: &OutboxMessageId,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let oid = Self::parse_id(id)?;
+
+        self.collection
+            .update_one(
+                doc! { "_id": oid },
+                doc! {
+                    "$inc": { "attempts": 1 },
+                    "$set": {
+                        "last_error": error,
+                        "next_attempt_at": bson::DateTime::from_chrono(retry_at)
+                    },
+                    "$unset": { "locked_until": "" }
+                },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(())
+    }
+}
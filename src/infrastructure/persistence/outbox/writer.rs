@@ -0,0 +1,223 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::DomainEvent;
+use crate::infrastructure::persistence::outbox::model::OutboxDocument;
+use crate::infrastructure::persistence::outbox::repository::OUTBOX_COLLECTION;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use mongodb::{
+    Client, ClientSession, Collection, Database,
+    bson::Document,
+    results::{InsertOneResult, UpdateResult},
+};
+
+/// Whether a write actually changed state, so its events should be recorded.
+pub trait WriteOutcome {
+    fn changed(&self) -> bool;
+}
+
+impl WriteOutcome for InsertOneResult {
+    fn changed(&self) -> bool {
+        true
+    }
+}
+
+impl WriteOutcome for UpdateResult {
+    fn changed(&self) -> bool {
+        self.matched_count > 0
+    }
+}
+
+impl<T> WriteOutcome for Option<T> {
+    fn changed(&self) -> bool {
+        self.is_some()
+    }
+}
+
+/// Writes a state change and its domain events to the outbox atomically.
+#[derive(Clone)]
+pub struct OutboxWriter {
+    client: Client,
+    outbox: Collection<OutboxDocument>,
+    /// Standalone `mongod` has no transactions; writes then run back to back.
+    transactions: bool,
+}
+
+impl OutboxWriter {
+    pub fn new(db: &Database, transactions: bool) -> Self {
+        if !transactions {
+            tracing::warn!(
+                "MongoDB deployment does not support transactions; outbox writes are not atomic"
+            );
+        }
+
+        Self {
+            client: db.client().clone(),
+            outbox: db.collection(OUTBOX_COLLECTION),
+            transactions,
+        }
+    }
+
+    /// Whether writes run in a transaction, or back to back on a standalone `mongod`.
+    pub fn transactions(&self) -> bool {
+        self.transactions
+    }
+
+    /// Runs `write` inside a transaction and, if it changed anything, inserts
+    /// `events` into the outbox in the same transaction. `write` may be retried
+    /// on transient errors, so it must not consume `context`.
+    pub async fn write<R, C, F>(
+        &self,
+        events: &[DomainEvent],
+        mut context: C,
+        mut write: F,
+    ) -> DomainResult<R>
+    where
+        R: WriteOutcome + Send,
+        C: Send,
+        F: for<'b> FnMut(
+                &'b mut ClientSession,
+                &'b mut C,
+            ) -> BoxFuture<'b, mongodb::error::Result<R>>
+            + Send,
+    {
+        let docs: Vec<OutboxDocument> = events.iter().map(OutboxDocument::from).collect();
+
+        let mut session = self
+            .client
+            .start_session()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        if !self.transactions {
+            let result = write(&mut session, &mut context)
+                .await
+                .map_err(|e| Error::database(e.to_string()))?;
+            if result.changed() && !docs.is_empty() {
+                self.outbox
+                    .insert_many(&docs)
+                    .session(&mut session)
+                    .await
+                    .map_err(|e| Error::database(e.to_string()))?;
+            }
+            return Ok(result);
+        }
+
+        session
+            .start_transaction()
+            .and_run(
+                (&mut context, &mut write, &docs, &self.outbox),
+                |session, (context, write, docs, outbox)| {
+                    async move {
+                        let result = write(&mut *session, &mut **context).await?;
+                        if result.changed() && !docs.is_empty() {
+                            outbox.insert_many(docs.iter()).session(session).await?;
+                        }
+                        Ok(result)
+                    }
+                    .boxed()
+                },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+
+    /// [`Self::write`] for writes that work out their own events and may refuse
+    /// to commit. `write` returns its result with the events to record, or
+    /// `Err(refusal)` to roll the transaction back; the refusal is then
+    /// returned as `Ok(Err(refusal))`. Without transactions nothing is rolled
+    /// back, so a refusing `write` must leave no partial changes behind.
+    pub async fn try_write<R, E, C, F>(
+        &self,
+        mut context: C,
+        mut write: F,
+    ) -> DomainResult<Result<R, E>>
+    where
+        R: Send,
+        E: Clone + Send + Sync + 'static,
+        C: Send,
+        F: for<'b> FnMut(
+                &'b mut ClientSession,
+                &'b mut C,
+            )
+                -> BoxFuture<'b, mongodb::error::Result<Result<(R, Vec<DomainEvent>), E>>>
+            + Send,
+    {
+        let mut session = self
+            .client
+            .start_session()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        if !self.transactions {
+            let (result, events) = match write(&mut session, &mut context)
+                .await
+                .map_err(|e| Error::database(e.to_string()))?
+            {
+                Ok(written) => written,
+                Err(refusal) => return Ok(Err(refusal)),
+            };
+            if !events.is_empty() {
+                let docs: Vec<OutboxDocument> = events.iter().map(OutboxDocument::from).collect();
+                self.outbox
+                    .insert_many(&docs)
+                    .session(&mut session)
+                    .await
+                    .map_err(|e| Error::database(e.to_string()))?;
+            }
+            return Ok(Ok(result));
+        }
+
+        let result = session
+            .start_transaction()
+            .and_run(
+                (&mut context, &mut write, &self.outbox),
+                |session, (context, write, outbox)| {
+                    async move {
+                        // A custom error aborts the transaction without a retry
+                        let (result, events) = write(&mut *session, &mut **context)
+                            .await?
+                            .map_err(mongodb::error::Error::custom)?;
+                        if !events.is_empty() {
+                            let docs = events.iter().map(OutboxDocument::from);
+                            outbox.insert_many(docs).session(session).await?;
+                        }
+                        Ok(result)
+                    }
+                    .boxed()
+                },
+            )
+            .await;
+
+        match result {
+            Ok(result) => Ok(Ok(result)),
+            Err(e) => match e.get_custom::<E>() {
+                Some(refusal) => Ok(Err(refusal.clone())),
+                None => Err(Error::database(e.to_string())),
+            },
+        }
+    }
+
+    /// [`Self::write`] for the common case of a single `update_one`.
+    pub async fn update_one<T: Send + Sync>(
+        &self,
+        events: &[DomainEvent],
+        collection: &Collection<T>,
+        filter: Document,
+        update: Document,
+    ) -> DomainResult<UpdateResult> {
+        self.write(
+            events,
+            (collection, filter, update),
+            |session, (collection, filter, update)| {
+                async move {
+                    collection
+                        .update_one(filter.clone(), update.clone())
+                        .session(session)
+                        .await
+                }
+                .boxed()
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,129 @@
+//! PostgreSQL (sqlx) adapters for the user, product and order repositories,
+//! and the outbox they record their events in.
+//!
+//! They follow the MongoDB repositories: soft-deleted rows are invisible,
+//! lists are newest first and paginated the same way, versions are checked
+//! and incremented, and events are inserted in the same transaction as the
+//! change they describe. IDs are ObjectId hex strings, so they mix with the
+//! IDs of the other adapters. The schema lives in `migrations/postgres/`.
+
+pub mod order;
+pub mod outbox;
+pub mod product;
+pub mod user;
+
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::DomainEvent;
+use crate::domain::pagination::Pagination;
+use crate::domain::stream::DomainStream;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use mongodb::bson::oid::ObjectId;
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool};
+use std::future::Future;
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+/// Applies the pending SQL migrations. Safe to run on every startup: sqlx
+/// records applied versions and serializes concurrent runners with a lock.
+pub async fn migrate(pool: &PgPool) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Rows read per query by the `stream_all` implementations.
+const STREAM_BATCH: i64 = 200;
+
+/// A new ID in the format MongoDB assigns.
+fn new_id() -> String {
+    ObjectId::new().to_hex()
+}
+
+/// Rejects IDs that are not ObjectIds, with the error the MongoDB repositories return.
+fn check_id(id: &str, param: &'static str, entity: &'static str) -> DomainResult<()> {
+    ObjectId::parse_str(id)
+        .map(|_| ())
+        .map_err(|_| Error::invalid_param(param, entity, id))
+}
+
+fn db_error(e: sqlx::Error) -> Error {
+    Error::database(e.to_string())
+}
+
+/// Whether `e` violates the unique constraint or index named `constraint`.
+fn violates(e: &sqlx::Error, constraint: &str) -> bool {
+    match e {
+        sqlx::Error::Database(err) => {
+            err.is_unique_violation() && err.constraint() == Some(constraint)
+        }
+        _ => false,
+    }
+}
+
+/// `LIMIT` and `OFFSET` for `pagination`, the same as the MongoDB queries.
+fn page(pagination: &Pagination) -> (i64, i64) {
+    (pagination.get_limit(), to_i64(pagination.get_skip()))
+}
+
+/// Versions and counts are `BIGINT` columns.
+fn to_u64(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or_default()
+}
+
+fn to_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+/// Inserts `events` into the outbox on `conn`, normally inside the
+/// transaction of the write they describe.
+async fn record_events(conn: &mut PgConnection, events: &[DomainEvent]) -> DomainResult<()> {
+    let occurred_at = Utc::now();
+    for event in events {
+        sqlx::query(
+            "INSERT INTO outbox (id, aggregate_type, aggregate_id, event_type, payload, occurred_at)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(new_id())
+        .bind(event.aggregate_type())
+        .bind(event.aggregate_id())
+        .bind(event.event_type())
+        .bind(Json(event))
+        .bind(occurred_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(db_error)?;
+    }
+    Ok(())
+}
+
+/// Position of the last row a stream has read: its `created_at` and ID.
+type Cursor = (DateTime<Utc>, String);
+
+/// Streams rows newest first in batches of [`STREAM_BATCH`]. Each call to
+/// `next_batch` reads the rows after the cursor (all rows for `None`), so no
+/// connection is held between batches.
+fn stream_batches<T, F, Fut>(cursor_of: fn(&T) -> Cursor, next_batch: F) -> DomainStream<T>
+where
+    T: Send + 'static,
+    F: Fn(Option<Cursor>) -> Fut + Send + 'static,
+    Fut: Future<Output = DomainResult<Vec<T>>> + Send + 'static,
+{
+    // `None` once the last batch has been read
+    futures::stream::unfold(Some(None), move |cursor: Option<Option<Cursor>>| {
+        let batch = cursor.map(&next_batch);
+        async move {
+            let items = match batch?.await {
+                Ok(items) => items,
+                Err(e) => return Some((vec![Err(e)], None)),
+            };
+            let next = match items.last() {
+                Some(last) if items.len() as i64 == STREAM_BATCH => Some(Some(cursor_of(last))),
+                _ => None,
+            };
+            Some((items.into_iter().map(Ok).collect(), next))
+        }
+    })
+    .flat_map(futures::stream::iter)
+    .boxed()
+}
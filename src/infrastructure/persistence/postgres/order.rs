@@ -0,0 +1,242 @@
+use super::{
+    Cursor, STREAM_BATCH, check_id, db_error, new_id, page, record_events, stream_batches, to_i64,
+    to_u64,
+};
+use crate::domain::entities::country::Country;
+use crate::domain::entities::order::{Order, OrderId};
+use crate::domain::entities::product::ProductId;
+use crate::domain::entities::user::UserId;
+use crate::domain::error::DomainResult;
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::order::OrderRepositoryPort;
+use crate::domain::stream::DomainStream;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+#[derive(sqlx::FromRow)]
+struct OrderRow {
+    id: String,
+    user_id: String,
+    product_id: String,
+    quantity: i32,
+    total_price: f64,
+    country: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<OrderRow> for Order {
+    fn from(row: OrderRow) -> Self {
+        Self {
+            id: Some(OrderId::new(row.id)),
+            user_id: UserId::new(row.user_id),
+            product_id: ProductId::new(row.product_id),
+            quantity: row.quantity,
+            total_price: row.total_price,
+            // Stored as the ISO code (`MEX`)
+            country: row
+                .country
+                .and_then(|code| Country::try_from(code.as_str()).ok()),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            version: to_u64(row.version),
+            deleted_at: row.deleted_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresOrderRepository {
+    pool: PgPool,
+}
+
+impl PostgresOrderRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OrderRepositoryPort for PostgresOrderRepository {
+    fn next_id(&self) -> OrderId {
+        OrderId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, order: &Order, events: &[DomainEvent]) -> DomainResult<OrderId> {
+        let id = order.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "Order")?;
+        check_id(&order.user_id, "user_id", "User")?;
+        check_id(&order.product_id, "product_id", "Product")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "INSERT INTO orders (id, user_id, product_id, quantity, total_price, country,
+                 created_at, updated_at, version, deleted_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(&*id)
+        .bind(&*order.user_id)
+        .bind(&*order.product_id)
+        .bind(order.quantity)
+        .bind(order.total_price)
+        .bind(order.country.as_ref().map(Country::to_string))
+        .bind(order.created_at)
+        .bind(order.updated_at)
+        .bind(to_i64(order.version))
+        .bind(order.deleted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &OrderId) -> DomainResult<Option<Order>> {
+        check_id(id, "id", "Order")?;
+
+        let row: Option<OrderRow> =
+            sqlx::query_as("SELECT * FROM orders WHERE id = $1 AND deleted_at IS NULL")
+                .bind(&**id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        Ok(row.map(Order::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Order>> {
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<OrderRow> = sqlx::query_as(
+            "SELECT * FROM orders WHERE deleted_at IS NULL
+             ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(Order::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_user_id(
+        &self,
+        user_id: &UserId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Order>> {
+        check_id(user_id, "user_id", "Order")?;
+
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<OrderRow> = sqlx::query_as(
+            "SELECT * FROM orders WHERE user_id = $1 AND deleted_at IS NULL
+             ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(&**user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(Order::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        user_id: Option<&UserId>,
+    ) -> DomainResult<DomainStream<Order>> {
+        let pool = self.pool.clone();
+        let range = range.clone();
+        let user_id = user_id.map(|id| id.to_string());
+
+        Ok(stream_batches(
+            |o: &Order| {
+                (
+                    o.created_at,
+                    o.id.as_deref().unwrap_or_default().to_string(),
+                )
+            },
+            move |after: Option<Cursor>| {
+                let pool = pool.clone();
+                let range = range.clone();
+                let user_id = user_id.clone();
+                async move {
+                    let (after_created, after_id) = after.unzip();
+                    let rows: Vec<OrderRow> = sqlx::query_as(
+                        "SELECT * FROM orders WHERE deleted_at IS NULL
+                           AND ($1::timestamptz IS NULL OR created_at >= $1)
+                           AND ($2::timestamptz IS NULL OR created_at < $2)
+                           AND ($3::text IS NULL OR user_id = $3)
+                           AND ($4::timestamptz IS NULL OR (created_at, id) < ($4, $5))
+                         ORDER BY created_at DESC, id DESC LIMIT $6",
+                    )
+                    .bind(range.from)
+                    .bind(range.to)
+                    .bind(user_id)
+                    .bind(after_created)
+                    .bind(after_id)
+                    .bind(STREAM_BATCH)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(db_error)?;
+                    Ok(rows.into_iter().map(Order::from).collect())
+                }
+            },
+        ))
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &OrderId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "Order")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let deleted = sqlx::query(
+            "UPDATE orders SET deleted_at = now(), version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(&**id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected()
+            > 0;
+        if !deleted {
+            return Ok(false);
+        }
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        let count: i64 = sqlx::query_scalar("SELECT count(*) FROM orders WHERE deleted_at IS NULL")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        Ok(to_u64(count))
+    }
+}
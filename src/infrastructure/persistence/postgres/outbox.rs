@@ -0,0 +1,134 @@
+use super::{check_id, db_error, record_events};
+use crate::domain::error::DomainResult;
+use crate::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use crate::domain::port::outbox::OutboxPort;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use sqlx::types::Json;
+use std::time::Duration;
+
+#[derive(sqlx::FromRow)]
+struct OutboxRow {
+    id: String,
+    payload: Json<DomainEvent>,
+    occurred_at: DateTime<Utc>,
+    attempts: i32,
+}
+
+impl From<OutboxRow> for OutboxMessage {
+    fn from(row: OutboxRow) -> Self {
+        Self {
+            id: OutboxMessageId::new(row.id),
+            event: row.payload.0,
+            occurred_at: row.occurred_at,
+            attempts: u32::try_from(row.attempts).unwrap_or_default(),
+        }
+    }
+}
+
+/// The outbox table the PostgreSQL repositories write their events to.
+#[derive(Clone)]
+pub struct PostgresOutbox {
+    pool: PgPool,
+}
+
+impl PostgresOutbox {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OutboxPort for PostgresOutbox {
+    // ===== APPEND =====
+
+    #[tracing::instrument(skip_all, fields(events = events.len()))]
+    async fn append(&self, events: &[DomainEvent]) -> DomainResult<u64> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(events.len() as u64)
+    }
+
+    // ===== RELAY =====
+
+    #[tracing::instrument(skip_all)]
+    async fn claim_due(&self, limit: i64, lease: Duration) -> DomainResult<Vec<OutboxMessage>> {
+        let now = Utc::now();
+        let lease_until = now + chrono::Duration::from_std(lease).unwrap_or_default();
+
+        // The head of each aggregate is its oldest pending message; later ones
+        // wait behind it. The lock check is repeated in the UPDATE, so of two
+        // relays racing for a head only the first claims it.
+        let mut rows: Vec<OutboxRow> = sqlx::query_as(
+            "WITH heads AS (
+                 SELECT DISTINCT ON (aggregate_type, aggregate_id)
+                        id, seq, next_attempt_at, locked_until
+                 FROM outbox WHERE published_at IS NULL
+                 ORDER BY aggregate_type, aggregate_id, seq
+             ), due AS (
+                 SELECT id FROM heads
+                 WHERE (next_attempt_at IS NULL OR next_attempt_at <= $1)
+                   AND (locked_until IS NULL OR locked_until <= $1)
+                 ORDER BY seq LIMIT $2
+             )
+             UPDATE outbox SET locked_until = $3
+             FROM due
+             WHERE outbox.id = due.id
+               AND (outbox.locked_until IS NULL OR outbox.locked_until <= $1)
+             RETURNING outbox.id, outbox.payload, outbox.occurred_at, outbox.attempts",
+        )
+        .bind(now)
+        .bind(limit)
+        .bind(lease_until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        rows.sort_by_key(|row| row.occurred_at);
+
+        Ok(rows.into_iter().map(OutboxMessage::from).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_published(&self, id: &OutboxMessageId) -> DomainResult<()> {
+        check_id(id, "id", "OutboxMessage")?;
+
+        sqlx::query(
+            "UPDATE outbox SET published_at = now(), locked_until = NULL, next_attempt_at = NULL
+             WHERE id = $1",
+        )
+        .bind(&**id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip_all, fields(%id))]
+    async fn mark_failed(
+        &self,
+        id: &OutboxMessageId,
+        error: &str,
+        retry_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        check_id(id, "id", "OutboxMessage")?;
+
+        sqlx::query(
+            "UPDATE outbox
+             SET attempts = attempts + 1, next_attempt_at = $1, locked_until = NULL,
+                 last_error = $2
+             WHERE id = $3",
+        )
+        .bind(retry_at)
+        .bind(error)
+        .bind(&**id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(())
+    }
+}
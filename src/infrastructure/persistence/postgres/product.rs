@@ -0,0 +1,642 @@
+use super::{
+    Cursor, STREAM_BATCH, check_id, db_error, new_id, page, record_events, stream_batches, to_i64,
+    to_u64, violates,
+};
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::inventory::InventoryMovement;
+use crate::domain::entities::product::{
+    Product, ProductId, ProductMetadata, ProductStatus, SkuUpsertOutcome,
+};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::inventory::InventoryLedgerPort;
+use crate::domain::port::product::ProductRepositoryPort;
+use crate::domain::stream::DomainStream;
+use crate::domain::values::INITIAL_VERSION;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const SKU_KEY: &str = "products_live_sku_key";
+
+#[derive(sqlx::FromRow)]
+struct ProductRow {
+    id: String,
+    name: String,
+    price: f64,
+    stock: i32,
+    status: String,
+    description: Option<String>,
+    category_id: Option<String>,
+    tags: Vec<String>,
+    sku: String,
+    reorder_threshold: Option<i32>,
+    low_stock_alerted_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<ProductRow> for Product {
+    fn from(row: ProductRow) -> Self {
+        Self {
+            id: Some(ProductId::new(row.id)),
+            name: row.name,
+            price: row.price,
+            stock: row.stock,
+            status: parse_status(&row.status),
+            metadata: ProductMetadata {
+                description: row.description,
+                category_id: row.category_id.map(CategoryId::new),
+                tags: row.tags,
+                sku: row.sku,
+            },
+            reorder_threshold: row.reorder_threshold,
+            low_stock_alerted_at: row.low_stock_alerted_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            version: to_u64(row.version),
+            deleted_at: row.deleted_at,
+        }
+    }
+}
+
+/// Stored as the product's JSON name; the column's check constraint keeps
+/// other values out.
+fn status_name(status: &ProductStatus) -> &'static str {
+    match status {
+        ProductStatus::Draft => "draft",
+        ProductStatus::Active => "active",
+        ProductStatus::Archived => "archived",
+        ProductStatus::OutOfStock => "outofstock",
+    }
+}
+
+fn parse_status(name: &str) -> ProductStatus {
+    match name {
+        "active" => ProductStatus::Active,
+        "archived" => ProductStatus::Archived,
+        "outofstock" => ProductStatus::OutOfStock,
+        _ => ProductStatus::Draft,
+    }
+}
+
+fn sku_error(e: sqlx::Error, sku: &str) -> Error {
+    if violates(&e, SKU_KEY) {
+        return Error::duplicate("Product", "sku", sku);
+    }
+    db_error(e)
+}
+
+fn category_strings(category_ids: &[CategoryId]) -> Vec<String> {
+    category_ids.iter().map(|id| id.to_string()).collect()
+}
+
+/// Products always have a `category_id` column, so there are no legacy
+/// free-form categories to migrate. SKUs are unique among live products.
+///
+/// The inventory ledger has no PostgreSQL adapter yet, so stock movements
+/// are appended to `ledger` just before the transaction commits: a movement
+/// that can't be recorded rolls the stock change back.
+#[derive(Clone)]
+pub struct PostgresProductRepository {
+    pool: PgPool,
+    ledger: Arc<dyn InventoryLedgerPort>,
+}
+
+impl PostgresProductRepository {
+    pub fn new(pool: PgPool, ledger: Arc<dyn InventoryLedgerPort>) -> Self {
+        Self { pool, ledger }
+    }
+
+    /// Applies `delta` to the live product's stock inside `tx`, refusing
+    /// outflows that exceed it.
+    async fn change_stock(
+        tx: &mut PgConnection,
+        id: &ProductId,
+        delta: i32,
+    ) -> DomainResult<Option<Product>> {
+        check_id(id, "id", "Product")?;
+
+        // The row lock serializes concurrent updates, so the check below
+        // still holds when the update runs
+        let stock: Option<i32> = sqlx::query_scalar(
+            "SELECT stock FROM products WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+        )
+        .bind(&**id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let Some(stock) = stock else {
+            return Ok(None);
+        };
+        // Never let an outflow take stock below zero
+        if delta < 0 && stock < -delta {
+            return Ok(None);
+        }
+
+        let row: ProductRow = sqlx::query_as(
+            "UPDATE products SET stock = stock + $1, updated_at = now(), version = version + 1
+             WHERE id = $2 RETURNING *",
+        )
+        .bind(delta)
+        .bind(&**id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        Ok(Some(Product::from(row)))
+    }
+}
+
+#[async_trait]
+impl ProductRepositoryPort for PostgresProductRepository {
+    fn next_id(&self) -> ProductId {
+        ProductId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, product: &Product, events: &[DomainEvent]) -> DomainResult<ProductId> {
+        let id = product.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "Product")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "INSERT INTO products (id, name, price, stock, status, description, category_id, tags,
+                 sku, reorder_threshold, low_stock_alerted_at, created_at, updated_at, version,
+                 deleted_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)",
+        )
+        .bind(&*id)
+        .bind(&product.name)
+        .bind(product.price)
+        .bind(product.stock)
+        .bind(status_name(&product.status))
+        .bind(&product.metadata.description)
+        .bind(product.metadata.category_id.as_deref())
+        .bind(&product.metadata.tags)
+        .bind(&product.metadata.sku)
+        .bind(product.reorder_threshold)
+        .bind(product.low_stock_alerted_at)
+        .bind(product.created_at)
+        .bind(product.updated_at)
+        .bind(to_i64(product.version))
+        .bind(product.deleted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| sku_error(e, &product.metadata.sku))?;
+        record_events(&mut tx, events).await?;
+        self.ledger
+            .append(&InventoryMovement::initial(id.clone(), product.stock))
+            .await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &ProductId) -> DomainResult<Option<Product>> {
+        check_id(id, "id", "Product")?;
+
+        let row: Option<ProductRow> =
+            sqlx::query_as("SELECT * FROM products WHERE id = $1 AND deleted_at IS NULL")
+                .bind(&**id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        Ok(row.map(Product::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<ProductRow> = sqlx::query_as(
+            "SELECT * FROM products WHERE deleted_at IS NULL
+             ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(Product::from).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(skus = skus.len()))]
+    async fn find_ids_by_skus(&self, skus: &[String]) -> DomainResult<Vec<(String, ProductId)>> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT sku, id FROM products WHERE deleted_at IS NULL AND sku = ANY($1)",
+        )
+        .bind(skus)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(sku, id)| (sku, ProductId::new(id)))
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn find_by_categories(
+        &self,
+        category_ids: &[CategoryId],
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Product>> {
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<ProductRow> = sqlx::query_as(
+            "SELECT * FROM products WHERE deleted_at IS NULL AND category_id = ANY($1)
+             ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3",
+        )
+        .bind(category_strings(category_ids))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(Product::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_low_stock(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<ProductRow> = sqlx::query_as(
+            "SELECT * FROM products
+             WHERE deleted_at IS NULL AND reorder_threshold IS NOT NULL
+               AND stock < reorder_threshold
+             ORDER BY stock ASC, id ASC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(Product::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        category_ids: Option<&[CategoryId]>,
+    ) -> DomainResult<DomainStream<Product>> {
+        let pool = self.pool.clone();
+        let range = range.clone();
+        let category_ids = category_ids.map(category_strings);
+
+        Ok(stream_batches(
+            |p: &Product| {
+                (
+                    p.created_at,
+                    p.id.as_deref().unwrap_or_default().to_string(),
+                )
+            },
+            move |after: Option<Cursor>| {
+                let pool = pool.clone();
+                let range = range.clone();
+                let category_ids = category_ids.clone();
+                async move {
+                    let (after_created, after_id) = after.unzip();
+                    let rows: Vec<ProductRow> = sqlx::query_as(
+                        "SELECT * FROM products WHERE deleted_at IS NULL
+                           AND ($1::timestamptz IS NULL OR created_at >= $1)
+                           AND ($2::timestamptz IS NULL OR created_at < $2)
+                           AND ($3::text[] IS NULL OR category_id = ANY($3))
+                           AND ($4::timestamptz IS NULL OR (created_at, id) < ($4, $5))
+                         ORDER BY created_at DESC, id DESC LIMIT $6",
+                    )
+                    .bind(range.from)
+                    .bind(range.to)
+                    .bind(category_ids)
+                    .bind(after_created)
+                    .bind(after_id)
+                    .bind(STREAM_BATCH)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(db_error)?;
+                    Ok(rows.into_iter().map(Product::from).collect())
+                }
+            },
+        ))
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn count_by_categories(&self, category_ids: &[CategoryId]) -> DomainResult<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM products WHERE deleted_at IS NULL AND category_id = ANY($1)",
+        )
+        .bind(category_strings(category_ids))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(to_u64(count))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_legacy_categories(&self) -> DomainResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all, fields(%legacy, %category_id))]
+    async fn assign_legacy_category(
+        &self,
+        legacy: &str,
+        category_id: &CategoryId,
+    ) -> DomainResult<u64> {
+        Ok(0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_metadata(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        metadata: &ProductMetadata,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let updated = sqlx::query(
+            "UPDATE products
+             SET description = $1, category_id = $2, tags = $3, sku = $4,
+                 updated_at = now(), version = version + 1
+             WHERE id = $5 AND deleted_at IS NULL AND version = $6",
+        )
+        .bind(&metadata.description)
+        .bind(metadata.category_id.as_deref())
+        .bind(&metadata.tags)
+        .bind(&metadata.sku)
+        .bind(&**id)
+        .bind(to_i64(expected_version))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| sku_error(e, &metadata.sku))?
+        .rows_affected()
+            > 0;
+        if !updated {
+            return Ok(false);
+        }
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn apply_movement(
+        &self,
+        movement: &InventoryMovement,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let Some(product) =
+            Self::change_stock(&mut tx, &movement.product_id, movement.delta).await?
+        else {
+            return Ok(None);
+        };
+        record_events(&mut tx, events).await?;
+        self.ledger
+            .append(&movement.clone().with_stock_after(product.stock))
+            .await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(Some(product))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_stock(
+        &self,
+        id: &ProductId,
+        delta: i32,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let Some(product) = Self::change_stock(&mut tx, id, delta).await? else {
+            return Ok(None);
+        };
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(Some(product))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_reorder_threshold(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        threshold: Option<i32>,
+    ) -> DomainResult<Option<Product>> {
+        check_id(id, "id", "Product")?;
+
+        // Clearing the threshold also re-arms alerting
+        let row: Option<ProductRow> = sqlx::query_as(
+            "UPDATE products
+             SET reorder_threshold = $1,
+                 low_stock_alerted_at = CASE WHEN $1 IS NULL THEN NULL ELSE low_stock_alerted_at END,
+                 updated_at = now(), version = version + 1
+             WHERE id = $2 AND deleted_at IS NULL AND version = $3
+             RETURNING *",
+        )
+        .bind(threshold)
+        .bind(&**id)
+        .bind(to_i64(expected_version))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(row.map(Product::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn mark_low_stock_alerted(
+        &self,
+        id: &ProductId,
+        at: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        // Only the first writer wins; later updates see the column and back off
+        let result = sqlx::query(
+            "UPDATE products SET low_stock_alerted_at = $1
+             WHERE id = $2 AND deleted_at IS NULL AND low_stock_alerted_at IS NULL",
+        )
+        .bind(at)
+        .bind(&**id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn clear_low_stock_alert(&self, id: &ProductId) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        let result = sqlx::query(
+            "UPDATE products SET low_stock_alerted_at = NULL
+             WHERE id = $1 AND low_stock_alerted_at IS NOT NULL",
+        )
+        .bind(&**id)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(batch = products.len()))]
+    async fn upsert_by_sku(
+        &self,
+        products: &[Product],
+        batch_id: &str,
+    ) -> DomainResult<Vec<SkuUpsertOutcome>> {
+        if products.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // Locked until commit, so the rows reported as previous are the ones replaced
+        let skus: Vec<String> = products.iter().map(|p| p.metadata.sku.clone()).collect();
+        let mut existing: HashMap<String, Product> = sqlx::query_as(
+            "SELECT * FROM products WHERE deleted_at IS NULL AND sku = ANY($1) FOR UPDATE",
+        )
+        .bind(&skus)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row: ProductRow| (row.sku.clone(), Product::from(row)))
+        .collect();
+
+        let mut outcomes = Vec::with_capacity(products.len());
+        for product in products {
+            // A savepoint per row, so a bad row doesn't stop the rest
+            let mut row_tx = sqlx::Acquire::begin(&mut *tx).await.map_err(db_error)?;
+            let upserted: Result<String, sqlx::Error> = sqlx::query_scalar(
+                "INSERT INTO products (id, name, price, stock, status, description, category_id,
+                     tags, sku, created_at, updated_at, version)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                 ON CONFLICT (sku) WHERE deleted_at IS NULL DO UPDATE
+                 SET name = EXCLUDED.name, price = EXCLUDED.price, stock = EXCLUDED.stock,
+                     description = EXCLUDED.description, category_id = EXCLUDED.category_id,
+                     tags = EXCLUDED.tags, updated_at = EXCLUDED.updated_at,
+                     version = products.version + 1
+                 RETURNING id",
+            )
+            .bind(new_id())
+            .bind(&product.name)
+            .bind(product.price)
+            .bind(product.stock)
+            .bind(status_name(&product.status))
+            .bind(&product.metadata.description)
+            .bind(product.metadata.category_id.as_deref())
+            .bind(&product.metadata.tags)
+            .bind(&product.metadata.sku)
+            .bind(product.created_at)
+            .bind(product.updated_at)
+            .bind(to_i64(INITIAL_VERSION))
+            .fetch_one(&mut *row_tx)
+            .await;
+
+            let outcome = match upserted {
+                Ok(id) => {
+                    row_tx.commit().await.map_err(db_error)?;
+                    match existing.remove(&product.metadata.sku) {
+                        Some(previous) => SkuUpsertOutcome::Updated {
+                            id: ProductId::new(id),
+                            previous: Box::new(previous),
+                        },
+                        None => SkuUpsertOutcome::Created(ProductId::new(id)),
+                    }
+                }
+                Err(e) => {
+                    row_tx.rollback().await.map_err(db_error)?;
+                    SkuUpsertOutcome::Failed(e.to_string())
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        let (movements, events) =
+            SkuUpsertOutcome::import_records(outcomes.iter().zip(products), batch_id);
+        record_events(&mut tx, &events).await?;
+        self.ledger.append_many(&movements).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(outcomes)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &ProductId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "Product")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let deleted = sqlx::query(
+            "UPDATE products SET deleted_at = now(), version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(&**id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected()
+            > 0;
+        if !deleted {
+            return Ok(false);
+        }
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        let count: i64 =
+            sqlx::query_scalar("SELECT count(*) FROM products WHERE deleted_at IS NULL")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        Ok(to_u64(count))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_low_stock(&self) -> DomainResult<u64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM products
+             WHERE deleted_at IS NULL AND reorder_threshold IS NOT NULL
+               AND stock < reorder_threshold",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(to_u64(count))
+    }
+}
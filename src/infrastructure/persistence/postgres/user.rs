@@ -0,0 +1,250 @@
+use super::{
+    Cursor, STREAM_BATCH, check_id, db_error, new_id, page, record_events, stream_batches, to_i64,
+    to_u64, violates,
+};
+use crate::domain::entities::user::{User, UserId};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::event::DomainEvent;
+use crate::domain::filter::DateRange;
+use crate::domain::pagination::Pagination;
+use crate::domain::port::user::UserRepositoryPort;
+use crate::domain::stream::DomainStream;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+const EMAIL_KEY: &str = "users_email_key";
+
+#[derive(sqlx::FromRow)]
+struct UserRow {
+    id: String,
+    name: String,
+    email: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i64,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserRow> for User {
+    fn from(row: UserRow) -> Self {
+        Self {
+            id: Some(UserId::new(row.id)),
+            name: row.name,
+            email: row.email,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            version: to_u64(row.version),
+            deleted_at: row.deleted_at,
+        }
+    }
+}
+
+fn email_error(e: sqlx::Error, email: &str) -> Error {
+    if violates(&e, EMAIL_KEY) {
+        return Error::duplicate("User", "email", email);
+    }
+    db_error(e)
+}
+
+#[derive(Clone)]
+pub struct PostgresUserRepository {
+    pool: PgPool,
+}
+
+impl PostgresUserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UserRepositoryPort for PostgresUserRepository {
+    fn next_id(&self) -> UserId {
+        UserId::new(new_id())
+    }
+
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(&self, user: &User, events: &[DomainEvent]) -> DomainResult<UserId> {
+        let id = user.id.clone().unwrap_or_else(|| self.next_id());
+        check_id(&id, "id", "User")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            "INSERT INTO users (id, name, email, created_at, updated_at, version, deleted_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(&*id)
+        .bind(&user.name)
+        .bind(&user.email)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .bind(to_i64(user.version))
+        .bind(user.deleted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| email_error(e, &user.email))?;
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(id)
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &UserId) -> DomainResult<Option<User>> {
+        check_id(id, "id", "User")?;
+
+        let row: Option<UserRow> =
+            sqlx::query_as("SELECT * FROM users WHERE id = $1 AND deleted_at IS NULL")
+                .bind(&**id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        Ok(row.map(User::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<User>> {
+        let row: Option<UserRow> =
+            sqlx::query_as("SELECT * FROM users WHERE email = $1 AND deleted_at IS NULL")
+                .bind(email)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(db_error)?;
+
+        Ok(row.map(User::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<User>> {
+        let (limit, offset) = page(&pagination);
+        let rows: Vec<UserRow> = sqlx::query_as(
+            "SELECT * FROM users WHERE deleted_at IS NULL
+             ORDER BY created_at DESC, id DESC LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(rows.into_iter().map(User::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(&self, range: &DateRange) -> DomainResult<DomainStream<User>> {
+        let pool = self.pool.clone();
+        let range = range.clone();
+
+        Ok(stream_batches(
+            |u: &User| {
+                (
+                    u.created_at,
+                    u.id.as_deref().unwrap_or_default().to_string(),
+                )
+            },
+            move |after: Option<Cursor>| {
+                let pool = pool.clone();
+                let range = range.clone();
+                async move {
+                    let (after_created, after_id) = after.unzip();
+                    let rows: Vec<UserRow> = sqlx::query_as(
+                        "SELECT * FROM users WHERE deleted_at IS NULL
+                           AND ($1::timestamptz IS NULL OR created_at >= $1)
+                           AND ($2::timestamptz IS NULL OR created_at < $2)
+                           AND ($3::timestamptz IS NULL OR (created_at, id) < ($3, $4))
+                         ORDER BY created_at DESC, id DESC LIMIT $5",
+                    )
+                    .bind(range.from)
+                    .bind(range.to)
+                    .bind(after_created)
+                    .bind(after_id)
+                    .bind(STREAM_BATCH)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(db_error)?;
+                    Ok(rows.into_iter().map(User::from).collect())
+                }
+            },
+        ))
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        user: &User,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool> {
+        check_id(id, "id", "User")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let updated = sqlx::query(
+            "UPDATE users SET name = $1, email = $2, updated_at = $3, version = version + 1
+             WHERE id = $4 AND deleted_at IS NULL AND version = $5",
+        )
+        .bind(&user.name)
+        .bind(&user.email)
+        .bind(user.updated_at)
+        .bind(&**id)
+        .bind(to_i64(expected_version))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| email_error(e, &user.email))?
+        .rows_affected()
+            > 0;
+        if !updated {
+            return Ok(false);
+        }
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(true)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &UserId, events: &[DomainEvent]) -> DomainResult<bool> {
+        check_id(id, "id", "User")?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let deleted = sqlx::query(
+            "UPDATE users SET deleted_at = now(), version = version + 1
+             WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(&**id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected()
+            > 0;
+        if !deleted {
+            return Ok(false);
+        }
+        record_events(&mut tx, events).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(true)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        let count: i64 = sqlx::query_scalar("SELECT count(*) FROM users WHERE deleted_at IS NULL")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        Ok(to_u64(count))
+    }
+}
@@ -11,8 +11,15 @@ pub struct ProductDocument {
     pub stock: i32,
     pub status: ProductStatus,
     pub metadata: ProductMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorder_threshold: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_stock_alerted_at: Option<bson::DateTime>,
     pub created_at: bson::DateTime,
     pub updated_at: bson::DateTime,
+    /// Missing on documents written before versioning; read as `0`.
+    #[serde(default)]
+    pub version: i64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<bson::DateTime>,
 }
@@ -28,8 +35,11 @@ impl From<Product> for ProductDocument {
             stock: entity.stock,
             status: entity.status,
             metadata: entity.metadata,
+            reorder_threshold: entity.reorder_threshold,
+            low_stock_alerted_at: entity.low_stock_alerted_at.map(bson::DateTime::from_chrono),
             created_at: bson::DateTime::from_chrono(entity.created_at),
             updated_at: bson::DateTime::from_chrono(entity.updated_at),
+            version: i64::try_from(entity.version).unwrap_or(i64::MAX),
             deleted_at: entity.deleted_at.map(bson::DateTime::from_chrono),
         }
     }
@@ -44,8 +54,11 @@ impl From<ProductDocument> for Product {
             stock: doc.stock,
             status: doc.status,
             metadata: doc.metadata,
+            reorder_threshold: doc.reorder_threshold,
+            low_stock_alerted_at: doc.low_stock_alerted_at.map(|dt| dt.to_chrono()),
             created_at: doc.created_at.to_chrono(),
             updated_at: doc.updated_at.to_chrono(),
+            version: u64::try_from(doc.version).unwrap_or_default(),
             deleted_at: doc.deleted_at.map(|dt| dt.to_chrono()),
         }
     }
@@ -1,31 +1,57 @@
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::inventory::InventoryMovement;
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
 use crate::domain::port::product::ProductRepositoryPort;
-use crate::domain::entities::product::{Product, ProductId, ProductMetadata};
+use crate::domain::entities::product::{Product, ProductId, ProductMetadata, SkuUpsertOutcome};
+use crate::domain::event::DomainEvent;
+use crate::domain::stream::DomainStream;
+use crate::infrastructure::persistence::filter::{at_version, live_in_range};
+use crate::infrastructure::persistence::inventory::model::InventoryMovementDocument;
+use crate::infrastructure::persistence::inventory::repository::INVENTORY_COLLECTION;
+use crate::infrastructure::persistence::DUPLICATE_KEY;
+use crate::infrastructure::persistence::outbox::writer::OutboxWriter;
 use crate::infrastructure::persistence::product::model::ProductDocument;
 use async_trait::async_trait;
-use futures::stream::TryStreamExt;
+use chrono::{DateTime, Utc};
+use futures::FutureExt;
+use futures::stream::{StreamExt, TryStreamExt};
 use mongodb::{
-    Collection, Database, IndexModel,
-    bson::{self, doc, oid::ObjectId},
-    options::IndexOptions,
+    ClientSession, Collection, Database, IndexModel,
+    bson::{self, Document, doc, oid::ObjectId},
+    options::{IndexOptions, ReturnDocument},
 };
+use std::collections::HashMap;
 
+pub const PRODUCTS_COLLECTION: &str = "products";
+
+/// Unique `{metadata.sku, deleted_at}` index that [`ProductRepositoryPort::upsert_by_sku`] relies on.
+pub const SKU_UNIQUE_INDEX: &str = "sku_deleted_unique_idx";
+
+/// Stock changes append their ledger movement in the same transaction as
+/// the product write.
 #[derive(Clone)]
 pub struct ProductRepository {
+    db: Database,
     collection: Collection<ProductDocument>,
+    ledger: Collection<InventoryMovementDocument>,
+    outbox: OutboxWriter,
 }
 
 impl ProductRepository {
-    pub fn new(db: &Database) -> Self {
+    pub fn new(db: &Database, outbox: OutboxWriter) -> Self {
         Self {
-            collection: db.collection("products"),
+            db: db.clone(),
+            collection: db.collection(PRODUCTS_COLLECTION),
+            ledger: db.collection(INVENTORY_COLLECTION),
+            outbox,
         }
     }
 
-    /// Create database indexes (idempotent — safe to call on every startup)
-    pub async fn create_indexes(&self) -> DomainResult<()> {
-        let indexes = vec![
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
             IndexModel::builder()
                 .keys(doc! { "deleted_at": 1, "created_at": -1 })
                 .options(
@@ -42,36 +68,218 @@ impl ProductRepository {
                         .build(),
                 )
                 .build(),
-        ];
+            // One live product per SKU; soft-deleted ones differ by `deleted_at`
+            IndexModel::builder()
+                .keys(doc! { "metadata.sku": 1, "deleted_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name(SKU_UNIQUE_INDEX.to_string())
+                        .unique(true)
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "deleted_at": 1, "metadata.category_id": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("deleted_category_created_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "deleted_at": 1, "reorder_threshold": 1, "stock": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("deleted_threshold_stock_compound_idx".to_string())
+                        .partial_filter_expression(
+                            doc! { "reorder_threshold": { "$exists": true } },
+                        )
+                        .build(),
+                )
+                .build(),
+        ]
+    }
 
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
         self.collection
-            .create_indexes(indexes)
+            .create_indexes(Self::indexes())
             .await
             .map_err(|e| Error::database(e.to_string()))?;
 
         tracing::info!("✓ Products indexes created");
         Ok(())
     }
+
+    /// One unordered `update` command upserting `rows` by SKU, after a single
+    /// `$in` read of the live products they replace, followed by the batch's
+    /// ledger movements; its events are returned for the outbox.
+    ///
+    /// Known SKUs are updated by `_id`; new ones are upserted with an `_id`
+    /// chosen up front, so a created row knows its ID without a second read.
+    /// A new SKU that a concurrent import inserted in between matches that
+    /// product instead of inserting, and fails, as does any row the server
+    /// rejects. In a transaction a rejected row aborts the whole command, so
+    /// the rows are refused instead and the caller retries without them.
+    async fn bulk_upsert(
+        &self,
+        session: &mut ClientSession,
+        rows: &[UpsertRow],
+        batch_id: &str,
+    ) -> mongodb::error::Result<Result<(Vec<SkuUpsertOutcome>, Vec<DomainEvent>), RejectedRows>>
+    {
+        let skus: Vec<&str> = rows
+            .iter()
+            .map(|row| row.product.metadata.sku.as_str())
+            .collect();
+        let mut previous: HashMap<String, ProductDocument> = self
+            .collection
+            .find(doc! { "metadata.sku": { "$in": skus }, "deleted_at": { "$exists": false } })
+            .session(&mut *session)
+            .await?
+            .stream(&mut *session)
+            .map_ok(|doc| (doc.metadata.sku.clone(), doc))
+            .try_collect()
+            .await?;
+
+        let mut targets = Vec::with_capacity(rows.len());
+        let mut updates = Vec::with_capacity(rows.len());
+        for row in rows {
+            let update = row.update.clone();
+            match previous.remove(&row.product.metadata.sku) {
+                Some(doc) => {
+                    updates.push(doc! {
+                        "q": { "_id": doc.id, "deleted_at": { "$exists": false } },
+                        "u": update,
+                    });
+                    targets.push(UpsertTarget::Update(Box::new(Product::from(doc))));
+                }
+                None => {
+                    let oid = ObjectId::new();
+                    targets.push(UpsertTarget::Insert(oid));
+                    let mut on_insert = row.on_insert.clone();
+                    on_insert.insert("_id", oid);
+                    let mut update = update;
+                    update.insert("$setOnInsert", on_insert);
+                    updates.push(doc! {
+                        "q": {
+                            "metadata.sku": &row.product.metadata.sku,
+                            "deleted_at": { "$exists": false }
+                        },
+                        "u": update,
+                        "upsert": true,
+                    });
+                }
+            }
+        }
+
+        let reply = self
+            .db
+            .run_command(doc! {
+                "update": PRODUCTS_COLLECTION,
+                "updates": updates,
+                "ordered": false,
+            })
+            .session(&mut *session)
+            .await?;
+        let upserted = reply_indexes(&reply, "upserted");
+        let rejected: Vec<(usize, String)> = reply
+            .get_array("writeErrors")
+            .map(|errors| {
+                errors
+                    .iter()
+                    .filter_map(|entry| write_error(entry, rows))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if self.outbox.transactions() && !rejected.is_empty() {
+            return Ok(Err(RejectedRows(rejected)));
+        }
+
+        let outcomes: Vec<SkuUpsertOutcome> = rows
+            .iter()
+            .zip(targets)
+            .enumerate()
+            .map(|(index, (row, target))| {
+                if let Some((_, reason)) = rejected.iter().find(|(i, _)| *i == index) {
+                    return SkuUpsertOutcome::Failed(reason.clone());
+                }
+                match target {
+                    UpsertTarget::Update(previous) => SkuUpsertOutcome::Updated {
+                        id: previous.id.clone().unwrap_or_default(),
+                        previous,
+                    },
+                    UpsertTarget::Insert(oid) if upserted.contains(&index) => {
+                        SkuUpsertOutcome::Created(ProductId::new(oid.to_hex()))
+                    }
+                    UpsertTarget::Insert(_) => {
+                        SkuUpsertOutcome::Failed(concurrent_import(&row.product))
+                    }
+                }
+            })
+            .collect();
+
+        let (movements, events) = SkuUpsertOutcome::import_records(
+            outcomes.iter().zip(rows.iter().map(|row| &row.product)),
+            batch_id,
+        );
+        if !movements.is_empty() {
+            let docs = movements
+                .into_iter()
+                .map(InventoryMovementDocument::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(mongodb::error::Error::custom)?;
+            self.ledger.insert_many(docs).session(session).await?;
+        }
+
+        Ok(Ok((outcomes, events)))
+    }
+
+    /// Live products whose stock is below their own reorder threshold.
+    fn low_stock_filter() -> Document {
+        doc! {
+            "deleted_at": { "$exists": false },
+            "reorder_threshold": { "$exists": true },
+            "$expr": { "$lt": ["$stock", "$reorder_threshold"] }
+        }
+    }
 }
 
 #[async_trait]
 impl ProductRepositoryPort for ProductRepository {
+    fn next_id(&self) -> ProductId {
+        ProductId::new(ObjectId::new().to_hex())
+    }
+
     // ===== CREATE =====
 
     #[tracing::instrument(skip_all)]
-    async fn create(&self, product: &Product) -> DomainResult<ProductId> {
-        let doc = ProductDocument::from(product.clone());
-        let result = self
-            .collection
-            .insert_one(doc)
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+    async fn create(&self, product: &Product, events: &[DomainEvent]) -> DomainResult<ProductId> {
+        let id = product.id.clone().unwrap_or_else(|| self.next_id());
+        let movement = movement_document(&InventoryMovement::initial(id.clone(), product.stock))
+            .map_err(|_| Error::invalid_param("id", "Product", &*id))?;
+        let doc = ProductDocument::from(Product {
+            id: Some(id.clone()),
+            ..product.clone()
+        });
+
+        self.outbox
+            .write(
+                events,
+                (&self.collection, &self.ledger, &doc, &movement),
+                |session, (collection, ledger, doc, movement)| {
+                    async move {
+                        let result = collection.insert_one(*doc).session(&mut *session).await?;
+                        ledger.insert_one(*movement).session(session).await?;
+                        Ok(result)
+                    }
+                    .boxed()
+                },
+            )
+            .await?;
 
-        result
-            .inserted_id
-            .as_object_id()
-            .map(|oid| ProductId::new(oid.to_hex()))
-            .ok_or_else(|| Error::internal("Failed to get inserted ID"))
+        Ok(id)
     }
 
     // ===== READ =====
@@ -109,79 +317,430 @@ impl ProductRepositoryPort for ProductRepository {
         Ok(docs.into_iter().map(Product::from).collect())
     }
 
+    #[tracing::instrument(skip_all, fields(skus = skus.len()))]
+    async fn find_ids_by_skus(&self, skus: &[String]) -> DomainResult<Vec<(String, ProductId)>> {
+        let cursor = self
+            .collection
+            .clone_with_type::<Document>()
+            .find(doc! {
+                "metadata.sku": { "$in": skus },
+                "deleted_at": { "$exists": false }
+            })
+            .projection(doc! { "_id": 1, "metadata.sku": 1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<Document> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs
+            .iter()
+            .filter_map(|doc| {
+                let oid = doc.get_object_id("_id").ok()?;
+                let sku = doc.get_document("metadata").ok()?.get_str("sku").ok()?;
+                Some((sku.to_string(), ProductId::new(oid.to_hex())))
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn find_by_categories(
+        &self,
+        category_ids: &[CategoryId],
+        pagination: Pagination,
+    ) -> DomainResult<Vec<Product>> {
+        let ids: Vec<&str> = category_ids.iter().map(|id| &**id).collect();
+
+        let cursor = self
+            .collection
+            .find(doc! {
+                "metadata.category_id": { "$in": ids },
+                "deleted_at": { "$exists": false }
+            })
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<ProductDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(Product::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_low_stock(&self, pagination: Pagination) -> DomainResult<Vec<Product>> {
+        let cursor = self
+            .collection
+            .find(Self::low_stock_filter())
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "stock": 1, "_id": 1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<ProductDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(Product::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(
+        &self,
+        range: &DateRange,
+        category_ids: Option<&[CategoryId]>,
+    ) -> DomainResult<DomainStream<Product>> {
+        let mut filter = live_in_range(range);
+        if let Some(category_ids) = category_ids {
+            let ids: Vec<&str> = category_ids.iter().map(|id| &**id).collect();
+            filter.insert("metadata.category_id", doc! { "$in": ids });
+        }
+
+        let cursor = self
+            .collection
+            .find(filter)
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(cursor
+            .map_ok(Product::from)
+            .map_err(|e| Error::database(e.to_string()))
+            .boxed())
+    }
+
+    #[tracing::instrument(skip_all, fields(categories = category_ids.len()))]
+    async fn count_by_categories(&self, category_ids: &[CategoryId]) -> DomainResult<u64> {
+        let ids: Vec<&str> = category_ids.iter().map(|id| &**id).collect();
+
+        self.collection
+            .count_documents(doc! {
+                "metadata.category_id": { "$in": ids },
+                "deleted_at": { "$exists": false }
+            })
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_legacy_categories(&self) -> DomainResult<Vec<String>> {
+        let values = self
+            .collection
+            .distinct(
+                "metadata.category",
+                doc! { "metadata.category_id": { "$exists": false } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(values
+            .into_iter()
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect())
+    }
+
     // ===== UPDATE =====
 
+    #[tracing::instrument(skip_all, fields(%legacy, %category_id))]
+    async fn assign_legacy_category(
+        &self,
+        legacy: &str,
+        category_id: &CategoryId,
+    ) -> DomainResult<u64> {
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        // Soft-deleted products are migrated too so restores keep a valid category.
+        let result = self
+            .collection
+            .update_many(
+                doc! {
+                    "metadata.category": legacy,
+                    "metadata.category_id": { "$exists": false }
+                },
+                doc! {
+                    "$set": { "metadata.category_id": &**category_id, "updated_at": now },
+                    "$unset": { "metadata.category": "" },
+                    "$inc": { "version": 1 }
+                },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.modified_count)
+    }
+
     #[tracing::instrument(skip_all)]
     async fn update_metadata(
         &self,
         id: &ProductId,
+        expected_version: u64,
         metadata: &ProductMetadata,
+        events: &[DomainEvent],
     ) -> DomainResult<bool> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
 
-        let bson_metadata = bson::serialize_to_bson(metadata)
-            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
-
-        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+        let update = metadata_update(
+            metadata,
+            doc! { "updated_at": bson::DateTime::from_chrono(chrono::Utc::now()) },
+        )?;
 
         let result = self
-            .collection
+            .outbox
             .update_one(
-                doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                events,
+                &self.collection,
                 doc! {
-                    "$set": {
-                        "metadata": bson_metadata,
-                        "updated_at": now
-                    }
+                    "_id": oid,
+                    "deleted_at": { "$exists": false },
+                    "version": at_version(expected_version)
                 },
+                update,
             )
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .await?;
 
         Ok(result.matched_count > 0)
     }
 
     #[tracing::instrument(skip_all)]
-    async fn update_stock(&self, id: &ProductId, delta: i32) -> DomainResult<bool> {
+    async fn apply_movement(
+        &self,
+        movement: &InventoryMovement,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        let (filter, update) = stock_update(&movement.product_id, movement.delta)?;
+        let entry = movement_document(movement)?;
+
+        let doc = self
+            .outbox
+            .write(
+                events,
+                (&self.collection, &self.ledger, filter, update, entry),
+                |session, (collection, ledger, filter, update, entry)| {
+                    async move {
+                        let Some(doc) = collection
+                            .find_one_and_update(filter.clone(), update.clone())
+                            .return_document(ReturnDocument::After)
+                            .session(&mut *session)
+                            .await?
+                        else {
+                            return Ok(None);
+                        };
+                        entry.stock_after = Some(doc.stock);
+                        ledger.insert_one(&*entry).session(session).await?;
+                        Ok(Some(doc))
+                    }
+                    .boxed()
+                },
+            )
+            .await?;
+
+        Ok(doc.map(Product::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_stock(
+        &self,
+        id: &ProductId,
+        delta: i32,
+        events: &[DomainEvent],
+    ) -> DomainResult<Option<Product>> {
+        let (filter, update) = stock_update(id, delta)?;
+
+        // Use $inc for atomic update and read back the resulting stock
+        let doc = self
+            .outbox
+            .write(
+                events,
+                (&self.collection, filter, update),
+                |session, (collection, filter, update)| {
+                    async move {
+                        collection
+                            .find_one_and_update(filter.clone(), update.clone())
+                            .return_document(ReturnDocument::After)
+                            .session(session)
+                            .await
+                    }
+                    .boxed()
+                },
+            )
+            .await?;
+
+        Ok(doc.map(Product::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_reorder_threshold(
+        &self,
+        id: &ProductId,
+        expected_version: u64,
+        threshold: Option<i32>,
+    ) -> DomainResult<Option<Product>> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
 
         let now = bson::DateTime::from_chrono(chrono::Utc::now());
 
-        // Use $inc for atomic update
+        let update = match threshold {
+            Some(threshold) => doc! {
+                "$set": { "reorder_threshold": threshold, "updated_at": now },
+                "$inc": { "version": 1 }
+            },
+            None => doc! {
+                "$set": { "updated_at": now },
+                "$unset": { "reorder_threshold": "", "low_stock_alerted_at": "" },
+                "$inc": { "version": 1 }
+            },
+        };
+
+        let doc = self
+            .collection
+            .find_one_and_update(
+                doc! {
+                    "_id": oid,
+                    "deleted_at": { "$exists": false },
+                    "version": at_version(expected_version)
+                },
+                update,
+            )
+            .return_document(ReturnDocument::After)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(Product::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn mark_low_stock_alerted(
+        &self,
+        id: &ProductId,
+        at: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        let oid =
+            ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
+
+        // Only the first writer wins; later updates see the field and back off
         let result = self
             .collection
             .update_one(
-                doc! { "_id": oid, "deleted_at": { "$exists": false } },
                 doc! {
-                    "$inc": { "stock": delta },
-                    "$set": { "updated_at": now },
+                    "_id": oid,
+                    "deleted_at": { "$exists": false },
+                    "low_stock_alerted_at": { "$exists": false }
                 },
+                doc! { "$set": { "low_stock_alerted_at": bson::DateTime::from_chrono(at) } },
             )
             .await
             .map_err(|e| Error::database(e.to_string()))?;
 
-        Ok(result.matched_count > 0)
+        Ok(result.modified_count > 0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn clear_low_stock_alert(&self, id: &ProductId) -> DomainResult<bool> {
+        let oid =
+            ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "low_stock_alerted_at": { "$exists": true } },
+                doc! { "$unset": { "low_stock_alerted_at": "" } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.modified_count > 0)
+    }
+
+    #[tracing::instrument(skip_all, fields(batch = products.len()))]
+    async fn upsert_by_sku(
+        &self,
+        products: &[Product],
+        batch_id: &str,
+    ) -> DomainResult<Vec<SkuUpsertOutcome>> {
+        let mut rows = Vec::with_capacity(products.len());
+        for product in products {
+            let doc = ProductDocument::from(product.clone());
+            let update = metadata_update(
+                &doc.metadata,
+                doc! {
+                    "name": doc.name,
+                    "price": doc.price,
+                    "stock": doc.stock,
+                    "updated_at": doc.updated_at
+                },
+            )?;
+            rows.push(UpsertRow {
+                product: product.clone(),
+                update,
+                on_insert: doc! {
+                    "status": serialize(&doc.status)?,
+                    "created_at": doc.created_at
+                },
+            });
+        }
+
+        // Each pass leaves out the rows the previous one was refused for, so
+        // this ends after at most one pass per row
+        let mut outcomes: Vec<Option<SkuUpsertOutcome>> = vec![None; products.len()];
+        loop {
+            let pending: Vec<usize> = (0..rows.len()).filter(|&i| outcomes[i].is_none()).collect();
+            if pending.is_empty() {
+                break;
+            }
+            let batch: Vec<UpsertRow> = pending.iter().map(|&i| rows[i].clone()).collect();
+
+            let written = self
+                .outbox
+                .try_write((self, &batch), |session, (repo, batch)| {
+                    repo.bulk_upsert(session, batch, batch_id).boxed()
+                })
+                .await?;
+
+            match written {
+                Ok(written) => {
+                    for (i, outcome) in pending.into_iter().zip(written) {
+                        outcomes[i] = Some(outcome);
+                    }
+                }
+                Err(RejectedRows(rejected)) => {
+                    for (index, reason) in rejected {
+                        outcomes[pending[index]] = Some(SkuUpsertOutcome::Failed(reason));
+                    }
+                }
+            }
+        }
+
+        Ok(outcomes.into_iter().flatten().collect())
     }
 
     // ===== SOFT DELETE =====
 
     #[tracing::instrument(skip_all)]
-    async fn delete(&self, id: &ProductId) -> DomainResult<bool> {
+    async fn delete(&self, id: &ProductId, events: &[DomainEvent]) -> DomainResult<bool> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
 
         let now = bson::DateTime::from_chrono(chrono::Utc::now());
 
         let result = self
-            .collection
+            .outbox
             .update_one(
+                events,
+                &self.collection,
                 doc! { "_id": oid, "deleted_at": { "$exists": false } },
-                doc! { "$set": { "deleted_at": now } },
+                doc! { "$set": { "deleted_at": now }, "$inc": { "version": 1 } },
             )
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .await?;
 
         Ok(result.matched_count > 0)
     }
@@ -195,4 +754,114 @@ impl ProductRepositoryPort for ProductRepository {
             .await
             .map_err(|e| Error::database(e.to_string()))
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_low_stock(&self) -> DomainResult<u64> {
+        self.collection
+            .count_documents(Self::low_stock_filter())
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+}
+
+/// Update writing `metadata` and the `fields` beside it as a new version.
+/// Metadata is `$set` one dotted path at a time, so keys the entity does not
+/// model — the legacy free-form `metadata.category` — survive until
+/// `migrate_legacy_categories` converts them; a `category_id` of `None` is
+/// `$unset`, since it is not serialized.
+fn metadata_update(metadata: &ProductMetadata, fields: Document) -> DomainResult<Document> {
+    let mut set: Document = bson::serialize_to_document(metadata)
+        .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?
+        .into_iter()
+        .map(|(key, value)| (format!("metadata.{key}"), value))
+        .collect();
+    set.extend(fields);
+
+    let mut update = doc! { "$set": set, "$inc": { "version": 1 } };
+    if metadata.category_id.is_none() {
+        update.insert("$unset", doc! { "metadata.category_id": "" });
+    }
+    Ok(update)
+}
+
+/// One product of [`ProductRepositoryPort::upsert_by_sku`] with its update,
+/// and the fields a newly inserted product also gets.
+#[derive(Clone)]
+struct UpsertRow {
+    product: Product,
+    update: Document,
+    on_insert: Document,
+}
+
+/// What a row of [`ProductRepository::bulk_upsert`] writes to: the live
+/// product it replaces, or the ID of the one it inserts.
+enum UpsertTarget {
+    Update(Box<Product>),
+    Insert(ObjectId),
+}
+
+/// Rows of an import batch the server rejected, by index in the batch, with
+/// the reason they failed.
+#[derive(Debug, Clone)]
+struct RejectedRows(Vec<(usize, String)>);
+
+/// The `index` of each entry of the reply's `field` array.
+fn reply_indexes(reply: &Document, field: &str) -> Vec<usize> {
+    reply
+        .get_array(field)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.as_document()?.get_i32("index").ok())
+                .map(|index| index as usize)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The row a `writeErrors` entry is about, and why it failed.
+fn write_error(entry: &bson::Bson, rows: &[UpsertRow]) -> Option<(usize, String)> {
+    let entry = entry.as_document()?;
+    let index = usize::try_from(entry.get_i32("index").ok()?).ok()?;
+    let row = rows.get(index)?;
+    let reason = match entry.get_i32("code") {
+        Ok(DUPLICATE_KEY) => concurrent_import(&row.product),
+        _ => entry.get_str("errmsg").unwrap_or("write error").to_string(),
+    };
+    Some((index, reason))
+}
+
+fn concurrent_import(product: &Product) -> String {
+    format!(
+        "SKU {} was written by a concurrent import",
+        product.metadata.sku
+    )
+}
+
+/// Filter and update applying `delta` to a live product's stock. Outflows
+/// only match while there is enough stock, so stock never goes below zero.
+fn stock_update(id: &ProductId, delta: i32) -> DomainResult<(Document, Document)> {
+    let oid =
+        ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "Product", &**id))?;
+
+    let mut filter = doc! { "_id": oid, "deleted_at": { "$exists": false } };
+    if delta < 0 {
+        filter.insert("stock", doc! { "$gte": -delta });
+    }
+
+    let update = doc! {
+        "$inc": { "stock": delta, "version": 1 },
+        "$set": { "updated_at": bson::DateTime::from_chrono(chrono::Utc::now()) },
+    };
+    Ok((filter, update))
+}
+
+fn movement_document(movement: &InventoryMovement) -> DomainResult<InventoryMovementDocument> {
+    InventoryMovementDocument::try_from(movement.clone())
+        .map_err(|_| Error::invalid_param("product_id", "Product", &*movement.product_id))
+}
+
+fn serialize<T: serde::Serialize>(value: &T) -> DomainResult<bson::Bson> {
+    bson::serialize_to_bson(value)
+        .map_err(|e| Error::internal(format!("Serialization error: {}", e)))
 }
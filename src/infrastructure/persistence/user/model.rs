@@ -10,6 +10,9 @@ pub struct UserDocument {
     pub email: String,
     pub created_at: bson::DateTime,
     pub updated_at: bson::DateTime,
+    /// Missing on documents written before versioning; read as `0`.
+    #[serde(default)]
+    pub version: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_at: Option<bson::DateTime>,
 }
@@ -25,6 +28,7 @@ impl From<User> for UserDocument {
             email: entity.email,
             created_at: bson::DateTime::from_chrono(entity.created_at),
             updated_at: bson::DateTime::from_chrono(entity.updated_at),
+            version: i64::try_from(entity.version).unwrap_or(i64::MAX),
             deleted_at: entity.deleted_at.map(bson::DateTime::from_chrono),
         }
     }
@@ -38,6 +42,7 @@ impl From<UserDocument> for User {
             email: doc.email,
             created_at: doc.created_at.to_chrono(),
             updated_at: doc.updated_at.to_chrono(),
+            version: u64::try_from(doc.version).unwrap_or_default(),
             deleted_at: doc.deleted_at.map(|dt| dt.to_chrono()),
         }
     }
@@ -1,31 +1,41 @@
 use crate::domain::error::{DomainResult, Error};
+use crate::domain::filter::DateRange;
 use crate::domain::pagination::Pagination;
 use crate::domain::port::user::UserRepositoryPort;
 use crate::domain::entities::user::{User, UserId};
+use crate::domain::event::DomainEvent;
+use crate::domain::stream::DomainStream;
+use crate::infrastructure::persistence::filter::{at_version, live_in_range};
+use crate::infrastructure::persistence::outbox::writer::OutboxWriter;
 use crate::infrastructure::persistence::user::model::UserDocument;
 use async_trait::async_trait;
-use futures::stream::TryStreamExt;
+use futures::FutureExt;
+use futures::stream::{StreamExt, TryStreamExt};
 use mongodb::{
     Collection, Database, IndexModel,
     bson::{doc, oid::ObjectId},
     options::IndexOptions,
 };
 
+pub const USERS_COLLECTION: &str = "users";
+
 #[derive(Clone)]
 pub struct UserRepository {
     collection: Collection<UserDocument>,
+    outbox: OutboxWriter,
 }
 
 impl UserRepository {
-    pub fn new(db: &Database) -> Self {
+    pub fn new(db: &Database, outbox: OutboxWriter) -> Self {
         Self {
-            collection: db.collection("users"),
+            collection: db.collection(USERS_COLLECTION),
+            outbox,
         }
     }
 
-    /// Create database indexes (idempotent — safe to call on every startup)
-    pub async fn create_indexes(&self) -> DomainResult<()> {
-        let indexes = vec![
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
             IndexModel::builder()
                 .keys(doc! { "email": 1 })
                 .options(
@@ -51,10 +61,13 @@ impl UserRepository {
                         .build(),
                 )
                 .build(),
-        ];
+        ]
+    }
 
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
         self.collection
-            .create_indexes(indexes)
+            .create_indexes(Self::indexes())
             .await
             .map_err(|e| Error::database(e.to_string()))?;
 
@@ -65,16 +78,25 @@ impl UserRepository {
 
 #[async_trait]
 impl UserRepositoryPort for UserRepository {
+    fn next_id(&self) -> UserId {
+        UserId::new(ObjectId::new().to_hex())
+    }
+
     // ===== CREATE =====
 
     #[tracing::instrument(skip_all)]
-    async fn create(&self, user: &User) -> DomainResult<UserId> {
+    async fn create(&self, user: &User, events: &[DomainEvent]) -> DomainResult<UserId> {
         let doc = UserDocument::from(user.clone());
         let result = self
-            .collection
-            .insert_one(doc)
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .outbox
+            .write(
+                events,
+                (&self.collection, &doc),
+                |session, (collection, doc)| {
+                    async move { collection.insert_one(*doc).session(session).await }.boxed()
+                },
+            )
+            .await?;
 
         result
             .inserted_id
@@ -132,25 +154,51 @@ impl UserRepositoryPort for UserRepository {
         Ok(docs.into_iter().map(User::from).collect())
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn stream_all(&self, range: &DateRange) -> DomainResult<DomainStream<User>> {
+        let cursor = self
+            .collection
+            .find(live_in_range(range))
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(cursor
+            .map_ok(User::from)
+            .map_err(|e| Error::database(e.to_string()))
+            .boxed())
+    }
+
     // ===== UPDATE =====
 
     #[tracing::instrument(skip_all)]
-    async fn update(&self, id: &UserId, user: &User) -> DomainResult<bool> {
+    async fn update(
+        &self,
+        id: &UserId,
+        expected_version: u64,
+        user: &User,
+        events: &[DomainEvent],
+    ) -> DomainResult<bool> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "User", &**id))?;
 
         let doc = UserDocument::from(user.clone());
-        let bson_doc = mongodb::bson::serialize_to_document(&doc)
+        let mut bson_doc = mongodb::bson::serialize_to_document(&doc)
             .map_err(|e| Error::internal(e.to_string()))?;
+        bson_doc.remove("_id");
+        bson_doc.remove("version");
+
+        let filter = doc! {
+            "_id": oid,
+            "deleted_at": { "$exists": false },
+            "version": at_version(expected_version)
+        };
+        let update = doc! { "$set": bson_doc, "$inc": { "version": 1 } };
 
         let result = self
-            .collection
-            .update_one(
-                doc! { "_id": oid, "deleted_at": { "$exists": false } },
-                doc! { "$set": bson_doc },
-            )
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .outbox
+            .update_one(events, &self.collection, filter, update)
+            .await?;
 
         Ok(result.matched_count > 0)
     }
@@ -158,20 +206,19 @@ impl UserRepositoryPort for UserRepository {
     // ===== SOFT DELETE =====
 
     #[tracing::instrument(skip_all)]
-    async fn delete(&self, id: &UserId) -> DomainResult<bool> {
+    async fn delete(&self, id: &UserId, events: &[DomainEvent]) -> DomainResult<bool> {
         let oid =
             ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "User", &**id))?;
 
         let now = mongodb::bson::DateTime::from_chrono(chrono::Utc::now());
 
+        let filter = doc! { "_id": oid, "deleted_at": { "$exists": false } };
+        let update = doc! { "$set": { "deleted_at": now }, "$inc": { "version": 1 } };
+
         let result = self
-            .collection
-            .update_one(
-                doc! { "_id": oid, "deleted_at": { "$exists": false } },
-                doc! { "$set": { "deleted_at": now } },
-            )
-            .await
-            .map_err(|e| Error::database(e.to_string()))?;
+            .outbox
+            .update_one(events, &self.collection, filter, update)
+            .await?;
 
         Ok(result.matched_count > 0)
     }
@@ -0,0 +1,146 @@
+use crate::domain::entities::webhook::{
+    DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookDeliveryId, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use mongodb::bson::{self, oid::ObjectId};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookSubscriptionDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_at: bson::DateTime,
+    pub updated_at: bson::DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<bson::DateTime>,
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionDocument {
+    fn from(entity: WebhookSubscription) -> Self {
+        Self {
+            id: entity
+                .id
+                .and_then(|id| ObjectId::parse_str(id.into_inner()).ok()),
+            url: entity.url,
+            secret: entity.secret,
+            events: entity.events,
+            active: entity.active,
+            description: entity.description,
+            created_at: bson::DateTime::from_chrono(entity.created_at),
+            updated_at: bson::DateTime::from_chrono(entity.updated_at),
+            deleted_at: entity.deleted_at.map(bson::DateTime::from_chrono),
+        }
+    }
+}
+
+impl From<WebhookSubscriptionDocument> for WebhookSubscription {
+    fn from(doc: WebhookSubscriptionDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| WebhookSubscriptionId::new(oid.to_hex())),
+            url: doc.url,
+            secret: doc.secret,
+            events: doc.events,
+            active: doc.active,
+            description: doc.description,
+            created_at: doc.created_at.to_chrono(),
+            updated_at: doc.updated_at.to_chrono(),
+            deleted_at: doc.deleted_at.map(|dt| dt.to_chrono()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeliveryAttemptDocument {
+    pub attempted_at: bson::DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: i64,
+}
+
+impl From<&DeliveryAttempt> for DeliveryAttemptDocument {
+    fn from(attempt: &DeliveryAttempt) -> Self {
+        Self {
+            attempted_at: bson::DateTime::from_chrono(attempt.attempted_at),
+            status_code: attempt.status_code.map(i32::from),
+            error: attempt.error.clone(),
+            duration_ms: i64::try_from(attempt.duration_ms).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+impl From<DeliveryAttemptDocument> for DeliveryAttempt {
+    fn from(doc: DeliveryAttemptDocument) -> Self {
+        Self {
+            attempted_at: doc.attempted_at.to_chrono(),
+            status_code: doc.status_code.and_then(|code| u16::try_from(code).ok()),
+            error: doc.error,
+            duration_ms: u64::try_from(doc.duration_ms).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDeliveryDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub subscription_id: ObjectId,
+    pub event_id: String,
+    pub event_type: String,
+    pub body: String,
+    pub status: DeliveryStatus,
+    #[serde(default)]
+    pub attempts: Vec<DeliveryAttemptDocument>,
+    pub created_at: bson::DateTime,
+    pub updated_at: bson::DateTime,
+}
+
+impl TryFrom<WebhookDelivery> for WebhookDeliveryDocument {
+    type Error = String;
+
+    fn try_from(delivery: WebhookDelivery) -> Result<Self, Self::Error> {
+        let subscription_id = ObjectId::parse_str(&*delivery.subscription_id).map_err(|_| {
+            format!(
+                "Invalid Webhook Subscription ID format: {}",
+                delivery.subscription_id
+            )
+        })?;
+
+        Ok(Self {
+            id: delivery
+                .id
+                .and_then(|id| ObjectId::parse_str(id.into_inner()).ok()),
+            subscription_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            body: delivery.body,
+            status: delivery.status,
+            attempts: delivery.attempts.iter().map(Into::into).collect(),
+            created_at: bson::DateTime::from_chrono(delivery.created_at),
+            updated_at: bson::DateTime::from_chrono(delivery.updated_at),
+        })
+    }
+}
+
+impl From<WebhookDeliveryDocument> for WebhookDelivery {
+    fn from(doc: WebhookDeliveryDocument) -> Self {
+        Self {
+            id: doc.id.map(|oid| WebhookDeliveryId::new(oid.to_hex())),
+            subscription_id: WebhookSubscriptionId::new(doc.subscription_id.to_hex()),
+            event_id: doc.event_id,
+            event_type: doc.event_type,
+            body: doc.body,
+            status: doc.status,
+            attempts: doc.attempts.into_iter().map(Into::into).collect(),
+            created_at: doc.created_at.to_chrono(),
+            updated_at: doc.updated_at.to_chrono(),
+        }
+    }
+}
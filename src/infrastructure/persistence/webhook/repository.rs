@@ -0,0 +1,453 @@
+use crate::domain::entities::webhook::{
+    ALL_EVENTS, DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookDeliveryId,
+    WebhookSubscription, WebhookSubscriptionId,
+};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::pagination::Pagination;
+use crate::domain::port::webhook::{WebhookDeliveryRepositoryPort, WebhookSubscriptionRepositoryPort};
+use crate::infrastructure::persistence::is_duplicate_key;
+use crate::infrastructure::persistence::webhook::model::{
+    DeliveryAttemptDocument, WebhookDeliveryDocument, WebhookSubscriptionDocument,
+};
+use async_trait::async_trait;
+use futures::stream::TryStreamExt;
+use mongodb::{
+    Collection, Database, IndexModel,
+    bson::{self, doc, oid::ObjectId},
+    options::{IndexOptions, ReturnDocument},
+};
+use std::time::Duration;
+
+pub const WEBHOOK_SUBSCRIPTIONS_COLLECTION: &str = "webhook_subscriptions";
+pub const WEBHOOK_DELIVERIES_COLLECTION: &str = "webhook_deliveries";
+
+/// Delivery log entries expire this long after the event was first dispatched.
+const DELIVERY_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+#[derive(Clone)]
+pub struct WebhookSubscriptionRepository {
+    collection: Collection<WebhookSubscriptionDocument>,
+}
+
+impl WebhookSubscriptionRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(WEBHOOK_SUBSCRIPTIONS_COLLECTION),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! { "deleted_at": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("deleted_created_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "deleted_at": 1, "active": 1, "events": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("deleted_active_events_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Webhook subscription indexes created");
+        Ok(())
+    }
+
+    fn parse_id(id: &WebhookSubscriptionId) -> DomainResult<ObjectId> {
+        ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("id", "WebhookSubscription", &**id))
+    }
+}
+
+#[async_trait]
+impl WebhookSubscriptionRepositoryPort for WebhookSubscriptionRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<WebhookSubscriptionId> {
+        let doc = WebhookSubscriptionDocument::from(subscription.clone());
+        let result = self
+            .collection
+            .insert_one(doc)
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        result
+            .inserted_id
+            .as_object_id()
+            .map(|oid| WebhookSubscriptionId::new(oid.to_hex()))
+            .ok_or_else(|| Error::internal("Failed to get inserted ID"))
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(
+        &self,
+        id: &WebhookSubscriptionId,
+    ) -> DomainResult<Option<WebhookSubscription>> {
+        let oid = Self::parse_id(id)?;
+
+        let doc = self
+            .collection
+            .find_one(doc! { "_id": oid, "deleted_at": { "$exists": false } })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(WebhookSubscription::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_all(&self, pagination: Pagination) -> DomainResult<Vec<WebhookSubscription>> {
+        let cursor = self
+            .collection
+            .find(doc! { "deleted_at": { "$exists": false } })
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<WebhookSubscriptionDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    #[tracing::instrument(skip_all, fields(%event_type))]
+    async fn find_for_event(&self, event_type: &str) -> DomainResult<Vec<WebhookSubscription>> {
+        let cursor = self
+            .collection
+            .find(doc! {
+                "deleted_at": { "$exists": false },
+                "active": true,
+                "events": { "$in": [event_type, ALL_EVENTS] }
+            })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<WebhookSubscriptionDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn update(
+        &self,
+        id: &WebhookSubscriptionId,
+        subscription: &WebhookSubscription,
+    ) -> DomainResult<bool> {
+        let oid = Self::parse_id(id)?;
+
+        let doc = WebhookSubscriptionDocument::from(subscription.clone());
+        let mut bson_doc =
+            bson::serialize_to_document(&doc).map_err(|e| Error::internal(e.to_string()))?;
+        bson_doc.remove("_id");
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                doc! { "$set": bson_doc },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    // ===== SOFT DELETE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn delete(&self, id: &WebhookSubscriptionId) -> DomainResult<bool> {
+        let oid = Self::parse_id(id)?;
+
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "deleted_at": { "$exists": false } },
+                doc! { "$set": { "deleted_at": now, "active": false } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    // ===== COUNT =====
+
+    #[tracing::instrument(skip_all)]
+    async fn count(&self) -> DomainResult<u64> {
+        self.collection
+            .count_documents(doc! { "deleted_at": { "$exists": false } })
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookDeliveryRepository {
+    collection: Collection<WebhookDeliveryDocument>,
+}
+
+impl WebhookDeliveryRepository {
+    pub fn new(db: &Database) -> Self {
+        Self {
+            collection: db.collection(WEBHOOK_DELIVERIES_COLLECTION),
+        }
+    }
+
+    /// Indexes created by [`Self::create_indexes`].
+    pub fn indexes() -> Vec<IndexModel> {
+        vec![
+            IndexModel::builder()
+                .keys(doc! { "subscription_id": 1, "event_id": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .unique(true)
+                        .name("subscription_event_unique_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "subscription_id": 1, "created_at": -1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("subscription_created_compound_idx".to_string())
+                        .build(),
+                )
+                .build(),
+            IndexModel::builder()
+                .keys(doc! { "created_at": 1 })
+                .options(
+                    IndexOptions::builder()
+                        .name("created_ttl_idx".to_string())
+                        .expire_after(DELIVERY_RETENTION)
+                        .build(),
+                )
+                .build(),
+        ]
+    }
+
+    /// Create database indexes (idempotent — safe to call on every startup)
+    pub async fn create_indexes(&self) -> DomainResult<()> {
+        self.collection
+            .create_indexes(Self::indexes())
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        tracing::info!("✓ Webhook delivery indexes created");
+        Ok(())
+    }
+
+    fn parse_id(id: &WebhookDeliveryId) -> DomainResult<ObjectId> {
+        ObjectId::parse_str(&**id).map_err(|_| Error::invalid_param("id", "WebhookDelivery", &**id))
+    }
+
+    fn parse_subscription_id(id: &WebhookSubscriptionId) -> DomainResult<ObjectId> {
+        ObjectId::parse_str(&**id)
+            .map_err(|_| Error::invalid_param("subscription_id", "WebhookSubscription", &**id))
+    }
+}
+
+#[async_trait]
+impl WebhookDeliveryRepositoryPort for WebhookDeliveryRepository {
+    // ===== CREATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn create_once(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> DomainResult<(WebhookDeliveryId, bool)> {
+        let doc = WebhookDeliveryDocument::try_from(delivery.clone()).map_err(Error::internal)?;
+        let filter = doc! { "subscription_id": doc.subscription_id, "event_id": &doc.event_id };
+
+        // The `_id` is chosen up front, so an insert knows its ID without a second read
+        let oid = ObjectId::new();
+        let mut insert =
+            bson::serialize_to_document(&doc).map_err(|e| Error::internal(e.to_string()))?;
+        insert.insert("_id", oid);
+
+        let previous = self
+            .collection
+            .find_one_and_update(filter.clone(), doc! { "$setOnInsert": insert })
+            .upsert(true)
+            .return_document(ReturnDocument::Before)
+            .await;
+
+        let existing = match previous {
+            Ok(None) => return Ok((WebhookDeliveryId::new(oid.to_hex()), true)),
+            Ok(existing) => existing,
+            // Two relays upserting the same event at once: the loser reads the winner's
+            Err(e) if is_duplicate_key(&e) => self
+                .collection
+                .find_one(filter)
+                .await
+                .map_err(|e| Error::database(e.to_string()))?,
+            Err(e) => return Err(Error::database(e.to_string())),
+        };
+
+        existing
+            .and_then(|doc| doc.id)
+            .map(|oid| (WebhookDeliveryId::new(oid.to_hex()), false))
+            .ok_or_else(|| Error::internal("Failed to get the existing delivery's ID"))
+    }
+
+    // ===== READ =====
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_id(&self, id: &WebhookDeliveryId) -> DomainResult<Option<WebhookDelivery>> {
+        let oid = Self::parse_id(id)?;
+
+        let doc = self
+            .collection
+            .find_one(doc! { "_id": oid })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(doc.map(WebhookDelivery::from))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn find_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        pagination: Pagination,
+    ) -> DomainResult<Vec<WebhookDelivery>> {
+        let oid = Self::parse_subscription_id(subscription_id)?;
+
+        let cursor = self
+            .collection
+            .find(doc! { "subscription_id": oid })
+            .skip(pagination.get_skip())
+            .limit(pagination.get_limit())
+            .sort(doc! { "created_at": -1 })
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        let docs: Vec<WebhookDeliveryDocument> = cursor
+            .try_collect()
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(docs.into_iter().map(WebhookDelivery::from).collect())
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn count_by_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> DomainResult<u64> {
+        let oid = Self::parse_subscription_id(subscription_id)?;
+
+        self.collection
+            .count_documents(doc! { "subscription_id": oid })
+            .await
+            .map_err(|e| Error::database(e.to_string()))
+    }
+
+    // ===== UPDATE =====
+
+    #[tracing::instrument(skip_all)]
+    async fn record_attempt(
+        &self,
+        id: &WebhookDeliveryId,
+        attempt: &DeliveryAttempt,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool> {
+        let oid = Self::parse_id(id)?;
+
+        let bson_attempt = bson::serialize_to_bson(&DeliveryAttemptDocument::from(attempt))
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+        let bson_status = bson::serialize_to_bson(&status)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid },
+                doc! {
+                    "$push": { "attempts": bson_attempt },
+                    "$set": { "status": bson_status, "updated_at": now }
+                },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_status(
+        &self,
+        id: &WebhookDeliveryId,
+        status: DeliveryStatus,
+    ) -> DomainResult<bool> {
+        let oid = Self::parse_id(id)?;
+
+        let bson_status = bson::serialize_to_bson(&status)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid },
+                doc! { "$set": { "status": bson_status, "updated_at": now } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.matched_count > 0)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn reopen(&self, id: &WebhookDeliveryId) -> DomainResult<bool> {
+        let oid = Self::parse_id(id)?;
+
+        let pending = bson::serialize_to_bson(&DeliveryStatus::Pending)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+        let now = bson::DateTime::from_chrono(chrono::Utc::now());
+
+        // Conditional on the status, so concurrent redeliveries queue one task
+        let result = self
+            .collection
+            .update_one(
+                doc! { "_id": oid, "status": { "$ne": &pending } },
+                doc! { "$set": { "status": pending, "updated_at": now } },
+            )
+            .await
+            .map_err(|e| Error::database(e.to_string()))?;
+
+        Ok(result.modified_count > 0)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::job::JobRun;
+use crate::domain::port::job_run::JobRunPort;
+use crate::infrastructure::providers::redis::RedisProvider;
+use async_trait::async_trait;
+
+/// Redis-backed [`JobRunPort`]: the `scheduler:runs` hash holds the last
+/// run of each job, as JSON by job name.
+#[derive(Clone)]
+pub struct JobRunProvider {
+    redis: RedisProvider,
+}
+
+impl JobRunProvider {
+    pub fn new(redis: RedisProvider) -> Self {
+        Self { redis }
+    }
+
+    fn key(&self) -> String {
+        self.redis.get_path(&["scheduler", "runs"])
+    }
+}
+
+#[async_trait]
+impl JobRunPort for JobRunProvider {
+    #[tracing::instrument(skip_all, fields(job = %run.job))]
+    async fn record(&self, run: &JobRun) -> DomainResult<()> {
+        let json = serde_json::to_string(run)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+
+        let mut conn = self.redis.connection();
+        redis::cmd("HSET")
+            .arg(self.key())
+            .arg(&run.job)
+            .arg(json)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))
+    }
+
+    #[tracing::instrument(skip_all, fields(%job))]
+    async fn last(&self, job: &str) -> DomainResult<Option<JobRun>> {
+        let mut conn = self.redis.connection();
+        let json: Option<String> = redis::cmd("HGET")
+            .arg(self.key())
+            .arg(job)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+
+        json.map(|json| {
+            serde_json::from_str(&json)
+                .map_err(|e| Error::internal(format!("Deserialization error: {}", e)))
+        })
+        .transpose()
+    }
+}
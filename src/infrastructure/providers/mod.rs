@@ -1,4 +1,7 @@
+pub mod job_run;
 pub mod mongo;
+pub mod postgres;
+pub mod propagation;
 pub mod redis;
 pub mod tasks;
 pub mod telemetry;
@@ -1,4 +1,19 @@
-use mongodb::{Client, Database, options::ClientOptions};
+use crate::config::{self, MongoConfig};
+use crate::domain::port::metrics::{Datastore, MetricsPort};
+use mongodb::event::EventHandler;
+use mongodb::event::cmap::CmapEvent;
+use mongodb::event::sdam::SdamEvent;
+use mongodb::options::{
+    Acknowledgment, ClientOptions, Compressor, ReadConcern, ReadPreference, SelectionCriteria,
+};
+use mongodb::{Client, Database};
+use std::future::IntoFuture;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+
+/// Longest wait between two startup pings.
+const MAX_CONNECT_DELAY: Duration = Duration::from_secs(30);
 
 #[derive(Clone)]
 pub struct MongoProvider {
@@ -6,28 +21,218 @@ pub struct MongoProvider {
 }
 
 impl MongoProvider {
-    pub async fn new(app_name: &str, mongo_url: &str, mongo_db: &str) -> Self {
-        let mut client_options = ClientOptions::parse(mongo_url)
-            .await
-            .expect("Failed to parse MongoDB URI");
-
+    /// Builds the client from `config` and pings MongoDB, retrying with
+    /// backoff so that a failover during a deploy does not fail startup.
+    pub async fn new(
+        app_name: &str,
+        config: &MongoConfig,
+        metrics: Arc<dyn MetricsPort>,
+    ) -> Result<Self, mongodb::error::Error> {
+        let mut client_options = ClientOptions::parse(config.url.expose()).await?;
         client_options.app_name = Some(app_name.to_string());
+        apply(&mut client_options, config);
+        client_options.cmap_event_handler = Some(pool_events(metrics));
+        client_options.sdam_event_handler = Some(topology_events());
 
-        let client =
-            Client::with_options(client_options).expect("Failed to initialize MongoDB client");
-
-        let db = client.database(mongo_db);
+        let client = Client::with_options(client_options)?;
+        let db = client.database(&config.db);
 
-        db.run_command(bson::doc! {"ping": 1})
-            .await
-            .expect("Failed to ping MongoDB");
+        let mut attempt = 1;
+        loop {
+            let ping = db
+                .run_command(bson::doc! {"ping": 1})
+                .into_future()
+                .instrument(tracing::info_span!("mongo_ping", attempt));
+            match ping.await {
+                Ok(_) => break,
+                Err(e) if attempt < config.connect_attempts => {
+                    let delay = connect_delay(config.connect_backoff_ms, attempt);
+                    tracing::warn!(
+                        "MongoDB ping failed (attempt {}/{}), retrying in {:?}: {}",
+                        attempt,
+                        config.connect_attempts,
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
 
-        tracing::info!("Connected to MongoDB: {}", mongo_db);
+        tracing::info!("Connected to MongoDB: {}", config.db);
 
-        Self { db }
+        Ok(Self { db })
     }
 
     pub fn get_database(&self) -> Database {
         self.db.clone()
     }
+
+    /// Multi-document transactions need a replica set or a sharded cluster.
+    pub async fn supports_transactions(&self) -> bool {
+        match self.db.run_command(bson::doc! {"hello": 1}).await {
+            Ok(reply) => {
+                reply.contains_key("setName") || reply.get_str("msg").is_ok_and(|m| m == "isdbgrid")
+            }
+            Err(e) => {
+                tracing::warn!("Failed to detect MongoDB topology: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Settings from `config` override the URL's; unset ones leave it alone.
+fn apply(options: &mut ClientOptions, config: &MongoConfig) {
+    options.max_pool_size = config.max_pool_size.or(options.max_pool_size);
+    options.min_pool_size = config.min_pool_size.or(options.min_pool_size);
+    if let Some(ms) = config.connect_timeout_ms {
+        options.connect_timeout = Some(Duration::from_millis(ms));
+    }
+    if let Some(ms) = config.server_selection_timeout_ms {
+        options.server_selection_timeout = Some(Duration::from_millis(ms));
+    }
+    if let Some(preference) = config.read_preference {
+        let preference = match preference {
+            config::ReadPreference::Primary => ReadPreference::Primary,
+            config::ReadPreference::PrimaryPreferred => {
+                ReadPreference::PrimaryPreferred { options: None }
+            }
+            config::ReadPreference::Secondary => ReadPreference::Secondary { options: None },
+            config::ReadPreference::SecondaryPreferred => {
+                ReadPreference::SecondaryPreferred { options: None }
+            }
+            config::ReadPreference::Nearest => ReadPreference::Nearest { options: None },
+        };
+        options.selection_criteria = Some(SelectionCriteria::ReadPreference(preference));
+    }
+    if let Some(level) = config.read_concern {
+        options.read_concern = Some(match level {
+            config::ReadConcern::Local => ReadConcern::local(),
+            config::ReadConcern::Available => ReadConcern::available(),
+            config::ReadConcern::Majority => ReadConcern::majority(),
+            config::ReadConcern::Linearizable => ReadConcern::linearizable(),
+        });
+    }
+    if let Some(w) = config.write_concern {
+        // Keeps the journal and timeout options of a write concern in the URL
+        let mut concern = options.write_concern.take().unwrap_or_default();
+        concern.w = Some(match w {
+            config::WriteConcern::Majority => Acknowledgment::Majority,
+            config::WriteConcern::Nodes(n) => Acknowledgment::Nodes(n),
+        });
+        options.write_concern = Some(concern);
+    }
+    options.retry_writes = config.retry_writes.or(options.retry_writes);
+    if !config.compressors.is_empty() {
+        options.compressors = Some(
+            config
+                .compressors
+                .iter()
+                .map(|compressor| match compressor {
+                    config::Compressor::Zstd => Compressor::Zstd { level: None },
+                    config::Compressor::Zlib => Compressor::Zlib { level: None },
+                    config::Compressor::Snappy => Compressor::Snappy,
+                })
+                .collect(),
+        );
+    }
+}
+
+fn connect_delay(backoff_ms: u64, attempt: u64) -> Duration {
+    Duration::from_millis(backoff_ms)
+        .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1) as u32))
+        .min(MAX_CONNECT_DELAY)
+}
+
+/// Logs connection pool events and counts them in the pool event metric.
+/// Checkouts are too frequent to log; their wait time is recorded as the
+/// `pool.checkout` datastore operation instead.
+fn pool_events(metrics: Arc<dyn MetricsPort>) -> EventHandler<CmapEvent> {
+    EventHandler::callback(move |event: CmapEvent| {
+        let name = match event {
+            CmapEvent::PoolCreated(e) => {
+                tracing::debug!(address = %e.address, "MongoDB pool created");
+                "pool_created"
+            }
+            CmapEvent::PoolReady(e) => {
+                tracing::debug!(address = %e.address, "MongoDB pool ready");
+                "pool_ready"
+            }
+            CmapEvent::PoolCleared(e) => {
+                tracing::warn!(address = %e.address, "MongoDB pool cleared");
+                "pool_cleared"
+            }
+            CmapEvent::PoolClosed(e) => {
+                tracing::debug!(address = %e.address, "MongoDB pool closed");
+                "pool_closed"
+            }
+            CmapEvent::ConnectionCreated(e) => {
+                tracing::debug!(
+                    address = %e.address,
+                    connection_id = e.connection_id,
+                    "MongoDB connection created"
+                );
+                "connection_created"
+            }
+            CmapEvent::ConnectionReady(e) => {
+                metrics.record_datastore_call(Datastore::Mongo, "pool.connect", e.duration);
+                "connection_ready"
+            }
+            CmapEvent::ConnectionClosed(e) => {
+                tracing::debug!(
+                    address = %e.address,
+                    connection_id = e.connection_id,
+                    reason = ?e.reason,
+                    "MongoDB connection closed"
+                );
+                "connection_closed"
+            }
+            CmapEvent::ConnectionCheckoutFailed(e) => {
+                tracing::warn!(
+                    address = %e.address,
+                    reason = ?e.reason,
+                    waited_ms = e.duration.as_millis() as u64,
+                    "MongoDB connection checkout failed"
+                );
+                "checkout_failed"
+            }
+            CmapEvent::ConnectionCheckedOut(e) => {
+                metrics.record_datastore_call(Datastore::Mongo, "pool.checkout", e.duration);
+                return;
+            }
+            // Checkout started, checked in
+            _ => return,
+        };
+        metrics.record_pool_event(Datastore::Mongo, name);
+    })
+}
+
+/// Logs what a failover looks like from the client: members changing role
+/// and failed heartbeats.
+fn topology_events() -> EventHandler<SdamEvent> {
+    EventHandler::callback(|event: SdamEvent| match event {
+        SdamEvent::ServerDescriptionChanged(e) => {
+            let before = e.previous_description.server_type();
+            let after = e.new_description.server_type();
+            if before != after {
+                tracing::info!(
+                    address = %e.address,
+                    from = %before,
+                    to = %after,
+                    "MongoDB server changed role"
+                );
+            }
+        }
+        SdamEvent::ServerHeartbeatFailed(e) => {
+            tracing::warn!(
+                address = %e.server_address,
+                "MongoDB heartbeat failed: {}",
+                e.failure
+            );
+        }
+        _ => {}
+    })
 }
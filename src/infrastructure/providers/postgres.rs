@@ -0,0 +1,25 @@
+use sqlx::PgPool;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::str::FromStr;
+
+#[derive(Clone)]
+pub struct PostgresProvider {
+    pool: PgPool,
+}
+
+impl PostgresProvider {
+    pub async fn new(app_name: &str, postgres_url: &str) -> Result<Self, sqlx::Error> {
+        let options = PgConnectOptions::from_str(postgres_url)?.application_name(app_name);
+
+        // Connects eagerly, so a bad URL or an unreachable server fails startup
+        let pool = PgPoolOptions::new().connect_with(options).await?;
+
+        tracing::info!("Connected to PostgreSQL");
+
+        Ok(Self { pool })
+    }
+
+    pub fn get_pool(&self) -> PgPool {
+        self.pool.clone()
+    }
+}
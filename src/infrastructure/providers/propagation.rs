@@ -0,0 +1,109 @@
+use crate::domain::port::trace_context::{TRACEPARENT, TraceContextPort};
+use opentelemetry::Context;
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Google Cloud load balancer header: `TRACE_ID/SPAN_ID;o=OPTIONS`, with a
+/// decimal span ID.
+pub const CLOUD_TRACE_CONTEXT: &str = "x-cloud-trace-context";
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Remote parent carried by `traceparent`, or by `X-Cloud-Trace-Context`
+/// when the former is absent or invalid.
+pub fn extract(headers: &HeaderMap) -> Option<Context> {
+    let context = TraceContextPropagator::new().extract(&HeaderExtractor(headers));
+    if context.span().span_context().is_valid() {
+        return Some(context);
+    }
+
+    let value = headers.get(CLOUD_TRACE_CONTEXT)?.to_str().ok()?;
+    parse_cloud_trace_context(value)
+        .map(|span_context| Context::new().with_remote_span_context(span_context))
+}
+
+fn parse_cloud_trace_context(value: &str) -> Option<SpanContext> {
+    let (trace_id, rest) = value.split_once('/')?;
+    let (span_id, options) = match rest.split_once(';') {
+        Some((span_id, options)) => (span_id, Some(options)),
+        None => (rest, None),
+    };
+
+    let trace_id = TraceId::from_hex(trace_id).ok()?;
+    let span_id = SpanId::from_bytes(span_id.parse::<u64>().ok()?.to_be_bytes());
+    let flags = if options == Some("o=1") {
+        TraceFlags::SAMPLED
+    } else {
+        TraceFlags::default()
+    };
+
+    let span_context = SpanContext::new(trace_id, span_id, flags, true, TraceState::default());
+    span_context.is_valid().then_some(span_context)
+}
+
+/// `traceparent` value identifying `span`, if it belongs to a trace.
+pub fn traceparent(span: &tracing::Span) -> Option<String> {
+    let context = span.context();
+    let otel_span = context.span();
+    let span_context = otel_span.span_context();
+    if !span_context.is_valid() {
+        return None;
+    }
+
+    Some(format!(
+        "00-{}-{}-{:02x}",
+        span_context.trace_id(),
+        span_context.span_id(),
+        span_context.trace_flags().to_u8()
+    ))
+}
+
+/// Headers that continue the current trace in an outgoing request.
+pub fn outgoing_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = traceparent(&tracing::Span::current())
+        && let Ok(value) = HeaderValue::from_str(&value)
+    {
+        headers.insert(TRACEPARENT, value);
+    }
+    headers
+}
+
+/// [`TraceContextPort`] over the OpenTelemetry tracing layer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OtelTraceContext;
+
+impl TraceContextPort for OtelTraceContext {
+    fn current(&self) -> Option<String> {
+        traceparent(&tracing::Span::current())
+    }
+
+    fn continue_trace(&self, span: &tracing::Span, carrier: &[(&str, &str)]) {
+        let headers: HeaderMap = carrier
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+
+        if let Some(parent) = extract(&headers) {
+            let _ = span.set_parent(parent);
+        }
+    }
+}
@@ -1,5 +1,49 @@
 #![allow(dead_code)]
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::port::lock::{Lease, LockPort};
+use async_trait::async_trait;
+use redis::Script;
 use redis::aio::MultiplexedConnection;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// `SET NX PX` and, when it took the lock, the next fencing token; `0` when
+/// the lock is held.
+static ACQUIRE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+        if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+            return redis.call('INCR', KEYS[2])
+        end
+        return 0
+        "#,
+    )
+});
+
+/// Extends the lease only if the caller still owns it.
+static RENEW: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+        if redis.call('GET', KEYS[1]) == ARGV[1] then
+            return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+        end
+        return 0
+        "#,
+    )
+});
+
+/// Deletes the lock only if the caller still owns it, so a holder whose
+/// lease lapsed cannot free the next holder's lock.
+static RELEASE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+        if redis.call('GET', KEYS[1]) == ARGV[1] then
+            return redis.call('DEL', KEYS[1])
+        end
+        return 0
+        "#,
+    )
+});
 
 #[derive(Clone)]
 pub struct RedisProvider {
@@ -36,3 +80,72 @@ impl RedisProvider {
         format!("{}:{}", self.prefix, key.join(":"))
     }
 }
+
+/// Redis-backed [`LockPort`].
+///
+/// - `lock:{name}` (string, PX expiry): owner of the current lease
+/// - `lock:{name}:fence` (counter, no expiry): last fencing token handed out
+#[derive(Clone)]
+pub struct RedisLock {
+    redis: RedisProvider,
+}
+
+impl RedisLock {
+    pub fn new(redis: RedisProvider) -> Self {
+        Self { redis }
+    }
+
+    fn key(&self, name: &str) -> String {
+        self.redis.get_path(&["lock", name])
+    }
+}
+
+#[async_trait]
+impl LockPort for RedisLock {
+    #[tracing::instrument(skip_all, fields(%name))]
+    async fn try_acquire(&self, name: &str, ttl: Duration) -> DomainResult<Option<Lease>> {
+        let owner = uuid::Uuid::new_v4().to_string();
+        let key = self.key(name);
+
+        let mut conn = self.redis.connection();
+        let token: u64 = ACQUIRE
+            .key(&key)
+            .key(format!("{}:fence", key))
+            .arg(&owner)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+
+        Ok((token > 0).then(|| Lease {
+            name: name.to_string(),
+            owner,
+            token,
+        }))
+    }
+
+    #[tracing::instrument(skip_all, fields(name = %lease.name))]
+    async fn renew(&self, lease: &Lease, ttl: Duration) -> DomainResult<bool> {
+        let mut conn = self.redis.connection();
+        let renewed: u8 = RENEW
+            .key(self.key(&lease.name))
+            .arg(&lease.owner)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+        Ok(renewed == 1)
+    }
+
+    #[tracing::instrument(skip_all, fields(name = %lease.name))]
+    async fn release(&self, lease: &Lease) -> DomainResult<bool> {
+        let mut conn = self.redis.connection();
+        let released: u8 = RELEASE
+            .key(self.key(&lease.name))
+            .arg(&lease.owner)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+        Ok(released == 1)
+    }
+}
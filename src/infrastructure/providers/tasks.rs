@@ -1,11 +1,332 @@
-// Placeholder for Cloud Tasks or similar
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::port::task_queue::TaskQueuePort;
+use crate::domain::task::{NewTask, Task, TaskId};
+use crate::infrastructure::providers::propagation;
+use crate::infrastructure::providers::redis::RedisProvider;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use redis::streams::{StreamAutoClaimReply, StreamId, StreamReadReply};
+use std::sync::LazyLock;
+use std::time::Duration;
 
-#[allow(dead_code)]
-pub struct TaskProvider {}
+const CONSUMER_GROUP: &str = "workers";
+
+/// A reserved task not completed within this long is handed to another worker.
+const VISIBILITY_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Due tasks moved from the schedule to the ready stream per reservation.
+const PROMOTE_BATCH_SIZE: usize = 100;
+
+/// Approximate number of dead-lettered tasks kept (`XADD ... MAXLEN ~`).
+const DEAD_LETTER_MAX_LEN: usize = 10_000;
+
+/// Atomically moves due task IDs from the schedule to the ready stream, so
+/// concurrent workers never promote the same task twice.
+static PROMOTE_DUE: LazyLock<redis::Script> = LazyLock::new(|| {
+    redis::Script::new(
+        r#"
+        local due = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1], 'LIMIT', 0, ARGV[2])
+        for _, id in ipairs(due) do
+            redis.call('ZREM', KEYS[1], id)
+            redis.call('XADD', KEYS[2], '*', 'task_id', id)
+        end
+        return #due
+        "#,
+    )
+});
+
+/// Redis-backed [`TaskQueuePort`].
+///
+/// - `tasks:data` (hash): task body by ID
+/// - `tasks:scheduled` (sorted set): delayed and retrying task IDs, scored by due time (ms)
+/// - `tasks:ready` (stream, consumer group `workers`): IDs of tasks due now
+/// - `tasks:dead` (stream): tasks that exhausted their attempts
+#[derive(Clone)]
+pub struct TaskProvider {
+    redis: RedisProvider,
+}
 
 impl TaskProvider {
-    #[allow(dead_code)]
-    pub async fn new() -> Self {
-        Self {}
+    pub async fn new(redis: RedisProvider) -> Result<Self, redis::RedisError> {
+        let provider = Self { redis };
+
+        let mut conn = provider.redis.connection();
+        let created = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(provider.key("ready"))
+            .arg(CONSUMER_GROUP)
+            .arg("0")
+            .arg("MKSTREAM")
+            .query_async::<()>(&mut conn)
+            .await;
+        match created {
+            Err(e) if e.code() != Some("BUSYGROUP") => return Err(e),
+            _ => {}
+        }
+
+        Ok(provider)
+    }
+
+    fn key(&self, name: &str) -> String {
+        self.redis.get_path(&["tasks", name])
     }
+
+    fn serialize(task: &Task) -> DomainResult<String> {
+        serde_json::to_string(task)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))
+    }
+
+    async fn promote_due(&self, conn: &mut redis::aio::MultiplexedConnection) -> DomainResult<()> {
+        PROMOTE_DUE
+            .key(self.key("scheduled"))
+            .key(self.key("ready"))
+            .arg(Utc::now().timestamp_millis())
+            .arg(PROMOTE_BATCH_SIZE)
+            .invoke_async::<i64>(conn)
+            .await
+            .map_err(redis_error)?;
+        Ok(())
+    }
+
+    /// An entry whose worker died without completing it, if any has timed out.
+    async fn claim_abandoned(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        worker: &str,
+    ) -> DomainResult<Option<StreamId>> {
+        let reply: StreamAutoClaimReply = redis::cmd("XAUTOCLAIM")
+            .arg(self.key("ready"))
+            .arg(CONSUMER_GROUP)
+            .arg(worker)
+            .arg(VISIBILITY_TIMEOUT.as_millis() as u64)
+            .arg("0-0")
+            .arg("COUNT")
+            .arg(1)
+            .query_async(conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(reply.claimed.into_iter().next())
+    }
+
+    async fn read_new(
+        &self,
+        conn: &mut redis::aio::MultiplexedConnection,
+        worker: &str,
+    ) -> DomainResult<Option<StreamId>> {
+        let reply: Option<StreamReadReply> = redis::cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg(CONSUMER_GROUP)
+            .arg(worker)
+            .arg("COUNT")
+            .arg(1)
+            .arg("STREAMS")
+            .arg(self.key("ready"))
+            .arg(">")
+            .query_async(conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(reply
+            .and_then(|reply| reply.keys.into_iter().next())
+            .and_then(|key| key.ids.into_iter().next()))
+    }
+
+    /// Acknowledges and removes the task's entry from the ready stream.
+    fn release(&self, pipe: &mut redis::Pipeline, receipt: &str) {
+        let ready = self.key("ready");
+        pipe.cmd("XACK")
+            .arg(&ready)
+            .arg(CONSUMER_GROUP)
+            .arg(receipt)
+            .ignore()
+            .cmd("XDEL")
+            .arg(&ready)
+            .arg(receipt)
+            .ignore();
+    }
+}
+
+#[async_trait]
+impl TaskQueuePort for TaskProvider {
+    #[tracing::instrument(skip_all, fields(kind = %task.kind))]
+    async fn enqueue(&self, task: NewTask) -> DomainResult<TaskId> {
+        let task_id = TaskId::new(uuid::Uuid::new_v4().to_string());
+        let body = Self::serialize(&Task {
+            id: task_id.clone(),
+            kind: task.kind,
+            payload: task.payload,
+            attempt: 0,
+            max_attempts: task.max_attempts,
+            enqueued_at: Utc::now(),
+            last_error: None,
+            trace_parent: propagation::traceparent(&tracing::Span::current()),
+            receipt: String::new(),
+        })?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .cmd("HSET")
+            .arg(self.key("data"))
+            .arg(&*task_id)
+            .arg(body)
+            .ignore();
+        if task.delay.is_zero() {
+            pipe.cmd("XADD")
+                .arg(self.key("ready"))
+                .arg("*")
+                .arg("task_id")
+                .arg(&*task_id)
+                .ignore();
+        } else {
+            let due = Utc::now() + chrono::Duration::from_std(task.delay).unwrap_or_default();
+            pipe.cmd("ZADD")
+                .arg(self.key("scheduled"))
+                .arg(due.timestamp_millis())
+                .arg(&*task_id)
+                .ignore();
+        }
+
+        let mut conn = self.redis.connection();
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(task_id)
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn reserve(&self, worker: &str) -> DomainResult<Option<Task>> {
+        let mut conn = self.redis.connection();
+        self.promote_due(&mut conn).await?;
+
+        let entry = match self.claim_abandoned(&mut conn, worker).await? {
+            Some(entry) => entry,
+            None => match self.read_new(&mut conn, worker).await? {
+                Some(entry) => entry,
+                None => return Ok(None),
+            },
+        };
+
+        let Some(task_id) = entry.get::<String>("task_id") else {
+            tracing::warn!(entry = %entry.id, "Dropping malformed ready entry");
+            let mut pipe = redis::pipe();
+            self.release(&mut pipe, &entry.id);
+            pipe.query_async::<()>(&mut conn)
+                .await
+                .map_err(redis_error)?;
+            return Ok(None);
+        };
+
+        let body: Option<String> = redis::cmd("HGET")
+            .arg(self.key("data"))
+            .arg(&task_id)
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        let Some(body) = body else {
+            // Completed by a worker whose claim had already expired
+            let mut pipe = redis::pipe();
+            self.release(&mut pipe, &entry.id);
+            pipe.query_async::<()>(&mut conn)
+                .await
+                .map_err(redis_error)?;
+            return Ok(None);
+        };
+
+        let mut task: Task = serde_json::from_str(&body)
+            .map_err(|e| Error::internal(format!("Invalid task {}: {}", task_id, e)))?;
+        task.attempt += 1;
+        task.receipt = entry.id;
+
+        // Persist the attempt so a crashing handler still runs out of attempts
+        redis::cmd("HSET")
+            .arg(self.key("data"))
+            .arg(&task_id)
+            .arg(Self::serialize(&task)?)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(Some(task))
+    }
+
+    #[tracing::instrument(skip_all, fields(task_id = %task.id))]
+    async fn complete(&self, task: &Task) -> DomainResult<()> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.release(&mut pipe, &task.receipt);
+        pipe.cmd("HDEL")
+            .arg(self.key("data"))
+            .arg(&*task.id)
+            .ignore();
+
+        let mut conn = self.redis.connection();
+        pipe.query_async::<()>(&mut conn).await.map_err(redis_error)
+    }
+
+    #[tracing::instrument(skip_all, fields(task_id = %task.id))]
+    async fn retry(&self, task: &Task, error: &str, retry_at: DateTime<Utc>) -> DomainResult<()> {
+        let mut retried = task.clone();
+        retried.last_error = Some(error.to_string());
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.release(&mut pipe, &task.receipt);
+        pipe.cmd("HSET")
+            .arg(self.key("data"))
+            .arg(&*task.id)
+            .arg(Self::serialize(&retried)?)
+            .ignore()
+            .cmd("ZADD")
+            .arg(self.key("scheduled"))
+            .arg(retry_at.timestamp_millis())
+            .arg(&*task.id)
+            .ignore();
+
+        let mut conn = self.redis.connection();
+        pipe.query_async::<()>(&mut conn).await.map_err(redis_error)
+    }
+
+    #[tracing::instrument(skip_all, fields(task_id = %task.id))]
+    async fn dead_letter(&self, task: &Task, error: &str) -> DomainResult<()> {
+        let payload = serde_json::to_string(&task.payload)
+            .map_err(|e| Error::internal(format!("Serialization error: {}", e)))?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.release(&mut pipe, &task.receipt);
+        pipe.cmd("HDEL")
+            .arg(self.key("data"))
+            .arg(&*task.id)
+            .ignore()
+            .cmd("XADD")
+            .arg(self.key("dead"))
+            .arg("MAXLEN")
+            .arg("~")
+            .arg(DEAD_LETTER_MAX_LEN)
+            .arg("*")
+            .arg("task_id")
+            .arg(&*task.id)
+            .arg("kind")
+            .arg(&task.kind)
+            .arg("payload")
+            .arg(payload)
+            .arg("attempts")
+            .arg(task.attempt)
+            .arg("error")
+            .arg(error)
+            .arg("enqueued_at")
+            .arg(task.enqueued_at.to_rfc3339())
+            .arg("failed_at")
+            .arg(Utc::now().to_rfc3339())
+            .ignore();
+
+        let mut conn = self.redis.connection();
+        pipe.query_async::<()>(&mut conn).await.map_err(redis_error)
+    }
+}
+
+fn redis_error(e: redis::RedisError) -> Error {
+    Error::external("Redis", e.to_string())
 }
@@ -1,70 +1,217 @@
+use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_gcloud_trace::GcpCloudTraceExporterBuilder;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
 use tracing_stackdriver::CloudTraceConfiguration;
-use tracing_subscriber::{EnvFilter, layer::SubscriberExt};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::{EnvFilter, Layer, Registry, filter, layer::SubscriberExt};
 
-use crate::config;
+use crate::config::{self, LogFormat, OtlpProtocol, TracingExporter};
+use crate::domain::port::metrics::MetricsPort;
+use crate::infrastructure::metrics::datastore_layer::DatastoreMetricsLayer;
+use std::sync::Arc;
 
-pub async fn init_tracing() -> anyhow::Result<()> {
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Flushes pending spans when dropped; keep it alive until the process exits.
+pub struct TelemetryGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take()
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to flush traces: {}", e);
+        }
+    }
+}
+
+/// Installs the global subscriber: the `LOG_FORMAT` layer for events, the
+/// `TRACING_EXPORTER` layer for spans and the datastore metrics layer.
+///
+/// `DEBUG_LEVEL` filters the first two only; the metrics layer has its own
+/// filter on the datastore modules, so quieter logs keep the metrics.
+///
+/// Never fails. If the trace exporter cannot be built, the subscriber is
+/// installed without it and the reason is logged.
+///
+/// Spans always get OpenTelemetry trace IDs, even when nothing is exported, so
+/// `traceparent` can be continued, echoed and propagated in every setup.
+pub async fn init_tracing(metrics: Arc<dyn MetricsPort>) -> TelemetryGuard {
     let config = config::get();
-    let service_name = &config.service_name;
-    let base_level = &config.debug_level;
-    let project_id = &config.project_id;
-    let app_env = &config.app_env;
-    let version = env!("CARGO_PKG_VERSION");
 
     let env_filter = EnvFilter::new(format!(
         "h2=warn,hyper=warn,tokio_util=warn,tower_http=warn,rig=warn,axum=warn,{}",
-        base_level
+        config.telemetry.debug_level
     ));
 
-    let stackdriver_layer = Some(tracing_stackdriver::layer().with_cloud_trace(
-        CloudTraceConfiguration {
-            project_id: project_id.clone(),
-        },
-    ));
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+    if let Some(layer) = log_layer(config.telemetry.log_format) {
+        layers.push(layer);
+    }
 
+    let (exporter, provider, exporter_error) =
+        match trace_layer(config.telemetry.tracing_exporter).await {
+            Ok((layer, provider)) => (layer, provider, None),
+            Err(e) => (None, None, Some(e)),
+        };
+    layers.extend(exporter);
+    let provider = match provider {
+        Some(provider) => provider,
+        None => {
+            let provider = SdkTracerProvider::builder()
+                .with_resource(resource())
+                .build();
+            layers.push(otel_layer(
+                provider.tracer(config.server.service_name.clone()),
+            ));
+            provider
+        }
+    };
+
+    let subscriber = tracing_subscriber::registry()
+        .with(layers.with_filter(env_filter))
+        .with(DatastoreMetricsLayer::new(metrics).with_filter(DatastoreMetricsLayer::targets()));
+    if let Err(e) = tracing::subscriber::set_global_default(subscriber) {
+        eprintln!("Setting default subscriber failed: {}", e);
+    }
+
+    match exporter_error {
+        Some(e) => tracing::warn!(
+            exporter = %config.telemetry.tracing_exporter,
+            "Trace export disabled: {:#}", e
+        ),
+        None => {
+            tracing::debug!(exporter = %config.telemetry.tracing_exporter, "Trace exporter installed")
+        }
+    }
+
+    TelemetryGuard {
+        provider: Some(provider),
+    }
+}
+
+/// Formats log events: `stackdriver` (Cloud Logging JSON), `json`, `pretty` or `none`.
+fn log_layer(format: LogFormat) -> Option<BoxedLayer> {
+    match format {
+        LogFormat::Stackdriver => Some(
+            tracing_stackdriver::layer()
+                .with_cloud_trace(CloudTraceConfiguration {
+                    project_id: config::get().telemetry.project_id.clone(),
+                })
+                .boxed(),
+        ),
+        LogFormat::Json => Some(
+            tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .boxed(),
+        ),
+        LogFormat::Pretty => Some(tracing_subscriber::fmt::layer().pretty().boxed()),
+        LogFormat::None => None,
+    }
+}
+
+/// Builds the span exporter layer: `gcp`, `otlp`, `stdout`, `json` or `none`.
+async fn trace_layer(
+    exporter: TracingExporter,
+) -> anyhow::Result<(Option<BoxedLayer>, Option<SdkTracerProvider>)> {
+    match exporter {
+        TracingExporter::Gcp => {
+            let (tracer, provider) = gcp_tracer().await?;
+            Ok((Some(otel_layer(tracer)), Some(provider)))
+        }
+        TracingExporter::Otlp => {
+            let provider = otlp_provider()?;
+            let tracer = provider.tracer(config::get().server.service_name.clone());
+            Ok((Some(otel_layer(tracer)), Some(provider)))
+        }
+        // Finished spans with their duration; events are left to LOG_FORMAT
+        TracingExporter::Stdout => Ok((
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .pretty()
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_filter(filter::filter_fn(|metadata| metadata.is_span()))
+                    .boxed(),
+            ),
+            None,
+        )),
+        TracingExporter::Json => Ok((
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_filter(filter::filter_fn(|metadata| metadata.is_span()))
+                    .boxed(),
+            ),
+            None,
+        )),
+        TracingExporter::None => Ok((None, None)),
+    }
+}
+
+fn otel_layer(tracer: Tracer) -> BoxedLayer {
+    tracing_opentelemetry::layer().with_tracer(tracer).boxed()
+}
+
+fn resource() -> opentelemetry_sdk::Resource {
+    let config = config::get();
+    opentelemetry_sdk::Resource::builder()
+        .with_attributes(vec![
+            opentelemetry::KeyValue::new("service.name", config.server.service_name.clone()),
+            opentelemetry::KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            opentelemetry::KeyValue::new("deployment.environment", config.server.app_env.clone()),
+            opentelemetry::KeyValue::new("project.id", config.telemetry.project_id.clone()),
+        ])
+        .build()
+}
+
+async fn gcp_tracer() -> anyhow::Result<(Tracer, SdkTracerProvider)> {
     let builder = GcpCloudTraceExporterBuilder::for_default_project_id()
         .await
-        .map_err(|e| {
-            tracing::warn!("Failed to initialize GCP exporter builder: {}", e);
-            e
-        })?
-        .with_resource(
-            opentelemetry_sdk::Resource::builder()
-                .with_attributes(vec![
-                    opentelemetry::KeyValue::new("service.name", service_name.to_string()),
-                    opentelemetry::KeyValue::new("service.version", version.to_string()),
-                    opentelemetry::KeyValue::new("deployment.environment", app_env.to_string()),
-                    opentelemetry::KeyValue::new("project.id", project_id.to_string()),
-                ])
-                .build(),
-        );
-
-    let provider = builder.create_provider().await.map_err(|e| {
-        let err = format!("Failed to create tracer provider: {}", e);
-        tracing::warn!("{}", err);
-        anyhow::anyhow!(err)
-    })?;
-
-    let tracer_provider: opentelemetry_sdk::trace::Tracer =
-        builder.install(&provider).await.map_err(|e| {
-            let err = format!("Failed to install tracer: {}", e);
-            tracing::warn!("{}", err);
-            anyhow::anyhow!(err)
-        })?;
-
-    let telemetry_layer = tracing_opentelemetry::layer().with_tracer(tracer_provider);
+        .map_err(|e| anyhow::anyhow!("Failed to initialize GCP exporter builder: {}", e))?
+        .with_resource(resource());
 
-    let subscriber = tracing_subscriber::registry()
-        .with(env_filter)
-        .with(stackdriver_layer)
-        .with(telemetry_layer);
+    let provider = builder
+        .create_provider()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create tracer provider: {}", e))?;
+
+    let tracer = builder
+        .install(&provider)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to install tracer: {}", e))?;
+
+    Ok((tracer, provider))
+}
+
+/// OTLP over gRPC (default, port 4317) or HTTP/protobuf (port 4318).
+fn otlp_provider() -> anyhow::Result<SdkTracerProvider> {
+    let config = config::get();
 
-    tracing::subscriber::set_global_default(subscriber).map_err(|e| {
-        let err = format!("Setting default subscriber failed: {}", e);
-        tracing::warn!("{}", err);
-        anyhow::anyhow!(err)
-    })?;
+    let exporter = match config.telemetry.otlp_protocol {
+        OtlpProtocol::Grpc => {
+            let mut builder = opentelemetry_otlp::SpanExporter::builder().with_tonic();
+            if let Some(endpoint) = &config.telemetry.otlp_endpoint {
+                builder = builder.with_endpoint(endpoint);
+            }
+            builder.build()?
+        }
+        OtlpProtocol::HttpProtobuf => {
+            let mut builder = opentelemetry_otlp::SpanExporter::builder().with_http();
+            if let Some(endpoint) = &config.telemetry.otlp_endpoint {
+                builder =
+                    builder.with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')));
+            }
+            builder.build()?
+        }
+    };
 
-    Ok(())
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource())
+        .build())
 }
@@ -0,0 +1,85 @@
+use crate::domain::error::DomainResult;
+use crate::domain::port::rate_limit::{RateLimit, RateLimitDecision, RateLimiterPort};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Buckets kept before full ones are dropped.
+const MAX_BUCKETS: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    limit: RateLimit,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let interval = self.limit.refill_interval().as_secs_f64();
+        let earned = now.duration_since(self.updated).as_secs_f64() / interval;
+        self.tokens = (self.tokens + earned).min(f64::from(self.limit.requests));
+        self.updated = now;
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= f64::from(self.limit.requests)
+    }
+}
+
+/// Token buckets in process; for local runs, tests, and when Redis is down
+/// (each instance then enforces the limits on its own).
+#[derive(Default)]
+pub struct InMemoryRateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl InMemoryRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimiterPort for InMemoryRateLimiter {
+    async fn acquire(&self, key: &str, limit: RateLimit) -> DomainResult<RateLimitDecision> {
+        let now = Instant::now();
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(key) {
+            buckets.retain(|_, bucket| {
+                bucket.refill(now);
+                !bucket.is_full()
+            });
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: f64::from(limit.requests),
+            updated: now,
+            limit,
+        });
+        bucket.limit = limit;
+        bucket.refill(now);
+
+        let interval = limit.refill_interval().as_secs_f64();
+        let allowed = bucket.tokens >= 1.0;
+        let retry_after = if allowed {
+            bucket.tokens -= 1.0;
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - bucket.tokens) * interval)
+        };
+
+        Ok(RateLimitDecision {
+            allowed,
+            remaining: bucket.tokens.floor() as u32,
+            reset_after: Duration::from_secs_f64(
+                (f64::from(limit.requests) - bucket.tokens) * interval,
+            ),
+            retry_after,
+        })
+    }
+}
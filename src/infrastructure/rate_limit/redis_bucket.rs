@@ -0,0 +1,77 @@
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::port::rate_limit::{RateLimit, RateLimitDecision, RateLimiterPort};
+use crate::infrastructure::providers::redis::RedisProvider;
+use async_trait::async_trait;
+use redis::Script;
+use std::time::Duration;
+
+/// Refills and takes from the bucket in one round trip, atomically, on
+/// Redis' clock so instances with skewed clocks agree.
+///
+/// Returns `{allowed, remaining, retry_after_ms, reset_ms}`.
+const TOKEN_BUCKET: &str = r#"
+local capacity = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local interval = window / capacity
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) / interval)
+
+local allowed = 0
+local retry_after = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+else
+    retry_after = math.ceil((1 - tokens) * interval)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], window)
+return {allowed, math.floor(tokens), retry_after, math.ceil((capacity - tokens) * interval)}
+"#;
+
+/// Token buckets in `{prefix}:ratelimit:{key}` hashes, shared by every
+/// instance. Idle buckets expire once they would be full again.
+pub struct RedisRateLimiter {
+    redis: RedisProvider,
+    script: Script,
+}
+
+impl RedisRateLimiter {
+    pub fn new(redis: RedisProvider) -> Self {
+        Self {
+            redis,
+            script: Script::new(TOKEN_BUCKET),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiterPort for RedisRateLimiter {
+    #[tracing::instrument(skip_all)]
+    async fn acquire(&self, key: &str, limit: RateLimit) -> DomainResult<RateLimitDecision> {
+        let bucket = self.redis.get_path(&["ratelimit", key]);
+
+        let mut conn = self.redis.connection();
+        let (allowed, remaining, retry_after_ms, reset_ms): (u8, u32, u64, u64) = self
+            .script
+            .key(&bucket)
+            .arg(limit.requests)
+            .arg(limit.window.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| Error::external("Redis", e.to_string()))?;
+
+        Ok(RateLimitDecision {
+            allowed: allowed == 1,
+            remaining,
+            reset_after: Duration::from_millis(reset_ms),
+            retry_after: Duration::from_millis(retry_after_ms),
+        })
+    }
+}
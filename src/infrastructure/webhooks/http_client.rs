@@ -0,0 +1,82 @@
+use crate::domain::entities::webhook::{WebhookDelivery, WebhookSubscription};
+use crate::domain::error::{DomainResult, Error};
+use crate::domain::port::webhook_client::WebhookClientPort;
+use crate::infrastructure::providers::propagation;
+use async_trait::async_trait;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+
+const SERVICE: &str = "webhook";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// POSTs signed deliveries with reqwest.
+///
+/// Headers sent with every request:
+/// - `X-Webhook-Id`: event ID, stable across retries (dedupe on it)
+/// - `X-Webhook-Event`: event type (`order_created`, ...)
+/// - `X-Webhook-Delivery`: delivery ID, for the delivery log
+/// - `X-Webhook-Timestamp`: Unix seconds when the request was signed
+/// - `X-Webhook-Signature`: `v1=` + hex HMAC-SHA256 of `{timestamp}.{body}`
+/// - `traceparent`: the delivery's trace, for receivers that continue it
+#[derive(Debug, Clone)]
+pub struct HttpWebhookClient {
+    client: reqwest::Client,
+}
+
+impl HttpWebhookClient {
+    pub fn new(timeout: Duration) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder().timeout(timeout).build()?;
+        Ok(Self { client })
+    }
+}
+
+/// `v1=<hex>` signature over `{timestamp}.{body}`.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("v1={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[async_trait]
+impl WebhookClientPort for HttpWebhookClient {
+    #[tracing::instrument(skip_all, fields(url = %subscription.url, event = %delivery.event_type))]
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> DomainResult<u16> {
+        let timestamp = chrono::Utc::now().timestamp();
+        let signature = sign(&subscription.secret, timestamp, &delivery.body);
+
+        let response = self
+            .client
+            .post(&subscription.url)
+            .headers(propagation::outgoing_headers())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Id", &delivery.event_id)
+            .header("X-Webhook-Event", &delivery.event_type)
+            .header(
+                "X-Webhook-Delivery",
+                delivery.id.as_deref().unwrap_or_default(),
+            )
+            .header("X-Webhook-Timestamp", timestamp.to_string())
+            .header("X-Webhook-Signature", signature)
+            .body(delivery.body.clone())
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    Error::external_timeout(SERVICE)
+                } else {
+                    Error::external(SERVICE, e.to_string())
+                }
+            })?;
+
+        Ok(response.status().as_u16())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod cli;
+pub mod config;
+
+// Layered Architecture Modules
+pub mod application;
+pub mod domain;
+pub mod infrastructure;
+pub mod presentation;
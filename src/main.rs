@@ -1,79 +1,576 @@
-mod config;
-
-// Layered Architecture Modules
-mod application;
-mod domain;
-mod infrastructure;
-mod presentation;
-
-use crate::infrastructure::providers::mongo::MongoProvider;
-use crate::presentation::server::ServerLauncher;
-use crate::presentation::state::AppState;
+use service::config::{self, Config, EventPublisher, Persistence};
+use service::cli;
+use service::infrastructure::providers::mongo::MongoProvider;
+use service::infrastructure::providers::postgres::PostgresProvider;
+use service::infrastructure::providers::propagation::OtelTraceContext;
+use service::infrastructure::providers::job_run::JobRunProvider;
+use service::infrastructure::providers::redis::{RedisLock, RedisProvider};
+use service::infrastructure::providers::tasks::TaskProvider;
+use service::presentation::http::rate_limit::RateLimits;
+use service::presentation::server::{MetricsRenderer, ServerLauncher};
+use service::presentation::state::AppState;
+use clap::Parser;
+use mongodb::Database;
+use sqlx::PgPool;
+use std::process::ExitCode;
 use std::sync::Arc;
 
-use crate::application::{order::OrderService, product::ProductService, user::UserService};
-use crate::domain::port::{
-    order::OrderRepositoryPort, product::ProductRepositoryPort, user::UserRepositoryPort,
+use service::application::{
+    api_key::ApiKeyService,
+    audit::AuditService,
+    category::CategoryService,
+    inventory::{InventoryService, LowStockAlertHandler, LowStockMonitor},
+    lock::Locks,
+    order::OrderService,
+    outbox::OutboxRelay,
+    product::ProductService,
+    scheduler::Scheduler,
+    seed::{Fixtures, Seeder},
+    tasks::{TaskRegistry, TaskWorkers},
+    user::UserService,
+    webhook::{DeliverWebhookHandler, WebhookService},
+};
+use service::domain::port::{
+    api_key::ApiKeyRepositoryPort,
+    audit::AuditLogPort,
+    category::CategoryRepositoryPort,
+    event_publisher::EventPublisherPort,
+    inventory::InventoryLedgerPort,
+    job_run::JobRunPort,
+    lock::LockPort,
+    metrics::MetricsPort,
+    notifier::NotifierPort,
+    order::OrderRepositoryPort,
+    outbox::OutboxPort,
+    product::ProductRepositoryPort,
+    rate_limit::RateLimiterPort,
+    task_queue::TaskQueuePort,
+    trace_context::TraceContextPort,
+    user::UserRepositoryPort,
+    webhook::{WebhookDeliveryRepositoryPort, WebhookSubscriptionRepositoryPort},
+};
+use service::infrastructure::events::{
+    fanout::FanoutPublisher, memory::InMemoryEventPublisher, redis_streams::RedisStreamsPublisher,
 };
-use crate::infrastructure::persistence::{
-    order::repository::OrderRepository, product::repository::ProductRepository,
+use service::infrastructure::metrics::{noop::NoopMetrics, prometheus::PrometheusMetrics};
+use service::infrastructure::rate_limit::{memory::InMemoryRateLimiter, redis_bucket::RedisRateLimiter};
+use service::infrastructure::notifier::{log::LogNotifier, webhook::WebhookNotifier};
+use service::infrastructure::persistence::{
+    self,
+    api_key::repository::ApiKeyRepository,
+    maintenance,
+    memory::{InMemoryRepositories, job_run::InMemoryJobRuns, lease::InMemoryLock},
+    postgres::{
+        order::PostgresOrderRepository, outbox::PostgresOutbox, product::PostgresProductRepository,
+        user::PostgresUserRepository,
+    },
+    audit::repository::AuditLogRepository,
+    category::repository::CategoryRepository,
+    inventory::repository::InventoryLedgerRepository,
+    job_run::MongoJobRuns,
+    lease::MongoLock,
+    order::repository::OrderRepository,
+    outbox::{repository::OutboxRepository, writer::OutboxWriter},
+    product::repository::ProductRepository,
     user::repository::UserRepository,
+    webhook::repository::{WebhookDeliveryRepository, WebhookSubscriptionRepository},
 };
+use service::infrastructure::webhooks::http_client::HttpWebhookClient;
+use std::time::Duration;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
+    let cli = cli::Cli::parse();
+
     rustls::crypto::ring::default_provider()
         .install_default()
         .expect("Failed to install rustls crypto provider");
 
-    let env = config::get();
+    let env = match config::load() {
+        Ok(env) => env,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if cli.print_config {
+        println!("{:#?}", env);
+        return ExitCode::SUCCESS;
+    }
 
-    if let Err(e) = crate::infrastructure::providers::telemetry::init_tracing().await {
-        eprintln!("Failed to initialize tracing: {}", e);
+    // Metrics come first: the tracing subscriber feeds datastore latencies into them
+    let prometheus = if env.telemetry.metrics_enabled {
+        Some(Arc::new(
+            PrometheusMetrics::new(&env.server.service_name).expect("Failed to register metrics"),
+        ))
+    } else {
+        None
+    };
+    let metrics: Arc<dyn MetricsPort> = match &prometheus {
+        Some(prometheus) => prometheus.clone(),
+        None => Arc::new(NoopMetrics),
+    };
+
+    let _telemetry =
+        service::infrastructure::providers::telemetry::init_tracing(metrics.clone()).await;
+    let trace_context: Arc<dyn TraceContextPort> = Arc::new(OtelTraceContext);
+
+    tracing::info!(
+        "Starting {} (env: {})",
+        env.server.service_name,
+        env.server.app_env
+    );
+    if let Some(file) = &env.file {
+        tracing::info!("Settings read from {}", file.display());
     }
 
-    tracing::info!("Starting {} (env: {})", env.service_name, env.app_env);
+    // 1. Initialize Repositories; PERSISTENCE picks the adapter set
+    let (repos, mongo_db) = match env.persistence.backend {
+        Persistence::Mongo => {
+            let mongo =
+                match MongoProvider::new(&env.server.service_name, &env.mongo, metrics.clone())
+                    .await
+                {
+                    Ok(mongo) => mongo,
+                    Err(e) => {
+                        tracing::error!("Failed to connect to MongoDB: {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                };
+            let db = mongo.get_database();
 
-    // Initialize infrastructure
-    let mongo = MongoProvider::new(&env.service_name, &env.mongo_url, &env.mongo_db).await;
-    let db = mongo.get_database();
+            if let Some(cli::Command::Migrate { action }) = cli.command {
+                return cli::migrate(&db, action).await;
+            }
 
-    // 1. Initialize Repositories
-    let user_repo = Arc::new(UserRepository::new(&db));
-    let product_repo = Arc::new(ProductRepository::new(&db));
-    let order_repo = Arc::new(OrderRepository::new(&db));
+            // Schema migrations run before indexes, which may depend on migrated fields
+            if let Err(e) = cli::migrate_on_startup(&db, env.mongo.migrations_on_startup).await {
+                tracing::error!("Failed to run startup migrations: {}", e);
+                return ExitCode::FAILURE;
+            }
 
-    // 2. Create database indexes (idempotent - safe to run on every startup)
-    tracing::info!("Creating database indexes...");
-    if let Err(e) = user_repo.create_indexes().await {
-        tracing::error!("Failed to create user indexes: {}", e);
-    }
-    if let Err(e) = product_repo.create_indexes().await {
-        tracing::error!("Failed to create product indexes: {}", e);
-    }
-    if let Err(e) = order_repo.create_indexes().await {
-        tracing::error!("Failed to create order indexes: {}", e);
+            let repos = Repositories::mongo(&db, mongo.supports_transactions().await, env).await;
+            (repos, Some(db))
+        }
+        Persistence::Postgres => {
+            if let Some(cli::Command::Migrate { .. }) = cli.command {
+                tracing::error!(
+                    "The migrate command is for MongoDB; SQL migrations run on startup"
+                );
+                return ExitCode::FAILURE;
+            }
+            let postgres =
+                match PostgresProvider::new(&env.server.service_name, env.postgres.url.expose())
+                    .await
+                {
+                    Ok(postgres) => postgres,
+                    Err(e) => {
+                        tracing::error!("Failed to connect to PostgreSQL: {}", e);
+                        return ExitCode::FAILURE;
+                    }
+                };
+            let pool = postgres.get_pool();
+
+            if let Err(e) = persistence::postgres::migrate(&pool).await {
+                tracing::error!("Failed to run SQL migrations: {}", e);
+                return ExitCode::FAILURE;
+            }
+
+            tracing::warn!(
+                "PERSISTENCE=postgres with POSTGRES_ALLOW_IN_MEMORY: categories, inventory ledger, webhooks, audit log and API keys are kept in process"
+            );
+            (Repositories::postgres(pool), None)
+        }
+        Persistence::Memory => {
+            if let Some(cli::Command::Migrate { .. }) = cli.command {
+                tracing::error!("Migrations need PERSISTENCE=mongo");
+                return ExitCode::FAILURE;
+            }
+            tracing::warn!("PERSISTENCE=memory: data is kept in process and lost on restart");
+            (Repositories::memory(), None)
+        }
+    };
+
+    // Redis is optional: without it events stay in the outbox and alerts are sent inline
+    let redis = match RedisProvider::new(env.redis.url.expose(), &env.server.service_name).await {
+        Ok(redis) => Some(redis),
+        Err(e) => {
+            tracing::error!("Failed to connect to Redis: {}", e);
+            None
+        }
+    };
+    let task_queue: Option<Arc<dyn TaskQueuePort>> = match &redis {
+        Some(redis) => match TaskProvider::new(redis.clone()).await {
+            Ok(queue) => Some(Arc::new(queue)),
+            Err(e) => {
+                tracing::error!("Failed to initialize task queue: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // 3. Initialize Notifiers
+    let notifier: Arc<dyn NotifierPort> = match &env.inventory.low_stock_webhook_url {
+        Some(url) => match WebhookNotifier::new(url.expose()) {
+            Ok(webhook) => Arc::new(webhook),
+            Err(e) => {
+                tracing::error!(
+                    "Failed to build webhook notifier, falling back to log: {}",
+                    e
+                );
+                Arc::new(LogNotifier)
+            }
+        },
+        None => Arc::new(LogNotifier),
+    };
+    let mut low_stock = LowStockMonitor::new(repos.products.clone(), notifier.clone());
+    if let Some(queue) = &task_queue {
+        low_stock = low_stock.with_queue(queue.clone());
     }
 
-    // 3. Initialize Services
-    let user_service = Arc::new(UserService::new(
-        user_repo.clone() as Arc<dyn UserRepositoryPort>
+    // 4. Initialize Services
+    let audit_service = Arc::new(AuditService::new(
+        repos.audit.clone(),
+        trace_context.clone(),
+    ));
+    let inventory_service = Arc::new(InventoryService::new(
+        repos.products.clone(),
+        repos.ledger.clone(),
+        low_stock,
+        metrics.clone(),
     ));
+    let user_service = Arc::new(UserService::new(repos.users.clone(), audit_service.clone()));
     let product_service = Arc::new(ProductService::new(
-        product_repo.clone() as Arc<dyn ProductRepositoryPort>
+        repos.products.clone(),
+        repos.categories.clone(),
+        inventory_service.clone(),
+        audit_service.clone(),
+    ));
+    let category_service = Arc::new(CategoryService::new(
+        repos.categories.clone(),
+        repos.products.clone(),
     ));
     let order_service = Arc::new(OrderService::new(
-        order_repo as Arc<dyn OrderRepositoryPort>,
-        user_repo as Arc<dyn UserRepositoryPort>,
-        product_repo as Arc<dyn ProductRepositoryPort>,
+        repos.orders.clone(),
+        repos.users.clone(),
+        repos.products.clone(),
+        inventory_service.clone(),
+        metrics.clone(),
+        audit_service.clone(),
     ));
+    let webhook_client = HttpWebhookClient::new(Duration::from_secs(env.webhooks.timeout_secs))
+        .expect("Failed to build webhook HTTP client");
+    let mut webhook_service = WebhookService::new(
+        repos.webhook_subscriptions.clone(),
+        repos.webhook_deliveries.clone(),
+        Arc::new(webhook_client),
+    );
+    if let Some(queue) = &task_queue {
+        webhook_service = webhook_service.with_queue(queue.clone());
+    }
+    let webhook_service = Arc::new(webhook_service);
+
+    // Fixtures go through the services, so seeding twice creates nothing new
+    if let Some(path) = &env.persistence.seed_file {
+        let seeder = Seeder::new(
+            user_service.clone(),
+            category_service.clone(),
+            product_service.clone(),
+            repos.categories.clone(),
+            repos.products.clone(),
+        );
+        match Fixtures::load(path) {
+            Ok(fixtures) => {
+                if let Err(e) = seeder.seed(&fixtures).await {
+                    tracing::error!("Failed to seed fixtures from {}: {}", path, e);
+                }
+            }
+            Err(e) => tracing::error!("Failed to read fixtures: {}", e),
+        }
+    }
+
+    // 5. Scheduled jobs; each occurrence runs on one instance, which takes a
+    //    lock shared through Redis or MongoDB
+    let shared: Option<(Arc<dyn LockPort>, Arc<dyn JobRunPort>)> = match (&redis, &mongo_db) {
+        (Some(redis), _) => Some((
+            Arc::new(RedisLock::new(redis.clone())),
+            Arc::new(JobRunProvider::new(redis.clone())),
+        )),
+        (None, Some(db)) => Some((
+            Arc::new(MongoLock::new(db)),
+            Arc::new(MongoJobRuns::new(db)),
+        )),
+        (None, None) => None,
+    };
+    let scheduler = match shared {
+        Some((lock, job_runs)) => scheduled_jobs(
+            Scheduler::new(env.scheduler.timezone(), Locks::new(lock), job_runs)
+                .with_trace_context(trace_context.clone()),
+            env,
+            mongo_db,
+            order_service.clone(),
+        ),
+        None => {
+            if env.scheduler.purge_deleted.is_some() || env.scheduler.sales_summary.is_some() {
+                tracing::warn!(
+                    "Scheduled jobs need Redis or PERSISTENCE=mongo to run on one instance, not scheduled"
+                );
+            }
+            // No jobs registered, so the local lock never guards anything
+            Scheduler::new(
+                env.scheduler.timezone(),
+                Locks::new(Arc::new(InMemoryLock::new())),
+                Arc::new(InMemoryJobRuns::new()),
+            )
+        }
+    };
 
-    // 4. Wire State
+    // 6. Wire State
     let state = AppState {
         user_service,
         product_service,
+        category_service,
         order_service,
+        inventory_service: inventory_service.clone(),
+        webhook_service: webhook_service.clone(),
+        audit_service,
+        scheduler: Arc::new(scheduler.clone()),
+        metrics,
+        trace_context: trace_context.clone(),
     };
 
-    ServerLauncher::new(state).with_http(env.port).run().await;
+    if env.inventory.reconcile_interval_secs > 0 {
+        inventory_service
+            .spawn_reconciliation(Duration::from_secs(env.inventory.reconcile_interval_secs));
+    }
+
+    // 7. Relay outbox events; without a publisher they wait in the outbox
+    let mut publishers: Vec<Arc<dyn EventPublisherPort>> = Vec::new();
+    let publisher: Option<Arc<dyn EventPublisherPort>> = match env.events.publisher {
+        EventPublisher::Redis => match &redis {
+            Some(redis) => Some(Arc::new(RedisStreamsPublisher::new(redis.clone()))),
+            None => {
+                tracing::error!("Redis unavailable, outbox relay disabled");
+                None
+            }
+        },
+        EventPublisher::Memory => Some(Arc::new(InMemoryEventPublisher::new())),
+        EventPublisher::None => None,
+    };
+    publishers.extend(publisher);
+    // Webhook deliveries are sent by the task workers
+    if task_queue.is_some() {
+        publishers.push(webhook_service.clone());
+    } else {
+        tracing::warn!("Task queue unavailable, webhooks will not be delivered");
+    }
+    if !publishers.is_empty() {
+        OutboxRelay::new(
+            repos.outbox.clone(),
+            Arc::new(FanoutPublisher::new(publishers)),
+        )
+        .spawn(Duration::from_millis(env.events.relay_interval_ms));
+    }
+
+    // 8. Background task workers and jobs
+    let mut launcher = ServerLauncher::new(state).with_http(env.server.port);
+    if let Some(prometheus) = prometheus {
+        let render: MetricsRenderer = Arc::new(move || prometheus.render());
+        launcher = launcher.with_metrics_endpoint(render);
+    }
+    if env.rate_limit.enabled {
+        let limiter: Arc<dyn RateLimiterPort> = match &redis {
+            Some(redis) => Arc::new(RedisRateLimiter::new(redis.clone())),
+            None => {
+                tracing::warn!("Redis unavailable, rate limits are enforced per instance");
+                Arc::new(InMemoryRateLimiter::new())
+            }
+        };
+        launcher = launcher.with_rate_limits(
+            RateLimits::new(
+                limiter,
+                Arc::new(ApiKeyService::new(repos.api_keys.clone())),
+                env.rate_limit.default,
+                env.rate_limit.groups.clone(),
+            )
+            .with_api_key_header(env.auth.api_key_header.clone()),
+        );
+    }
+    if let Some(queue) = task_queue
+        && env.tasks.workers > 0
+    {
+        let registry = TaskRegistry::new()
+            .register(LowStockAlertHandler::new(notifier))
+            .register(DeliverWebhookHandler::new(webhook_service));
+        let workers = TaskWorkers::new(
+            queue,
+            registry,
+            Duration::from_millis(env.tasks.poll_interval_ms),
+        )
+        .with_trace_context(trace_context);
+        launcher = launcher.with_workers(workers, env.tasks.workers);
+    }
+
+    if !scheduler.is_empty() {
+        launcher = launcher.with_scheduler(scheduler);
+    }
+
+    launcher.run().await;
+    ExitCode::SUCCESS
+}
+
+/// The adapter behind each repository port, as picked by `PERSISTENCE`.
+struct Repositories {
+    users: Arc<dyn UserRepositoryPort>,
+    products: Arc<dyn ProductRepositoryPort>,
+    categories: Arc<dyn CategoryRepositoryPort>,
+    orders: Arc<dyn OrderRepositoryPort>,
+    ledger: Arc<dyn InventoryLedgerPort>,
+    outbox: Arc<dyn OutboxPort>,
+    webhook_subscriptions: Arc<dyn WebhookSubscriptionRepositoryPort>,
+    webhook_deliveries: Arc<dyn WebhookDeliveryRepositoryPort>,
+    audit: Arc<dyn AuditLogPort>,
+    api_keys: Arc<dyn ApiKeyRepositoryPort>,
+}
+
+impl Repositories {
+    async fn mongo(db: &Database, transactions: bool, env: &Config) -> Self {
+        let outbox_writer = OutboxWriter::new(db, transactions);
+        let user_repo = UserRepository::new(db, outbox_writer.clone());
+        let product_repo = ProductRepository::new(db, outbox_writer.clone());
+        let category_repo = CategoryRepository::new(db, outbox_writer.clone());
+        let order_repo = OrderRepository::new(db, outbox_writer);
+        let ledger_repo = InventoryLedgerRepository::new(db);
+        let outbox_repo = OutboxRepository::new(db);
+        let webhook_subscription_repo = WebhookSubscriptionRepository::new(db);
+        let webhook_delivery_repo = WebhookDeliveryRepository::new(db);
+        let audit_repo = AuditLogRepository::new(
+            db,
+            Duration::from_secs(env.audit.retention_days * 24 * 60 * 60),
+        );
+
+        // 2. Create database indexes (idempotent - safe to run on every startup)
+        tracing::info!("Creating database indexes...");
+        if let Err(e) = user_repo.create_indexes().await {
+            tracing::error!("Failed to create user indexes: {}", e);
+        }
+        if let Err(e) = product_repo.create_indexes().await {
+            tracing::error!("Failed to create product indexes: {}", e);
+        }
+        if let Err(e) = category_repo.create_indexes().await {
+            tracing::error!("Failed to create category indexes: {}", e);
+        }
+        if let Err(e) = order_repo.create_indexes().await {
+            tracing::error!("Failed to create order indexes: {}", e);
+        }
+        if let Err(e) = ledger_repo.create_indexes().await {
+            tracing::error!("Failed to create inventory ledger indexes: {}", e);
+        }
+        if let Err(e) = outbox_repo.create_indexes().await {
+            tracing::error!("Failed to create outbox indexes: {}", e);
+        }
+        if let Err(e) = webhook_subscription_repo.create_indexes().await {
+            tracing::error!("Failed to create webhook subscription indexes: {}", e);
+        }
+        if let Err(e) = webhook_delivery_repo.create_indexes().await {
+            tracing::error!("Failed to create webhook delivery indexes: {}", e);
+        }
+        if let Err(e) = audit_repo.create_indexes().await {
+            tracing::error!("Failed to create audit log indexes: {}", e);
+        }
+        let api_key_repo = ApiKeyRepository::new(db);
+        if let Err(e) = api_key_repo.create_indexes().await {
+            tracing::error!("Failed to create API key indexes: {}", e);
+        }
+
+        Self {
+            users: Arc::new(user_repo),
+            products: Arc::new(product_repo),
+            categories: Arc::new(category_repo),
+            orders: Arc::new(order_repo),
+            ledger: Arc::new(ledger_repo),
+            outbox: Arc::new(outbox_repo),
+            webhook_subscriptions: Arc::new(webhook_subscription_repo),
+            webhook_deliveries: Arc::new(webhook_delivery_repo),
+            audit: Arc::new(audit_repo),
+            api_keys: Arc::new(api_key_repo),
+        }
+    }
+
+    /// Users, products, orders and their outbox in PostgreSQL; the ports with
+    /// no PostgreSQL adapter yet use the in-memory ones, which the config only
+    /// allows with `POSTGRES_ALLOW_IN_MEMORY`.
+    fn postgres(pool: PgPool) -> Self {
+        let memory = Self::memory();
+        Self {
+            users: Arc::new(PostgresUserRepository::new(pool.clone())),
+            products: Arc::new(PostgresProductRepository::new(
+                pool.clone(),
+                memory.ledger.clone(),
+            )),
+            orders: Arc::new(PostgresOrderRepository::new(pool.clone())),
+            outbox: Arc::new(PostgresOutbox::new(pool)),
+            ..memory
+        }
+    }
+
+    fn memory() -> Self {
+        let repos = InMemoryRepositories::new();
+        Self {
+            users: repos.users,
+            products: repos.products,
+            categories: repos.categories,
+            orders: repos.orders,
+            ledger: repos.ledger,
+            outbox: Arc::new(repos.outbox),
+            webhook_subscriptions: repos.webhook_subscriptions,
+            webhook_deliveries: repos.webhook_deliveries,
+            audit: repos.audit,
+            api_keys: repos.api_keys,
+        }
+    }
+}
+
+/// Registers the jobs whose cron expression is set.
+fn scheduled_jobs(
+    mut scheduler: Scheduler,
+    env: &Config,
+    db: Option<Database>,
+    orders: Arc<OrderService>,
+) -> Scheduler {
+    if let Some(cron) = &env.scheduler.purge_deleted {
+        match db {
+            Some(db) => {
+                let retention = chrono::Duration::days(env.scheduler.purge_after_days as i64);
+                scheduler = scheduler.register("purge-deleted", cron.clone(), move || {
+                    let db = db.clone();
+                    async move {
+                        let before = chrono::Utc::now() - retention;
+                        let reports = maintenance::purge_deleted(&db, before, false).await?;
+                        Ok(reports
+                            .iter()
+                            .map(|report| format!("{}: {}", report.collection, report.purged))
+                            .collect::<Vec<_>>()
+                            .join(", "))
+                    }
+                });
+            }
+            None => tracing::warn!("The purge-deleted job needs PERSISTENCE=mongo, not scheduled"),
+        }
+    }
+
+    if let Some(cron) = &env.scheduler.sales_summary {
+        let tz = env.scheduler.timezone();
+        scheduler = scheduler.register("sales-summary", cron.clone(), move || {
+            let orders = orders.clone();
+            async move {
+                let today = chrono::Utc::now().with_timezone(&tz).date_naive();
+                let yesterday = today.pred_opt().unwrap_or(today);
+                Ok(orders.sales_summary(yesterday, tz).await?.to_string())
+            }
+        });
+    }
+
+    scheduler
 }
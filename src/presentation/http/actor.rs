@@ -0,0 +1,57 @@
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{Extensions, HeaderMap, request::Parts},
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use crate::domain::entities::audit::{ANONYMOUS_ACTOR, Actor};
+
+/// Header identifying the caller, set by the gateway or internal tooling.
+pub const ACTOR_HEADER: &str = "x-user-id";
+
+/// The caller of a mutating request, recorded in the audit log.
+///
+/// The IP is the last `X-Forwarded-For` hop when present (the one appended by
+/// the nginx in front of the service), otherwise the peer address.
+#[derive(Debug, Clone)]
+pub struct RequestActor(pub Actor);
+
+impl<S> FromRequestParts<S> for RequestActor
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let id = header(&parts.headers, ACTOR_HEADER).unwrap_or(ANONYMOUS_ACTOR);
+        let ip = client_ip(&parts.headers, &parts.extensions);
+
+        Ok(RequestActor(Actor::new(id, ip)))
+    }
+}
+
+/// The last `X-Forwarded-For` hop, otherwise the peer address.
+///
+/// nginx appends the address it saw to whatever the client sent, so only the
+/// right-most hop can be trusted; the ones before it are client-supplied.
+pub fn client_ip(headers: &HeaderMap, extensions: &Extensions) -> Option<String> {
+    let forwarded = header(headers, "x-forwarded-for")
+        .and_then(|value| value.rsplit(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty())
+        .map(str::to_string);
+    forwarded.or_else(|| {
+        extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+    })
+}
+
+pub(crate) fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
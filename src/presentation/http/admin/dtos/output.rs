@@ -0,0 +1,49 @@
+use crate::application::scheduler::JobStatus;
+use crate::domain::job::{JobOutcome, JobRun};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct JobStatusOutput {
+    pub name: String,
+    pub schedule: String,
+    pub timezone: String,
+    pub next_run: Option<String>,
+    pub last_run: Option<JobRunOutput>,
+}
+
+#[derive(Serialize)]
+pub struct JobRunOutput {
+    pub scheduled_for: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: i64,
+    pub outcome: JobOutcome,
+    pub detail: String,
+    pub trace_id: Option<String>,
+}
+
+impl From<JobStatus> for JobStatusOutput {
+    fn from(status: JobStatus) -> Self {
+        Self {
+            name: status.name,
+            schedule: status.schedule,
+            timezone: status.timezone.name().to_string(),
+            next_run: status.next_run.map(|at| at.to_rfc3339()),
+            last_run: status.last_run.map(Into::into),
+        }
+    }
+}
+
+impl From<JobRun> for JobRunOutput {
+    fn from(run: JobRun) -> Self {
+        Self {
+            scheduled_for: run.scheduled_for.to_rfc3339(),
+            started_at: run.started_at.to_rfc3339(),
+            finished_at: run.finished_at.to_rfc3339(),
+            duration_ms: (run.finished_at - run.started_at).num_milliseconds(),
+            outcome: run.outcome,
+            detail: run.detail,
+            trace_id: run.trace_id,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::application::scheduler::Scheduler;
+use crate::presentation::{
+    http::{admin::dtos::JobStatusOutput, error::ApiError, response::GenericApiResponse},
+    state::AppState,
+};
+use axum::{Router, extract::State, routing::get};
+use std::sync::Arc;
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/jobs", get(list_jobs))
+}
+
+/// Scheduled jobs with their next occurrence and last run, from any instance.
+#[tracing::instrument(skip_all)]
+pub async fn list_jobs(
+    State(scheduler): State<Arc<Scheduler>>,
+) -> Result<GenericApiResponse<Vec<JobStatusOutput>>, ApiError> {
+    let statuses = scheduler.status().await?;
+    Ok(GenericApiResponse::success(
+        statuses.into_iter().map(Into::into).collect(),
+    ))
+}
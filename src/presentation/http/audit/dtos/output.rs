@@ -0,0 +1,31 @@
+use crate::domain::entities::audit::{AuditAction, AuditEntry, FieldChange};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct AuditEntryOutput {
+    pub id: String,
+    pub actor: String,
+    pub action: AuditAction,
+    pub entity: String,
+    pub entity_id: String,
+    pub changes: Vec<FieldChange>,
+    pub trace_id: Option<String>,
+    pub ip: Option<String>,
+    pub created_at: String,
+}
+
+impl From<AuditEntry> for AuditEntryOutput {
+    fn from(entry: AuditEntry) -> Self {
+        Self {
+            id: entry.id.map(|id| id.into_inner()).unwrap_or_default(),
+            actor: entry.actor,
+            action: entry.action,
+            entity: entry.entity,
+            entity_id: entry.entity_id,
+            changes: entry.changes,
+            trace_id: entry.trace_id,
+            ip: entry.ip,
+            created_at: entry.created_at.to_rfc3339(),
+        }
+    }
+}
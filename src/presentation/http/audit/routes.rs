@@ -0,0 +1,69 @@
+use crate::application::audit::AuditService;
+use crate::domain::filter::AuditFilter;
+use crate::domain::pagination::Pagination;
+use crate::presentation::{
+    http::{
+        audit::dtos::AuditEntryOutput,
+        error::ApiError,
+        response::{GenericApiResponse, GenericPagination},
+    },
+    state::AppState,
+};
+use axum::{
+    Router,
+    extract::{Query, State},
+    routing::get,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuditQuery {
+    /// `User`, `Product` or `Order`.
+    #[validate(length(min = 1, max = 64))]
+    pub entity: Option<String>,
+
+    #[validate(length(min = 1, max = 64))]
+    pub entity_id: Option<String>,
+
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(list_audit_entries))
+}
+
+/// Audit trail, newest first, optionally narrowed to one entity.
+#[tracing::instrument(skip_all)]
+pub async fn list_audit_entries(
+    State(service): State<Arc<AuditService>>,
+    Query(query): Query<AuditQuery>,
+) -> Result<GenericApiResponse<GenericPagination<AuditEntryOutput>>, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    if query.entity_id.is_some() && query.entity.is_none() {
+        return Err(ApiError::BadRequest(
+            "entity is required when entity_id is given".to_string(),
+        ));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+    let filter = AuditFilter {
+        entity: query.entity,
+        entity_id: query.entity_id,
+    };
+
+    let entries = service.list(&filter, pagination).await?;
+    let total = service.count(&filter).await?;
+    let data: Vec<AuditEntryOutput> = entries.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCategoryInput {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+
+    /// Derived from `name` when omitted.
+    #[validate(length(min = 1, max = 64, message = "Slug must be 1-64 characters"))]
+    pub slug: Option<String>,
+
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateCategoryInput {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 64, message = "Slug must be 1-64 characters"))]
+    pub slug: Option<String>,
+
+    /// `null` moves the category to the root of the tree.
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub parent_id: Option<String>,
+}
@@ -0,0 +1,53 @@
+use crate::application::category::LegacyCategoryMigration;
+use crate::domain::entities::category::{Category, CategoryId};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CategoryOutput {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    pub parent_id: Option<String>,
+    pub path: String,
+    pub depth: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Category> for CategoryOutput {
+    fn from(category: Category) -> Self {
+        let depth = category.depth();
+        Self {
+            id: category
+                .id
+                .map(|id: CategoryId| id.into_inner())
+                .unwrap_or_default(),
+            name: category.name,
+            slug: category.slug,
+            parent_id: category.parent_id.map(|id| id.into_inner()),
+            path: category.path,
+            depth,
+            created_at: category.created_at.to_rfc3339(),
+            updated_at: category.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LegacyCategoryMigrationOutput {
+    pub legacy: String,
+    pub category_id: String,
+    pub created: bool,
+    pub products_updated: u64,
+}
+
+impl From<LegacyCategoryMigration> for LegacyCategoryMigrationOutput {
+    fn from(migration: LegacyCategoryMigration) -> Self {
+        Self {
+            legacy: migration.legacy,
+            category_id: migration.category_id.into_inner(),
+            created: migration.created,
+            products_updated: migration.products_updated,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::application::category::CategoryService;
+use crate::domain::entities::category::CategoryId;
+use crate::domain::pagination::Pagination;
+use crate::presentation::{
+    http::{
+        category::dtos::{
+            CategoryOutput, CreateCategoryInput, LegacyCategoryMigrationOutput, UpdateCategoryInput,
+        },
+        error::ApiError,
+        response::{GenericApiResponse, GenericPagination},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CategoryQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_category).get(list_categories))
+        .route("/roots", get(list_roots))
+        .route("/migrations/legacy", post(migrate_legacy_categories))
+        .route(
+            "/{id}",
+            get(get_category)
+                .put(update_category)
+                .delete(delete_category),
+        )
+        .route("/{id}/children", get(list_children))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_category(
+    State(service): State<Arc<CategoryService>>,
+    ValidatedJson(req): ValidatedJson<CreateCategoryInput>,
+) -> Result<GenericApiResponse<CategoryOutput>, ApiError> {
+    let parent_id = req.parent_id.map(CategoryId::new);
+    let category = service
+        .create_category(&req.name, req.slug.as_deref(), parent_id.as_ref())
+        .await?;
+    Ok(GenericApiResponse::success(category.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_category(
+    State(service): State<Arc<CategoryService>>,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<CategoryOutput>, ApiError> {
+    let category_id = CategoryId::new(id);
+    let category = service.get_category(&category_id).await?;
+    Ok(GenericApiResponse::success(category.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_categories(
+    State(service): State<Arc<CategoryService>>,
+    Query(query): Query<CategoryQuery>,
+) -> Result<GenericApiResponse<GenericPagination<CategoryOutput>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+
+    let categories = service.list_categories(pagination).await?;
+    let total = service.count_categories().await?;
+    let data: Vec<CategoryOutput> = categories.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_roots(
+    State(service): State<Arc<CategoryService>>,
+) -> Result<GenericApiResponse<Vec<CategoryOutput>>, ApiError> {
+    let categories = service.list_children(None).await?;
+    let dtos = categories.into_iter().map(Into::into).collect();
+    Ok(GenericApiResponse::success(dtos))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_children(
+    State(service): State<Arc<CategoryService>>,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<Vec<CategoryOutput>>, ApiError> {
+    let category_id = CategoryId::new(id);
+    let categories = service.list_children(Some(&category_id)).await?;
+    let dtos = categories.into_iter().map(Into::into).collect();
+    Ok(GenericApiResponse::success(dtos))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn update_category(
+    State(service): State<Arc<CategoryService>>,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateCategoryInput>,
+) -> Result<GenericApiResponse<CategoryOutput>, ApiError> {
+    let category_id = CategoryId::new(id);
+    let parent_id = req.parent_id.map(CategoryId::new);
+    let category = service
+        .update_category(
+            &category_id,
+            &req.name,
+            req.slug.as_deref(),
+            parent_id.as_ref(),
+        )
+        .await?;
+    Ok(GenericApiResponse::success(category.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn delete_category(
+    State(service): State<Arc<CategoryService>>,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<()>, ApiError> {
+    let category_id = CategoryId::new(id);
+    service.delete_category(&category_id).await?;
+    Ok(GenericApiResponse::success(()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn migrate_legacy_categories(
+    State(service): State<Arc<CategoryService>>,
+) -> Result<GenericApiResponse<Vec<LegacyCategoryMigrationOutput>>, ApiError> {
+    let report = service.migrate_legacy_categories().await?;
+    let dtos = report.into_iter().map(Into::into).collect();
+    Ok(GenericApiResponse::success(dtos))
+}
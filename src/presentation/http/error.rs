@@ -24,6 +24,18 @@ pub enum ApiError {
     #[error("Forbidden: {0}")]
     Forbidden(String),
 
+    #[error("Not acceptable: {0}")]
+    NotAcceptable(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
+
     #[error("Business logic error: {0}")]
     UnprocessableEntity(String),
 
@@ -49,6 +61,10 @@ impl From<DomainError> for ApiError {
             DomainError::Unauthorized(msg) => ApiError::Unauthorized(msg),
             DomainError::Forbidden(msg) => ApiError::Forbidden(msg),
             DomainError::BusinessRule(msg) => ApiError::UnprocessableEntity(msg),
+            DomainError::Conflict(msg) => ApiError::Conflict(msg),
+            err @ DomainError::VersionConflict { .. } => {
+                ApiError::PreconditionFailed(err.to_string())
+            }
             DomainError::ExternalService { service, message } => {
                 tracing::error!("External service error [{}]: {}", service, message);
                 ApiError::Internal(format!("External service error: {}", service))
@@ -73,6 +89,10 @@ impl IntoResponse for ApiError {
             ApiError::Conflict(msg) => (StatusCode::CONFLICT, msg),
             ApiError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
             ApiError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
+            ApiError::NotAcceptable(msg) => (StatusCode::NOT_ACCEPTABLE, msg),
+            ApiError::PreconditionFailed(msg) => (StatusCode::PRECONDITION_FAILED, msg),
+            ApiError::PreconditionRequired(msg) => (StatusCode::PRECONDITION_REQUIRED, msg),
+            ApiError::TooManyRequests(msg) => (StatusCode::TOO_MANY_REQUESTS, msg),
             ApiError::UnprocessableEntity(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg),
             ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
@@ -0,0 +1,85 @@
+use axum::{
+    extract::FromRequestParts,
+    http::{HeaderMap, HeaderValue, StatusCode, header, request::Parts},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::presentation::http::{error::ApiError, response::GenericApiResponse};
+
+/// Entity tag of an entity version: `"3"`.
+pub fn etag(version: u64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Version named by an entity tag; weak tags (`W/"3"`) are accepted.
+fn parse(tag: &str) -> Option<u64> {
+    let tag = tag.trim();
+    let tag = tag.strip_prefix("W/").unwrap_or(tag);
+    tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+}
+
+/// Version from the `If-Match` header of a PUT or PATCH. Required: updates
+/// without it are rejected with `428 Precondition Required`.
+#[derive(Debug, Clone, Copy)]
+pub struct IfMatch(pub u64);
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts.headers.get(header::IF_MATCH).ok_or_else(|| {
+            ApiError::PreconditionRequired(
+                "If-Match header with the resource ETag is required".to_string(),
+            )
+        })?;
+
+        value
+            .to_str()
+            .ok()
+            .and_then(parse)
+            .map(IfMatch)
+            .ok_or_else(|| {
+                ApiError::BadRequest("If-Match must be a single ETag from this API".to_string())
+            })
+    }
+}
+
+/// `response` with the `ETag` of `version`.
+pub fn with_etag<T: Serialize>(version: u64, response: GenericApiResponse<T>) -> Response {
+    let mut response = response.into_response();
+    insert_etag(&mut response, version);
+    response
+}
+
+/// Like [`with_etag`], but answers `304 Not Modified` without a body when the
+/// request's `If-None-Match` already names `version`.
+pub fn conditional<T: Serialize>(
+    headers: &HeaderMap,
+    version: u64,
+    response: GenericApiResponse<T>,
+) -> Response {
+    let not_modified = headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || parse(tag) == Some(version));
+
+    if !not_modified {
+        return with_etag(version, response);
+    }
+
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    insert_etag(&mut response, version);
+    response
+}
+
+fn insert_etag(response: &mut Response, version: u64) {
+    if let Ok(value) = HeaderValue::from_str(&etag(version)) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::domain::entities::country::Country;
+use crate::domain::filter::DateRange;
+use crate::domain::stream::DomainStream;
+use crate::presentation::http::error::ApiError;
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Rows already pulled from the cursor are coalesced into one chunk, up to this many.
+const ROWS_PER_CHUNK: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// Picks the format from the `Accept` header; NDJSON when unspecified.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, ApiError> {
+        let accept = headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("*/*");
+
+        for media in accept.split(',') {
+            let mime = media.split(';').next().unwrap_or_default().trim();
+            match mime.to_ascii_lowercase().as_str() {
+                "text/csv" | "application/csv" => return Ok(Self::Csv),
+                "application/x-ndjson" | "application/ndjson" | "application/jsonl" | "*/*" => {
+                    return Ok(Self::Ndjson);
+                }
+                _ => {}
+            }
+        }
+
+        Err(ApiError::NotAcceptable(format!(
+            "Cannot export as '{}': use text/csv or application/x-ndjson",
+            accept
+        )))
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// Query parameters shared by every export endpoint.
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Render timestamps in this country's timezone (`MEX`, `CHL`, `COL`, `PER`).
+    pub country: Option<String>,
+    /// Only rows created at or after this instant (RFC 3339).
+    pub from: Option<DateTime<Utc>>,
+    /// Only rows created before this instant (RFC 3339).
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl ExportQuery {
+    pub fn range(&self) -> DateRange {
+        DateRange {
+            from: self.from,
+            to: self.to,
+        }
+    }
+
+    pub fn timestamps(&self) -> Result<Timestamps, ApiError> {
+        let tz = match self.country.as_deref() {
+            Some(code) => Some(
+                code.to_ascii_uppercase()
+                    .parse::<Country>()
+                    .map_err(ApiError::BadRequest)?
+                    .timezone_offset(),
+            ),
+            None => None,
+        };
+        Ok(Timestamps(tz))
+    }
+}
+
+/// Formats timestamps in UTC or in the requested country's timezone.
+#[derive(Debug, Clone, Copy)]
+pub struct Timestamps(Option<chrono_tz::Tz>);
+
+impl Timestamps {
+    pub fn format(&self, value: DateTime<Utc>) -> String {
+        match self.0 {
+            Some(tz) => value.with_timezone(&tz).to_rfc3339(),
+            None => value.to_rfc3339(),
+        }
+    }
+}
+
+/// Streams `rows` as a chunked CSV/NDJSON download without buffering the collection.
+pub fn stream_response<T, R, F>(
+    rows: DomainStream<T>,
+    format: ExportFormat,
+    filename: &str,
+    to_row: F,
+) -> Response
+where
+    T: Send + 'static,
+    R: Serialize,
+    F: Fn(T) -> R + Send + 'static,
+{
+    let mut encoder = RowEncoder::new(format);
+
+    let body = rows.ready_chunks(ROWS_PER_CHUNK).map(move |items| {
+        let mut chunk = Vec::new();
+        for item in items {
+            let entity = item.map_err(|e| {
+                tracing::error!(error = %e, "Export aborted while reading from storage");
+                std::io::Error::other(e.to_string())
+            })?;
+            encoder
+                .encode(&to_row(entity), &mut chunk)
+                .map_err(std::io::Error::other)?;
+        }
+        Ok::<_, std::io::Error>(Bytes::from(chunk))
+    });
+
+    let disposition = format!(
+        "attachment; filename=\"{}.{}\"",
+        filename,
+        format.extension()
+    );
+
+    (
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+struct RowEncoder {
+    format: ExportFormat,
+    wrote_header: bool,
+}
+
+impl RowEncoder {
+    fn new(format: ExportFormat) -> Self {
+        Self {
+            format,
+            wrote_header: false,
+        }
+    }
+
+    fn encode<R: Serialize>(&mut self, row: &R, out: &mut Vec<u8>) -> Result<(), String> {
+        match self.format {
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut *out, row).map_err(|e| e.to_string())?;
+                out.push(b'\n');
+            }
+            ExportFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .has_headers(!self.wrote_header)
+                    .from_writer(&mut *out);
+                writer.serialize(row).map_err(|e| e.to_string())?;
+                writer.flush().map_err(|e| e.to_string())?;
+                self.wrote_header = true;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AdjustStockInput {
+    /// Signed change in units; negative values remove stock.
+    pub delta: i32,
+
+    #[validate(length(min = 3, max = 500, message = "Reason must be 3-500 characters"))]
+    pub reason: String,
+}
@@ -0,0 +1,70 @@
+use crate::application::inventory::ReconciliationReport;
+use crate::domain::entities::inventory::{InventoryMovement, MovementKind, StockDrift};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct MovementOutput {
+    pub id: String,
+    pub product_id: String,
+    pub delta: i32,
+    pub kind: MovementKind,
+    pub reference: Option<String>,
+    pub reason: Option<String>,
+    pub stock_after: Option<i32>,
+    pub created_at: String,
+}
+
+impl From<InventoryMovement> for MovementOutput {
+    fn from(movement: InventoryMovement) -> Self {
+        Self {
+            id: movement.id.map(|id| id.into_inner()).unwrap_or_default(),
+            product_id: movement.product_id.into_inner(),
+            delta: movement.delta,
+            kind: movement.kind,
+            reference: movement.reference,
+            reason: movement.reason,
+            stock_after: movement.stock_after,
+            created_at: movement.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StockDriftOutput {
+    pub product_id: String,
+    pub sku: String,
+    pub stock: i32,
+    pub ledger_stock: i64,
+    pub drift: i64,
+}
+
+impl From<StockDrift> for StockDriftOutput {
+    fn from(drift: StockDrift) -> Self {
+        Self {
+            drift: drift.drift(),
+            product_id: drift.product_id.into_inner(),
+            sku: drift.sku,
+            stock: drift.stock,
+            ledger_stock: drift.ledger_stock,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ReconciliationReportOutput {
+    pub checked: u64,
+    pub in_sync: u64,
+    pub baselined: u64,
+    pub drifted: Vec<StockDriftOutput>,
+}
+
+impl From<ReconciliationReport> for ReconciliationReportOutput {
+    fn from(report: ReconciliationReport) -> Self {
+        Self {
+            checked: report.checked,
+            in_sync: report.in_sync,
+            baselined: report.baselined,
+            drifted: report.drifted.into_iter().map(Into::into).collect(),
+        }
+    }
+}
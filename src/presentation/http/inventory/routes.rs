@@ -0,0 +1,79 @@
+use crate::application::inventory::InventoryService;
+use crate::domain::entities::product::ProductId;
+use crate::domain::pagination::Pagination;
+use crate::presentation::{
+    http::{
+        error::ApiError,
+        inventory::dtos::{AdjustStockInput, MovementOutput, ReconciliationReportOutput},
+        product::dtos::ProductOutput,
+        response::{GenericApiResponse, GenericPagination},
+        validation::ValidatedJson,
+    },
+    state::AppState,
+};
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MovementQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/products/{id}/movements", get(list_movements))
+        .route("/products/{id}/adjustments", post(adjust_stock))
+        .route("/reconciliation", post(reconcile))
+}
+
+/// Ledger of stock movements for one product, newest first.
+#[tracing::instrument(skip_all)]
+pub async fn list_movements(
+    State(service): State<Arc<InventoryService>>,
+    Path(id): Path<String>,
+    Query(query): Query<MovementQuery>,
+) -> Result<GenericApiResponse<GenericPagination<MovementOutput>>, ApiError> {
+    let product_id = ProductId::new(id);
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+
+    let movements = service.list_movements(&product_id, pagination).await?;
+    let total = service.count_movements(&product_id).await?;
+    let data: Vec<MovementOutput> = movements.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
+}
+
+/// Manual stock correction; the reason is stored in the ledger.
+#[tracing::instrument(skip_all)]
+pub async fn adjust_stock(
+    State(service): State<Arc<InventoryService>>,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<AdjustStockInput>,
+) -> Result<GenericApiResponse<ProductOutput>, ApiError> {
+    let product_id = ProductId::new(id);
+    let product = service
+        .adjust_stock(&product_id, req.delta, &req.reason)
+        .await?;
+    Ok(GenericApiResponse::success(product.into()))
+}
+
+/// Recomputes stock from the ledger and reports products that drifted.
+#[tracing::instrument(skip_all)]
+pub async fn reconcile(
+    State(service): State<Arc<InventoryService>>,
+) -> Result<GenericApiResponse<ReconciliationReportOutput>, ApiError> {
+    let report = service.reconcile().await?;
+    Ok(GenericApiResponse::success(report.into()))
+}
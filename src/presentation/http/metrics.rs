@@ -0,0 +1,36 @@
+use crate::domain::port::metrics::MetricsPort;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Route label for requests that matched no route, to keep label cardinality bounded.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Records rate, errors and duration of every request, labelled with the
+/// matched route template.
+pub async fn track_requests(
+    State(metrics): State<Arc<dyn MetricsPort>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let started = Instant::now();
+    let method = request.method().clone();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+
+    let response = next.run(request).await;
+
+    metrics.record_http_request(
+        method.as_str(),
+        route.as_deref().unwrap_or(UNMATCHED_ROUTE),
+        response.status().as_u16(),
+        started.elapsed(),
+    );
+    response
+}
@@ -1,16 +1,32 @@
 use crate::presentation::state::AppState;
 use axum::Router;
 
+pub mod actor;
+pub mod admin;
+pub mod audit;
+pub mod category;
 pub mod error;
+pub mod etag;
+pub mod export;
+pub mod inventory;
+pub mod metrics;
 pub mod order;
 pub mod product;
+pub mod rate_limit;
 pub mod response;
+pub mod trace_context;
 pub mod user;
 pub mod validation;
+pub mod webhook;
 
 pub fn app_router() -> Router<AppState> {
     Router::new()
         .nest("/users", user::routes::router())
         .nest("/products", product::routes::router())
+        .nest("/categories", category::routes::router())
         .nest("/orders", order::routes::router())
+        .nest("/inventory", inventory::routes::router())
+        .nest("/webhooks", webhook::routes::router())
+        .nest("/audit", audit::routes::router())
+        .nest("/admin", admin::routes::router())
 }
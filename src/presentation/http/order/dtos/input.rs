@@ -11,4 +11,8 @@ pub struct CreateOrderInput {
 
     #[validate(range(min = 1, message = "Quantity must be at least 1"))]
     pub quantity: i32,
+
+    /// ISO 3166-1 alpha-3 market code (`MEX`, `CHL`, `COL`, `PER`).
+    #[validate(length(equal = 3, message = "Country must be a 3-letter code"))]
+    pub country: Option<String>,
 }
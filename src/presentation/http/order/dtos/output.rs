@@ -1,4 +1,5 @@
-use crate::domain::entities::order::{Order, OrderId};
+use crate::domain::entities::order::{Order, OrderId, OrderStatus};
+use crate::presentation::http::export::Timestamps;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -8,12 +9,17 @@ pub struct OrderOutput {
     pub product_id: String,
     pub quantity: i32,
     pub total_price: f64,
+    pub status: OrderStatus,
+    pub country: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Same value as the `ETag` header; send it back in `If-Match`.
+    pub version: u64,
 }
 
 impl From<Order> for OrderOutput {
     fn from(order: Order) -> Self {
+        let status = order.status();
         Self {
             id: order
                 .id
@@ -23,8 +29,37 @@ impl From<Order> for OrderOutput {
             product_id: order.product_id.into_inner(),
             quantity: order.quantity,
             total_price: order.total_price,
+            status,
+            country: order.country.map(|country| country.to_string()),
             created_at: order.created_at.to_rfc3339(),
             updated_at: order.updated_at.to_rfc3339(),
+            version: order.version,
+        }
+    }
+}
+
+/// Flat row used by `GET /orders/export`.
+#[derive(Serialize)]
+pub struct OrderExportRow {
+    pub id: String,
+    pub user_id: String,
+    pub product_id: String,
+    pub quantity: i32,
+    pub total_price: f64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl OrderExportRow {
+    pub fn new(order: Order, timestamps: &Timestamps) -> Self {
+        Self {
+            id: order.id.map(|id| id.into_inner()).unwrap_or_default(),
+            user_id: order.user_id.into_inner(),
+            product_id: order.product_id.into_inner(),
+            quantity: order.quantity,
+            total_price: order.total_price,
+            created_at: timestamps.format(order.created_at),
+            updated_at: timestamps.format(order.updated_at),
         }
     }
 }
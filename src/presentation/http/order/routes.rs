@@ -1,12 +1,17 @@
 use crate::application::order::OrderService;
-use crate::domain::entities::order::OrderId;
+use crate::application::order_feed::{OrderUpdate, OrderUpdateFilter};
+use crate::domain::entities::country::Country;
+use crate::domain::entities::order::{OrderId, OrderStatus};
 use crate::domain::pagination::Pagination;
 use crate::domain::entities::product::ProductId;
 use crate::domain::entities::user::UserId;
 use crate::presentation::{
     http::{
+        actor::RequestActor,
         error::ApiError,
-        order::dtos::{CreateOrderInput, OrderOutput},
+        etag,
+        export::{self, ExportFormat, ExportQuery},
+        order::dtos::{CreateOrderInput, OrderExportRow, OrderOutput},
         response::GenericApiResponse,
         validation::ValidatedJson,
     },
@@ -15,12 +20,23 @@ use crate::presentation::{
 use axum::{
     Router,
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::{
+        Response,
+        sse::{Event, KeepAlive, Sse},
+    },
     routing::{get, post},
 };
+use futures::Stream;
 use serde::Deserialize;
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 use validator::Validate;
 
+/// Comment line sent on idle streams so proxies keep the connection open.
+const STREAM_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct OrderQuery {
     #[validate(range(min = 1))]
@@ -30,33 +46,134 @@ pub struct OrderQuery {
     pub limit: Option<u32>,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct OrderExportQuery {
+    #[serde(flatten)]
+    pub export: ExportQuery,
+
+    #[validate(length(equal = 24, message = "Invalid User ID format"))]
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct OrderStreamQuery {
+    #[validate(length(equal = 24, message = "Invalid User ID format"))]
+    pub user_id: Option<String>,
+
+    /// Only orders that are `placed` or `cancelled` after the change.
+    pub status: Option<OrderStatus>,
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_order).get(list_orders))
-        .route("/{id}", get(get_order))
+        .route("/export", get(export_orders))
+        .route("/stream", get(stream_orders))
+        .route("/{id}", get(get_order).delete(cancel_order))
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn create_order(
     State(service): State<Arc<OrderService>>,
+    RequestActor(actor): RequestActor,
     ValidatedJson(req): ValidatedJson<CreateOrderInput>,
-) -> Result<GenericApiResponse<OrderOutput>, ApiError> {
+) -> Result<Response, ApiError> {
     let user_id = UserId::new(req.user_id);
     let product_id = ProductId::new(req.product_id);
+    let country = req
+        .country
+        .map(|code| code.to_ascii_uppercase().parse::<Country>())
+        .transpose()
+        .map_err(ApiError::BadRequest)?;
     let order = service
-        .create_order(&user_id, &product_id, req.quantity)
+        .create_order(&actor, &user_id, &product_id, req.quantity, country)
+        .await?;
+    let version = order.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(OrderOutput::from(order)),
+    ))
+}
+
+/// Streams orders as CSV or NDJSON depending on the `Accept` header.
+#[tracing::instrument(skip_all)]
+pub async fn export_orders(
+    State(service): State<Arc<OrderService>>,
+    Query(query): Query<OrderExportQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let format = ExportFormat::from_headers(&headers)?;
+    let timestamps = query.export.timestamps()?;
+    let user_id = query.user_id.map(UserId::new);
+
+    let orders = service
+        .export_orders(&query.export.range(), user_id.as_ref())
         .await?;
-    Ok(GenericApiResponse::success(order.into()))
+    Ok(export::stream_response(
+        orders,
+        format,
+        "orders",
+        move |order| OrderExportRow::new(order, &timestamps),
+    ))
+}
+
+/// Server-Sent Events stream of order changes (`order_created`,
+/// `order_cancelled`). Send `Last-Event-ID` to resume after a disconnect.
+#[tracing::instrument(skip_all)]
+pub async fn stream_orders(
+    State(service): State<Arc<OrderService>>,
+    Query(query): Query<OrderStreamQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let filter = OrderUpdateFilter {
+        user_id: query.user_id.map(UserId::new),
+        status: query.status,
+    };
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok());
+
+    let subscription = service.subscribe_updates(filter, last_event_id);
+    let events = futures::stream::unfold(subscription, |mut subscription| async move {
+        let update = subscription.next().await?;
+        Some((Ok(order_event(&update)), subscription))
+    });
+
+    Ok(Sse::new(events).keep_alive(
+        KeepAlive::new()
+            .interval(STREAM_HEARTBEAT_INTERVAL)
+            .text("heartbeat"),
+    ))
+}
+
+fn order_event(update: &OrderUpdate) -> Event {
+    let data = OrderOutput::from(update.order.clone());
+    Event::default()
+        .id(&update.id)
+        .event(update.kind.as_str())
+        .data(serde_json::to_string(&data).unwrap_or_default())
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn get_order(
     State(service): State<Arc<OrderService>>,
     Path(id): Path<String>,
-) -> Result<GenericApiResponse<OrderOutput>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let order_id = OrderId::new(id);
     let order = service.get_order(&order_id).await?;
-    Ok(GenericApiResponse::success(order.into()))
+    let version = order.version;
+    Ok(etag::conditional(
+        &headers,
+        version,
+        GenericApiResponse::success(OrderOutput::from(order)),
+    ))
 }
 
 #[tracing::instrument(skip_all)]
@@ -73,3 +190,15 @@ pub async fn list_orders(
     let dtos = orders.into_iter().map(Into::into).collect();
     Ok(GenericApiResponse::success(dtos))
 }
+
+/// Cancels the order and returns its units to stock.
+#[tracing::instrument(skip_all)]
+pub async fn cancel_order(
+    State(service): State<Arc<OrderService>>,
+    RequestActor(actor): RequestActor,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<()>, ApiError> {
+    let order_id = OrderId::new(id);
+    service.cancel_order(&actor, &order_id).await?;
+    Ok(GenericApiResponse::success(()))
+}
@@ -12,8 +12,8 @@ pub struct CreateProductInput {
     #[validate(range(min = 0, message = "Stock must be non-negative"))]
     pub stock: i32,
 
-    #[validate(length(min = 1, message = "Category is required"))]
-    pub category: String,
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub category_id: String,
 
     #[validate(length(min = 1, message = "SKU is required"))]
     pub sku: String,
@@ -26,11 +26,52 @@ pub struct CreateProductInput {
 pub struct UpdateProductMetadataInput {
     pub description: Option<String>,
 
-    #[validate(length(min = 1, message = "Category is required"))]
-    pub category: String,
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub category_id: String,
 
     pub tags: Vec<String>,
 
     #[validate(length(min = 1, message = "SKU is required"))]
     pub sku: String,
 }
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateReorderThresholdInput {
+    /// `null` disables low-stock alerts for the product.
+    #[validate(range(min = 0, message = "Threshold must be non-negative"))]
+    pub threshold: Option<i32>,
+}
+
+/// One CSV row of a bulk import. Tags are `|`-separated.
+#[derive(Debug, Deserialize)]
+pub struct ImportProductCsvRow {
+    pub name: String,
+    pub price: f64,
+    pub stock: i32,
+    pub category_id: String,
+    pub sku: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Option<String>,
+}
+
+impl From<ImportProductCsvRow> for CreateProductInput {
+    fn from(row: ImportProductCsvRow) -> Self {
+        Self {
+            name: row.name,
+            price: row.price,
+            stock: row.stock,
+            category_id: row.category_id,
+            sku: row.sku,
+            description: row.description.filter(|d| !d.is_empty()),
+            tags: row.tags.map(|tags| {
+                tags.split('|')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            }),
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::application::product::{ImportReport, ImportRowResult, ImportRowStatus};
 use crate::domain::entities::product::{Product, ProductId};
+use crate::presentation::http::export::Timestamps;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -9,15 +11,20 @@ pub struct ProductOutput {
     pub stock: i32,
     pub status: String,
     pub description: Option<String>,
-    pub category: Option<String>,
+    pub category_id: Option<String>,
     pub tags: Option<Vec<String>>,
     pub sku: Option<String>,
+    pub reorder_threshold: Option<i32>,
+    pub low_stock: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Same value as the `ETag` header; send it back in `If-Match`.
+    pub version: u64,
 }
 
 impl From<Product> for ProductOutput {
     fn from(product: Product) -> Self {
+        let low_stock = product.is_low_stock();
         Self {
             id: product
                 .id
@@ -28,11 +35,126 @@ impl From<Product> for ProductOutput {
             stock: product.stock,
             status: format!("{:?}", product.status),
             description: product.metadata.description,
-            category: Some(product.metadata.category),
+            category_id: product.metadata.category_id.map(|id| id.into_inner()),
             tags: Some(product.metadata.tags),
             sku: Some(product.metadata.sku),
+            reorder_threshold: product.reorder_threshold,
+            low_stock,
             created_at: product.created_at.to_rfc3339(),
             updated_at: product.updated_at.to_rfc3339(),
+            version: product.version,
+        }
+    }
+}
+
+/// Entry of the `GET /products/low-stock` report.
+#[derive(Serialize)]
+pub struct LowStockOutput {
+    pub id: String,
+    pub sku: String,
+    pub name: String,
+    pub stock: i32,
+    pub reorder_threshold: i32,
+    /// Units needed to get back to the threshold.
+    pub shortfall: i32,
+    pub alerted_at: Option<String>,
+}
+
+impl From<Product> for LowStockOutput {
+    fn from(product: Product) -> Self {
+        let threshold = product.reorder_threshold.unwrap_or_default();
+        Self {
+            id: product.id.map(|id| id.into_inner()).unwrap_or_default(),
+            sku: product.metadata.sku,
+            name: product.name,
+            stock: product.stock,
+            reorder_threshold: threshold,
+            shortfall: (threshold - product.stock).max(0),
+            alerted_at: product.low_stock_alerted_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+/// Flat row used by `GET /products/export`; tags are `|`-separated like the import format.
+#[derive(Serialize)]
+pub struct ProductExportRow {
+    pub id: String,
+    pub sku: String,
+    pub name: String,
+    pub price: f64,
+    pub stock: i32,
+    pub status: String,
+    pub description: Option<String>,
+    pub category_id: Option<String>,
+    pub tags: String,
+    pub reorder_threshold: Option<i32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl ProductExportRow {
+    pub fn new(product: Product, timestamps: &Timestamps) -> Self {
+        Self {
+            id: product.id.map(|id| id.into_inner()).unwrap_or_default(),
+            sku: product.metadata.sku,
+            name: product.name,
+            price: product.price,
+            stock: product.stock,
+            status: format!("{:?}", product.status),
+            description: product.metadata.description,
+            category_id: product.metadata.category_id.map(|id| id.into_inner()),
+            tags: product.metadata.tags.join("|"),
+            reorder_threshold: product.reorder_threshold,
+            created_at: timestamps.format(product.created_at),
+            updated_at: timestamps.format(product.updated_at),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImportRowOutput {
+    pub row: usize,
+    pub sku: Option<String>,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl From<ImportRowResult> for ImportRowOutput {
+    fn from(result: ImportRowResult) -> Self {
+        Self {
+            row: result.row,
+            sku: result.sku,
+            status: match result.status {
+                ImportRowStatus::Created => "created",
+                ImportRowStatus::Updated => "updated",
+                ImportRowStatus::Failed => "failed",
+            },
+            product_id: result.product_id.map(|id| id.into_inner()),
+            reason: result.reason,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ImportReportOutput {
+    pub dry_run: bool,
+    pub created: u64,
+    pub updated: u64,
+    pub failed: u64,
+    pub rows: Vec<ImportRowOutput>,
+}
+
+impl From<ImportReport> for ImportReportOutput {
+    fn from(report: ImportReport) -> Self {
+        Self {
+            dry_run: report.dry_run,
+            created: report.created,
+            updated: report.updated,
+            failed: report.failed,
+            rows: report.rows.into_iter().map(Into::into).collect(),
         }
     }
 }
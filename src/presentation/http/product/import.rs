@@ -0,0 +1,150 @@
+use crate::presentation::http::error::ApiError;
+use crate::presentation::http::product::dtos::{CreateProductInput, ImportProductCsvRow};
+use axum::body::Body;
+use futures::TryStreamExt;
+use std::io::{BufRead, BufReader, Read};
+use tokio::sync::mpsc;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+use validator::Validate;
+
+/// Parsed rows buffered between the blocking parser and the request handler.
+const ROW_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ImportFormat {
+    /// Explicit `?format=` wins over the request `Content-Type`.
+    pub fn detect(explicit: Option<&str>, content_type: Option<&str>) -> Result<Self, ApiError> {
+        let value = explicit.or(content_type).unwrap_or_default();
+        let mime = value.split(';').next().unwrap_or_default().trim();
+
+        match mime.to_ascii_lowercase().as_str() {
+            "csv" | "text/csv" | "application/csv" => Ok(Self::Csv),
+            "ndjson"
+            | "jsonl"
+            | "application/x-ndjson"
+            | "application/ndjson"
+            | "application/jsonl" => Ok(Self::Ndjson),
+            _ => Err(ApiError::BadRequest(format!(
+                "Unsupported import format '{}': use text/csv or application/x-ndjson",
+                value
+            ))),
+        }
+    }
+}
+
+/// A data row after parsing and `CreateProductInput` validation.
+pub struct ParsedRow {
+    /// 1-based data row number (the CSV header is not counted).
+    pub row: usize,
+    pub sku: Option<String>,
+    pub input: Result<CreateProductInput, String>,
+}
+
+/// Parses `body` on a blocking thread and yields rows as they are decoded,
+/// so the upload is never held in memory as a whole.
+pub fn parse_body(body: Body, format: ImportFormat) -> mpsc::Receiver<ParsedRow> {
+    let (tx, rx) = mpsc::channel(ROW_CHANNEL_CAPACITY);
+
+    let stream = body.into_data_stream().map_err(std::io::Error::other);
+    let reader = SyncIoBridge::new(StreamReader::new(stream));
+
+    tokio::task::spawn_blocking(move || match format {
+        ImportFormat::Csv => parse_csv(reader, &tx),
+        ImportFormat::Ndjson => parse_ndjson(reader, &tx),
+    });
+
+    rx
+}
+
+fn parse_csv(reader: impl Read, tx: &mpsc::Sender<ParsedRow>) {
+    let mut csv_reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+
+    let headers = match csv_reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(e) => {
+            let _ = tx.blocking_send(failed(1, None, format!("Invalid CSV header: {}", e)));
+            return;
+        }
+    };
+    let sku_column = headers.iter().position(|h| h == "sku");
+
+    for (index, record) in csv_reader.records().enumerate() {
+        let row = index + 1;
+        let parsed = match record {
+            Ok(record) => {
+                let sku = sku_column.and_then(|i| record.get(i)).map(str::to_string);
+                let input = record
+                    .deserialize::<ImportProductCsvRow>(Some(&headers))
+                    .map_err(|e| e.to_string())
+                    .map(CreateProductInput::from)
+                    .and_then(validate);
+                ParsedRow { row, sku, input }
+            }
+            Err(e) if e.is_io_error() => {
+                let _ = tx.blocking_send(failed(row, None, format!("Upload aborted: {}", e)));
+                return;
+            }
+            Err(e) => failed(row, None, e.to_string()),
+        };
+
+        if tx.blocking_send(parsed).is_err() {
+            return;
+        }
+    }
+}
+
+fn parse_ndjson(reader: impl Read, tx: &mpsc::Sender<ParsedRow>) {
+    let mut row = 0;
+
+    for line in BufReader::new(reader).lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                let _ = tx.blocking_send(failed(row + 1, None, format!("Upload aborted: {}", e)));
+                return;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        row += 1;
+
+        let parsed = match serde_json::from_str::<serde_json::Value>(&line) {
+            Ok(value) => {
+                let sku = value
+                    .get("sku")
+                    .and_then(|s| s.as_str())
+                    .map(str::to_string);
+                let input = serde_json::from_value::<CreateProductInput>(value)
+                    .map_err(|e| e.to_string())
+                    .and_then(validate);
+                ParsedRow { row, sku, input }
+            }
+            Err(e) => failed(row, None, format!("Invalid JSON: {}", e)),
+        };
+
+        if tx.blocking_send(parsed).is_err() {
+            return;
+        }
+    }
+}
+
+fn validate(input: CreateProductInput) -> Result<CreateProductInput, String> {
+    input.validate().map_err(|e| e.to_string())?;
+    Ok(input)
+}
+
+fn failed(row: usize, sku: Option<String>, reason: String) -> ParsedRow {
+    ParsedRow {
+        row,
+        sku,
+        input: Err(reason),
+    }
+}
@@ -1,2 +1,3 @@
 pub mod dtos;
+pub mod import;
 pub mod routes;
@@ -1,19 +1,30 @@
 use crate::application::product::ProductService;
 use crate::domain::pagination::Pagination;
-use crate::domain::entities::product::{ProductId, ProductMetadata};
+use crate::domain::entities::category::CategoryId;
+use crate::domain::entities::product::{ProductDraft, ProductId, ProductMetadata};
 use crate::presentation::{
     http::{
+        actor::RequestActor,
         error::ApiError,
-        product::dtos::{CreateProductInput, ProductOutput, UpdateProductMetadataInput},
-        response::GenericApiResponse,
+        etag::{self, IfMatch},
+        export::{self, ExportFormat, ExportQuery},
+        product::dtos::{
+            CreateProductInput, ImportReportOutput, LowStockOutput, ProductExportRow,
+            ProductOutput, UpdateProductMetadataInput, UpdateReorderThresholdInput,
+        },
+        product::import::{self, ImportFormat},
+        response::{GenericApiResponse, GenericPagination},
         validation::ValidatedJson,
     },
     state::AppState,
 };
 use axum::{
     Router,
+    body::Body,
     extract::{Path, Query, State},
-    routing::{get, patch, post},
+    http::{HeaderMap, header},
+    response::Response,
+    routing::{get, patch, post, put},
 };
 use serde::Deserialize;
 use std::sync::Arc;
@@ -26,41 +37,154 @@ pub struct ProductQuery {
 
     #[validate(range(min = 1, max = 100))]
     pub limit: Option<u32>,
+
+    /// Restrict to this category and all of its descendants.
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub category_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LowStockQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// `csv` or `ndjson`; defaults to the request `Content-Type`.
+    pub format: Option<String>,
+
+    /// Validate and report without writing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ProductExportQuery {
+    #[serde(flatten)]
+    pub export: ExportQuery,
+
+    /// Restrict to this category and all of its descendants.
+    #[validate(length(equal = 24, message = "Invalid Category ID format"))]
+    pub category_id: Option<String>,
 }
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_product).get(list_products))
+        .route("/import", post(import_products))
+        .route("/export", get(export_products))
+        .route("/low-stock", get(list_low_stock))
         .route("/{id}", get(get_product).delete(delete_product))
         .route("/{id}/metadata", patch(update_metadata))
+        .route("/{id}/reorder-threshold", put(update_reorder_threshold))
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn create_product(
     State(service): State<Arc<ProductService>>,
+    RequestActor(actor): RequestActor,
     ValidatedJson(req): ValidatedJson<CreateProductInput>,
-) -> Result<GenericApiResponse<ProductOutput>, ApiError> {
+) -> Result<Response, ApiError> {
     let metadata = ProductMetadata {
         description: req.description,
-        category: req.category,
+        category_id: Some(CategoryId::new(req.category_id)),
         tags: req.tags.unwrap_or_default(),
         sku: req.sku,
     };
 
     let product = service
-        .create_product(&req.name, req.price, req.stock, metadata)
+        .create_product(&actor, &req.name, req.price, req.stock, metadata)
         .await?;
-    Ok(GenericApiResponse::success(product.into()))
+    let version = product.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(ProductOutput::from(product)),
+    ))
+}
+
+/// Streams products as CSV or NDJSON depending on the `Accept` header.
+#[tracing::instrument(skip_all)]
+pub async fn export_products(
+    State(service): State<Arc<ProductService>>,
+    Query(query): Query<ProductExportQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    query
+        .validate()
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let format = ExportFormat::from_headers(&headers)?;
+    let timestamps = query.export.timestamps()?;
+    let category_id = query.category_id.map(CategoryId::new);
+
+    let products = service
+        .export_products(&query.export.range(), category_id.as_ref())
+        .await?;
+    Ok(export::stream_response(
+        products,
+        format,
+        "products",
+        move |product| ProductExportRow::new(product, &timestamps),
+    ))
+}
+
+#[tracing::instrument(skip_all, fields(dry_run = query.dry_run))]
+pub async fn import_products(
+    State(service): State<Arc<ProductService>>,
+    RequestActor(actor): RequestActor,
+    Query(query): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<GenericApiResponse<ImportReportOutput>, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let format = ImportFormat::detect(query.format.as_deref(), content_type)?;
+
+    let mut import = service.start_import(&actor, query.dry_run);
+    let mut rows = import::parse_body(body, format);
+
+    while let Some(parsed) = rows.recv().await {
+        match parsed.input {
+            Ok(req) => {
+                let draft = ProductDraft {
+                    name: req.name,
+                    price: req.price,
+                    stock: req.stock,
+                    metadata: ProductMetadata {
+                        description: req.description,
+                        category_id: Some(CategoryId::new(req.category_id)),
+                        tags: req.tags.unwrap_or_default(),
+                        sku: req.sku,
+                    },
+                };
+                import.push(parsed.row, draft).await?;
+            }
+            Err(reason) => import.reject(parsed.row, parsed.sku, reason),
+        }
+    }
+
+    let report = import.finish().await?;
+    Ok(GenericApiResponse::success(report.into()))
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn get_product(
     State(service): State<Arc<ProductService>>,
     Path(id): Path<String>,
-) -> Result<GenericApiResponse<ProductOutput>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let product_id = ProductId::new(id);
     let product = service.get_product(&product_id).await?;
-    Ok(GenericApiResponse::success(product.into()))
+    let version = product.version;
+    Ok(etag::conditional(
+        &headers,
+        version,
+        GenericApiResponse::success(ProductOutput::from(product)),
+    ))
 }
 
 #[tracing::instrument(skip_all)]
@@ -73,7 +197,14 @@ pub async fn list_products(
         limit: query.limit.unwrap_or(20),
     };
 
-    let products = service.list_products(pagination).await?;
+    let products = match query.category_id {
+        Some(category_id) => {
+            service
+                .list_products_by_category(&CategoryId::new(category_id), pagination)
+                .await?
+        }
+        None => service.list_products(pagination).await?,
+    };
     let dtos = products.into_iter().map(Into::into).collect();
     Ok(GenericApiResponse::success(dtos))
 }
@@ -81,27 +212,72 @@ pub async fn list_products(
 #[tracing::instrument(skip_all)]
 pub async fn update_metadata(
     State(service): State<Arc<ProductService>>,
+    RequestActor(actor): RequestActor,
     Path(id): Path<String>,
+    IfMatch(version): IfMatch,
     ValidatedJson(req): ValidatedJson<UpdateProductMetadataInput>,
-) -> Result<GenericApiResponse<ProductOutput>, ApiError> {
+) -> Result<Response, ApiError> {
     let product_id = ProductId::new(id);
     let metadata = ProductMetadata {
         description: req.description,
-        category: req.category,
+        category_id: Some(CategoryId::new(req.category_id)),
         tags: req.tags,
         sku: req.sku,
     };
 
-    let product = service.update_metadata(&product_id, metadata).await?;
-    Ok(GenericApiResponse::success(product.into()))
+    let product = service
+        .update_metadata(&actor, &product_id, version, metadata)
+        .await?;
+    let version = product.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(ProductOutput::from(product)),
+    ))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn update_reorder_threshold(
+    State(service): State<Arc<ProductService>>,
+    RequestActor(actor): RequestActor,
+    Path(id): Path<String>,
+    IfMatch(version): IfMatch,
+    ValidatedJson(req): ValidatedJson<UpdateReorderThresholdInput>,
+) -> Result<Response, ApiError> {
+    let product_id = ProductId::new(id);
+    let product = service
+        .set_reorder_threshold(&actor, &product_id, version, req.threshold)
+        .await?;
+    let version = product.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(ProductOutput::from(product)),
+    ))
+}
+
+/// Products currently below their reorder threshold, lowest stock first.
+#[tracing::instrument(skip_all)]
+pub async fn list_low_stock(
+    State(service): State<Arc<ProductService>>,
+    Query(query): Query<LowStockQuery>,
+) -> Result<GenericApiResponse<GenericPagination<LowStockOutput>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+
+    let products = service.list_low_stock(pagination).await?;
+    let total = service.count_low_stock().await?;
+    let data: Vec<LowStockOutput> = products.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn delete_product(
     State(service): State<Arc<ProductService>>,
+    RequestActor(actor): RequestActor,
     Path(id): Path<String>,
 ) -> Result<GenericApiResponse<()>, ApiError> {
     let product_id = ProductId::new(id);
-    service.delete_product(&product_id).await?;
+    service.delete_product(&actor, &product_id).await?;
     Ok(GenericApiResponse::success(()))
 }
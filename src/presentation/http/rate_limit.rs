@@ -0,0 +1,146 @@
+use crate::application::api_key::ApiKeyService;
+use crate::domain::entities::api_key::ApiKey;
+use crate::domain::port::rate_limit::{RateLimit, RateLimiterPort};
+use crate::presentation::http::actor::{client_ip, header};
+use crate::presentation::http::error::ApiError;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::{Extensions, HeaderMap, HeaderName, HeaderValue, header::RETRY_AFTER},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Header carrying a partner's API key unless
+/// [`RateLimits::with_api_key_header`] sets another.
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+const API_PREFIX: &str = "/api/v1/";
+
+const RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+const RATELIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+/// The limiter and the limit of each route group.
+#[derive(Clone)]
+pub struct RateLimits {
+    limiter: Arc<dyn RateLimiterPort>,
+    api_keys: Arc<ApiKeyService>,
+    api_key_header: HeaderName,
+    default: RateLimit,
+    groups: Arc<BTreeMap<String, RateLimit>>,
+}
+
+impl RateLimits {
+    pub fn new(
+        limiter: Arc<dyn RateLimiterPort>,
+        api_keys: Arc<ApiKeyService>,
+        default: RateLimit,
+        groups: BTreeMap<String, RateLimit>,
+    ) -> Self {
+        Self {
+            limiter,
+            api_keys,
+            api_key_header: API_KEY_HEADER,
+            default,
+            groups: Arc::new(groups),
+        }
+    }
+
+    pub fn with_api_key_header(mut self, header: HeaderName) -> Self {
+        self.api_key_header = header;
+        self
+    }
+
+    fn limit_for(&self, group: &str) -> RateLimit {
+        self.groups.get(group).copied().unwrap_or(self.default)
+    }
+
+    /// Who the limit applies to: the API key once it is known and not
+    /// revoked, otherwise the client IP. Unknown keys share the IP's bucket,
+    /// so rotating the header does not buy a fresh one.
+    async fn client_key(&self, headers: &HeaderMap, extensions: &Extensions) -> String {
+        if let Some(secret) = header(headers, self.api_key_header.as_str()) {
+            match self.api_keys.authenticate(secret).await {
+                Ok(Some(ApiKey { id: Some(id), .. })) => return format!("key:{}", id),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("API key lookup failed, limiting by IP: {}", e),
+            }
+        }
+        match client_ip(headers, extensions) {
+            Some(ip) => format!("ip:{}", ip),
+            None => "ip:unknown".to_string(),
+        }
+    }
+}
+
+/// Takes one request from the caller's bucket for the route group
+/// (`/api/v1/orders/...` is `orders`), answering 429 with `Retry-After` once
+/// it is empty. Every limited response carries the `RateLimit-*` headers.
+///
+/// Fails open: when the limiter is unreachable, requests go through.
+pub async fn enforce(State(limits): State<RateLimits>, request: Request, next: Next) -> Response {
+    // Unmatched requests are 404s, not worth a round trip to Redis
+    let Some(group) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| route_group(path.as_str()).to_string())
+    else {
+        return next.run(request).await;
+    };
+    let limit = limits.limit_for(&group);
+    let key = format!(
+        "{}:{}",
+        group,
+        limits
+            .client_key(request.headers(), request.extensions())
+            .await
+    );
+
+    let decision = match limits.limiter.acquire(&key, limit).await {
+        Ok(decision) => decision,
+        Err(e) => {
+            tracing::warn!("Rate limiter unavailable, request let through: {}", e);
+            return next.run(request).await;
+        }
+    };
+
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        tracing::info!(group, limit = %limit, "Rate limit exceeded");
+        let mut response =
+            ApiError::TooManyRequests(format!("Rate limit of {} exceeded", limit)).into_response();
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, seconds(decision.retry_after));
+        response
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(RATELIMIT_LIMIT, HeaderValue::from(limit.requests));
+    headers.insert(RATELIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(RATELIMIT_RESET, seconds(decision.reset_after));
+    if let Ok(policy) =
+        HeaderValue::try_from(format!("{};w={}", limit.requests, limit.window.as_secs()))
+    {
+        headers.insert(RATELIMIT_POLICY, policy);
+    }
+    response
+}
+
+/// `/api/v1/orders/{id}` -> `orders`.
+fn route_group(route: &str) -> &str {
+    route
+        .strip_prefix(API_PREFIX)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or(route)
+}
+
+/// Whole seconds, rounded up so clients never retry too early.
+fn seconds(duration: Duration) -> HeaderValue {
+    HeaderValue::from(duration.as_secs() + u64::from(duration.subsec_nanos() > 0))
+}
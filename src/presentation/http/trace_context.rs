@@ -0,0 +1,71 @@
+use crate::domain::port::trace_context::{TRACEPARENT, TraceContextPort};
+use axum::{
+    extract::{MatchedPath, Request, State},
+    http::HeaderValue,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use tower_http::trace::MakeSpan;
+
+/// Request headers that may carry an upstream trace, in order of preference.
+const TRACE_HEADERS: &[&str] = &[TRACEPARENT, "x-cloud-trace-context"];
+
+/// Creates the span of each request, as a child of the caller's trace when
+/// the request carries `traceparent` or `X-Cloud-Trace-Context`.
+#[derive(Clone)]
+pub struct RequestSpan {
+    trace_context: Arc<dyn TraceContextPort>,
+}
+
+impl RequestSpan {
+    pub fn new(trace_context: Arc<dyn TraceContextPort>) -> Self {
+        Self { trace_context }
+    }
+}
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &axum::http::Request<B>) -> tracing::Span {
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or_else(|| request.uri().path(), |path| path.as_str());
+
+        let span = tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            otel.name = %format!("{} {}", request.method(), route),
+            otel.kind = "server",
+        );
+
+        let carrier: Vec<(&str, &str)> = TRACE_HEADERS
+            .iter()
+            .filter_map(|name| {
+                let value = request.headers().get(*name)?.to_str().ok()?;
+                Some((*name, value))
+            })
+            .collect();
+        if !carrier.is_empty() {
+            self.trace_context.continue_trace(&span, &carrier);
+        }
+
+        span
+    }
+}
+
+/// Returns the request's `traceparent` so callers can correlate the response
+/// with the trace, whether it continued theirs or started a new one.
+pub async fn echo_traceparent(
+    State(trace_context): State<Arc<dyn TraceContextPort>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let traceparent = trace_context.current();
+    let mut response = next.run(request).await;
+
+    if let Some(value) = traceparent.and_then(|value| HeaderValue::from_str(&value).ok()) {
+        response.headers_mut().insert(TRACEPARENT, value);
+    }
+    response
+}
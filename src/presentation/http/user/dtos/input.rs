@@ -8,3 +8,11 @@ pub struct CreateUserInput {
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
 }
+
+#[derive(Deserialize, Validate)]
+pub struct UpdateUserInput {
+    #[validate(length(min = 1, message = "Name cannot be empty"))]
+    pub name: String,
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+}
@@ -1,4 +1,5 @@
 use crate::domain::entities::user::{User, UserId};
+use crate::presentation::http::export::Timestamps;
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -8,6 +9,8 @@ pub struct UserOutput {
     pub email: String,
     pub created_at: String,
     pub updated_at: String,
+    /// Same value as the `ETag` header; send it back in `If-Match`.
+    pub version: u64,
 }
 
 impl From<User> for UserOutput {
@@ -21,6 +24,29 @@ impl From<User> for UserOutput {
             email: user.email,
             created_at: user.created_at.to_rfc3339(),
             updated_at: user.updated_at.to_rfc3339(),
+            version: user.version,
+        }
+    }
+}
+
+/// Flat row used by `GET /users/export`.
+#[derive(Serialize)]
+pub struct UserExportRow {
+    pub id: String,
+    pub name: String,
+    pub email: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl UserExportRow {
+    pub fn new(user: User, timestamps: &Timestamps) -> Self {
+        Self {
+            id: user.id.map(|id| id.into_inner()).unwrap_or_default(),
+            name: user.name,
+            email: user.email,
+            created_at: timestamps.format(user.created_at),
+            updated_at: timestamps.format(user.updated_at),
         }
     }
 }
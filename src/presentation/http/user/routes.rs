@@ -3,9 +3,12 @@ use crate::domain::pagination::Pagination;
 use crate::domain::entities::user::{User, UserId};
 use crate::presentation::{
     http::{
+        actor::RequestActor,
         error::ApiError,
+        etag::{self, IfMatch},
+        export::{self, ExportFormat, ExportQuery},
         response::{GenericApiResponse, GenericPagination},
-        user::dtos::{CreateUserInput, UserOutput},
+        user::dtos::{CreateUserInput, UpdateUserInput, UserExportRow, UserOutput},
         validation::ValidatedJson,
     },
     state::AppState,
@@ -13,6 +16,8 @@ use crate::presentation::{
 use axum::{
     Router,
     extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
     routing::{get, post},
 };
 use serde::Deserialize;
@@ -31,26 +36,59 @@ pub struct UserQuery {
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/", post(create_user).get(list_users))
-        .route("/{id}", get(get_user).delete(delete_user))
+        .route("/export", get(export_users))
+        .route("/{id}", get(get_user).put(update_user).delete(delete_user))
 }
 
 #[tracing::instrument(skip_all)]
 pub async fn create_user(
     State(service): State<Arc<UserService>>,
+    RequestActor(actor): RequestActor,
     ValidatedJson(req): ValidatedJson<CreateUserInput>,
-) -> Result<GenericApiResponse<UserOutput>, ApiError> {
-    let user: User = service.create_user(&req.name, &req.email).await?;
-    Ok(GenericApiResponse::success(user.into()))
+) -> Result<Response, ApiError> {
+    let user: User = service.create_user(&actor, &req.name, &req.email).await?;
+    let version = user.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(UserOutput::from(user)),
+    ))
 }
 
+/// Answers `304 Not Modified` when `If-None-Match` carries the current ETag.
 #[tracing::instrument(skip_all)]
 pub async fn get_user(
     State(service): State<Arc<UserService>>,
     Path(id): Path<String>,
-) -> Result<GenericApiResponse<UserOutput>, ApiError> {
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
     let user_id = UserId::new(id);
     let user: User = service.get_user(&user_id).await?;
-    Ok(GenericApiResponse::success(user.into()))
+    let version = user.version;
+    Ok(etag::conditional(
+        &headers,
+        version,
+        GenericApiResponse::success(UserOutput::from(user)),
+    ))
+}
+
+/// Requires `If-Match`; answers `412 Precondition Failed` if the user changed since.
+#[tracing::instrument(skip_all)]
+pub async fn update_user(
+    State(service): State<Arc<UserService>>,
+    RequestActor(actor): RequestActor,
+    Path(id): Path<String>,
+    IfMatch(version): IfMatch,
+    ValidatedJson(req): ValidatedJson<UpdateUserInput>,
+) -> Result<Response, ApiError> {
+    let user_id = UserId::new(id);
+    let user: User = service
+        .update_user(&actor, &user_id, version, &req.name, &req.email)
+        .await?;
+    let version = user.version;
+    Ok(etag::with_etag(
+        version,
+        GenericApiResponse::success(UserOutput::from(user)),
+    ))
 }
 
 #[tracing::instrument(skip_all)]
@@ -69,12 +107,32 @@ pub async fn list_users(
     Ok(GenericApiResponse::paginated(data, total, page, limit))
 }
 
+/// Streams users as CSV or NDJSON depending on the `Accept` header.
+#[tracing::instrument(skip_all)]
+pub async fn export_users(
+    State(service): State<Arc<UserService>>,
+    Query(query): Query<ExportQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let format = ExportFormat::from_headers(&headers)?;
+    let timestamps = query.timestamps()?;
+
+    let users = service.export_users(&query.range()).await?;
+    Ok(export::stream_response(
+        users,
+        format,
+        "users",
+        move |user| UserExportRow::new(user, &timestamps),
+    ))
+}
+
 #[tracing::instrument(skip_all)]
 pub async fn delete_user(
     State(service): State<Arc<UserService>>,
+    RequestActor(actor): RequestActor,
     Path(id): Path<String>,
 ) -> Result<GenericApiResponse<()>, ApiError> {
     let user_id = UserId::new(id);
-    service.delete_user(&user_id).await?;
+    service.delete_user(&actor, &user_id).await?;
     Ok(GenericApiResponse::success(()))
 }
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+use validator::Validate;
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateWebhookInput {
+    #[validate(url(message = "Invalid URL"))]
+    pub url: String,
+
+    /// Event types to receive, or `["*"]` for all of them.
+    #[validate(length(min = 1, message = "At least one event is required"))]
+    pub events: Vec<String>,
+
+    /// Generated when omitted.
+    #[validate(length(min = 16, max = 128, message = "Secret must be 16-128 characters"))]
+    pub secret: Option<String>,
+
+    #[serde(default = "default_active")]
+    pub active: bool,
+
+    #[validate(length(max = 256, message = "Description cannot exceed 256 characters"))]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateWebhookInput {
+    #[validate(url(message = "Invalid URL"))]
+    pub url: String,
+
+    #[validate(length(min = 1, message = "At least one event is required"))]
+    pub events: Vec<String>,
+
+    pub active: bool,
+
+    #[validate(length(max = 256, message = "Description cannot exceed 256 characters"))]
+    pub description: Option<String>,
+}
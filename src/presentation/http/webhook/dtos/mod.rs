@@ -0,0 +1,5 @@
+pub mod input;
+pub mod output;
+
+pub use input::*;
+pub use output::*;
@@ -0,0 +1,97 @@
+use crate::domain::entities::webhook::{
+    DeliveryAttempt, DeliveryStatus, WebhookDelivery, WebhookSubscription,
+};
+use serde::Serialize;
+
+/// Subscription as listed; the secret is never returned after creation.
+#[derive(Serialize)]
+pub struct WebhookOutput {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<WebhookSubscription> for WebhookOutput {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription
+                .id
+                .map(|id| id.into_inner())
+                .unwrap_or_default(),
+            url: subscription.url,
+            events: subscription.events,
+            active: subscription.active,
+            description: subscription.description,
+            created_at: subscription.created_at.to_rfc3339(),
+            updated_at: subscription.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Returned once by `POST /webhooks`, with the signing secret.
+#[derive(Serialize)]
+pub struct CreatedWebhookOutput {
+    #[serde(flatten)]
+    pub webhook: WebhookOutput,
+    pub secret: String,
+}
+
+impl From<WebhookSubscription> for CreatedWebhookOutput {
+    fn from(mut subscription: WebhookSubscription) -> Self {
+        let secret = std::mem::take(&mut subscription.secret);
+        Self {
+            webhook: subscription.into(),
+            secret,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DeliveryAttemptOutput {
+    pub attempted_at: String,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl From<DeliveryAttempt> for DeliveryAttemptOutput {
+    fn from(attempt: DeliveryAttempt) -> Self {
+        Self {
+            attempted_at: attempt.attempted_at.to_rfc3339(),
+            status_code: attempt.status_code,
+            error: attempt.error,
+            duration_ms: attempt.duration_ms,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryOutput {
+    pub id: String,
+    pub subscription_id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub status: DeliveryStatus,
+    pub attempts: Vec<DeliveryAttemptOutput>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryOutput {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.map(|id| id.into_inner()).unwrap_or_default(),
+            subscription_id: delivery.subscription_id.into_inner(),
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status,
+            attempts: delivery.attempts.into_iter().map(Into::into).collect(),
+            created_at: delivery.created_at.to_rfc3339(),
+            updated_at: delivery.updated_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::application::webhook::{SubscriptionInput, WebhookService};
+use crate::domain::entities::webhook::{WebhookDeliveryId, WebhookSubscriptionId};
+use crate::domain::pagination::Pagination;
+use crate::presentation::{
+    http::{
+        error::ApiError,
+        response::{GenericApiResponse, GenericPagination},
+        validation::ValidatedJson,
+        webhook::dtos::{
+            CreateWebhookInput, CreatedWebhookOutput, UpdateWebhookInput, WebhookDeliveryOutput,
+            WebhookOutput,
+        },
+    },
+    state::AppState,
+};
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    routing::{get, post},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct WebhookQuery {
+    #[validate(range(min = 1))]
+    pub page: Option<u32>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", post(create_webhook).get(list_webhooks))
+        .route(
+            "/{id}",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route("/{id}/deliveries", get(list_deliveries))
+        .route("/{id}/deliveries/{delivery_id}/redeliver", post(redeliver))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn create_webhook(
+    State(service): State<Arc<WebhookService>>,
+    ValidatedJson(req): ValidatedJson<CreateWebhookInput>,
+) -> Result<GenericApiResponse<CreatedWebhookOutput>, ApiError> {
+    let input = SubscriptionInput {
+        url: req.url,
+        events: req.events,
+        active: req.active,
+        description: req.description,
+    };
+    let subscription = service.create_subscription(input, req.secret).await?;
+    Ok(GenericApiResponse::success(subscription.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn get_webhook(
+    State(service): State<Arc<WebhookService>>,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<WebhookOutput>, ApiError> {
+    let subscription_id = WebhookSubscriptionId::new(id);
+    let subscription = service.get_subscription(&subscription_id).await?;
+    Ok(GenericApiResponse::success(subscription.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_webhooks(
+    State(service): State<Arc<WebhookService>>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<GenericApiResponse<GenericPagination<WebhookOutput>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+
+    let subscriptions = service.list_subscriptions(pagination).await?;
+    let total = service.count_subscriptions().await?;
+    let data: Vec<WebhookOutput> = subscriptions.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn update_webhook(
+    State(service): State<Arc<WebhookService>>,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<UpdateWebhookInput>,
+) -> Result<GenericApiResponse<WebhookOutput>, ApiError> {
+    let subscription_id = WebhookSubscriptionId::new(id);
+    let input = SubscriptionInput {
+        url: req.url,
+        events: req.events,
+        active: req.active,
+        description: req.description,
+    };
+    let subscription = service.update_subscription(&subscription_id, input).await?;
+    Ok(GenericApiResponse::success(subscription.into()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn delete_webhook(
+    State(service): State<Arc<WebhookService>>,
+    Path(id): Path<String>,
+) -> Result<GenericApiResponse<()>, ApiError> {
+    let subscription_id = WebhookSubscriptionId::new(id);
+    service.delete_subscription(&subscription_id).await?;
+    Ok(GenericApiResponse::success(()))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn list_deliveries(
+    State(service): State<Arc<WebhookService>>,
+    Path(id): Path<String>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<GenericApiResponse<GenericPagination<WebhookDeliveryOutput>>, ApiError> {
+    let subscription_id = WebhookSubscriptionId::new(id);
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(20);
+    let pagination = Pagination { page, limit };
+
+    let deliveries = service
+        .list_deliveries(&subscription_id, pagination)
+        .await?;
+    let total = service.count_deliveries(&subscription_id).await?;
+    let data: Vec<WebhookDeliveryOutput> = deliveries.into_iter().map(Into::into).collect();
+
+    Ok(GenericApiResponse::paginated(data, total, page, limit))
+}
+
+#[tracing::instrument(skip_all)]
+pub async fn redeliver(
+    State(service): State<Arc<WebhookService>>,
+    Path((id, delivery_id)): Path<(String, String)>,
+) -> Result<GenericApiResponse<WebhookDeliveryOutput>, ApiError> {
+    let subscription_id = WebhookSubscriptionId::new(id);
+    let delivery_id = WebhookDeliveryId::new(delivery_id);
+    let delivery = service.redeliver(&subscription_id, &delivery_id).await?;
+    Ok(GenericApiResponse::success(delivery.into()))
+}
@@ -1,6 +1,8 @@
-use axum::{Router, extract::DefaultBodyLimit};
+use axum::{Router, extract::DefaultBodyLimit, middleware, routing::get};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tokio::signal;
+use tokio::sync::watch;
 use tower_http::{
     compression::CompressionLayer,
     cors::{Any, CorsLayer},
@@ -8,13 +10,22 @@ use tower_http::{
     trace::TraceLayer,
 };
 
-use crate::config;
+use crate::application::scheduler::Scheduler;
+use crate::application::tasks::TaskWorkers;
+use crate::config::{self, CorsOrigins};
 use crate::presentation::http;
 use crate::presentation::state::AppState;
 
+/// Produces the body of `GET /metrics`.
+pub type MetricsRenderer = Arc<dyn Fn() -> String + Send + Sync>;
+
 pub struct ServerLauncher {
     state: AppState,
     http_port: Option<u16>,
+    workers: Option<(TaskWorkers, usize)>,
+    scheduler: Option<Scheduler>,
+    metrics: Option<MetricsRenderer>,
+    rate_limits: Option<http::rate_limit::RateLimits>,
 }
 
 impl ServerLauncher {
@@ -22,6 +33,10 @@ impl ServerLauncher {
         Self {
             state,
             http_port: None,
+            workers: None,
+            scheduler: None,
+            metrics: None,
+            rate_limits: None,
         }
     }
 
@@ -30,46 +45,125 @@ impl ServerLauncher {
         self
     }
 
+    /// Run `concurrency` background task workers alongside the servers.
+    pub fn with_workers(mut self, workers: TaskWorkers, concurrency: usize) -> Self {
+        self.workers = Some((workers, concurrency));
+        self
+    }
+
+    /// Run the scheduler's jobs alongside the servers.
+    pub fn with_scheduler(mut self, scheduler: Scheduler) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// Serve `GET /metrics` (outside `/api/v1`, not itself measured).
+    pub fn with_metrics_endpoint(mut self, render: MetricsRenderer) -> Self {
+        self.metrics = Some(render);
+        self
+    }
+
+    /// Limit requests per client and route group under `/api/v1`.
+    pub fn with_rate_limits(mut self, limits: http::rate_limit::RateLimits) -> Self {
+        self.rate_limits = Some(limits);
+        self
+    }
+
     pub async fn run(self) {
         let env = config::get();
 
+        let (stop_workers, shutdown) = watch::channel(false);
+        let mut worker_handles = match &self.workers {
+            Some((workers, concurrency)) => {
+                tracing::info!("Starting {} task workers", concurrency);
+                workers.spawn(*concurrency, shutdown.clone())
+            }
+            None => Vec::new(),
+        };
+        if let Some(scheduler) = &self.scheduler {
+            worker_handles.extend(scheduler.spawn(shutdown));
+        }
+
         if let Some(port) = self.http_port {
             let state = self.state.clone();
+            let order_service = self.state.order_service.clone();
 
-            let cors = if env.cors_origins == "*" {
-                CorsLayer::permissive()
+            let cors = match &env.cors.origins {
+                CorsOrigins::Any => CorsLayer::permissive()
                     .allow_methods(Any)
-                    .allow_headers(Any)
-            } else {
-                let origins: Vec<_> = env
-                    .cors_origins
-                    .split(',')
-                    .map(|s| s.parse().expect("Invalid CORS origin"))
-                    .collect();
-
-                CorsLayer::new()
+                    .allow_headers(Any),
+                CorsOrigins::List(origins) => CorsLayer::new()
                     .allow_methods(Any)
                     .allow_headers(Any)
-                    .allow_origin(origins)
+                    .allow_origin(origins.clone()),
             };
 
-            let rest_router = Router::new()
-                .nest("/api/v1", http::app_router())
-                .layer(TraceLayer::new_for_http())
+            let mut api = Router::new().nest("/api/v1", http::app_router());
+            // Innermost, so rejected requests are still traced and measured
+            if let Some(limits) = self.rate_limits.clone() {
+                api = api.layer(middleware::from_fn_with_state(
+                    limits,
+                    http::rate_limit::enforce,
+                ));
+            }
+
+            let mut rest_router = api
+                .layer(middleware::from_fn_with_state(
+                    state.trace_context.clone(),
+                    http::trace_context::echo_traceparent,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.metrics.clone(),
+                    http::metrics::track_requests,
+                ))
+                .layer(TraceLayer::new_for_http().make_span_with(
+                    http::trace_context::RequestSpan::new(state.trace_context.clone()),
+                ))
                 .layer(CompressionLayer::new())
                 .layer(RequestDecompressionLayer::new())
                 .layer(DefaultBodyLimit::max(32 * 1024 * 1024))
                 .layer(cors)
                 .with_state(state);
 
+            if let Some(render) = self.metrics.clone() {
+                rest_router = rest_router.route(
+                    "/metrics",
+                    get(move || async move {
+                        (
+                            [(
+                                axum::http::header::CONTENT_TYPE,
+                                "text/plain; version=0.0.4",
+                            )],
+                            render(),
+                        )
+                    }),
+                );
+            }
+
             let rest_addr = SocketAddr::from(([0, 0, 0, 0], port));
             tracing::info!("REST Server listening on {}", rest_addr);
 
             let listener = tokio::net::TcpListener::bind(rest_addr).await.unwrap();
-            axum::serve(listener, rest_router)
-                .with_graceful_shutdown(shutdown_signal("REST"))
-                .await
-                .unwrap();
+            // Peer addresses feed the audit log when X-Forwarded-For is absent
+            axum::serve(
+                listener,
+                rest_router.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(async move {
+                shutdown_signal("REST").await;
+                // Open SSE streams would otherwise hold the shutdown forever
+                order_service.close_update_streams();
+            })
+            .await
+            .unwrap();
+        } else if !worker_handles.is_empty() {
+            shutdown_signal("Workers").await;
+        }
+
+        // Let in-flight tasks and jobs finish; unfinished tasks are redelivered later
+        let _ = stop_workers.send(true);
+        for handle in worker_handles {
+            let _ = handle.await;
         }
     }
 }
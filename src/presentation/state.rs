@@ -1,4 +1,10 @@
-use crate::application::{order::OrderService, product::ProductService, user::UserService};
+use crate::application::{
+    audit::AuditService, category::CategoryService, inventory::InventoryService,
+    order::OrderService, product::ProductService, scheduler::Scheduler, user::UserService,
+    webhook::WebhookService,
+};
+use crate::domain::port::metrics::MetricsPort;
+use crate::domain::port::trace_context::TraceContextPort;
 use axum::extract::FromRef;
 use std::sync::Arc;
 
@@ -6,7 +12,14 @@ use std::sync::Arc;
 pub struct AppState {
     pub user_service: Arc<UserService>,
     pub product_service: Arc<ProductService>,
+    pub category_service: Arc<CategoryService>,
     pub order_service: Arc<OrderService>,
+    pub inventory_service: Arc<InventoryService>,
+    pub webhook_service: Arc<WebhookService>,
+    pub audit_service: Arc<AuditService>,
+    pub scheduler: Arc<Scheduler>,
+    pub metrics: Arc<dyn MetricsPort>,
+    pub trace_context: Arc<dyn TraceContextPort>,
 }
 
 impl FromRef<AppState> for Arc<UserService> {
@@ -21,8 +34,50 @@ impl FromRef<AppState> for Arc<ProductService> {
     }
 }
 
+impl FromRef<AppState> for Arc<CategoryService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.category_service.clone()
+    }
+}
+
 impl FromRef<AppState> for Arc<OrderService> {
     fn from_ref(state: &AppState) -> Self {
         state.order_service.clone()
     }
 }
+
+impl FromRef<AppState> for Arc<InventoryService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.inventory_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WebhookService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.webhook_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AuditService> {
+    fn from_ref(state: &AppState) -> Self {
+        state.audit_service.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Scheduler> {
+    fn from_ref(state: &AppState) -> Self {
+        state.scheduler.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn MetricsPort> {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn TraceContextPort> {
+    fn from_ref(state: &AppState) -> Self {
+        state.trace_context.clone()
+    }
+}
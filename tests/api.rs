@@ -0,0 +1,101 @@
+//! The HTTP API end to end over the in-memory adapters: routing, status
+//! codes and the services behind them.
+
+mod app;
+
+use app::{TestApp, id, json};
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+use service::domain::pagination::Pagination;
+
+#[tokio::test]
+async fn users_are_created_read_and_kept_unique() {
+    let app = TestApp::new();
+    let id = app.create_user("Ada", "ada@example.com").await;
+
+    let response = app.get(&format!("/api/v1/users/{id}")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(header::ETAG));
+    assert_eq!(json(response).await["data"]["email"], "ada@example.com");
+
+    let response = app
+        .post(
+            "/api/v1/users",
+            json!({ "name": "Ada again", "email": "ada@example.com" }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = app.get("/api/v1/users").await;
+    assert_eq!(json(response).await["data"]["total"], 1);
+}
+
+#[tokio::test]
+async fn unknown_and_malformed_ids_are_told_apart() {
+    let app = TestApp::new();
+
+    let response = app.get("/api/v1/users/000000000000000000000000").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.get("/api/v1/users/not-an-id").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn orders_take_stock_and_cancelling_gives_it_back() {
+    let app = TestApp::new();
+    let user = app.create_user("Ada", "ada@example.com").await;
+    let product = app.create_product("MUG-1", 5).await;
+
+    let order = app
+        .create(
+            "/api/v1/orders",
+            json!({ "user_id": user, "product_id": product, "quantity": 2 }),
+        )
+        .await;
+    assert_eq!(app.stock(&product).await, 3);
+
+    let response = app
+        .post(
+            "/api/v1/orders",
+            json!({ "user_id": user, "product_id": product, "quantity": 4 }),
+        )
+        .await;
+    assert!(
+        response.status().is_client_error(),
+        "got {}",
+        response.status()
+    );
+    assert_eq!(
+        app.stock(&product).await,
+        3,
+        "a refused order takes nothing"
+    );
+
+    let response = app
+        .send(
+            Request::delete(format!("/api/v1/orders/{}", id(&order)))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(app.stock(&product).await, 5);
+}
+
+#[tokio::test]
+async fn services_share_the_in_memory_state() {
+    let app = TestApp::new();
+    let product = app.create_product("MUG-1", 5).await;
+
+    // Written through the router, read straight from the service
+    let products = app
+        .state
+        .product_service
+        .list_products(Pagination { page: 1, limit: 10 })
+        .await
+        .expect("products listed");
+    assert_eq!(products.len(), 1);
+    assert_eq!(products[0].id.as_deref(), Some(product.as_str()));
+}
@@ -0,0 +1,223 @@
+//! The `/api/v1` router over the in-memory adapters, driven with `oneshot`
+//! as a client would, with no database or Redis.
+//!
+//! Each test file uses a part of it, so unused helpers are expected.
+#![allow(dead_code)]
+
+use axum::Router;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use serde_json::{Value, json};
+use service::application::audit::AuditService;
+use service::application::category::CategoryService;
+use service::application::inventory::{InventoryService, LowStockMonitor};
+use service::application::lock::Locks;
+use service::application::order::OrderService;
+use service::application::product::ProductService;
+use service::application::scheduler::Scheduler;
+use service::application::user::UserService;
+use service::application::webhook::WebhookService;
+use service::domain::port::notifier::NotifierPort;
+use service::infrastructure::metrics::noop::NoopMetrics;
+use service::infrastructure::notifier::log::LogNotifier;
+use service::infrastructure::persistence::memory::{
+    InMemoryRepositories, job_run::InMemoryJobRuns, lease::InMemoryLock,
+};
+use service::infrastructure::providers::propagation::OtelTraceContext;
+use service::infrastructure::webhooks::http_client::HttpWebhookClient;
+use service::presentation::http;
+use service::presentation::state::AppState;
+use std::sync::Arc;
+use std::time::Duration;
+use tower::ServiceExt;
+
+pub struct TestApp {
+    pub repos: InMemoryRepositories,
+    pub state: AppState,
+    router: Router,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        Self::with_notifier(Arc::new(LogNotifier))
+    }
+
+    /// Low-stock alerts go to `notifier`.
+    pub fn with_notifier(notifier: Arc<dyn NotifierPort>) -> Self {
+        let repos = InMemoryRepositories::new();
+        let metrics = Arc::new(NoopMetrics);
+        let trace_context = Arc::new(OtelTraceContext);
+
+        let audit_service = Arc::new(AuditService::new(
+            repos.audit.clone(),
+            trace_context.clone(),
+        ));
+        let inventory_service = Arc::new(InventoryService::new(
+            repos.products.clone(),
+            repos.ledger.clone(),
+            LowStockMonitor::new(repos.products.clone(), notifier),
+            metrics.clone(),
+        ));
+        let state = AppState {
+            user_service: Arc::new(UserService::new(repos.users.clone(), audit_service.clone())),
+            product_service: Arc::new(ProductService::new(
+                repos.products.clone(),
+                repos.categories.clone(),
+                inventory_service.clone(),
+                audit_service.clone(),
+            )),
+            category_service: Arc::new(CategoryService::new(
+                repos.categories.clone(),
+                repos.products.clone(),
+            )),
+            order_service: Arc::new(OrderService::new(
+                repos.orders.clone(),
+                repos.users.clone(),
+                repos.products.clone(),
+                inventory_service.clone(),
+                metrics.clone(),
+                audit_service.clone(),
+            )),
+            inventory_service,
+            webhook_service: Arc::new(WebhookService::new(
+                repos.webhook_subscriptions.clone(),
+                repos.webhook_deliveries.clone(),
+                Arc::new(HttpWebhookClient::new(Duration::from_secs(5)).unwrap()),
+            )),
+            audit_service,
+            scheduler: Arc::new(Scheduler::new(
+                chrono_tz::UTC,
+                Locks::new(Arc::new(InMemoryLock::new())),
+                Arc::new(InMemoryJobRuns::new()),
+            )),
+            metrics,
+            trace_context,
+        };
+        let router = Router::new()
+            .nest("/api/v1", http::app_router())
+            .with_state(state.clone());
+
+        Self {
+            repos,
+            state,
+            router,
+        }
+    }
+
+    pub async fn send(&self, request: Request<Body>) -> Response {
+        self.router.clone().oneshot(request).await.unwrap()
+    }
+
+    pub async fn get(&self, path: &str) -> Response {
+        self.send(Request::get(path).body(Body::empty()).unwrap())
+            .await
+    }
+
+    pub async fn post(&self, path: &str, body: Value) -> Response {
+        self.send(json_request("POST", path, body)).await
+    }
+
+    /// A PUT or PATCH guarded by `If-Match: etag`.
+    pub async fn update(&self, method: &str, path: &str, etag: &str, body: Value) -> Response {
+        let mut request = json_request(method, path, body);
+        request
+            .headers_mut()
+            .insert(header::IF_MATCH, etag.parse().unwrap());
+        self.send(request).await
+    }
+
+    /// Current `ETag` of the resource at `path`.
+    pub async fn etag(&self, path: &str) -> String {
+        let response = self.get(path).await;
+        assert_eq!(response.status(), StatusCode::OK, "GET {path}");
+        response.headers()[header::ETAG]
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    /// Creates a resource and returns its `data`, failing on any error.
+    pub async fn create(&self, path: &str, body: Value) -> Value {
+        let response = self.post(path, body).await;
+        let status = response.status();
+        let body = json(response).await;
+        assert!(status.is_success(), "POST {path}: {status} {body}");
+        body["data"].clone()
+    }
+
+    pub async fn create_user(&self, name: &str, email: &str) -> String {
+        let user = self
+            .create("/api/v1/users", json!({ "name": name, "email": email }))
+            .await;
+        id(&user)
+    }
+
+    pub async fn create_category(&self, name: &str) -> String {
+        let category = self
+            .create("/api/v1/categories", json!({ "name": name }))
+            .await;
+        id(&category)
+    }
+
+    /// A product of `stock` units in a category of its own.
+    pub async fn create_product(&self, sku: &str, stock: i32) -> String {
+        let category = self.create_category(&format!("Category {sku}")).await;
+        let product = self
+            .create(
+                "/api/v1/products",
+                json!({
+                    "name": format!("Product {sku}"),
+                    "price": 10.0,
+                    "stock": stock,
+                    "category_id": category,
+                    "sku": sku
+                }),
+            )
+            .await;
+        id(&product)
+    }
+
+    /// Orders `quantity` units of `product_id` for `user_id`; returns the order ID.
+    pub async fn order(&self, user_id: &str, product_id: &str, quantity: i32) -> String {
+        let order = self
+            .create(
+                "/api/v1/orders",
+                json!({ "user_id": user_id, "product_id": product_id, "quantity": quantity }),
+            )
+            .await;
+        id(&order)
+    }
+
+    pub async fn stock(&self, product_id: &str) -> i64 {
+        let response = self.get(&format!("/api/v1/products/{product_id}")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        json(response).await["data"]["stock"]
+            .as_i64()
+            .expect("stock")
+    }
+}
+
+pub fn json_request(method: &str, path: &str, body: Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(path)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+pub fn id(resource: &Value) -> String {
+    resource["id"].as_str().expect("resource id").to_string()
+}
+
+pub async fn text(response: Response) -> String {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+pub async fn json(response: Response) -> Value {
+    serde_json::from_str(&text(response).await).unwrap_or(Value::Null)
+}
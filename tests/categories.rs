@@ -0,0 +1,141 @@
+//! Category tree: slugs, materialised paths and moving subtrees, with
+//! `CategoryService` over the in-memory repositories.
+
+use service::application::category::CategoryService;
+use service::domain::entities::category::{Category, CategoryId};
+use service::domain::error::DomainError;
+use service::domain::pagination::Pagination;
+use service::infrastructure::persistence::memory::InMemoryRepositories;
+
+#[test]
+fn slugs_are_lowercase_words_joined_by_dashes() {
+    assert_eq!(Category::slugify("Home & Garden"), "home-garden");
+    assert_eq!(Category::slugify("  Kids -- Toys  "), "kids-toys");
+    assert_eq!(Category::slugify("4K/HDR"), "4k-hdr");
+    assert_eq!(Category::slugify("¿?"), "");
+}
+
+#[test]
+fn paths_nest_slugs_under_the_parent() {
+    assert_eq!(Category::child_path(None, "home"), "home");
+    assert_eq!(
+        Category::child_path(Some("home/garden"), "tools"),
+        "home/garden/tools"
+    );
+
+    let garden = category("home/garden");
+    assert_eq!(garden.depth(), 1);
+    assert!(garden.contains_path("home/garden"));
+    assert!(garden.contains_path("home/garden/tools"));
+    // A sibling sharing the prefix is not below it
+    assert!(!garden.contains_path("home/gardening"));
+    assert!(!garden.contains_path("home"));
+}
+
+fn category(path: &str) -> Category {
+    let now = chrono::Utc::now();
+    Category {
+        id: None,
+        name: path.to_string(),
+        slug: path.rsplit('/').next().unwrap_or(path).to_string(),
+        parent_id: None,
+        path: path.to_string(),
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
+
+fn service() -> CategoryService {
+    let repos = InMemoryRepositories::new();
+    CategoryService::new(repos.categories.clone(), repos.products.clone())
+}
+
+async fn create(service: &CategoryService, name: &str, parent: Option<&CategoryId>) -> CategoryId {
+    service
+        .create_category(name, None, parent)
+        .await
+        .expect("category created")
+        .id
+        .expect("category id")
+}
+
+async fn paths(service: &CategoryService) -> Vec<String> {
+    service
+        .list_categories(Pagination { page: 1, limit: 50 })
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.path)
+        .collect()
+}
+
+#[tokio::test]
+async fn moving_a_category_rewrites_its_subtree() {
+    let service = service();
+    let home = create(&service, "Home", None).await;
+    let garden = create(&service, "Garden", Some(&home)).await;
+    let tools = create(&service, "Tools", Some(&garden)).await;
+    create(&service, "Hand Tools", Some(&tools)).await;
+    let outdoor = create(&service, "Outdoor", None).await;
+
+    let moved = service
+        .update_category(&garden, "Gardening", None, Some(&outdoor))
+        .await
+        .unwrap();
+
+    assert_eq!(moved.path, "outdoor/gardening");
+    assert_eq!(moved.parent_id.as_ref(), Some(&outdoor));
+    let mut paths = paths(&service).await;
+    paths.sort();
+    assert_eq!(
+        paths,
+        [
+            "home",
+            "outdoor",
+            "outdoor/gardening",
+            "outdoor/gardening/tools",
+            "outdoor/gardening/tools/hand-tools"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn a_category_cannot_move_into_its_own_subtree() {
+    let service = service();
+    let home = create(&service, "Home", None).await;
+    let garden = create(&service, "Garden", Some(&home)).await;
+
+    for parent in [&home, &garden] {
+        let result = service
+            .update_category(&home, "Home", None, Some(parent))
+            .await;
+        assert!(
+            matches!(result, Err(DomainError::BusinessRule(_))),
+            "moving below {parent} should be refused, got {result:?}"
+        );
+    }
+    assert_eq!(paths(&service).await, ["home", "home/garden"]);
+}
+
+#[tokio::test]
+async fn paths_must_stay_unique() {
+    let service = service();
+    let home = create(&service, "Home", None).await;
+    create(&service, "Garden", Some(&home)).await;
+    let garden = create(&service, "Garden", None).await;
+
+    let result = service
+        .update_category(&garden, "Garden", None, Some(&home))
+        .await;
+    assert!(
+        matches!(result, Err(DomainError::AlreadyExists { .. })),
+        "got {result:?}"
+    );
+
+    let result = service.create_category("!!!", None, None).await;
+    assert!(
+        matches!(result, Err(DomainError::Invalid { field: "slug", .. })),
+        "got {result:?}"
+    );
+}
@@ -0,0 +1,220 @@
+//! Layered settings: defaults, then the TOML file, then the environment.
+
+use service::config::{Config, CorsOrigins, LogFormat, Persistence};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+    vars.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// A TOML file removed when the test ends.
+struct ConfigFile(PathBuf);
+
+impl ConfigFile {
+    fn new(contents: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(&path, contents).expect("write config file");
+        Self(path)
+    }
+
+    fn path(&self) -> &str {
+        self.0.to_str().expect("utf-8 temp path")
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn defaults_only_need_the_service_and_project() {
+    let config = Config::resolve(env(&[
+        ("SERVICE_NAME", "svc"),
+        ("PROJECT_ID", "project"),
+        ("PERSISTENCE", "memory"),
+    ]))
+    .expect("valid settings");
+
+    assert_eq!(config.server.port, 3000);
+    assert_eq!(config.persistence.backend, Persistence::Memory);
+    assert_eq!(config.telemetry.log_format, LogFormat::Stackdriver);
+    assert!(matches!(config.cors.origins, CorsOrigins::Any));
+    assert_eq!(config.auth.api_key_header, "x-api-key");
+    assert_eq!(config.auth.api_key_prefix, "sk_");
+    assert!(config.file.is_none());
+}
+
+#[test]
+fn project_id_is_only_needed_by_gcp_telemetry() {
+    let local = [
+        ("SERVICE_NAME", "svc"),
+        ("PERSISTENCE", "memory"),
+        ("LOG_FORMAT", "pretty"),
+        ("TRACING_EXPORTER", "otlp"),
+    ];
+    Config::resolve(env(&local)).expect("valid without PROJECT_ID");
+
+    for gcp in [("LOG_FORMAT", "stackdriver"), ("TRACING_EXPORTER", "gcp")] {
+        let mut vars = env(&local);
+        vars.insert(gcp.0.to_string(), gcp.1.to_string());
+        let error = Config::resolve(vars).expect_err("PROJECT_ID required");
+        assert!(
+            error.problems.join("\n").contains("PROJECT_ID"),
+            "{}={} should need PROJECT_ID",
+            gcp.0,
+            gcp.1
+        );
+    }
+}
+
+#[test]
+fn environment_overrides_the_file() {
+    let file = ConfigFile::new(
+        r#"
+        [server]
+        service_name = "from-file"
+        port = 8080
+
+        [cors]
+        origins = ["https://a.example", "https://b.example"]
+
+        [telemetry]
+        project_id = "from-file"
+
+        [auth]
+        api_key_header = "x-partner-key"
+        "#,
+    );
+    let config = Config::resolve(env(&[
+        ("CONFIG_FILE", file.path()),
+        ("PERSISTENCE", "memory"),
+        ("PORT", "9090"),
+        ("API_KEY_HEADER", "X-Client-Key"),
+    ]))
+    .expect("valid settings");
+
+    assert_eq!(config.server.service_name, "from-file");
+    assert_eq!(config.server.port, 9090);
+    assert_eq!(config.auth.api_key_header, "x-client-key");
+    assert!(matches!(&config.cors.origins, CorsOrigins::List(origins) if origins.len() == 2));
+}
+
+#[test]
+fn every_problem_is_reported_at_once() {
+    let file = ConfigFile::new(
+        r#"
+        [server]
+        prot = 8080
+        "#,
+    );
+    let error = Config::resolve(env(&[
+        ("CONFIG_FILE", file.path()),
+        ("PORT", "eighty"),
+        ("LOG_FORMAT", "xml"),
+        ("CORS_ORIGINS", "*,https://a.example"),
+        ("TASK_POLL_INTERVAL_MS", "0"),
+        ("API_KEY_HEADER", "x api key"),
+    ]))
+    .expect_err("invalid settings");
+
+    let problems = error.problems.join("\n");
+    for expected in [
+        "PORT",
+        "LOG_FORMAT",
+        "CORS_ORIGINS",
+        "TASK_POLL_INTERVAL_MS",
+        "SERVICE_NAME",
+        "MONGO_URL",
+        "PROJECT_ID",
+        "API_KEY_HEADER",
+        "server.prot",
+    ] {
+        assert!(
+            problems.contains(expected),
+            "{expected} missing from:\n{problems}"
+        );
+    }
+}
+
+#[test]
+fn secrets_are_redacted() {
+    let config = Config::resolve(env(&[
+        ("SERVICE_NAME", "svc"),
+        ("MONGO_URL", "mongodb://admin:hunter2@db:27017"),
+        ("MONGO_DB", "service"),
+        ("PROJECT_ID", "project"),
+    ]))
+    .expect("valid settings");
+
+    assert_eq!(
+        config.mongo.url.expose(),
+        "mongodb://admin:hunter2@db:27017"
+    );
+    assert!(!format!("{config:?}").contains("hunter2"));
+}
+
+#[test]
+fn rate_limits_per_route_group() {
+    let file = ConfigFile::new(
+        r#"
+        [rate_limit]
+        enabled = true
+        orders = "60/1m"
+        "#,
+    );
+    let config = Config::resolve(env(&[
+        ("CONFIG_FILE", file.path()),
+        ("SERVICE_NAME", "svc"),
+        ("PROJECT_ID", "project"),
+        ("PERSISTENCE", "memory"),
+        ("RATE_LIMIT_DEFAULT", "20/s"),
+    ]))
+    .expect("valid settings");
+
+    assert!(config.rate_limit.enabled);
+    assert_eq!(config.rate_limit.default.requests, 20);
+    assert_eq!(config.rate_limit.default.window, Duration::from_secs(1));
+    assert_eq!(
+        config.rate_limit.groups["orders"].window,
+        Duration::from_secs(60)
+    );
+    assert!(!config.rate_limit.groups.contains_key("users"));
+
+    let error = Config::resolve(env(&[
+        ("SERVICE_NAME", "svc"),
+        ("PROJECT_ID", "project"),
+        ("PERSISTENCE", "memory"),
+        ("RATE_LIMIT_ORDERS", "60 per minute"),
+    ]))
+    .expect_err("invalid limit");
+    assert!(error.problems.join("\n").contains("RATE_LIMIT_ORDERS"));
+}
+
+#[test]
+fn postgres_needs_the_in_memory_opt_in() {
+    let vars = [
+        ("SERVICE_NAME", "svc"),
+        ("PROJECT_ID", "project"),
+        ("PERSISTENCE", "postgres"),
+        ("POSTGRES_URL", "postgres://postgres@localhost:5432/service"),
+    ];
+    let error = Config::resolve(env(&vars)).expect_err("mixed backends refused");
+    assert!(
+        error
+            .problems
+            .join("\n")
+            .contains("POSTGRES_ALLOW_IN_MEMORY")
+    );
+
+    let config = Config::resolve(env(
+        &[&vars[..], &[("POSTGRES_ALLOW_IN_MEMORY", "true")]].concat()
+    ))
+    .expect("valid settings");
+    assert!(config.postgres.allow_in_memory);
+}
@@ -0,0 +1,484 @@
+//! Behaviour every repository adapter must share, whatever its storage.
+//!
+//! Each suite takes a fresh, empty repository and panics on the first broken
+//! expectation. Adapter test files call them once per port.
+
+use chrono::{Duration, Utc};
+use futures::TryStreamExt;
+use service::domain::entities::category::{Category, CategoryId};
+use service::domain::entities::inventory::{InventoryMovement, MovementKind};
+use service::domain::entities::order::{Order, OrderId};
+use service::domain::entities::product::{Product, ProductId, ProductMetadata, ProductStatus};
+use service::domain::entities::user::{User, UserId};
+use service::domain::error::{DomainError, DomainResult};
+use service::domain::filter::DateRange;
+use service::domain::pagination::Pagination;
+use service::domain::port::category::CategoryRepositoryPort;
+use service::domain::port::inventory::InventoryLedgerPort;
+use service::domain::port::order::OrderRepositoryPort;
+use service::domain::port::product::ProductRepositoryPort;
+use service::domain::port::user::UserRepositoryPort;
+use service::domain::values::INITIAL_VERSION;
+use std::fmt::Debug;
+
+/// Well-formed (ObjectId) but never assigned.
+const UNKNOWN_ID: &str = "000000000000000000000000";
+const MALFORMED_ID: &str = "not-an-id";
+
+fn first_page() -> Pagination {
+    Pagination { page: 1, limit: 10 }
+}
+
+fn ids<T>(items: &[T], id: impl Fn(&T) -> Option<&str>) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| id(item).unwrap_or_default().to_string())
+        .collect()
+}
+
+fn assert_invalid<T: Debug>(result: DomainResult<T>, operation: &str) {
+    assert!(
+        matches!(result, Err(DomainError::Invalid { .. })),
+        "{operation} with a malformed ID should be Invalid, got {result:?}"
+    );
+}
+
+// ===== USERS =====
+
+fn user(id: UserId, name: &str, minutes_ago: i64) -> User {
+    let created_at = Utc::now() - Duration::minutes(minutes_ago);
+    User {
+        id: Some(id),
+        name: name.to_string(),
+        email: format!("{}@example.com", name.to_lowercase()),
+        created_at,
+        updated_at: created_at,
+        version: INITIAL_VERSION,
+        deleted_at: None,
+    }
+}
+
+pub async fn user_repository(repo: &dyn UserRepositoryPort) {
+    // Inserted out of order, so sorting can't come from insertion order
+    let middle = user(repo.next_id(), "Middle", 20);
+    let oldest = user(repo.next_id(), "Oldest", 30);
+    let newest = user(repo.next_id(), "Newest", 10);
+    for u in [&middle, &oldest, &newest] {
+        repo.create(u, &[]).await.expect("create user");
+    }
+
+    let all = repo.find_all(first_page()).await.expect("find_all");
+    assert_eq!(
+        ids(&all, |u| u.id.as_deref()),
+        ids(&[&newest, &middle, &oldest], |u| u.id.as_deref()),
+        "find_all must be newest first"
+    );
+    let second = repo
+        .find_all(Pagination { page: 2, limit: 2 })
+        .await
+        .expect("find_all page 2");
+    assert_eq!(
+        ids(&second, |u| u.id.as_deref()),
+        ids(&[&oldest], |u| u.id.as_deref())
+    );
+    assert_eq!(repo.count().await.expect("count"), 3);
+
+    let middle_id = middle.id.clone().unwrap();
+    assert!(repo.delete(&middle_id, &[]).await.expect("delete"));
+    assert!(
+        !repo.delete(&middle_id, &[]).await.expect("delete again"),
+        "deleting twice must return false"
+    );
+
+    assert!(
+        repo.find_by_id(&middle_id)
+            .await
+            .expect("find_by_id")
+            .is_none()
+    );
+    assert!(
+        repo.find_by_email(&middle.email)
+            .await
+            .expect("find_by_email")
+            .is_none()
+    );
+    let all = repo.find_all(first_page()).await.expect("find_all");
+    assert_eq!(
+        ids(&all, |u| u.id.as_deref()),
+        ids(&[&newest, &oldest], |u| u.id.as_deref())
+    );
+    let streamed: Vec<User> = repo
+        .stream_all(&DateRange::default())
+        .await
+        .expect("stream_all")
+        .try_collect()
+        .await
+        .expect("stream items");
+    assert_eq!(
+        ids(&streamed, |u| u.id.as_deref()),
+        ids(&all, |u| u.id.as_deref())
+    );
+    assert_eq!(repo.count().await.expect("count"), 2);
+    assert!(
+        !repo
+            .update(&middle_id, middle.version, &middle, &[])
+            .await
+            .expect("update deleted"),
+        "deleted users can't be updated"
+    );
+
+    let newest_id = newest.id.clone().unwrap();
+    assert!(
+        repo.update(&newest_id, newest.version, &newest, &[])
+            .await
+            .expect("update")
+    );
+    assert!(
+        !repo
+            .update(&newest_id, newest.version, &newest, &[])
+            .await
+            .expect("stale update"),
+        "an update at a stale version must not apply"
+    );
+    let updated = repo
+        .find_by_id(&newest_id)
+        .await
+        .expect("find_by_id")
+        .unwrap();
+    assert_eq!(updated.version, newest.version + 1);
+
+    assert!(
+        repo.find_by_id(&UserId::new(UNKNOWN_ID))
+            .await
+            .expect("find unknown")
+            .is_none()
+    );
+    assert!(
+        !repo
+            .delete(&UserId::new(UNKNOWN_ID), &[])
+            .await
+            .expect("delete unknown")
+    );
+
+    let malformed = UserId::new(MALFORMED_ID);
+    assert_invalid(repo.find_by_id(&malformed).await, "find_by_id");
+    assert_invalid(repo.update(&malformed, 1, &newest, &[]).await, "update");
+    assert_invalid(repo.delete(&malformed, &[]).await, "delete");
+}
+
+// ===== PRODUCTS =====
+
+/// An uncategorised product created `minutes_ago`.
+pub fn product(id: ProductId, sku: &str, stock: i32, minutes_ago: i64) -> Product {
+    let created_at = Utc::now() - Duration::minutes(minutes_ago);
+    Product {
+        id: Some(id),
+        name: format!("Product {sku}"),
+        price: 9.99,
+        stock,
+        status: ProductStatus::Active,
+        metadata: ProductMetadata {
+            description: None,
+            category_id: None,
+            tags: Vec::new(),
+            sku: sku.to_string(),
+        },
+        reorder_threshold: None,
+        low_stock_alerted_at: None,
+        created_at,
+        updated_at: created_at,
+        version: INITIAL_VERSION,
+        deleted_at: None,
+    }
+}
+
+/// `ledger` is the one `repo` writes its stock movements to.
+pub async fn product_repository(
+    repo: &dyn ProductRepositoryPort,
+    ledger: &dyn InventoryLedgerPort,
+) {
+    let middle = product(repo.next_id(), "SKU-MIDDLE", 5, 20);
+    let oldest = product(repo.next_id(), "SKU-OLDEST", 5, 30);
+    let newest = product(repo.next_id(), "SKU-NEWEST", 5, 10);
+    for p in [&middle, &oldest, &newest] {
+        repo.create(p, &[]).await.expect("create product");
+    }
+
+    let all = repo.find_all(first_page()).await.expect("find_all");
+    assert_eq!(
+        ids(&all, |p| p.id.as_deref()),
+        ids(&[&newest, &middle, &oldest], |p| p.id.as_deref()),
+        "find_all must be newest first"
+    );
+    assert_eq!(repo.count().await.expect("count"), 3);
+
+    let newest_id = newest.id.clone().unwrap();
+    let movements = |id: ProductId| async move {
+        ledger
+            .find_by_product(&id, first_page())
+            .await
+            .expect("find_by_product")
+    };
+    let initial = movements(newest_id.clone()).await;
+    assert_eq!(initial.len(), 1, "create must record the opening stock");
+    assert_eq!(initial[0].kind, MovementKind::Initial);
+    assert_eq!((initial[0].delta, initial[0].stock_after), (5, Some(5)));
+
+    let sale = InventoryMovement::new(newest_id.clone(), -2, MovementKind::Sale);
+    let after = repo
+        .apply_movement(&sale, &[])
+        .await
+        .expect("apply_movement")
+        .expect("stock available");
+    assert_eq!(after.stock, 3);
+    assert!(
+        repo.apply_movement(
+            &InventoryMovement::new(newest_id.clone(), -4, MovementKind::Sale),
+            &[]
+        )
+        .await
+        .expect("apply_movement")
+        .is_none(),
+        "stock must never go negative"
+    );
+    let history = movements(newest_id.clone()).await;
+    assert_eq!(history.len(), 2, "a refused movement must not be recorded");
+    assert_eq!(history[0].kind, MovementKind::Sale);
+    assert_eq!(history[0].stock_after, Some(3));
+
+    let after = repo
+        .update_stock(&newest_id, -2, &[])
+        .await
+        .expect("update_stock")
+        .expect("stock available");
+    assert_eq!(after.stock, 1);
+    assert!(
+        repo.update_stock(&newest_id, -4, &[])
+            .await
+            .expect("update_stock")
+            .is_none(),
+        "stock must never go negative"
+    );
+    assert_eq!(
+        movements(newest_id.clone()).await.len(),
+        2,
+        "update_stock must leave the ledger alone"
+    );
+
+    let oldest_id = oldest.id.clone().unwrap();
+    let mut metadata = oldest.metadata.clone();
+    metadata.category_id = Some(CategoryId::new(UNKNOWN_ID));
+    for version in [INITIAL_VERSION, INITIAL_VERSION + 1] {
+        assert!(
+            repo.update_metadata(&oldest_id, version, &metadata, &[])
+                .await
+                .expect("update_metadata")
+        );
+        metadata.category_id = None;
+    }
+    let stored = repo
+        .find_by_id(&oldest_id)
+        .await
+        .expect("find_by_id")
+        .expect("product");
+    assert!(
+        stored.metadata.category_id.is_none(),
+        "update_metadata must clear the category"
+    );
+
+    let middle_id = middle.id.clone().unwrap();
+    assert!(repo.delete(&middle_id, &[]).await.expect("delete"));
+    assert!(
+        !repo.delete(&middle_id, &[]).await.expect("delete again"),
+        "deleting twice must return false"
+    );
+
+    assert!(
+        repo.find_by_id(&middle_id)
+            .await
+            .expect("find_by_id")
+            .is_none()
+    );
+    let all = repo.find_all(first_page()).await.expect("find_all");
+    assert_eq!(
+        ids(&all, |p| p.id.as_deref()),
+        ids(&[&newest, &oldest], |p| p.id.as_deref())
+    );
+    let skus = repo
+        .find_ids_by_skus(std::slice::from_ref(&middle.metadata.sku))
+        .await
+        .expect("find_ids_by_skus");
+    assert!(skus.is_empty(), "deleted products must not match their SKU");
+    assert!(
+        repo.update_stock(&middle_id, 1, &[])
+            .await
+            .expect("update_stock deleted")
+            .is_none()
+    );
+    assert_eq!(repo.count().await.expect("count"), 2);
+
+    assert!(
+        !repo
+            .delete(&ProductId::new(UNKNOWN_ID), &[])
+            .await
+            .expect("delete unknown")
+    );
+
+    let malformed = ProductId::new(MALFORMED_ID);
+    assert_invalid(repo.find_by_id(&malformed).await, "find_by_id");
+    assert_invalid(repo.update_stock(&malformed, 1, &[]).await, "update_stock");
+    let adjustment = InventoryMovement::new(malformed.clone(), 1, MovementKind::Adjustment);
+    assert_invalid(
+        repo.apply_movement(&adjustment, &[]).await,
+        "apply_movement",
+    );
+    assert_invalid(repo.delete(&malformed, &[]).await, "delete");
+}
+
+// ===== CATEGORIES =====
+
+/// A root category at `path`.
+fn category(path: &str) -> Category {
+    let now = Utc::now();
+    Category {
+        id: None,
+        name: path.to_string(),
+        slug: path.to_string(),
+        parent_id: None,
+        path: path.to_string(),
+        created_at: now,
+        updated_at: now,
+        deleted_at: None,
+    }
+}
+
+fn assert_path_taken<T: Debug>(result: DomainResult<T>, operation: &str) {
+    assert!(
+        matches!(result, Err(DomainError::AlreadyExists { .. })),
+        "{operation} onto a live category's path should be AlreadyExists, got {result:?}"
+    );
+}
+
+/// One live category per path, whatever the service checked beforehand.
+pub async fn category_repository(repo: &dyn CategoryRepositoryPort) {
+    let home = repo.create(&category("home")).await.expect("create home");
+    let garden = repo
+        .create(&category("garden"))
+        .await
+        .expect("create garden");
+
+    assert_path_taken(repo.create(&category("home")).await, "create");
+    assert_path_taken(repo.update(&garden, &category("home")).await, "update");
+    assert_path_taken(
+        repo.move_subtree(&garden, &category("home"), "garden")
+            .await,
+        "move_subtree",
+    );
+    assert!(
+        repo.update(&home, &category("home")).await.expect("update"),
+        "a category keeps its own path"
+    );
+
+    // A soft-deleted category frees its path
+    assert!(repo.delete(&home).await.expect("delete"));
+    repo.create(&category("home"))
+        .await
+        .expect("create over a deleted path");
+    assert_eq!(repo.count().await.expect("count"), 2);
+}
+
+// ===== ORDERS =====
+
+fn order(id: OrderId, user_id: &UserId, minutes_ago: i64) -> Order {
+    let created_at = Utc::now() - Duration::minutes(minutes_ago);
+    Order {
+        id: Some(id),
+        user_id: user_id.clone(),
+        product_id: ProductId::new(UNKNOWN_ID),
+        quantity: 1,
+        total_price: 9.99,
+        country: None,
+        created_at,
+        updated_at: created_at,
+        version: INITIAL_VERSION,
+        deleted_at: None,
+    }
+}
+
+pub async fn order_repository(repo: &dyn OrderRepositoryPort) {
+    let buyer = UserId::new("65f000000000000000000001");
+    let other = UserId::new("65f000000000000000000002");
+
+    let middle = order(repo.next_id(), &buyer, 20);
+    let oldest = order(repo.next_id(), &buyer, 30);
+    let newest = order(repo.next_id(), &other, 10);
+    for o in [&middle, &oldest, &newest] {
+        repo.create(o, &[]).await.expect("create order");
+    }
+
+    let all = repo.find_all(first_page()).await.expect("find_all");
+    assert_eq!(
+        ids(&all, |o| o.id.as_deref()),
+        ids(&[&newest, &middle, &oldest], |o| o.id.as_deref()),
+        "find_all must be newest first"
+    );
+    let by_buyer = repo
+        .find_by_user_id(&buyer, first_page())
+        .await
+        .expect("find_by_user_id");
+    assert_eq!(
+        ids(&by_buyer, |o| o.id.as_deref()),
+        ids(&[&middle, &oldest], |o| o.id.as_deref())
+    );
+    assert_eq!(repo.count().await.expect("count"), 3);
+
+    let middle_id = middle.id.clone().unwrap();
+    assert!(repo.delete(&middle_id, &[]).await.expect("delete"));
+    assert!(
+        !repo.delete(&middle_id, &[]).await.expect("delete again"),
+        "deleting twice must return false"
+    );
+
+    assert!(
+        repo.find_by_id(&middle_id)
+            .await
+            .expect("find_by_id")
+            .is_none()
+    );
+    let by_buyer = repo
+        .find_by_user_id(&buyer, first_page())
+        .await
+        .expect("find_by_user_id");
+    assert_eq!(
+        ids(&by_buyer, |o| o.id.as_deref()),
+        ids(&[&oldest], |o| o.id.as_deref())
+    );
+    let streamed: Vec<Order> = repo
+        .stream_all(&DateRange::default(), None)
+        .await
+        .expect("stream_all")
+        .try_collect()
+        .await
+        .expect("stream items");
+    assert_eq!(
+        ids(&streamed, |o| o.id.as_deref()),
+        ids(&[&newest, &oldest], |o| o.id.as_deref())
+    );
+    assert_eq!(repo.count().await.expect("count"), 2);
+
+    assert!(
+        !repo
+            .delete(&OrderId::new(UNKNOWN_ID), &[])
+            .await
+            .expect("delete unknown")
+    );
+
+    let malformed = OrderId::new(MALFORMED_ID);
+    assert_invalid(repo.find_by_id(&malformed).await, "find_by_id");
+    assert_invalid(
+        repo.find_by_user_id(&UserId::new(MALFORMED_ID), first_page())
+            .await,
+        "find_by_user_id",
+    );
+    assert_invalid(repo.delete(&malformed, &[]).await, "delete");
+}
@@ -0,0 +1,71 @@
+//! Which spans `DatastoreMetricsLayer` times, and under which store.
+
+use service::domain::entities::country::Country;
+use service::domain::port::metrics::{Datastore, MetricsPort};
+use service::infrastructure::metrics::datastore_layer::DatastoreMetricsLayer;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Layer;
+
+#[derive(Default)]
+struct RecordingMetrics {
+    calls: Mutex<Vec<(Datastore, String)>>,
+}
+
+impl MetricsPort for RecordingMetrics {
+    fn record_http_request(&self, _: &str, _: &str, _: u16, _: Duration) {}
+
+    fn record_datastore_call(&self, store: Datastore, operation: &str, _: Duration) {
+        self.calls
+            .lock()
+            .unwrap()
+            .push((store, operation.to_string()));
+    }
+
+    fn record_pool_event(&self, _: Datastore, _: &str) {}
+
+    fn order_created(&self, _: Option<&Country>, _: f64) {}
+
+    fn stock_out(&self) {}
+}
+
+#[test]
+fn spans_are_timed_by_the_store_behind_them() {
+    let metrics = Arc::new(RecordingMetrics::default());
+    let layer =
+        DatastoreMetricsLayer::new(metrics.clone()).with_filter(DatastoreMetricsLayer::targets());
+    let subscriber = tracing_subscriber::registry().with(layer);
+
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info_span!(
+            target: "service::infrastructure::persistence::order::repository",
+            "find_by_id"
+        )
+        .in_scope(|| {});
+        tracing::info_span!(
+            target: "service::infrastructure::persistence::postgres::order",
+            "find_by_id"
+        )
+        .in_scope(|| {});
+        tracing::info_span!(
+            target: "service::infrastructure::persistence::memory::order",
+            "find_by_id"
+        )
+        .in_scope(|| {});
+        tracing::info_span!(
+            target: "service::infrastructure::providers::redis",
+            "try_acquire"
+        )
+        .in_scope(|| {});
+    });
+
+    assert_eq!(
+        *metrics.calls.lock().unwrap(),
+        vec![
+            (Datastore::Mongo, "order.find_by_id".to_string()),
+            (Datastore::Postgres, "order.find_by_id".to_string()),
+            (Datastore::Redis, "redis.try_acquire".to_string()),
+        ]
+    );
+}
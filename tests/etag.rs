@@ -0,0 +1,73 @@
+//! Optimistic concurrency over HTTP: `ETag`, `If-Match` and `If-None-Match`.
+
+mod app;
+
+use app::{TestApp, json, json_request};
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use serde_json::json;
+
+#[tokio::test]
+async fn a_stale_if_match_is_412_and_a_missing_one_428() {
+    let app = TestApp::new();
+    let id = app.create_user("Ada", "ada@example.com").await;
+    let path = format!("/api/v1/users/{id}");
+    let original = app.etag(&path).await;
+
+    let response = app
+        .update(
+            "PUT",
+            &path,
+            &original,
+            json!({"name": "Ada L.", "email": "ada@example.com"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let current = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(current, original);
+
+    // Another admin still holding the first version
+    let response = app
+        .update(
+            "PUT",
+            &path,
+            &original,
+            json!({"name": "Ada K.", "email": "ada@example.com"}),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(json(app.get(&path).await).await["data"]["name"], "Ada L.");
+
+    let response = app
+        .send(json_request(
+            "PUT",
+            &path,
+            json!({"name": "Ada K.", "email": "ada@example.com"}),
+        ))
+        .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+}
+
+#[tokio::test]
+async fn if_none_match_on_the_current_version_is_304() {
+    let app = TestApp::new();
+    let id = app.create_user("Ada", "ada@example.com").await;
+    let path = format!("/api/v1/users/{id}");
+    let etag = app.etag(&path).await;
+
+    let conditional = |tag: &str| {
+        Request::get(&path)
+            .header(header::IF_NONE_MATCH, tag)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let response = app.send(conditional(&etag)).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+
+    let response = app.send(conditional("\"999\"")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
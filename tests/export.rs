@@ -0,0 +1,90 @@
+//! Streaming exports: the format comes from `Accept`, timestamps optionally
+//! in a country's timezone.
+
+mod app;
+
+use app::{TestApp, text};
+use axum::body::Body;
+use axum::http::{Request, Response, StatusCode, header};
+
+async fn export(app: &TestApp, path: &str, accept: &str) -> Response<Body> {
+    app.send(
+        Request::get(path)
+            .header(header::ACCEPT, accept)
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+}
+
+fn content_type(response: &Response<Body>) -> &str {
+    response.headers()[header::CONTENT_TYPE].to_str().unwrap()
+}
+
+async fn two_users() -> TestApp {
+    let app = TestApp::new();
+    app.create_user("Ada", "ada@example.com").await;
+    app.create_user("Grace", "grace@example.com").await;
+    app
+}
+
+#[tokio::test]
+async fn csv_is_a_header_and_one_line_per_row() {
+    let app = two_users().await;
+
+    let response = export(&app, "/api/v1/users/export", "text/csv").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(content_type(&response).starts_with("text/csv"));
+
+    let body = text(response).await;
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines[0], "id,name,email,created_at,updated_at");
+    assert_eq!(lines.len(), 3, "{body}");
+    assert!(
+        lines[1..]
+            .iter()
+            .any(|line| line.contains("ada@example.com"))
+    );
+}
+
+#[tokio::test]
+async fn ndjson_is_one_json_object_per_line() {
+    let app = two_users().await;
+
+    let response = export(&app, "/api/v1/users/export", "application/x-ndjson").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_type(&response), "application/x-ndjson");
+
+    let body = text(response).await;
+    let mut emails: Vec<String> = body
+        .lines()
+        .map(|line| {
+            let row: serde_json::Value = serde_json::from_str(line).expect("one object per line");
+            row["email"].as_str().unwrap().to_string()
+        })
+        .collect();
+    emails.sort();
+    assert_eq!(emails, ["ada@example.com", "grace@example.com"]);
+}
+
+#[tokio::test]
+async fn timestamps_follow_the_country_and_other_formats_are_refused() {
+    let app = two_users().await;
+
+    // Bogotá has no daylight saving time
+    let response = export(
+        &app,
+        "/api/v1/users/export?country=COL",
+        "application/x-ndjson",
+    )
+    .await;
+    let body = text(response).await;
+    let row: serde_json::Value = serde_json::from_str(body.lines().next().unwrap()).unwrap();
+    assert!(
+        row["created_at"].as_str().unwrap().ends_with("-05:00"),
+        "{row}"
+    );
+
+    let response = export(&app, "/api/v1/users/export", "application/xml").await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+}
@@ -0,0 +1,71 @@
+//! The stock ledger: every change through the API is recorded, and
+//! reconciliation finds stock that changed behind its back.
+
+mod app;
+
+use app::{TestApp, json};
+use axum::http::StatusCode;
+use serde_json::json;
+use service::domain::entities::product::ProductId;
+use service::domain::port::product::ProductRepositoryPort;
+
+#[tokio::test]
+async fn adjustments_need_a_reason_and_land_in_the_ledger() {
+    let app = TestApp::new();
+    let user = app.create_user("Ada", "ada@example.com").await;
+    let product = app.create_product("MUG-1", 5).await;
+    app.order(&user, &product, 2).await;
+
+    let path = format!("/api/v1/inventory/products/{product}/adjustments");
+    let response = app.post(&path, json!({ "delta": 4, "reason": "" })).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .post(&path, json!({ "delta": 4, "reason": "Found in the back" }))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(app.stock(&product).await, 7);
+
+    let response = app
+        .get(&format!("/api/v1/inventory/products/{product}/movements"))
+        .await;
+    let movements = json(response).await["data"]["data"].clone();
+    let deltas: Vec<i64> = movements
+        .as_array()
+        .expect("movements")
+        .iter()
+        .map(|m| m["delta"].as_i64().unwrap())
+        .collect();
+    assert_eq!(deltas, [4, -2, 5], "newest first, opening stock last");
+}
+
+#[tokio::test]
+async fn reconciliation_reports_and_repairs_a_gap_in_the_ledger() {
+    let app = TestApp::new();
+    let user = app.create_user("Ada", "ada@example.com").await;
+    let product = app.create_product("MUG-1", 5).await;
+    app.order(&user, &product, 2).await;
+
+    // A write that skips the ledger, as a manual fix in the database would
+    app.repos
+        .products
+        .update_stock(&ProductId::new(&product), 10, &[])
+        .await
+        .expect("update_stock")
+        .expect("product");
+
+    let inventory = &app.state.inventory_service;
+    let report = inventory.reconcile().await.expect("reconcile");
+    assert_eq!((report.checked, report.in_sync), (1, 0));
+    let [drift] = &report.drifted[..] else {
+        panic!("expected one drifted product, got {:?}", report.drifted);
+    };
+    assert_eq!((drift.stock, drift.ledger_stock), (13, 3));
+
+    let report = inventory.rebuild_stock(false).await.expect("rebuild");
+    assert_eq!(report.repaired, 1);
+    assert_eq!(app.stock(&product).await, 3);
+
+    let report = inventory.reconcile().await.expect("reconcile");
+    assert_eq!((report.in_sync, report.drifted.len()), (1, 0));
+}
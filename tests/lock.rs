@@ -0,0 +1,137 @@
+//! Lock leases and fencing tokens, against the in-memory, Redis and MongoDB
+//! adapters.
+//!
+//! The Redis and MongoDB tests need a running server, so they are ignored by
+//! default:
+//!
+//! ```sh
+//! REDIS_TEST_URL=redis://localhost:6379 MONGO_TEST_URL=mongodb://localhost:27017 \
+//!     cargo test --test lock -- --ignored
+//! ```
+
+use service::application::lock::Locks;
+use service::domain::port::lock::LockPort;
+use service::infrastructure::persistence::lease::MongoLock;
+use service::infrastructure::persistence::memory::lease::InMemoryLock;
+use service::infrastructure::providers::redis::{RedisLock, RedisProvider};
+use std::sync::Arc;
+use std::time::Duration;
+
+const TTL: Duration = Duration::from_millis(150);
+
+fn name() -> String {
+    format!("test-{}", uuid::Uuid::new_v4().simple())
+}
+
+/// Behaviour every adapter must share.
+async fn contract(locks: &dyn LockPort) {
+    let name = name();
+
+    let first = locks
+        .try_acquire(&name, TTL)
+        .await
+        .unwrap()
+        .expect("free lock");
+    assert!(locks.try_acquire(&name, TTL).await.unwrap().is_none());
+    assert!(locks.renew(&first, TTL).await.unwrap());
+
+    // Lapsed: the next holder gets a greater token, the old one lost the lease
+    tokio::time::sleep(TTL * 2).await;
+    let second = locks
+        .try_acquire(&name, TTL)
+        .await
+        .unwrap()
+        .expect("lapsed lock");
+    assert!(second.token > first.token);
+    assert!(!locks.renew(&first, TTL).await.unwrap());
+    assert!(!locks.release(&first).await.unwrap());
+    assert!(locks.try_acquire(&name, TTL).await.unwrap().is_none());
+
+    assert!(locks.release(&second).await.unwrap());
+    let third = locks
+        .try_acquire(&name, TTL)
+        .await
+        .unwrap()
+        .expect("released lock");
+    assert!(third.token > second.token);
+}
+
+#[tokio::test]
+async fn in_memory() {
+    contract(&InMemoryLock::new()).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a local Redis (REDIS_TEST_URL)"]
+async fn redis() {
+    let url = std::env::var("REDIS_TEST_URL").unwrap_or_else(|_| "redis://localhost:6379".into());
+    let redis = RedisProvider::new(&url, "lock-contract")
+        .await
+        .expect("REDIS_TEST_URL is not reachable");
+    contract(&RedisLock::new(redis)).await;
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn mongo() {
+    let url =
+        std::env::var("MONGO_TEST_URL").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = mongodb::Client::with_uri_str(&url)
+        .await
+        .expect("MONGO_TEST_URL is not a valid MongoDB URL");
+    let db = client.database(&format!("lock_{}", uuid::Uuid::new_v4().simple()));
+    contract(&MongoLock::new(&db)).await;
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+async fn guard_renews_until_released() {
+    let locks = Locks::new(Arc::new(InMemoryLock::new()));
+    let name = name();
+
+    let guard = locks
+        .try_lock(&name, TTL)
+        .await
+        .unwrap()
+        .expect("free lock");
+    tokio::time::sleep(TTL * 3).await;
+    assert!(guard.is_held());
+    assert!(locks.try_lock(&name, TTL).await.unwrap().is_none());
+    assert_eq!(
+        locks
+            .run_exclusive(&name, TTL, async { "ran" })
+            .await
+            .unwrap(),
+        None
+    );
+
+    guard.release().await;
+    assert_eq!(
+        locks
+            .run_exclusive(&name, TTL, async { "ran" })
+            .await
+            .unwrap(),
+        Some("ran")
+    );
+}
+
+#[tokio::test]
+async fn dropped_guard_releases_the_lock() {
+    let locks = Locks::new(Arc::new(InMemoryLock::new()));
+    let name = name();
+
+    drop(
+        locks
+            .try_lock(&name, TTL)
+            .await
+            .unwrap()
+            .expect("free lock"),
+    );
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let guard = locks
+        .lock(&name, TTL, Duration::from_secs(1))
+        .await
+        .expect("released lock");
+    assert!(guard.token() > 1);
+}
@@ -0,0 +1,79 @@
+//! Low-stock alerts: one per drop below the reorder threshold, re-armed once
+//! stock recovers.
+
+mod app;
+
+use app::{TestApp, json};
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use serde_json::json;
+use service::domain::error::DomainResult;
+use service::domain::event::LowStock;
+use service::domain::port::notifier::NotifierPort;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Default)]
+struct RecordingNotifier {
+    alerts: Mutex<Vec<i32>>,
+}
+
+#[async_trait]
+impl NotifierPort for RecordingNotifier {
+    async fn notify_low_stock(&self, event: &LowStock) -> DomainResult<()> {
+        self.alerts.lock().unwrap().push(event.stock);
+        Ok(())
+    }
+}
+
+impl RecordingNotifier {
+    /// Stock levels alerted so far, once the background checks settled.
+    async fn alerts(&self) -> Vec<i32> {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        self.alerts.lock().unwrap().clone()
+    }
+}
+
+#[tokio::test]
+async fn one_alert_per_drop_below_the_threshold() {
+    let notifier = Arc::new(RecordingNotifier::default());
+    let app = TestApp::with_notifier(notifier.clone());
+    let user = app.create_user("Ada", "ada@example.com").await;
+    let product = app.create_product("MUG-1", 10).await;
+
+    let path = format!("/api/v1/products/{product}");
+    let etag = app.etag(&path).await;
+    let response = app
+        .update(
+            "PUT",
+            &format!("{path}/reorder-threshold"),
+            &etag,
+            json!({ "threshold": 5 }),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    app.order(&user, &product, 4).await;
+    assert!(notifier.alerts().await.is_empty(), "6 is not below 5");
+
+    let first = app.order(&user, &product, 2).await;
+    assert_eq!(notifier.alerts().await, [4]);
+    app.order(&user, &product, 1).await;
+    assert_eq!(notifier.alerts().await, [4], "still low: no second alert");
+
+    let response = app.get("/api/v1/products/low-stock").await;
+    assert_eq!(json(response).await["data"]["total"], 1);
+
+    // Back to 5 re-arms the alert for the next drop
+    app.send(
+        Request::delete(format!("/api/v1/orders/{first}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(app.stock(&product).await, 5);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    app.order(&user, &product, 1).await;
+    assert_eq!(notifier.alerts().await, [4, 4]);
+}
@@ -0,0 +1,26 @@
+//! Repository contract against the in-memory adapters.
+
+mod contract;
+
+use service::infrastructure::persistence::memory::InMemoryRepositories;
+
+#[tokio::test]
+async fn users() {
+    contract::user_repository(InMemoryRepositories::new().users.as_ref()).await;
+}
+
+#[tokio::test]
+async fn products() {
+    let repos = InMemoryRepositories::new();
+    contract::product_repository(repos.products.as_ref(), repos.ledger.as_ref()).await;
+}
+
+#[tokio::test]
+async fn categories() {
+    contract::category_repository(InMemoryRepositories::new().categories.as_ref()).await;
+}
+
+#[tokio::test]
+async fn orders() {
+    contract::order_repository(InMemoryRepositories::new().orders.as_ref()).await;
+}
@@ -0,0 +1,84 @@
+//! Migrations against a real MongoDB: a second run finds nothing pending and
+//! every `up` survives being applied twice.
+
+use futures::TryStreamExt;
+use mongodb::bson::{Document, doc};
+use service::application::lock::Locks;
+use service::domain::values::INITIAL_VERSION;
+use service::infrastructure::persistence::lease::MongoLock;
+use service::infrastructure::persistence::migrations::{self, MIGRATIONS_COLLECTION, runner::Migrator};
+use std::sync::Arc;
+
+async fn database() -> mongodb::Database {
+    let url =
+        std::env::var("MONGO_TEST_URL").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = mongodb::Client::with_uri_str(&url)
+        .await
+        .expect("MONGO_TEST_URL is not a valid MongoDB URL");
+    client.database(&format!("migrations_{}", uuid::Uuid::new_v4().simple()))
+}
+
+async fn applied(db: &mongodb::Database) -> Vec<Document> {
+    db.collection::<Document>(MIGRATIONS_COLLECTION)
+        .find(doc! {})
+        .sort(doc! { "_id": 1 })
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn running_the_migrations_again_applies_nothing() {
+    let db = database().await;
+    db.collection::<Document>("users")
+        .insert_one(doc! { "name": "Ada", "email": "ada@example.com" })
+        .await
+        .unwrap();
+    let migrator = Migrator::new(&db, Locks::new(Arc::new(MongoLock::new(&db))));
+
+    let first = migrator.run(false).await.unwrap();
+    assert_eq!(first.len(), migrations::all().len());
+    let recorded = applied(&db).await;
+
+    assert!(migrator.run(false).await.unwrap().is_empty());
+    assert_eq!(applied(&db).await, recorded, "_migrations is untouched");
+    assert!(
+        migrator
+            .status()
+            .await
+            .unwrap()
+            .iter()
+            .all(|status| !status.is_pending())
+    );
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn every_migration_can_be_applied_twice() {
+    // An instance that dies after `up` but before recording it runs `up` again
+    let db = database().await;
+    db.collection::<Document>("users")
+        .insert_one(doc! { "name": "Ada", "email": "ada@example.com" })
+        .await
+        .unwrap();
+
+    for migration in migrations::all() {
+        for attempt in 1..=2 {
+            if let Err(e) = migration.up(&db, false).await {
+                panic!("{} failed on attempt {attempt}: {e}", migration.name());
+            }
+        }
+    }
+    let user = db
+        .collection::<Document>("users")
+        .find_one(doc! {})
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.get_i64("version").unwrap(), INITIAL_VERSION as i64);
+    db.drop().await.expect("drop test database");
+}
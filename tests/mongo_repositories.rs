@@ -0,0 +1,206 @@
+//! Repository contract against the MongoDB adapters.
+//!
+//! Needs a running MongoDB, so the tests are ignored by default:
+//!
+//! ```sh
+//! MONGO_TEST_URL=mongodb://localhost:27017 cargo test --test mongo_repositories -- --ignored
+//! ```
+//!
+//! Each test works in its own throwaway database, dropped when it passes.
+
+mod contract;
+
+use mongodb::bson::{Document, doc, oid::ObjectId};
+use mongodb::{Client, Database};
+use service::application::category::CategoryService;
+use service::domain::entities::product::{ProductMetadata, SkuUpsertOutcome};
+use service::domain::pagination::Pagination;
+use service::domain::port::product::ProductRepositoryPort;
+use service::infrastructure::persistence::{
+    category::repository::CategoryRepository,
+    inventory::repository::InventoryLedgerRepository,
+    order::repository::OrderRepository,
+    outbox::writer::OutboxWriter,
+    product::repository::{PRODUCTS_COLLECTION, ProductRepository},
+    user::repository::UserRepository,
+};
+use std::sync::Arc;
+
+async fn database() -> Database {
+    let url =
+        std::env::var("MONGO_TEST_URL").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = Client::with_uri_str(&url)
+        .await
+        .expect("MONGO_TEST_URL is not a valid MongoDB URL");
+    client.database(&format!("contract_{}", uuid::Uuid::new_v4().simple()))
+}
+
+/// Standalone instances have no transactions; the contract doesn't need them.
+fn outbox(db: &Database) -> OutboxWriter {
+    OutboxWriter::new(db, false)
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn users() {
+    let db = database().await;
+    let repo = UserRepository::new(&db, outbox(&db));
+    repo.create_indexes().await.expect("create indexes");
+
+    contract::user_repository(&repo).await;
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn products() {
+    let db = database().await;
+    let repo = ProductRepository::new(&db, outbox(&db));
+    repo.create_indexes().await.expect("create indexes");
+
+    contract::product_repository(&repo, &InventoryLedgerRepository::new(&db)).await;
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn categories() {
+    let db = database().await;
+    let repo = CategoryRepository::new(&db, outbox(&db));
+    repo.create_indexes().await.expect("create indexes");
+
+    contract::category_repository(&repo).await;
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn orders() {
+    let db = database().await;
+    let repo = OrderRepository::new(&db, outbox(&db));
+    repo.create_indexes().await.expect("create indexes");
+
+    contract::order_repository(&repo).await;
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn metadata_updates_keep_the_legacy_category() {
+    let db = database().await;
+    let repo = ProductRepository::new(&db, outbox(&db));
+    let product = contract::product(repo.next_id(), "SKU-LEGACY", 5, 0);
+    let id = repo.create(&product, &[]).await.expect("create product");
+    let oid = ObjectId::parse_str(&*id).unwrap();
+    db.collection::<Document>(PRODUCTS_COLLECTION)
+        .update_one(
+            doc! { "_id": oid },
+            doc! { "$set": { "metadata.category": "Garden Tools" } },
+        )
+        .await
+        .expect("set legacy category");
+
+    let metadata = ProductMetadata {
+        description: Some("Now with a description".to_string()),
+        ..product.metadata.clone()
+    };
+    assert!(
+        repo.update_metadata(&id, product.version, &metadata, &[])
+            .await
+            .expect("update_metadata")
+    );
+
+    // Still there for `migrate_legacy_categories` to convert
+    assert_eq!(
+        repo.find_legacy_categories()
+            .await
+            .expect("legacy categories"),
+        ["Garden Tools"]
+    );
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn sku_upserts_report_the_products_they_replace() {
+    let db = database().await;
+    let repo = ProductRepository::new(&db, outbox(&db));
+    repo.create_indexes().await.expect("create indexes");
+    let existing = contract::product(repo.next_id(), "SKU-KEEP", 5, 0);
+    let id = repo.create(&existing, &[]).await.expect("create product");
+
+    let outcomes = repo
+        .upsert_by_sku(
+            &[
+                contract::product(repo.next_id(), "SKU-KEEP", 9, 0),
+                contract::product(repo.next_id(), "SKU-NEW", 3, 0),
+            ],
+            "batch-1",
+        )
+        .await
+        .expect("upsert_by_sku");
+
+    match &outcomes[..] {
+        [
+            SkuUpsertOutcome::Updated {
+                id: updated,
+                previous,
+            },
+            SkuUpsertOutcome::Created(created),
+        ] => {
+            assert_eq!(updated, &id);
+            assert_eq!(previous.stock, 5);
+            let created = repo.find_by_id(created).await.expect("find_by_id");
+            assert_eq!(created.map(|p| p.stock), Some(3));
+        }
+        other => panic!("expected an update then a creation, got {other:?}"),
+    }
+    let updated = repo.find_by_id(&id).await.expect("find_by_id").unwrap();
+    assert_eq!((updated.stock, updated.version), (9, existing.version + 1));
+    db.drop().await.expect("drop test database");
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn moving_a_category_rewrites_its_subtree() {
+    let db = database().await;
+    let service = CategoryService::new(
+        Arc::new(CategoryRepository::new(&db, outbox(&db))),
+        Arc::new(ProductRepository::new(&db, outbox(&db))),
+    );
+    let create = async |name: &str, parent| {
+        service
+            .create_category(name, None, parent)
+            .await
+            .expect("create category")
+            .id
+            .expect("category id")
+    };
+    let home = create("Home", None).await;
+    let garden = create("Garden", Some(&home)).await;
+    create("Tools", Some(&garden)).await;
+    let outdoor = create("Outdoor", None).await;
+
+    service
+        .update_category(&garden, "Gardening", None, Some(&outdoor))
+        .await
+        .expect("move category");
+
+    let paths: Vec<String> = service
+        .list_categories(Pagination { page: 1, limit: 10 })
+        .await
+        .expect("list categories")
+        .into_iter()
+        .map(|c| c.path)
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "home",
+            "outdoor",
+            "outdoor/gardening",
+            "outdoor/gardening/tools"
+        ]
+    );
+    db.drop().await.expect("drop test database");
+}
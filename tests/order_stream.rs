@@ -0,0 +1,111 @@
+//! `GET /orders/stream`: server-sent order updates, filtered per subscriber
+//! and resumable with `Last-Event-ID`.
+
+mod app;
+
+use app::TestApp;
+use axum::body::{Body, BodyDataStream};
+use axum::http::{Request, StatusCode, header};
+use futures::StreamExt;
+use std::time::Duration;
+
+/// One server-sent event.
+#[derive(Debug)]
+struct Event {
+    id: String,
+    event: String,
+    data: serde_json::Value,
+}
+
+struct EventStream {
+    body: BodyDataStream,
+    buffer: String,
+}
+
+impl EventStream {
+    async fn open(app: &TestApp, path: &str, last_event_id: Option<&str>) -> Self {
+        let mut request = Request::get(path).header(header::ACCEPT, "text/event-stream");
+        if let Some(last_event_id) = last_event_id {
+            request = request.header("last-event-id", last_event_id);
+        }
+        let response = app.send(request.body(Body::empty()).unwrap()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        Self {
+            body: response.into_body().into_data_stream(),
+            buffer: String::new(),
+        }
+    }
+
+    /// The next event, skipping heartbeats.
+    async fn next(&mut self) -> Event {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let frame: String = self.buffer.drain(..end + 2).collect();
+                let field = |name: &str| {
+                    frame
+                        .lines()
+                        .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                        .map(|value| value.trim_start().to_string())
+                };
+                if let (Some(id), Some(event), Some(data)) =
+                    (field("id"), field("event"), field("data"))
+                {
+                    return Event {
+                        id,
+                        event,
+                        data: serde_json::from_str(&data).unwrap(),
+                    };
+                }
+                continue;
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(2), self.body.next())
+                .await
+                .expect("an event within 2s")
+                .expect("stream still open")
+                .unwrap();
+            self.buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+}
+
+#[tokio::test]
+async fn subscribers_only_see_their_orders_and_resume_after_the_last_one() {
+    let app = TestApp::new();
+    let ada = app.create_user("Ada", "ada@example.com").await;
+    let grace = app.create_user("Grace", "grace@example.com").await;
+    let product = app.create_product("MUG-1", 10).await;
+
+    let path = format!("/api/v1/orders/stream?user_id={ada}");
+    let mut stream = EventStream::open(&app, &path, None).await;
+    app.order(&grace, &product, 1).await;
+    let first = app.order(&ada, &product, 1).await;
+
+    let event = stream.next().await;
+    assert_eq!(event.event, "order_created");
+    assert_eq!(
+        event.data["id"],
+        first.as_str(),
+        "Grace's order is filtered out"
+    );
+    drop(stream);
+
+    // Missed while disconnected, replayed on reconnect
+    let response = app
+        .send(
+            Request::delete(format!("/api/v1/orders/{first}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let second = app.order(&ada, &product, 2).await;
+
+    let mut stream = EventStream::open(&app, &path, Some(&event.id)).await;
+    let cancelled = stream.next().await;
+    assert_eq!(
+        (cancelled.event.as_str(), &cancelled.data["id"]),
+        ("order_cancelled", &serde_json::json!(first))
+    );
+    let created = stream.next().await;
+    assert_eq!(created.data["id"], second.as_str());
+}
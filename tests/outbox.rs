@@ -0,0 +1,97 @@
+//! The outbox relay over the in-memory outbox: failed publishes back off and
+//! are retried, and each aggregate's events go out in order.
+
+use async_trait::async_trait;
+use service::application::outbox::OutboxRelay;
+use service::domain::entities::user::UserId;
+use service::domain::error::{DomainResult, Error};
+use service::domain::event::{DomainEvent, OutboxMessage};
+use service::domain::port::event_publisher::EventPublisherPort;
+use service::domain::port::outbox::OutboxPort;
+use service::infrastructure::events::memory::InMemoryEventPublisher;
+use service::infrastructure::persistence::memory::outbox::InMemoryOutbox;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Fails the first `failures` publishes, then delivers to `inner`.
+struct FlakyPublisher {
+    failures: AtomicUsize,
+    inner: InMemoryEventPublisher,
+}
+
+#[async_trait]
+impl EventPublisherPort for FlakyPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> DomainResult<()> {
+        let failing = self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok();
+        if failing {
+            return Err(Error::external("Broker", "unavailable"));
+        }
+        self.inner.publish(message).await
+    }
+}
+
+fn user_created(user_id: &str, name: &str) -> DomainEvent {
+    DomainEvent::UserCreated {
+        user_id: UserId::new(user_id),
+        name: name.to_string(),
+        email: format!("{}@example.com", name.to_lowercase()),
+    }
+}
+
+#[tokio::test]
+async fn failed_publishes_are_retried_after_a_backoff_in_aggregate_order() {
+    let ada = "0123456789abcdef01234567";
+    let grace = "89abcdef0123456789abcdef";
+    let outbox = InMemoryOutbox::new();
+    for event in [
+        user_created(ada, "Ada"),
+        user_created(ada, "Ada Lovelace"),
+        user_created(grace, "Grace"),
+    ] {
+        outbox.append(&[event]).await.expect("append");
+    }
+    let publisher = Arc::new(FlakyPublisher {
+        failures: AtomicUsize::new(1),
+        inner: InMemoryEventPublisher::new(),
+    });
+    let relay = OutboxRelay::new(Arc::new(outbox), publisher.clone());
+    let published = || -> Vec<(String, u32)> {
+        publisher
+            .inner
+            .published()
+            .into_iter()
+            .map(|message| match message.event {
+                DomainEvent::UserCreated { name, .. } => (name, message.attempts),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect()
+    };
+
+    // One head per aggregate: Ada's first event fails, Grace's goes out
+    assert_eq!(relay.relay_once().await, 2);
+    assert_eq!(published(), [("Grace".to_string(), 0)]);
+
+    // Ada's head backs off, and her second event waits behind it
+    assert_eq!(relay.relay_once().await, 0);
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    assert_eq!(relay.relay_once().await, 1);
+    assert_eq!(relay.relay_once().await, 1);
+    assert_eq!(
+        relay.relay_once().await,
+        0,
+        "everything is marked published"
+    );
+    assert_eq!(
+        published(),
+        [
+            ("Grace".to_string(), 0),
+            ("Ada".to_string(), 1),
+            ("Ada Lovelace".to_string(), 0),
+        ]
+    );
+}
@@ -0,0 +1,128 @@
+//! Repository contract against the PostgreSQL adapters.
+//!
+//! Needs a running PostgreSQL, so the tests are ignored by default:
+//!
+//! ```sh
+//! POSTGRES_TEST_URL=postgres://postgres@localhost:5432/postgres \
+//!   cargo test --test postgres_repositories -- --ignored
+//! ```
+//!
+//! Each test creates its own throwaway database, dropped when it passes.
+
+// Categories stay in memory with PERSISTENCE=postgres: no category suite here
+#[allow(dead_code)]
+mod contract;
+
+use chrono::Utc;
+use service::domain::entities::user::{User, UserId};
+use service::domain::error::DomainError;
+use service::domain::port::user::UserRepositoryPort;
+use service::domain::values::INITIAL_VERSION;
+use service::infrastructure::persistence::memory::inventory::InMemoryInventoryLedger;
+use service::infrastructure::persistence::postgres::{
+    self, order::PostgresOrderRepository, product::PostgresProductRepository,
+    user::PostgresUserRepository,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{Executor, PgPool};
+use std::str::FromStr;
+use std::sync::Arc;
+
+struct TestDatabase {
+    admin: PgPool,
+    name: String,
+    pool: PgPool,
+}
+
+impl TestDatabase {
+    async fn create() -> Self {
+        let url = std::env::var("POSTGRES_TEST_URL")
+            .unwrap_or_else(|_| "postgres://postgres@localhost:5432/postgres".into());
+        let options = PgConnectOptions::from_str(&url).expect("POSTGRES_TEST_URL is not valid");
+        let admin = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(options.clone())
+            .await
+            .expect("connect to POSTGRES_TEST_URL");
+
+        let name = format!("contract_{}", uuid::Uuid::new_v4().simple());
+        admin
+            .execute(format!("CREATE DATABASE {name}").as_str())
+            .await
+            .expect("create test database");
+        let pool = PgPoolOptions::new()
+            .connect_with(options.database(&name))
+            .await
+            .expect("connect to test database");
+        postgres::migrate(&pool).await.expect("run migrations");
+
+        Self { admin, name, pool }
+    }
+
+    async fn drop(self) {
+        self.pool.close().await;
+        self.admin
+            .execute(format!("DROP DATABASE {} WITH (FORCE)", self.name).as_str())
+            .await
+            .expect("drop test database");
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a local PostgreSQL (POSTGRES_TEST_URL)"]
+async fn users() {
+    let db = TestDatabase::create().await;
+
+    contract::user_repository(&PostgresUserRepository::new(db.pool.clone())).await;
+    db.drop().await;
+}
+
+#[tokio::test]
+#[ignore = "needs a local PostgreSQL (POSTGRES_TEST_URL)"]
+async fn products() {
+    let db = TestDatabase::create().await;
+
+    let ledger = Arc::new(InMemoryInventoryLedger::new());
+    let repo = PostgresProductRepository::new(db.pool.clone(), ledger.clone());
+
+    contract::product_repository(&repo, ledger.as_ref()).await;
+    db.drop().await;
+}
+
+#[tokio::test]
+#[ignore = "needs a local PostgreSQL (POSTGRES_TEST_URL)"]
+async fn orders() {
+    let db = TestDatabase::create().await;
+
+    contract::order_repository(&PostgresOrderRepository::new(db.pool.clone())).await;
+    db.drop().await;
+}
+
+#[tokio::test]
+#[ignore = "needs a local PostgreSQL (POSTGRES_TEST_URL)"]
+async fn duplicate_email_is_already_exists() {
+    let db = TestDatabase::create().await;
+    let repo = PostgresUserRepository::new(db.pool.clone());
+
+    let user = |id: UserId| {
+        let now = Utc::now();
+        User {
+            id: Some(id),
+            name: "Taken".to_string(),
+            email: "taken@example.com".to_string(),
+            created_at: now,
+            updated_at: now,
+            version: INITIAL_VERSION,
+            deleted_at: None,
+        }
+    };
+    repo.create(&user(repo.next_id()), &[])
+        .await
+        .expect("create user");
+    let result = repo.create(&user(repo.next_id()), &[]).await;
+    assert!(
+        matches!(result, Err(DomainError::AlreadyExists { .. })),
+        "a taken email should be AlreadyExists, got {result:?}"
+    );
+    db.drop().await;
+}
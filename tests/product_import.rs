@@ -0,0 +1,253 @@
+//! Bulk product import: the upload parser and the report built by
+//! `ProductImport` over the in-memory repositories.
+
+use axum::body::Body;
+use service::application::audit::AuditService;
+use service::application::category::CategoryService;
+use service::application::inventory::{InventoryService, LowStockMonitor};
+use service::application::product::{ImportReport, ImportRowStatus, ProductService};
+use service::domain::entities::audit::{Actor, AuditAction};
+use service::domain::entities::category::CategoryId;
+use service::domain::entities::product::{ProductDraft, ProductMetadata};
+use service::domain::filter::AuditFilter;
+use service::domain::event::DomainEvent;
+use service::domain::pagination::Pagination;
+use service::domain::port::audit::AuditLogPort;
+use service::domain::port::inventory::InventoryLedgerPort;
+use service::infrastructure::metrics::noop::NoopMetrics;
+use service::infrastructure::notifier::log::LogNotifier;
+use service::infrastructure::persistence::memory::InMemoryRepositories;
+use service::infrastructure::providers::propagation::OtelTraceContext;
+use service::presentation::http::product::import::{self, ImportFormat, ParsedRow};
+use std::sync::Arc;
+
+async fn parse(body: &str, format: ImportFormat) -> Vec<ParsedRow> {
+    let mut rows = import::parse_body(Body::from(body.to_string()), format);
+    let mut parsed = Vec::new();
+    while let Some(row) = rows.recv().await {
+        parsed.push(row);
+    }
+    parsed
+}
+
+#[tokio::test]
+async fn csv_rows_are_numbered_and_validated() {
+    let category = "0123456789abcdef01234567";
+    let body = format!(
+        "name,price,stock,category_id,sku,tags\n\
+         Mug,9.5,10,{category},MUG-1,kitchen | gift\n\
+         ,9.5,10,{category},MUG-2,\n\
+         Cup,cheap,10,{category},CUP-1,\n"
+    );
+
+    let rows = parse(&body, ImportFormat::Csv).await;
+
+    assert_eq!(rows.len(), 3);
+    let mug = rows[0].input.as_ref().expect("valid row");
+    assert_eq!(rows[0].row, 1);
+    assert_eq!(mug.sku, "MUG-1");
+    assert_eq!(
+        mug.tags.as_deref(),
+        Some(&["kitchen".to_string(), "gift".to_string()][..])
+    );
+
+    // Validation and type errors keep the row number and SKU for the report
+    assert_eq!((rows[1].row, rows[1].sku.as_deref()), (2, Some("MUG-2")));
+    assert!(
+        rows[1]
+            .input
+            .as_ref()
+            .unwrap_err()
+            .contains("Name cannot be empty")
+    );
+    assert_eq!((rows[2].row, rows[2].sku.as_deref()), (3, Some("CUP-1")));
+    assert!(rows[2].input.is_err());
+}
+
+#[tokio::test]
+async fn ndjson_skips_blank_lines_and_reports_bad_json() {
+    let body = "\n{\"name\":\"Mug\",\"price\":1,\"stock\":1,\"category_id\":\"0123456789abcdef01234567\",\"sku\":\"MUG-1\"}\n\nnot json\n";
+
+    let rows = parse(body, ImportFormat::Ndjson).await;
+
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].input.is_ok());
+    assert_eq!(rows[1].row, 2);
+    assert!(
+        rows[1]
+            .input
+            .as_ref()
+            .unwrap_err()
+            .starts_with("Invalid JSON")
+    );
+}
+
+struct Fixture {
+    repos: InMemoryRepositories,
+    products: ProductService,
+    category: CategoryId,
+}
+
+async fn fixture() -> Fixture {
+    let repos = InMemoryRepositories::new();
+    let inventory = InventoryService::new(
+        repos.products.clone(),
+        repos.ledger.clone(),
+        LowStockMonitor::new(repos.products.clone(), Arc::new(LogNotifier)),
+        Arc::new(NoopMetrics),
+    );
+    let products = ProductService::new(
+        repos.products.clone(),
+        repos.categories.clone(),
+        Arc::new(inventory),
+        Arc::new(AuditService::new(
+            repos.audit.clone(),
+            Arc::new(OtelTraceContext),
+        )),
+    );
+    let category = CategoryService::new(repos.categories.clone(), repos.products.clone())
+        .create_category("Kitchen", None, None)
+        .await
+        .expect("category created")
+        .id
+        .expect("category id");
+    Fixture {
+        repos,
+        products,
+        category,
+    }
+}
+
+fn draft(sku: &str, stock: i32, category_id: &CategoryId) -> ProductDraft {
+    ProductDraft {
+        name: format!("Product {sku}"),
+        price: 10.0,
+        stock,
+        metadata: ProductMetadata {
+            description: None,
+            category_id: Some(category_id.clone()),
+            tags: Vec::new(),
+            sku: sku.to_string(),
+        },
+    }
+}
+
+async fn import(fixture: &Fixture, rows: Vec<ProductDraft>, dry_run: bool) -> ImportReport {
+    let actor = Actor::new("importer", None);
+    let mut import = fixture.products.start_import(&actor, dry_run);
+    for (index, draft) in rows.into_iter().enumerate() {
+        import.push(index + 1, draft).await.expect("row accepted");
+    }
+    import.finish().await.expect("import finished")
+}
+
+#[tokio::test]
+async fn report_counts_created_updated_and_failed_rows() {
+    let fixture = fixture().await;
+    let category = fixture.category.clone();
+    let missing = CategoryId::new("0123456789abcdef01234567");
+
+    let first = import(
+        &fixture,
+        vec![
+            draft("MUG-1", 5, &category),
+            draft("MUG-1", 7, &category),
+            draft("CUP-1", 3, &missing),
+        ],
+        false,
+    )
+    .await;
+    assert_eq!((first.created, first.updated, first.failed), (1, 0, 2));
+    let statuses: Vec<_> = first.rows.iter().map(|r| (r.row, r.status)).collect();
+    assert_eq!(
+        statuses,
+        [
+            (1, ImportRowStatus::Created),
+            (2, ImportRowStatus::Failed),
+            (3, ImportRowStatus::Failed)
+        ]
+    );
+    assert!(first.rows[1].reason.as_deref().unwrap().contains("row 1"));
+    assert!(
+        first.rows[2]
+            .reason
+            .as_deref()
+            .unwrap()
+            .starts_with("Category not found")
+    );
+    let mug = first.rows[0]
+        .product_id
+        .clone()
+        .expect("created product id");
+
+    let preview = import(&fixture, vec![draft("MUG-1", 9, &category)], true).await;
+    assert_eq!((preview.created, preview.updated), (0, 1));
+
+    let second = import(&fixture, vec![draft("MUG-1", 9, &category)], false).await;
+    assert_eq!((second.created, second.updated, second.failed), (0, 1, 0));
+    assert_eq!(second.rows[0].product_id.as_ref(), Some(&mug));
+
+    // The ledger holds the initial stock and the delta, not the dry run
+    let movements = fixture
+        .repos
+        .ledger
+        .find_by_product(&mug, Pagination { page: 1, limit: 10 })
+        .await
+        .unwrap();
+    let mut deltas: Vec<i32> = movements.iter().map(|m| m.delta).collect();
+    deltas.sort();
+    assert_eq!(deltas, [4, 5]);
+
+    // Each written row has its event in the outbox; failed rows and the dry run don't
+    let imported: Vec<(bool, i32)> = fixture
+        .repos
+        .outbox
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            DomainEvent::ProductImported { created, stock, .. } => Some((created, stock)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(imported, [(true, 5), (false, 9)]);
+
+    // The update is audited against the product it replaced
+    let filter = AuditFilter {
+        entity: Some("Product".to_string()),
+        entity_id: Some(mug.to_string()),
+    };
+    let entries = fixture
+        .repos
+        .audit
+        .find(&filter, Pagination { page: 1, limit: 10 })
+        .await
+        .unwrap();
+    let update = entries
+        .iter()
+        .find(|entry| entry.action == AuditAction::Update)
+        .expect("update audited");
+    let stock = update
+        .changes
+        .iter()
+        .find(|change| change.field == "stock")
+        .expect("stock change audited");
+    assert_eq!(
+        (stock.before.clone(), stock.after.clone()),
+        (Some(5.into()), Some(9.into()))
+    );
+}
+
+#[tokio::test]
+async fn concurrent_imports_create_a_sku_once() {
+    let fixture = fixture().await;
+    let category = fixture.category.clone();
+
+    let (a, b) = tokio::join!(
+        import(&fixture, vec![draft("MUG-1", 5, &category)], false),
+        import(&fixture, vec![draft("MUG-1", 6, &category)], false),
+    );
+
+    assert_eq!(a.created + b.created, 1);
+    assert_eq!(a.updated + b.updated, 1);
+    assert_eq!(a.rows[0].product_id, b.rows[0].product_id);
+}
@@ -0,0 +1,136 @@
+//! Per-client rate limiting of `/api/v1`, with the in-memory limiter.
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::{Router, middleware};
+use service::application::api_key::ApiKeyService;
+use service::domain::port::rate_limit::RateLimit;
+use service::infrastructure::persistence::memory::api_key::InMemoryApiKeyRepository;
+use service::infrastructure::rate_limit::memory::InMemoryRateLimiter;
+use service::presentation::http::rate_limit::{self, RateLimits};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+struct App {
+    router: Router,
+    api_keys: Arc<ApiKeyService>,
+}
+
+impl App {
+    async fn send(&self, request: Request<Body>) -> axum::response::Response {
+        self.router.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn issue(&self, name: &str) -> String {
+        self.api_keys.issue(name).await.expect("key issued").secret
+    }
+}
+
+fn app() -> App {
+    let orders = "2/1m".parse::<RateLimit>().expect("valid limit");
+    let default = "100/1m".parse::<RateLimit>().expect("valid limit");
+    let api_keys = Arc::new(ApiKeyService::new(
+        Arc::new(InMemoryApiKeyRepository::new()),
+    ));
+    let limits = RateLimits::new(
+        Arc::new(InMemoryRateLimiter::new()),
+        api_keys.clone(),
+        default,
+        BTreeMap::from([("orders".to_string(), orders)]),
+    );
+
+    let router = Router::new()
+        .route("/api/v1/orders", get(|| async { "orders" }))
+        .route("/api/v1/products", get(|| async { "products" }))
+        .layer(middleware::from_fn_with_state(limits, rate_limit::enforce));
+    App { router, api_keys }
+}
+
+fn request(path: &str, api_key: &str) -> Request<Body> {
+    Request::get(path)
+        .header("x-api-key", api_key)
+        .header("x-forwarded-for", "203.0.113.7")
+        .body(Body::empty())
+        .expect("valid request")
+}
+
+#[tokio::test]
+async fn over_the_limit_gets_429_with_retry_after() {
+    let app = app();
+    let partner = app.issue("partner").await;
+
+    for remaining in ["1", "0"] {
+        let response = app.send(request("/api/v1/orders", &partner)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["ratelimit-limit"], "2");
+        assert_eq!(response.headers()["ratelimit-remaining"], remaining);
+        assert_eq!(response.headers()["ratelimit-policy"], "2;w=60");
+    }
+
+    let response = app.send(request("/api/v1/orders", &partner)).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(
+        (1..=30).contains(&retry_after),
+        "retry after {retry_after}s"
+    );
+}
+
+#[tokio::test]
+async fn buckets_are_per_client_and_route_group() {
+    let app = app();
+    let partner = app.issue("partner").await;
+    let another_partner = app.issue("another-partner").await;
+    for _ in 0..2 {
+        app.send(request("/api/v1/orders", &partner)).await;
+    }
+
+    let other_client = app.send(request("/api/v1/orders", &another_partner)).await;
+    assert_eq!(other_client.status(), StatusCode::OK);
+
+    let other_group = app.send(request("/api/v1/products", &partner)).await;
+    assert_eq!(other_group.status(), StatusCode::OK);
+    assert_eq!(other_group.headers()["ratelimit-limit"], "100");
+}
+
+#[tokio::test]
+async fn rotating_headers_does_not_reset_the_bucket() {
+    let app = app();
+
+    // Made-up keys and spoofed hops in front of the one nginx appended
+    for attempt in 0..3 {
+        let response = app
+            .send(
+                Request::get("/api/v1/orders")
+                    .header("x-api-key", format!("made-up-{attempt}"))
+                    .header("x-user-id", format!("user-{attempt}"))
+                    .header("x-forwarded-for", format!("10.0.0.{attempt}, 203.0.113.7"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await;
+        let expected = if attempt < 2 {
+            StatusCode::OK
+        } else {
+            StatusCode::TOO_MANY_REQUESTS
+        };
+        assert_eq!(response.status(), expected, "attempt {attempt}");
+    }
+
+    // An unknown key is limited by its IP, which is already exhausted
+    let response = app
+        .send(request("/api/v1/orders", "another-made-up-key"))
+        .await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // A real key gets its own bucket
+    let partner = app.issue("partner").await;
+    let response = app.send(request("/api/v1/orders", &partner)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
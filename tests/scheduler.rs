@@ -0,0 +1,105 @@
+//! Cron schedules and single runs across instances, with the in-memory lock
+//! and run store shared by two schedulers.
+
+use service::application::lock::Locks;
+use service::application::scheduler::Scheduler;
+use service::domain::job::{Cron, JobOutcome, JobRun};
+use service::domain::port::job_run::JobRunPort;
+use service::infrastructure::persistence::job_run::MongoJobRuns;
+use service::infrastructure::persistence::memory::{job_run::InMemoryJobRuns, lease::InMemoryLock};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::watch;
+
+#[test]
+fn five_field_expressions_start_on_the_minute() {
+    let cron: Cron = "30 2 * * *".parse().expect("valid expression");
+    let after = chrono::DateTime::parse_from_rfc3339("2026-03-01T10:00:00-03:00").unwrap();
+
+    let next = cron.next_after(&after).expect("an occurrence");
+    assert_eq!(next.to_rfc3339(), "2026-03-02T02:30:00-03:00");
+    assert_eq!(cron.to_string(), "30 2 * * *");
+    assert!("61 * * * *".parse::<Cron>().is_err());
+}
+
+#[tokio::test]
+async fn each_occurrence_runs_on_one_instance() {
+    let lock = Arc::new(InMemoryLock::new());
+    let runs: Arc<dyn JobRunPort> = Arc::new(InMemoryJobRuns::new());
+    let count = Arc::new(AtomicUsize::new(0));
+    let every_second: Cron = "* * * * * *".parse().unwrap();
+
+    let instances: Vec<Scheduler> = (0..2)
+        .map(|_| {
+            let count = count.clone();
+            Scheduler::new(chrono_tz::UTC, Locks::new(lock.clone()), runs.clone()).register(
+                "count",
+                every_second.clone(),
+                move || {
+                    let count = count.clone();
+                    async move {
+                        let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+                        Ok(format!("run {}", n))
+                    }
+                },
+            )
+        })
+        .collect();
+
+    let (stop, shutdown) = watch::channel(false);
+    let handles: Vec<_> = instances
+        .iter()
+        .flat_map(|scheduler| scheduler.spawn(shutdown.clone()))
+        .collect();
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    let _ = stop.send(true);
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    // Two or three occurrences fit in the window, whatever the start offset
+    let ran = count.load(Ordering::SeqCst);
+    assert!((2..=3).contains(&ran), "ran {ran} times");
+
+    let status = instances[0].status().await.unwrap();
+    let last = status[0].last_run.as_ref().expect("a recorded run");
+    assert_eq!(last.outcome, JobOutcome::Succeeded);
+    assert_eq!(last.detail, format!("run {}", ran));
+    assert!(status[0].next_run.is_some());
+}
+
+#[tokio::test]
+#[ignore = "needs a local MongoDB (MONGO_TEST_URL)"]
+async fn mongo_job_runs_keep_the_last_run() {
+    let url =
+        std::env::var("MONGO_TEST_URL").unwrap_or_else(|_| "mongodb://localhost:27017".into());
+    let client = mongodb::Client::with_uri_str(&url)
+        .await
+        .expect("MONGO_TEST_URL is not a valid MongoDB URL");
+    let db = client.database(&format!("job_runs_{}", uuid::Uuid::new_v4().simple()));
+    let runs = MongoJobRuns::new(&db);
+
+    assert!(runs.last("count").await.unwrap().is_none());
+    for (outcome, detail) in [
+        (JobOutcome::Failed, "boom"),
+        (JobOutcome::Succeeded, "run 2"),
+    ] {
+        let now = chrono::Utc::now();
+        let run = JobRun {
+            job: "count".to_string(),
+            scheduled_for: now,
+            started_at: now,
+            finished_at: now,
+            outcome,
+            detail: detail.to_string(),
+            trace_id: None,
+        };
+        runs.record(&run).await.unwrap();
+    }
+
+    let last = runs.last("count").await.unwrap().expect("a recorded run");
+    assert_eq!(last.outcome, JobOutcome::Succeeded);
+    assert_eq!(last.detail, "run 2");
+    db.drop().await.expect("drop test database");
+}
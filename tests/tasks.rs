@@ -0,0 +1,167 @@
+//! Task workers: failures are retried with a growing backoff, then moved to
+//! the dead-letter queue; malformed payloads go there straight away.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use service::application::tasks::{TaskHandler, TaskRegistry, TaskWorkers};
+use service::domain::error::{DomainResult, Error};
+use service::domain::port::task_queue::TaskQueuePort;
+use service::domain::task::{NewTask, Task, TaskId, TaskPayload};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Hands tasks out in order. Retries are due at once, so a test need not wait
+/// out the backoff; the delays asked for are kept instead.
+#[derive(Default)]
+struct ScriptedQueue {
+    ready: Mutex<VecDeque<Task>>,
+    retry_delays: Mutex<Vec<i64>>,
+    dead: Mutex<Vec<(Task, String)>>,
+}
+
+impl ScriptedQueue {
+    fn dead(&self) -> Vec<(Task, String)> {
+        self.dead.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl TaskQueuePort for ScriptedQueue {
+    async fn enqueue(&self, task: NewTask) -> DomainResult<TaskId> {
+        let id = TaskId::new(uuid::Uuid::new_v4().to_string());
+        self.ready.lock().unwrap().push_back(Task {
+            id: id.clone(),
+            kind: task.kind,
+            payload: task.payload,
+            attempt: 0,
+            max_attempts: task.max_attempts,
+            enqueued_at: Utc::now(),
+            last_error: None,
+            trace_parent: None,
+            receipt: String::new(),
+        });
+        Ok(id)
+    }
+
+    async fn reserve(&self, _worker: &str) -> DomainResult<Option<Task>> {
+        Ok(self.ready.lock().unwrap().pop_front().map(|mut task| {
+            task.attempt += 1;
+            task
+        }))
+    }
+
+    async fn complete(&self, _task: &Task) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn retry(&self, task: &Task, error: &str, retry_at: DateTime<Utc>) -> DomainResult<()> {
+        let delay = (retry_at - Utc::now()).num_seconds();
+        self.retry_delays.lock().unwrap().push(delay);
+        let mut task = task.clone();
+        task.last_error = Some(error.to_string());
+        self.ready.lock().unwrap().push_back(task);
+        Ok(())
+    }
+
+    async fn dead_letter(&self, task: &Task, error: &str) -> DomainResult<()> {
+        self.dead
+            .lock()
+            .unwrap()
+            .push((task.clone(), error.to_string()));
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Charge {
+    amount: u32,
+}
+
+impl TaskPayload for Charge {
+    const KIND: &'static str = "charge";
+}
+
+/// Never succeeds: the payment provider is down.
+#[derive(Clone, Default)]
+struct FailingHandler {
+    attempts: Arc<Mutex<Vec<u32>>>,
+}
+
+#[async_trait]
+impl TaskHandler for FailingHandler {
+    type Payload = Charge;
+
+    async fn handle(&self, _payload: Charge, task: &Task) -> DomainResult<()> {
+        self.attempts.lock().unwrap().push(task.attempt);
+        Err(Error::external("Payments", "unavailable"))
+    }
+}
+
+/// Runs one worker until `done` holds, or fails after a few seconds.
+async fn work_until(queue: Arc<ScriptedQueue>, registry: TaskRegistry, done: impl Fn() -> bool) {
+    let workers = TaskWorkers::new(queue, registry, Duration::from_millis(5));
+    let (stop, shutdown) = watch::channel(false);
+    let handles = workers.spawn(1, shutdown);
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    while !done() {
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "workers did not finish"
+        );
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    let _ = stop.send(true);
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn failing_tasks_are_dead_lettered_after_max_attempts() {
+    let queue = Arc::new(ScriptedQueue::default());
+    let handler = FailingHandler::default();
+    let task = NewTask::new(&Charge { amount: 10 })
+        .unwrap()
+        .with_max_attempts(3);
+    queue.enqueue(task).await.unwrap();
+
+    let registry = TaskRegistry::new().register(handler.clone());
+    work_until(queue.clone(), registry, || !queue.dead().is_empty()).await;
+
+    assert_eq!(*handler.attempts.lock().unwrap(), [1, 2, 3]);
+    let delays = queue.retry_delays.lock().unwrap().clone();
+    assert_eq!(delays.len(), 2);
+    assert!(delays[1] > delays[0], "backoff grows: {delays:?}");
+
+    let [(task, error)] = &queue.dead()[..] else {
+        panic!("expected one dead-lettered task");
+    };
+    assert_eq!(task.attempt, 3);
+    assert!(error.contains("unavailable"), "{error}");
+}
+
+#[tokio::test]
+async fn malformed_payloads_are_dead_lettered_without_retries() {
+    let queue = Arc::new(ScriptedQueue::default());
+    let handler = FailingHandler::default();
+    queue
+        .enqueue(NewTask {
+            kind: Charge::KIND.to_string(),
+            payload: serde_json::json!({ "amount": "ten" }),
+            delay: Duration::ZERO,
+            max_attempts: 3,
+        })
+        .await
+        .unwrap();
+
+    let registry = TaskRegistry::new().register(handler.clone());
+    work_until(queue.clone(), registry, || !queue.dead().is_empty()).await;
+
+    assert!(handler.attempts.lock().unwrap().is_empty());
+    assert!(queue.retry_delays.lock().unwrap().is_empty());
+    assert_eq!(queue.dead()[0].0.attempt, 1);
+}
@@ -0,0 +1,311 @@
+//! Webhook deliveries against a local receiver: signing, retries and
+//! redelivery, with the in-memory repositories and a recording task queue.
+
+use async_trait::async_trait;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use service::application::tasks::TaskHandler;
+use service::application::webhook::{
+    DeliverWebhook, DeliverWebhookHandler, SubscriptionInput, WebhookService,
+};
+use service::domain::entities::user::UserId;
+use service::domain::entities::webhook::{DeliveryStatus, WebhookDeliveryId, WebhookSubscription};
+use service::domain::error::DomainResult;
+use service::domain::event::{DomainEvent, OutboxMessage, OutboxMessageId};
+use service::domain::port::task_queue::TaskQueuePort;
+use service::domain::task::{NewTask, Task, TaskId};
+use service::infrastructure::persistence::memory::InMemoryRepositories;
+use service::infrastructure::webhooks::http_client::HttpWebhookClient;
+use sha2::Sha256;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Keeps enqueued tasks for the test to run by hand.
+#[derive(Default)]
+struct RecordingQueue {
+    tasks: Mutex<Vec<NewTask>>,
+}
+
+impl RecordingQueue {
+    fn take(&self) -> Vec<NewTask> {
+        std::mem::take(&mut *self.tasks.lock().unwrap())
+    }
+}
+
+#[async_trait]
+impl TaskQueuePort for RecordingQueue {
+    async fn enqueue(&self, task: NewTask) -> DomainResult<TaskId> {
+        self.tasks.lock().unwrap().push(task);
+        Ok(TaskId::new(uuid::Uuid::new_v4().to_string()))
+    }
+
+    async fn reserve(&self, _worker: &str) -> DomainResult<Option<Task>> {
+        Ok(None)
+    }
+
+    async fn complete(&self, _task: &Task) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn retry(
+        &self,
+        _task: &Task,
+        _error: &str,
+        _retry_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn dead_letter(&self, _task: &Task, _error: &str) -> DomainResult<()> {
+        Ok(())
+    }
+}
+
+/// A request as the partner saw it.
+struct Received {
+    headers: HeaderMap,
+    body: String,
+}
+
+/// Answers with the queued statuses in order, then 200.
+#[derive(Clone, Default)]
+struct Receiver {
+    answers: Arc<Mutex<VecDeque<StatusCode>>>,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+    receiver
+        .received
+        .lock()
+        .unwrap()
+        .push(Received { headers, body });
+    receiver
+        .answers
+        .lock()
+        .unwrap()
+        .pop_front()
+        .unwrap_or(StatusCode::OK)
+}
+
+/// The outbox message the relay hands to the dispatcher.
+fn message() -> OutboxMessage {
+    OutboxMessage {
+        id: OutboxMessageId::new("evt-1"),
+        event: DomainEvent::UserCreated {
+            user_id: UserId::new("0123456789abcdef01234567"),
+            name: "Ada".to_string(),
+            email: "ada@example.com".to_string(),
+        },
+        occurred_at: Utc::now(),
+        attempts: 0,
+    }
+}
+
+struct Fixture {
+    service: Arc<WebhookService>,
+    queue: Arc<RecordingQueue>,
+    receiver: Receiver,
+    subscription: WebhookSubscription,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let receiver = Receiver::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .with_state(receiver.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let repos = InMemoryRepositories::new();
+        let queue = Arc::new(RecordingQueue::default());
+        let client = HttpWebhookClient::new(Duration::from_secs(5)).unwrap();
+        let service = Arc::new(
+            WebhookService::new(
+                repos.webhook_subscriptions.clone(),
+                repos.webhook_deliveries.clone(),
+                Arc::new(client),
+            )
+            .with_queue(queue.clone()),
+        );
+        let subscription = service
+            .create_subscription(
+                SubscriptionInput {
+                    url: format!("http://{addr}/hook"),
+                    events: vec!["user_created".to_string()],
+                    active: true,
+                    description: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        Self {
+            service,
+            queue,
+            receiver,
+            subscription,
+        }
+    }
+
+    /// Dispatches one event and returns its delivery's ID.
+    async fn dispatch(&self) -> WebhookDeliveryId {
+        self.service.dispatch(&message()).await.unwrap();
+
+        let [task] = self.queue.take().try_into().expect("one task queued");
+        self.payload(&task).delivery_id
+    }
+
+    fn payload(&self, task: &NewTask) -> DeliverWebhook {
+        serde_json::from_value(task.payload.clone()).unwrap()
+    }
+
+    /// Runs attempt `attempt` of a delivery task, as a worker would.
+    async fn run(&self, delivery_id: &WebhookDeliveryId, attempt: u32) -> DomainResult<()> {
+        let payload = DeliverWebhook {
+            delivery_id: delivery_id.clone(),
+        };
+        let task = Task {
+            id: TaskId::new(uuid::Uuid::new_v4().to_string()),
+            kind: "deliver_webhook".to_string(),
+            payload: serde_json::to_value(&payload).unwrap(),
+            attempt,
+            max_attempts: 10,
+            enqueued_at: Utc::now(),
+            last_error: None,
+            trace_parent: None,
+            receipt: String::new(),
+        };
+        DeliverWebhookHandler::new(self.service.clone())
+            .handle(payload, &task)
+            .await
+    }
+
+    async fn status(&self, delivery_id: &WebhookDeliveryId) -> (DeliveryStatus, usize) {
+        let subscription_id = self.subscription.id.clone().unwrap();
+        let deliveries = self
+            .service
+            .list_deliveries(&subscription_id, Default::default())
+            .await
+            .unwrap();
+        let delivery = deliveries
+            .into_iter()
+            .find(|d| d.id.as_ref() == Some(delivery_id))
+            .expect("delivery listed");
+        (delivery.status, delivery.attempts.len())
+    }
+
+    fn received(&self) -> usize {
+        self.receiver.received.lock().unwrap().len()
+    }
+}
+
+#[tokio::test]
+async fn deliveries_are_signed_over_timestamp_and_body() {
+    let fixture = Fixture::new().await;
+    let delivery_id = fixture.dispatch().await;
+
+    fixture.run(&delivery_id, 1).await.unwrap();
+
+    let received = fixture.receiver.received.lock().unwrap();
+    let [request] = &received[..] else {
+        panic!("expected one request, got {}", received.len());
+    };
+    let header = |name: &str| request.headers[name].to_str().unwrap().to_string();
+    let timestamp: i64 = header("x-webhook-timestamp").parse().unwrap();
+    assert!((Utc::now().timestamp() - timestamp).abs() < 60);
+    // Recomputed the way a receiver would, without the service's `sign()`
+    let mut mac = Hmac::<Sha256>::new_from_slice(fixture.subscription.secret.as_bytes()).unwrap();
+    mac.update(format!("{}.{}", timestamp, request.body).as_bytes());
+    let expected = format!("v1={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(header("x-webhook-signature"), expected);
+    assert_eq!(header("x-webhook-id"), "evt-1");
+    assert_eq!(header("x-webhook-event"), "user_created");
+    assert_eq!(header("x-webhook-delivery"), delivery_id.to_string());
+
+    let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(body["type"], "user_created");
+    assert_eq!(body["data"]["email"], "ada@example.com");
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_until_a_2xx() {
+    let fixture = Fixture::new().await;
+    fixture
+        .receiver
+        .answers
+        .lock()
+        .unwrap()
+        .push_back(StatusCode::SERVICE_UNAVAILABLE);
+    let delivery_id = fixture.dispatch().await;
+
+    // The failed attempt errors, so the worker schedules another one
+    assert!(fixture.run(&delivery_id, 1).await.is_err());
+    assert_eq!(
+        fixture.status(&delivery_id).await,
+        (DeliveryStatus::Pending, 1)
+    );
+
+    fixture.run(&delivery_id, 2).await.unwrap();
+    assert_eq!(
+        fixture.status(&delivery_id).await,
+        (DeliveryStatus::Succeeded, 2)
+    );
+    assert_eq!(fixture.received(), 2);
+}
+
+#[tokio::test]
+async fn redispatching_a_message_does_not_queue_it_again() {
+    let fixture = Fixture::new().await;
+    let delivery_id = fixture.dispatch().await;
+
+    // The relay retries a message whose dispatch it could not mark published
+    fixture.service.dispatch(&message()).await.unwrap();
+    assert!(fixture.queue.take().is_empty());
+
+    fixture.run(&delivery_id, 1).await.unwrap();
+    assert_eq!(fixture.received(), 1);
+    assert_eq!(
+        fixture.status(&delivery_id).await,
+        (DeliveryStatus::Succeeded, 1)
+    );
+}
+
+#[tokio::test]
+async fn redelivery_sends_again_once() {
+    let fixture = Fixture::new().await;
+    let delivery_id = fixture.dispatch().await;
+    fixture.run(&delivery_id, 1).await.unwrap();
+    let subscription_id = fixture.subscription.id.clone().unwrap();
+
+    let delivery = fixture
+        .service
+        .redeliver(&subscription_id, &delivery_id)
+        .await
+        .unwrap();
+    assert_eq!(delivery.status, DeliveryStatus::Pending);
+
+    // Asked again before the task ran: still pending, nothing more queued
+    fixture
+        .service
+        .redeliver(&subscription_id, &delivery_id)
+        .await
+        .unwrap();
+    let [task] = fixture.queue.take().try_into().expect("one task queued");
+    assert_eq!(fixture.payload(&task).delivery_id, delivery_id);
+
+    fixture.run(&delivery_id, 1).await.unwrap();
+    assert_eq!(fixture.received(), 2);
+    assert_eq!(
+        fixture.status(&delivery_id).await,
+        (DeliveryStatus::Succeeded, 2)
+    );
+}